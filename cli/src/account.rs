@@ -3,7 +3,8 @@
 
 use clap::Parser;
 use dialoguer::Input;
-use iota_wallet::account::AccountHandle;
+use iota_wallet::account::{types::NativeTokenMetadataOverrides, AccountHandle, AliasGovernanceUpdate};
+use serde_json::Value;
 
 use crate::{
     account_history::AccountHistory,
@@ -11,11 +12,15 @@ use crate::{
         addresses_command, balance_command, burn_native_token_command, burn_nft_command, claim_command,
         consolidate_command, create_alias_outputs_command, decrease_native_token_command,
         decrease_voting_power_command, destroy_alias_command, destroy_foundry_command, faucet_command,
-        increase_native_token_command, increase_voting_power_command, mint_native_token_command, mint_nft_command,
-        new_address_command, output_command, outputs_command, participation_overview_command, send_command,
-        send_micro_command, send_native_token_command, send_nft_command, stop_participating_command, sync_command,
-        transactions_command, unspent_outputs_command, vote_command, voting_output_command, voting_power_command,
-        AccountCli, AccountCommand,
+        increase_native_token_command, increase_voting_power_command, memos_command, messaging_key_command,
+        mint_native_token_command, mint_nft_command, new_address_command, nfts_command, output_command,
+        outputs_command, participation_overview_command, pay_command, read_memo_block_command, send_command,
+        send_memo_block_command, send_memo_command, send_micro_command, send_native_token_command, send_nft_command,
+        set_token_metadata_command, set_token_trusted_command, stop_participating_command, sync_command,
+        token_holders_command, token_supply_command, transactions_command, transfer_alias_command,
+        unspent_outputs_command, update_alias_governance_command, update_alias_state_command,
+        update_foundry_metadata_command, vote_command, voting_output_command, voting_power_command, AccountCli,
+        AccountCommand, TransactionConfirmation,
     },
     error::Error,
     helper::bytes_from_hex_or_file,
@@ -70,99 +75,10 @@ pub async fn account_prompt_internal(
                     return Ok(false);
                 }
             };
-            if let Err(err) = match account_cli.command {
-                AccountCommand::Addresses => addresses_command(&account_handle).await,
-                AccountCommand::Balance => balance_command(&account_handle).await,
-                AccountCommand::BurnNativeToken { token_id, amount } => {
-                    burn_native_token_command(&account_handle, token_id, amount).await
-                }
-                AccountCommand::BurnNft { nft_id } => burn_nft_command(&account_handle, nft_id).await,
-                AccountCommand::Claim { output_id } => claim_command(&account_handle, output_id).await,
-                AccountCommand::Consolidate => consolidate_command(&account_handle).await,
-                AccountCommand::CreateAliasOutput => create_alias_outputs_command(&account_handle).await,
-                AccountCommand::DecreaseNativeTokenSupply { token_id, amount } => {
-                    decrease_native_token_command(&account_handle, token_id, amount).await
-                }
-                AccountCommand::DestroyAlias { alias_id } => destroy_alias_command(&account_handle, alias_id).await,
-                AccountCommand::DestroyFoundry { foundry_id } => {
-                    destroy_foundry_command(&account_handle, foundry_id).await
-                }
-                AccountCommand::Exit => {
-                    return Ok(true);
-                }
-                AccountCommand::Faucet { url, address } => faucet_command(&account_handle, url, address).await,
-                AccountCommand::IncreaseNativeTokenSupply { token_id, amount } => {
-                    increase_native_token_command(&account_handle, token_id, amount).await
-                }
-                AccountCommand::MintNativeToken {
-                    circulating_supply,
-                    maximum_supply,
-                    foundry_metadata_hex,
-                    foundry_metadata_file,
-                } => {
-                    mint_native_token_command(
-                        &account_handle,
-                        circulating_supply,
-                        maximum_supply,
-                        bytes_from_hex_or_file(foundry_metadata_hex, foundry_metadata_file).await?,
-                    )
-                    .await
-                }
-                AccountCommand::MintNft {
-                    address,
-                    immutable_metadata_hex,
-                    immutable_metadata_file,
-                    metadata_hex,
-                    metadata_file,
-                    tag,
-                    sender,
-                    issuer,
-                } => {
-                    mint_nft_command(
-                        &account_handle,
-                        address,
-                        bytes_from_hex_or_file(immutable_metadata_hex, immutable_metadata_file).await?,
-                        bytes_from_hex_or_file(metadata_hex, metadata_file).await?,
-                        tag,
-                        sender,
-                        issuer,
-                    )
-                    .await
-                }
-                AccountCommand::NewAddress => new_address_command(&account_handle).await,
-                AccountCommand::Output { output_id } => output_command(&account_handle, output_id).await,
-                AccountCommand::Outputs => outputs_command(&account_handle).await,
-                AccountCommand::Send { address, amount } => send_command(&account_handle, address, amount).await,
-                AccountCommand::SendMicro { address, amount } => {
-                    send_micro_command(&account_handle, address, amount).await
-                }
-                AccountCommand::SendNativeToken {
-                    address,
-                    token_id,
-                    amount,
-                    gift_storage_deposit,
-                } => send_native_token_command(&account_handle, address, token_id, amount, gift_storage_deposit).await,
-                AccountCommand::SendNft { address, nft_id } => send_nft_command(&account_handle, address, nft_id).await,
-                AccountCommand::Sync => sync_command(&account_handle).await,
-                AccountCommand::Transactions => transactions_command(&account_handle).await,
-                AccountCommand::UnspentOutputs => unspent_outputs_command(&account_handle).await,
-                AccountCommand::Vote { event_id, answers } => vote_command(&account_handle, event_id, answers).await,
-                AccountCommand::StopParticipating { event_id } => {
-                    stop_participating_command(&account_handle, event_id).await
-                }
-                AccountCommand::ParticipationOverview { event_ids } => {
-                    let event_ids = (!event_ids.is_empty()).then_some(event_ids);
-                    participation_overview_command(&account_handle, event_ids).await
-                }
-                AccountCommand::VotingPower => voting_power_command(&account_handle).await,
-                AccountCommand::IncreaseVotingPower { amount } => {
-                    increase_voting_power_command(&account_handle, amount).await
-                }
-                AccountCommand::DecreaseVotingPower { amount } => {
-                    decrease_voting_power_command(&account_handle, amount).await
-                }
-                AccountCommand::VotingOutput => voting_output_command(&account_handle).await,
-            } {
+            if let AccountCommand::Exit = account_cli.command {
+                return Ok(true);
+            }
+            if let Err(err) = account_command(&account_handle, account_cli.command, account_cli.confirmation).await {
                 log::error!("{}", err);
             }
         }
@@ -170,3 +86,214 @@ pub async fn account_prompt_internal(
 
     Ok(false)
 }
+
+/// Executes an account command and returns its result, which is also logged in a human readable form.
+pub async fn account_command(
+    account_handle: &AccountHandle,
+    command: AccountCommand,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    match command {
+        AccountCommand::Addresses => addresses_command(account_handle).await,
+        AccountCommand::Balance => balance_command(account_handle).await,
+        AccountCommand::BurnNativeToken { token_id, amount } => {
+            burn_native_token_command(account_handle, token_id, amount, confirmation).await
+        }
+        AccountCommand::BurnNft { nft_id } => burn_nft_command(account_handle, nft_id, confirmation).await,
+        AccountCommand::Claim { output_id } => claim_command(account_handle, output_id, confirmation).await,
+        AccountCommand::Consolidate => consolidate_command(account_handle, confirmation).await,
+        AccountCommand::CreateAliasOutput => create_alias_outputs_command(account_handle, confirmation).await,
+        AccountCommand::DecreaseNativeTokenSupply { token_id, amount } => {
+            decrease_native_token_command(account_handle, token_id, amount, confirmation).await
+        }
+        AccountCommand::DestroyAlias { alias_id } => {
+            destroy_alias_command(account_handle, alias_id, confirmation).await
+        }
+        AccountCommand::DestroyFoundry { foundry_id } => {
+            destroy_foundry_command(account_handle, foundry_id, confirmation).await
+        }
+        // Only meaningful in the account prompt.
+        AccountCommand::Exit => Ok(Value::Null),
+        AccountCommand::Faucet { url, address } => faucet_command(account_handle, url, address).await,
+        AccountCommand::IncreaseNativeTokenSupply { token_id, amount } => {
+            increase_native_token_command(account_handle, token_id, amount, confirmation).await
+        }
+        AccountCommand::Memos => memos_command(account_handle).await,
+        AccountCommand::MessagingKey { address } => messaging_key_command(account_handle, address).await,
+        AccountCommand::MintNativeToken {
+            circulating_supply,
+            maximum_supply,
+            foundry_metadata_hex,
+            foundry_metadata_file,
+        } => {
+            mint_native_token_command(
+                account_handle,
+                circulating_supply,
+                maximum_supply,
+                bytes_from_hex_or_file(foundry_metadata_hex, foundry_metadata_file).await?,
+                confirmation,
+            )
+            .await
+        }
+        AccountCommand::MintNft {
+            address,
+            immutable_metadata_hex,
+            immutable_metadata_file,
+            metadata_hex,
+            metadata_file,
+            tag,
+            sender,
+            issuer,
+        } => {
+            mint_nft_command(
+                account_handle,
+                address,
+                bytes_from_hex_or_file(immutable_metadata_hex, immutable_metadata_file).await?,
+                bytes_from_hex_or_file(metadata_hex, metadata_file).await?,
+                tag,
+                sender,
+                issuer,
+                confirmation,
+            )
+            .await
+        }
+        AccountCommand::NewAddress => new_address_command(account_handle).await,
+        AccountCommand::Nfts => nfts_command(account_handle).await,
+        AccountCommand::Output { output_id } => output_command(account_handle, output_id).await,
+        AccountCommand::Outputs => outputs_command(account_handle).await,
+        AccountCommand::Pay { uri } => pay_command(account_handle, uri, confirmation).await,
+        AccountCommand::ReadMemoBlock { block_id } => read_memo_block_command(account_handle, block_id).await,
+        AccountCommand::Send { address, amount } => send_command(account_handle, address, amount, confirmation).await,
+        AccountCommand::SendMemo {
+            address,
+            memo,
+            messaging_key,
+        } => send_memo_command(account_handle, address, memo, messaging_key, confirmation).await,
+        AccountCommand::SendMemoBlock { messaging_key, memo } => {
+            send_memo_block_command(account_handle, messaging_key, memo).await
+        }
+        AccountCommand::SendMicro { address, amount } => {
+            send_micro_command(account_handle, address, amount, confirmation).await
+        }
+        AccountCommand::SendNativeToken {
+            address,
+            token_id,
+            amount,
+            gift_storage_deposit,
+        } => {
+            send_native_token_command(
+                account_handle,
+                address,
+                token_id,
+                amount,
+                gift_storage_deposit,
+                confirmation,
+            )
+            .await
+        }
+        AccountCommand::SendNft { address, nft_id } => {
+            send_nft_command(account_handle, address, nft_id, confirmation).await
+        }
+        AccountCommand::SetTokenMetadata {
+            token_id,
+            name,
+            symbol,
+            decimals,
+            description,
+            logo_url,
+        } => {
+            set_token_metadata_command(
+                account_handle,
+                token_id,
+                NativeTokenMetadataOverrides {
+                    name,
+                    symbol,
+                    decimals,
+                    description,
+                    logo_url,
+                },
+            )
+            .await
+        }
+        AccountCommand::SetTokenTrusted { token_id, trusted } => {
+            set_token_trusted_command(account_handle, token_id, trusted).await
+        }
+        AccountCommand::Sync => sync_command(account_handle).await,
+        AccountCommand::TokenHolders { token_id, cursor } => {
+            token_holders_command(account_handle, token_id, cursor).await
+        }
+        AccountCommand::TokenSupply { token_id } => token_supply_command(account_handle, token_id).await,
+        AccountCommand::Transactions => transactions_command(account_handle).await,
+        AccountCommand::TransferAlias { alias_id, address } => {
+            transfer_alias_command(account_handle, alias_id, address, confirmation).await
+        }
+        AccountCommand::UnspentOutputs => unspent_outputs_command(account_handle).await,
+        AccountCommand::UpdateAliasGovernance {
+            alias_id,
+            state_controller,
+            governor,
+            metadata_hex,
+            metadata_file,
+        } => {
+            update_alias_governance_command(
+                account_handle,
+                alias_id,
+                AliasGovernanceUpdate {
+                    state_controller,
+                    governor,
+                    metadata: bytes_from_hex_or_file(metadata_hex, metadata_file).await?,
+                },
+                confirmation,
+            )
+            .await
+        }
+        AccountCommand::UpdateAliasState {
+            alias_id,
+            state_metadata_hex,
+            state_metadata_file,
+        } => {
+            update_alias_state_command(
+                account_handle,
+                alias_id,
+                bytes_from_hex_or_file(state_metadata_hex, state_metadata_file)
+                    .await?
+                    .unwrap_or_default(),
+                confirmation,
+            )
+            .await
+        }
+        AccountCommand::UpdateFoundryMetadata {
+            token_id,
+            metadata_hex,
+            metadata_file,
+        } => {
+            update_foundry_metadata_command(
+                account_handle,
+                token_id,
+                bytes_from_hex_or_file(metadata_hex, metadata_file)
+                    .await?
+                    .unwrap_or_default(),
+                confirmation,
+            )
+            .await
+        }
+        AccountCommand::Vote { event_id, answers } => {
+            vote_command(account_handle, event_id, answers, confirmation).await
+        }
+        AccountCommand::StopParticipating { event_id } => {
+            stop_participating_command(account_handle, event_id, confirmation).await
+        }
+        AccountCommand::ParticipationOverview { event_ids } => {
+            let event_ids = (!event_ids.is_empty()).then_some(event_ids);
+            participation_overview_command(account_handle, event_ids).await
+        }
+        AccountCommand::VotingPower => voting_power_command(account_handle).await,
+        AccountCommand::IncreaseVotingPower { amount } => {
+            increase_voting_power_command(account_handle, amount, confirmation).await
+        }
+        AccountCommand::DecreaseVotingPower { amount } => {
+            decrease_voting_power_command(account_handle, amount, confirmation).await
+        }
+        AccountCommand::VotingOutput => voting_output_command(account_handle).await,
+    }
+}
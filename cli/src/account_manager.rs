@@ -14,7 +14,7 @@ use crate::{
         set_node_command, sync_command, AccountManagerCli, AccountManagerCommand,
     },
     error::Error,
-    helper::get_password,
+    helper::get_stronghold_password,
 };
 
 pub async fn new_account_manager(cli: AccountManagerCli) -> Result<(Option<AccountManager>, Option<String>), Error> {
@@ -29,9 +29,9 @@ pub async fn new_account_manager(cli: AccountManagerCli) -> Result<(Option<Accou
     );
     let snapshot_path = std::path::Path::new("./stardust-cli-wallet.stronghold");
     let password = if let Some(AccountManagerCommand::Restore { .. }) = &cli.command {
-        get_password("Stronghold password", false)?
+        get_stronghold_password(cli.password_fd, false)?
     } else {
-        get_password("Stronghold password", !snapshot_path.exists())?
+        get_stronghold_password(cli.password_fd, !snapshot_path.exists())?
     };
     let secret_manager = SecretManager::Stronghold(
         StrongholdSecretManager::builder()
@@ -56,11 +56,15 @@ pub async fn new_account_manager(cli: AccountManagerCli) -> Result<(Option<Accou
             let mut account = None;
 
             match command {
+                // The account command, if any, is executed by the caller.
+                AccountManagerCommand::Account { alias, .. } => account = Some(alias),
                 AccountManagerCommand::Backup { path } => {
                     backup_command(&account_manager, path, &password).await?;
                     return Ok((None, None));
                 }
                 AccountManagerCommand::ChangePassword => change_password_command(&account_manager, &password).await?,
+                // The dashboard is shown by the caller.
+                AccountManagerCommand::Dashboard { .. } => {}
                 AccountManagerCommand::New { alias } => account = Some(new_command(&account_manager, alias).await?),
                 AccountManagerCommand::SetNode { url } => set_node_command(&account_manager, url).await?,
                 AccountManagerCommand::Sync => sync_command(&account_manager).await?,
@@ -1,28 +1,40 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::str::FromStr;
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use dialoguer::Confirm;
 use iota_wallet::{
     account::{
-        types::{AccountAddress, TransactionDto},
-        AccountHandle, OutputsToClaim,
+        types::{
+            AccountAddress, AccountBalanceDto, NativeTokenHoldersDto, NativeTokenMetadataOverrides,
+            NativeTokenSupplyDto, NftCollectionDto, Transaction, TransactionDto,
+        },
+        AccountHandle, AliasGovernanceUpdate, MintTokenTransaction, MintTokenTransactionDto, OutputDataDto,
+        OutputsToClaim, SyncOptions,
     },
     iota_client::{
+        api::PreparedTransactionData,
         api_types::plugins::participation::types::ParticipationEventId,
         block::{
             address::Address,
             output::{
-                unlock_condition::AddressUnlockCondition, AliasId, BasicOutputBuilder, FoundryId, NativeToken, NftId,
-                OutputId, TokenId, UnlockCondition,
+                unlock_condition::{AddressUnlockCondition, UnlockConditions},
+                AliasId, BasicOutputBuilder, FoundryId, NativeToken, NftId, Output, OutputId, TokenId, UnlockCondition,
             },
+            payload::transaction::TransactionEssence,
+            BlockId,
         },
         request_funds_from_faucet,
     },
     AddressAndNftId, AddressNativeTokens, AddressWithAmount, AddressWithMicroAmount, NativeTokenOptions, NftOptions,
     U256,
 };
+use serde_json::{json, Value};
 
 use crate::error::Error;
 
@@ -32,9 +44,25 @@ use crate::error::Error;
 pub struct AccountCli {
     #[clap(subcommand)]
     pub command: AccountCommand,
+    #[clap(flatten)]
+    pub confirmation: TransactionConfirmation,
 }
 
-#[derive(Debug, Subcommand)]
+/// How transactions are handled after they got prepared and their preview got shown.
+#[derive(Debug, Clone, Copy, Default, Args)]
+pub struct TransactionConfirmation {
+    /// Sign and submit transactions without asking for confirmation.
+    #[clap(short, long, global = true)]
+    pub yes: bool,
+    /// Only prepare and preview transactions, without signing and submitting them.
+    #[clap(long, global = true, conflicts_with = "yes")]
+    pub dry_run: bool,
+    /// Set for `--output json`, where transactions can't be confirmed interactively.
+    #[clap(skip)]
+    pub non_interactive: bool,
+}
+
+#[derive(Debug, Clone, Subcommand)]
 pub enum AccountCommand {
     /// List the account addresses.
     Addresses,
@@ -65,6 +93,11 @@ pub enum AccountCommand {
     },
     /// Mint more of a native token: `increase-native-token-supply 0x... 100`
     IncreaseNativeTokenSupply { token_id: String, amount: String },
+    /// List the transactions with an encrypted memo that the account sent or received.
+    Memos,
+    /// Print the messaging key of the account, or the messaging key of a bech32 address from its memos:
+    /// `messaging-key rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3`
+    MessagingKey { address: Option<String> },
     /// Mint a native token: `mint-native-token 100 100 --foundry-metadata-hex 0x...`
     MintNativeToken {
         circulating_supply: String,
@@ -96,13 +129,31 @@ pub enum AccountCommand {
     },
     /// Generate a new address.
     NewAddress,
+    /// List the NFTs of the account grouped by their issuer, with their IRC27 metadata.
+    Nfts,
     /// Display an output.
     Output { output_id: String },
     /// List all outputs.
     Outputs,
+    /// Pay with a payment URI: `pay
+    /// iota:rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3?amount=1000000&tag=0x6f726465722d3432`
+    Pay { uri: String },
+    /// Read the encrypted memo of a data block: `read-memo-block 0x...`
+    ReadMemoBlock { block_id: String },
     /// Send an amount to a bech32 encoded address: `send
     /// rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3 1000000`
     Send { address: String, amount: u64 },
+    /// Send an encrypted memo to a bech32 address: `send-memo
+    /// rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3 "thanks for the coffee"`
+    /// The messaging key of the address is taken from its memos, or set with `--messaging-key 0x...`.
+    SendMemo {
+        address: String,
+        memo: String,
+        #[clap(long)]
+        messaging_key: Option<String>,
+    },
+    /// Send an encrypted memo to a messaging key in a data block: `send-memo-block 0x... "thanks for the coffee"`
+    SendMemoBlock { messaging_key: String, memo: String },
     /// Send an amount below the storage deposit minimum to a bech32 address: `send
     /// rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3 1`
     SendMicro { address: String, amount: u64 },
@@ -119,12 +170,68 @@ pub enum AccountCommand {
     },
     /// Send an NFT to a bech32 encoded address
     SendNft { address: String, nft_id: String },
+    /// Replace the IRC30 metadata of a native token: `set-token-metadata 0x... --symbol TOK --decimals 6`, without
+    /// options the previous values are removed.
+    SetTokenMetadata {
+        token_id: String,
+        #[clap(long)]
+        name: Option<String>,
+        #[clap(long)]
+        symbol: Option<String>,
+        #[clap(long)]
+        decimals: Option<u32>,
+        #[clap(long)]
+        description: Option<String>,
+        #[clap(long)]
+        logo_url: Option<String>,
+    },
+    /// Mark a native token as trusted or untrusted: `set-token-trusted 0x... true`
+    SetTokenTrusted { token_id: String, trusted: bool },
     /// Sync the account with the Tangle.
     Sync,
+    /// List a page of the addresses holding a native token with a foundry controlled by the account: `token-holders
+    /// 0x...`, with the cursor of the previous page to list the next one: `token-holders 0x... 3.0x...`
+    TokenHolders { token_id: String, cursor: Option<String> },
+    /// Show the minted, melted, circulating and maximum supply of a native token: `token-supply 0x...`, without token
+    /// id of all known native tokens
+    TokenSupply { token_id: Option<String> },
     /// List the account transactions.
     Transactions,
+    /// Transfer an alias to an address that becomes its state controller and governor: `transfer-alias 0x...
+    /// rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3`
+    TransferAlias { alias_id: String, address: String },
     /// List the unspent outputs.
     UnspentOutputs,
+    /// Change the state controller, governor or metadata of an alias: `update-alias-governance 0x...
+    /// --governor rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3`
+    UpdateAliasGovernance {
+        alias_id: String,
+        #[clap(long)]
+        state_controller: Option<String>,
+        #[clap(long)]
+        governor: Option<String>,
+        #[clap(long, group = "metadata")]
+        metadata_hex: Option<String>,
+        #[clap(long, group = "metadata")]
+        metadata_file: Option<String>,
+    },
+    /// Update the state metadata of an alias: `update-alias-state 0x... --state-metadata-hex 0xabcdef`
+    UpdateAliasState {
+        alias_id: String,
+        #[clap(long, group = "state_metadata")]
+        state_metadata_hex: Option<String>,
+        #[clap(long, group = "state_metadata")]
+        state_metadata_file: Option<String>,
+    },
+    /// Replace the metadata of a foundry controlled by the account: `update-foundry-metadata 0x... --metadata-hex
+    /// 0xabcdef`, without metadata it's removed
+    UpdateFoundryMetadata {
+        token_id: String,
+        #[clap(long, group = "metadata")]
+        metadata_hex: Option<String>,
+        #[clap(long, group = "metadata")]
+        metadata_file: Option<String>,
+    },
     /// Cast given votes for a given event
     Vote {
         event_id: ParticipationEventId,
@@ -148,18 +255,18 @@ pub enum AccountCommand {
 }
 
 /// `addresses` command
-pub async fn addresses_command(account_handle: &AccountHandle) -> Result<(), Error> {
+pub async fn addresses_command(account_handle: &AccountHandle) -> Result<Value, Error> {
     let addresses = account_handle.addresses().await?;
 
     if addresses.is_empty() {
         log::info!("No addresses found");
     } else {
-        for address in addresses {
-            print_address(account_handle, &address).await?;
+        for address in &addresses {
+            print_address(account_handle, address).await?;
         }
     }
 
-    Ok(())
+    Ok(serde_json::to_value(addresses)?)
 }
 
 // `burn-native-token` command
@@ -167,16 +274,21 @@ pub async fn burn_native_token_command(
     account_handle: &AccountHandle,
     token_id: String,
     amount: String,
-) -> Result<(), Error> {
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
     log::info!("Burning native token {token_id} {amount}.");
 
-    let transaction = account_handle
-        .burn_native_token(
+    let prepared_transaction = account_handle
+        .prepare_burn_native_token(
             TokenId::from_str(&token_id)?,
             U256::from_dec_str(&amount).map_err(|e| Error::Miscellaneous(e.to_string()))?,
             None,
         )
         .await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Burning transaction sent:\n{:?}\n{:?}",
@@ -184,14 +296,22 @@ pub async fn burn_native_token_command(
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
 // `burn-nft` command
-pub async fn burn_nft_command(account_handle: &AccountHandle, nft_id: String) -> Result<(), Error> {
+pub async fn burn_nft_command(
+    account_handle: &AccountHandle,
+    nft_id: String,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
     log::info!("Burning nft {nft_id}.");
 
-    let transaction = account_handle.burn_nft(NftId::from_str(&nft_id)?, None).await?;
+    let prepared_transaction = account_handle.prepare_burn_nft(NftId::from_str(&nft_id)?, None).await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Burning transaction sent:\n{:?}\n{:?}",
@@ -199,30 +319,28 @@ pub async fn burn_nft_command(account_handle: &AccountHandle, nft_id: String) ->
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
 // `balance` command
-pub async fn balance_command(account_handle: &AccountHandle) -> Result<(), Error> {
-    log::info!("{:?}", account_handle.balance().await?);
+pub async fn balance_command(account_handle: &AccountHandle) -> Result<Value, Error> {
+    let balance = account_handle.balance().await?;
 
-    Ok(())
+    log::info!("{balance:?}");
+
+    Ok(serde_json::to_value(AccountBalanceDto::from(&balance))?)
 }
 
 // `claim` command
-pub async fn claim_command(account_handle: &AccountHandle, output_id: Option<String>) -> Result<(), Error> {
-    if let Some(output_id) = output_id {
+pub async fn claim_command(
+    account_handle: &AccountHandle,
+    output_id: Option<String>,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    let output_ids = if let Some(output_id) = output_id {
         log::info!("Claiming output {output_id}");
 
-        let transaction = account_handle
-            .claim_outputs(vec![OutputId::from_str(&output_id)?])
-            .await?;
-
-        log::info!(
-            "Claiming transaction sent:\n{:?}\n{:?}",
-            transaction.transaction_id,
-            transaction.block_id
-        );
+        vec![OutputId::from_str(&output_id)?]
     } else {
         log::info!("Claiming outputs.");
 
@@ -234,26 +352,43 @@ pub async fn claim_command(account_handle: &AccountHandle, output_id: Option<Str
             log::info!("No outputs available to claim.");
         }
 
-        // Doing chunks of only 60, because we might need to create the double amount of outputs, because of potential
-        // storage deposit return unlock conditions and also consider the remainder output.
-        for output_ids_chunk in output_ids.chunks(60) {
-            let transaction = account_handle.claim_outputs(output_ids_chunk.to_vec()).await?;
-            log::info!(
-                "Claiming transaction sent:\n{:?}\n{:?}",
-                transaction.transaction_id,
-                transaction.block_id
-            );
-        }
+        output_ids
     };
 
-    Ok(())
+    let mut results = Vec::new();
+
+    // Doing chunks of only 60, because we might need to create the double amount of outputs, because of potential
+    // storage deposit return unlock conditions and also consider the remainder output.
+    for output_ids_chunk in output_ids.chunks(60) {
+        let prepared_transaction = account_handle.prepare_claim_outputs(output_ids_chunk.to_vec()).await?;
+        match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+            Submission::Submitted(transaction) => {
+                log::info!(
+                    "Claiming transaction sent:\n{:?}\n{:?}",
+                    transaction.transaction_id,
+                    transaction.block_id
+                );
+                results.push(transaction_to_value(&transaction)?);
+            }
+            Submission::Previewed(preview) => results.push(preview),
+        }
+    }
+
+    Ok(Value::Array(results))
 }
 
 // `consolidate` command
-pub async fn consolidate_command(account_handle: &AccountHandle) -> Result<(), Error> {
+pub async fn consolidate_command(
+    account_handle: &AccountHandle,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
     log::info!("Consolidating outputs.");
 
-    let transaction = account_handle.consolidate_outputs(true, None).await?;
+    let prepared_transaction = account_handle.prepare_consolidate_outputs(true, None).await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Consolidation transaction sent:\n{:?}\n{:?}",
@@ -261,14 +396,21 @@ pub async fn consolidate_command(account_handle: &AccountHandle) -> Result<(), E
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
 // `create-alias-output` command
-pub async fn create_alias_outputs_command(account_handle: &AccountHandle) -> Result<(), Error> {
+pub async fn create_alias_outputs_command(
+    account_handle: &AccountHandle,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
     log::info!("Creating alias output.");
 
-    let transaction = account_handle.create_alias_output(None, None).await?;
+    let prepared_transaction = account_handle.prepare_create_alias_output(None, None).await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Alias output creation transaction sent:\n{:?}\n{:?}",
@@ -276,7 +418,7 @@ pub async fn create_alias_outputs_command(account_handle: &AccountHandle) -> Res
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
 // `decrease-native-token-supply` command
@@ -284,14 +426,19 @@ pub async fn decrease_native_token_command(
     account_handle: &AccountHandle,
     token_id: String,
     amount: String,
-) -> Result<(), Error> {
-    let transaction = account_handle
-        .decrease_native_token_supply(
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    let prepared_transaction = account_handle
+        .prepare_decrease_native_token_supply(
             TokenId::from_str(&token_id)?,
             U256::from_dec_str(&amount).map_err(|e| Error::Miscellaneous(e.to_string()))?,
             None,
         )
         .await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Native token melting transaction sent:\n{:?}\n{:?}",
@@ -299,16 +446,24 @@ pub async fn decrease_native_token_command(
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
 // `destroy-alias` command
-pub async fn destroy_alias_command(account_handle: &AccountHandle, alias_id: String) -> Result<(), Error> {
+pub async fn destroy_alias_command(
+    account_handle: &AccountHandle,
+    alias_id: String,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
     log::info!("Destroying alias {alias_id}.");
 
-    let transaction = account_handle
-        .destroy_alias(AliasId::from_str(&alias_id)?, None)
+    let prepared_transaction = account_handle
+        .prepare_destroy_alias(AliasId::from_str(&alias_id)?, None)
         .await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Destroying alias transaction sent:\n{:?}\n{:?}",
@@ -316,16 +471,24 @@ pub async fn destroy_alias_command(account_handle: &AccountHandle, alias_id: Str
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
 // `destroy-foundry` command
-pub async fn destroy_foundry_command(account_handle: &AccountHandle, foundry_id: String) -> Result<(), Error> {
+pub async fn destroy_foundry_command(
+    account_handle: &AccountHandle,
+    foundry_id: String,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
     log::info!("Destroying foundry {foundry_id}.");
 
-    let transaction = account_handle
-        .destroy_foundry(FoundryId::from_str(&foundry_id)?, None)
+    let prepared_transaction = account_handle
+        .prepare_destroy_foundry(FoundryId::from_str(&foundry_id)?, None)
         .await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Destroying foundry transaction sent:\n{:?}\n{:?}",
@@ -333,7 +496,7 @@ pub async fn destroy_foundry_command(account_handle: &AccountHandle, foundry_id:
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
 // `faucet` command
@@ -341,7 +504,7 @@ pub async fn faucet_command(
     account_handle: &AccountHandle,
     url: Option<String>,
     address: Option<String>,
-) -> Result<(), Error> {
+) -> Result<Value, Error> {
     let address = if let Some(address) = address {
         address
     } else {
@@ -355,9 +518,11 @@ pub async fn faucet_command(
         None => "https://faucet.testnet.shimmer.network/api/enqueue",
     };
 
-    log::info!("{}", request_funds_from_faucet(faucet_url, &address).await?);
+    let response = request_funds_from_faucet(faucet_url, &address).await?;
 
-    Ok(())
+    log::info!("{response}");
+
+    Ok(serde_json::from_str(&response).unwrap_or(Value::String(response)))
 }
 
 // `increase-native-token-supply` command
@@ -365,15 +530,24 @@ pub async fn increase_native_token_command(
     account_handle: &AccountHandle,
     token_id: String,
     amount: String,
-) -> Result<(), Error> {
-    let mint_transaction = account_handle
-        .increase_native_token_supply(
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    let prepared_transaction = account_handle
+        .prepare_increase_native_token_supply(
             TokenId::from_str(&token_id)?,
             U256::from_dec_str(&amount).map_err(|e| Error::Miscellaneous(e.to_string()))?,
             None,
             None,
         )
         .await?;
+    let mint_transaction =
+        match submit_after_preview(account_handle, prepared_transaction.transaction, confirmation).await? {
+            Submission::Submitted(transaction) => MintTokenTransaction {
+                token_id: prepared_transaction.token_id,
+                transaction,
+            },
+            Submission::Previewed(preview) => return Ok(preview),
+        };
 
     log::info!(
         "Minting more native token transaction sent:\n{:?}\n{:?}",
@@ -381,7 +555,57 @@ pub async fn increase_native_token_command(
         mint_transaction.transaction.block_id
     );
 
-    Ok(())
+    Ok(serde_json::to_value(MintTokenTransactionDto::from(&mint_transaction))?)
+}
+
+// `memos` command
+pub async fn memos_command(account_handle: &AccountHandle) -> Result<Value, Error> {
+    // Memos are decrypted during syncing
+    account_handle
+        .sync(Some(SyncOptions {
+            force_syncing: true,
+            ..Default::default()
+        }))
+        .await?;
+
+    let mut transactions = account_handle.transactions().await?;
+    transactions.extend(account_handle.incoming_transactions().await?.into_values());
+    transactions.retain(|transaction| transaction.memo.is_some());
+    transactions.sort_by_key(|transaction| transaction.timestamp);
+
+    if transactions.is_empty() {
+        log::info!("No memos found");
+    }
+    for transaction in &transactions {
+        if let Some(memo) = &transaction.memo {
+            log::info!(
+                "{} {}: {}",
+                transaction.transaction_id,
+                if transaction.incoming { "received" } else { "sent" },
+                memo.text
+            );
+        }
+    }
+
+    Ok(serde_json::to_value(
+        transactions.iter().map(TransactionDto::from).collect::<Vec<_>>(),
+    )?)
+}
+
+// `messaging-key` command
+pub async fn messaging_key_command(account_handle: &AccountHandle, address: Option<String>) -> Result<Value, Error> {
+    let messaging_key = match &address {
+        Some(address) => account_handle.messaging_key_of(address).await?,
+        None => Some(account_handle.messaging_key().await?),
+    };
+
+    match (&messaging_key, &address) {
+        (Some(messaging_key), _) => log::info!("Messaging key: {messaging_key}"),
+        (None, Some(address)) => log::info!("No messaging key known for {address}"),
+        (None, None) => {}
+    }
+
+    Ok(serde_json::to_value(messaging_key)?)
 }
 
 // `mint-native-token` command
@@ -390,10 +614,16 @@ pub async fn mint_native_token_command(
     circulating_supply: String,
     maximum_supply: String,
     foundry_metadata: Option<Vec<u8>>,
-) -> Result<(), Error> {
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
     // If no alias output exists, create one first
     if account_handle.balance().await?.aliases.is_empty() {
-        let transaction = account_handle.create_alias_output(None, None).await?;
+        let prepared_transaction = account_handle.prepare_create_alias_output(None, None).await?;
+        let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+            Submission::Submitted(transaction) => transaction,
+            // The foundry can only be prepared once the alias output exists
+            Submission::Previewed(preview) => return Ok(preview),
+        };
         log::info!(
             "Alias output minting transaction sent:\n{:?}\n{:?}",
             transaction.transaction_id,
@@ -413,7 +643,17 @@ pub async fn mint_native_token_command(
         foundry_metadata,
     };
 
-    let mint_transaction = account_handle.mint_native_token(native_token_options, None).await?;
+    let prepared_transaction = account_handle
+        .prepare_mint_native_token(native_token_options, None)
+        .await?;
+    let mint_transaction =
+        match submit_after_preview(account_handle, prepared_transaction.transaction, confirmation).await? {
+            Submission::Submitted(transaction) => MintTokenTransaction {
+                token_id: prepared_transaction.token_id,
+                transaction,
+            },
+            Submission::Previewed(preview) => return Ok(preview),
+        };
 
     log::info!(
         "Native token minting transaction sent:\n{:?}\n{:?}",
@@ -421,10 +661,11 @@ pub async fn mint_native_token_command(
         mint_transaction.transaction.block_id
     );
 
-    Ok(())
+    Ok(serde_json::to_value(MintTokenTransactionDto::from(&mint_transaction))?)
 }
 
 // `mint-nft` command
+#[allow(clippy::too_many_arguments)]
 pub async fn mint_nft_command(
     account_handle: &AccountHandle,
     address: Option<String>,
@@ -433,7 +674,8 @@ pub async fn mint_nft_command(
     tag: Option<String>,
     sender: Option<String>,
     issuer: Option<String>,
-) -> Result<(), Error> {
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
     let tag = if let Some(hex) = tag {
         Some(prefix_hex::decode(&hex).map_err(|e| Error::Miscellaneous(e.to_string()))?)
     } else {
@@ -447,7 +689,11 @@ pub async fn mint_nft_command(
         immutable_metadata,
         metadata,
     }];
-    let transaction = account_handle.mint_nfts(nft_options, None).await?;
+    let prepared_transaction = account_handle.prepare_mint_nfts(nft_options, None).await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "NFT minting transaction sent:\n{:?}\n{:?}",
@@ -455,49 +701,88 @@ pub async fn mint_nft_command(
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
 // `new-address` command
-pub async fn new_address_command(account_handle: &AccountHandle) -> Result<(), Error> {
+pub async fn new_address_command(account_handle: &AccountHandle) -> Result<Value, Error> {
     let address = account_handle.generate_addresses(1, None).await?;
 
     print_address(account_handle, &address[0]).await?;
 
-    Ok(())
+    Ok(serde_json::to_value(&address[0])?)
+}
+
+// `nfts` command
+pub async fn nfts_command(account_handle: &AccountHandle) -> Result<Value, Error> {
+    let collections = account_handle.nft_collections().await?;
+
+    if collections.is_empty() {
+        log::info!("No NFTs found");
+    }
+    for collection in &collections {
+        let mut log = match (&collection.collection_metadata, collection.collection_id) {
+            (Some(metadata), _) => format!("Collection \"{}\"", metadata.name),
+            (None, Some(collection_id)) => format!("Collection {collection_id}"),
+            (None, None) => match collection.issuer {
+                Some(issuer) => format!("Issuer {issuer:?}"),
+                None => "Without issuer".to_string(),
+            },
+        };
+        for nft in &collection.nfts {
+            match &nft.irc27_metadata {
+                Some(metadata) => log = format!("{log}\n  {}: {} ({})", nft.nft_id, metadata.name, metadata.uri),
+                None => log = format!("{log}\n  {}", nft.nft_id),
+            }
+        }
+        log::info!("{log}");
+    }
+
+    Ok(serde_json::to_value(
+        collections.iter().map(NftCollectionDto::from).collect::<Vec<_>>(),
+    )?)
 }
 
 /// `output` command
-pub async fn output_command(account_handle: &AccountHandle, output_id: String) -> Result<(), Error> {
+pub async fn output_command(account_handle: &AccountHandle, output_id: String) -> Result<Value, Error> {
     let output = account_handle.get_output(&OutputId::from_str(&output_id)?).await;
 
-    if let Some(output) = output {
+    if let Some(output) = &output {
         log::info!("{output:#?}");
     } else {
         log::info!("Output not found");
     }
 
-    Ok(())
+    Ok(serde_json::to_value(output.as_ref().map(OutputDataDto::from))?)
 }
 
 /// `outputs` command
-pub async fn outputs_command(account_handle: &AccountHandle) -> Result<(), Error> {
+pub async fn outputs_command(account_handle: &AccountHandle) -> Result<Value, Error> {
     let outputs = account_handle.outputs(None).await?;
+    let output_ids: Vec<OutputId> = outputs.iter().map(|o| o.output_id).collect();
 
-    if outputs.is_empty() {
+    if output_ids.is_empty() {
         log::info!("No outputs found");
     } else {
-        let output_ids: Vec<OutputId> = outputs.iter().map(|o| o.output_id).collect();
         log::info!("Outputs: {output_ids:#?}");
     }
 
-    Ok(())
+    Ok(serde_json::to_value(output_ids)?)
 }
 
 // `send` command
-pub async fn send_command(account_handle: &AccountHandle, address: String, amount: u64) -> Result<(), Error> {
+pub async fn send_command(
+    account_handle: &AccountHandle,
+    address: String,
+    amount: u64,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
     let outputs = vec![AddressWithAmount { address, amount }];
-    let transaction = account_handle.send_amount(outputs, None).await?;
+    let prepared_transaction = account_handle.prepare_send_amount(outputs, None).await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Transaction sent:\n{:?}\n{:?}",
@@ -505,11 +790,93 @@ pub async fn send_command(account_handle: &AccountHandle, address: String, amoun
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
+}
+
+// `pay` command
+pub async fn pay_command(
+    account_handle: &AccountHandle,
+    uri: String,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    let payment_uri = account_handle.parse_payment_uri(&uri).await?;
+    if payment_uri.is_expired(account_handle.client().get_time_checked().await?) {
+        return Err(Error::Miscellaneous("the payment URI is expired".to_string()));
+    }
+    log::info!("Paying {uri}");
+
+    let output = account_handle
+        .prepare_output(payment_uri.to_output_options(), None)
+        .await?;
+    let prepared_transaction = account_handle.prepare_transaction(vec![output], None).await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
+
+    log::info!(
+        "Transaction sent:\n{:?}\n{:?}",
+        transaction.transaction_id,
+        transaction.block_id
+    );
+
+    transaction_to_value(&transaction)
+}
+
+// `read-memo-block` command
+pub async fn read_memo_block_command(account_handle: &AccountHandle, block_id: String) -> Result<Value, Error> {
+    let memo = account_handle.read_memo_block(&BlockId::from_str(&block_id)?).await?;
+
+    log::info!("Memo: {}", memo.text);
+
+    Ok(serde_json::to_value(memo)?)
+}
+
+// `send-memo` command
+pub async fn send_memo_command(
+    account_handle: &AccountHandle,
+    address: String,
+    memo: String,
+    messaging_key: Option<String>,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    let prepared_transaction = account_handle
+        .prepare_send_memo(address, memo, messaging_key, None)
+        .await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
+
+    log::info!(
+        "Memo sent:\n{:?}\n{:?}",
+        transaction.transaction_id,
+        transaction.block_id
+    );
+
+    transaction_to_value(&transaction)
+}
+
+// `send-memo-block` command
+pub async fn send_memo_block_command(
+    account_handle: &AccountHandle,
+    messaging_key: String,
+    memo: String,
+) -> Result<Value, Error> {
+    let block_id = account_handle.send_memo_block(&messaging_key, &memo).await?;
+
+    log::info!("Memo sent in block {block_id}");
+
+    Ok(serde_json::to_value(block_id)?)
 }
 
 // `send-micro` command
-pub async fn send_micro_command(account_handle: &AccountHandle, address: String, amount: u64) -> Result<(), Error> {
+pub async fn send_micro_command(
+    account_handle: &AccountHandle,
+    address: String,
+    amount: u64,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
     let outputs = vec![AddressWithMicroAmount {
         address,
         amount,
@@ -517,7 +884,11 @@ pub async fn send_micro_command(account_handle: &AccountHandle, address: String,
         expiration: None,
     }];
 
-    let transaction = account_handle.send_micro_transaction(outputs, None).await?;
+    let prepared_transaction = account_handle.prepare_send_micro_transaction(outputs, None).await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Micro transaction sent:\n{:?}\n{:?}",
@@ -525,7 +896,7 @@ pub async fn send_micro_command(account_handle: &AccountHandle, address: String,
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
 // `send-native-token` command
@@ -535,8 +906,9 @@ pub async fn send_native_token_command(
     token_id: String,
     amount: String,
     gift_storage_deposit: Option<bool>,
-) -> Result<(), Error> {
-    let transaction = if gift_storage_deposit.unwrap_or(false) {
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    let prepared_transaction = if gift_storage_deposit.unwrap_or(false) {
         // Send native tokens together with the required storage deposit
         let rent_structure = account_handle.client().get_rent_structure().await?;
         let token_supply = account_handle.client().get_token_supply().await?;
@@ -553,7 +925,7 @@ pub async fn send_native_token_command(
                 .finish_output(token_supply)?,
         ];
 
-        account_handle.send(outputs, None).await?
+        account_handle.prepare_transaction(outputs, None).await?
     } else {
         // Send native tokens with storage deposit return and expiration
         let outputs = vec![AddressNativeTokens {
@@ -564,7 +936,11 @@ pub async fn send_native_token_command(
             )],
             ..Default::default()
         }];
-        account_handle.send_native_tokens(outputs, None).await?
+        account_handle.prepare_send_native_tokens(outputs, None).await?
+    };
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
     };
 
     log::info!(
@@ -573,16 +949,25 @@ pub async fn send_native_token_command(
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
 // `send-nft` command
-pub async fn send_nft_command(account_handle: &AccountHandle, address: String, nft_id: String) -> Result<(), Error> {
+pub async fn send_nft_command(
+    account_handle: &AccountHandle,
+    address: String,
+    nft_id: String,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
     let outputs = vec![AddressAndNftId {
         address,
         nft_id: NftId::from_str(&nft_id)?,
     }];
-    let transaction = account_handle.send_nft(outputs, None).await?;
+    let prepared_transaction = account_handle.prepare_send_nft(outputs, None).await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Nft transaction sent:\n{:?}\n{:?}",
@@ -590,53 +975,256 @@ pub async fn send_nft_command(account_handle: &AccountHandle, address: String, n
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
+}
+
+// `set-token-metadata` command
+pub async fn set_token_metadata_command(
+    account_handle: &AccountHandle,
+    token_id: String,
+    overrides: NativeTokenMetadataOverrides,
+) -> Result<Value, Error> {
+    let metadata = account_handle
+        .set_native_token_metadata_overrides(TokenId::from_str(&token_id)?, overrides)
+        .await?;
+
+    log::info!("Token metadata of {token_id}: {metadata:?}");
+
+    Ok(serde_json::to_value(metadata)?)
+}
+
+// `set-token-trusted` command
+pub async fn set_token_trusted_command(
+    account_handle: &AccountHandle,
+    token_id: String,
+    trusted: bool,
+) -> Result<Value, Error> {
+    let metadata = account_handle
+        .set_native_token_trusted(TokenId::from_str(&token_id)?, trusted)
+        .await?;
+
+    log::info!("Token metadata of {token_id}: {metadata:?}");
+
+    Ok(serde_json::to_value(metadata)?)
 }
 
 // `sync` command
-pub async fn sync_command(account_handle: &AccountHandle) -> Result<(), Error> {
+pub async fn sync_command(account_handle: &AccountHandle) -> Result<Value, Error> {
     let sync = account_handle.sync(None).await?;
 
     log::info!("Synced: {sync:?}");
 
-    Ok(())
+    Ok(serde_json::to_value(AccountBalanceDto::from(&sync))?)
+}
+
+// `token-holders` command
+pub async fn token_holders_command(
+    account_handle: &AccountHandle,
+    token_id: String,
+    cursor: Option<String>,
+) -> Result<Value, Error> {
+    let holders = account_handle
+        .native_token_holders(&TokenId::from_str(&token_id)?, cursor)
+        .await?;
+
+    if holders.holders.is_empty() {
+        log::info!("No holders of {token_id} found in this page");
+    }
+    for holder in &holders.holders {
+        log::info!(
+            "{:?}: {} in {} output(s)",
+            holder.address,
+            holder.amount,
+            holder.output_count
+        );
+    }
+    if let Some(cursor) = &holders.cursor {
+        log::info!("Next page: token-holders {token_id} {cursor}");
+    }
+
+    Ok(serde_json::to_value(NativeTokenHoldersDto::from(&holders))?)
+}
+
+// `token-supply` command
+pub async fn token_supply_command(account_handle: &AccountHandle, token_id: Option<String>) -> Result<Value, Error> {
+    let supplies = match token_id {
+        Some(token_id) => vec![
+            account_handle
+                .native_token_supply(&TokenId::from_str(&token_id)?)
+                .await?,
+        ],
+        None => account_handle.native_token_supplies().await,
+    };
+
+    if supplies.is_empty() {
+        log::info!("No native tokens found");
+    }
+    for supply in &supplies {
+        log::info!(
+            "{}: minted {}, melted {}, circulating {}, maximum {}{}",
+            supply.token_id,
+            supply.minted_tokens,
+            supply.melted_tokens,
+            supply.circulating_supply,
+            supply.maximum_supply,
+            if supply.controlled { " (controlled)" } else { "" }
+        );
+    }
+
+    Ok(serde_json::to_value(
+        supplies.iter().map(NativeTokenSupplyDto::from).collect::<Vec<_>>(),
+    )?)
 }
 
 /// `transactions` command
-pub async fn transactions_command(account_handle: &AccountHandle) -> Result<(), Error> {
-    let transactions = account_handle.transactions().await?;
+pub async fn transactions_command(account_handle: &AccountHandle) -> Result<Value, Error> {
+    let transactions: Vec<TransactionDto> = account_handle
+        .transactions()
+        .await?
+        .iter()
+        .map(TransactionDto::from)
+        .collect();
 
     if transactions.is_empty() {
         log::info!("No transactions found");
     } else {
-        for tx in transactions {
-            log::info!("{}", serde_json::to_string(&TransactionDto::from(&tx))?);
+        for tx in &transactions {
+            log::info!("{}", serde_json::to_string(tx)?);
         }
     }
 
-    Ok(())
+    Ok(serde_json::to_value(transactions)?)
+}
+
+// `transfer-alias` command
+pub async fn transfer_alias_command(
+    account_handle: &AccountHandle,
+    alias_id: String,
+    address: String,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    log::info!("Transferring alias {alias_id} to {address}.");
+
+    let prepared_transaction = account_handle
+        .prepare_transfer_alias(AliasId::from_str(&alias_id)?, address, None)
+        .await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
+
+    log::info!(
+        "Alias transfer transaction sent:\n{:?}\n{:?}",
+        transaction.transaction_id,
+        transaction.block_id
+    );
+
+    transaction_to_value(&transaction)
 }
 
 /// `unspent-outputs` command
-pub async fn unspent_outputs_command(account_handle: &AccountHandle) -> Result<(), Error> {
+pub async fn unspent_outputs_command(account_handle: &AccountHandle) -> Result<Value, Error> {
     let outputs = account_handle.unspent_outputs(None).await?;
+    let output_ids: Vec<OutputId> = outputs.iter().map(|o| o.output_id).collect();
 
-    if outputs.is_empty() {
+    if output_ids.is_empty() {
         log::info!("No outputs found");
     } else {
-        let output_ids: Vec<OutputId> = outputs.iter().map(|o| o.output_id).collect();
         log::info!("Unspent outputs: {output_ids:#?}");
     }
 
-    Ok(())
+    Ok(serde_json::to_value(output_ids)?)
+}
+
+// `update-alias-governance` command
+pub async fn update_alias_governance_command(
+    account_handle: &AccountHandle,
+    alias_id: String,
+    update: AliasGovernanceUpdate,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    log::info!("Updating governance of alias {alias_id}.");
+
+    let prepared_transaction = account_handle
+        .prepare_update_alias_governance(AliasId::from_str(&alias_id)?, update, None)
+        .await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
+
+    log::info!(
+        "Alias governance transaction sent:\n{:?}\n{:?}",
+        transaction.transaction_id,
+        transaction.block_id
+    );
+
+    transaction_to_value(&transaction)
+}
+
+// `update-alias-state` command
+pub async fn update_alias_state_command(
+    account_handle: &AccountHandle,
+    alias_id: String,
+    state_metadata: Vec<u8>,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    log::info!("Updating state of alias {alias_id}.");
+
+    let prepared_transaction = account_handle
+        .prepare_update_alias_state(AliasId::from_str(&alias_id)?, state_metadata, None)
+        .await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
+
+    log::info!(
+        "Alias state transaction sent:\n{:?}\n{:?}",
+        transaction.transaction_id,
+        transaction.block_id
+    );
+
+    transaction_to_value(&transaction)
+}
+
+// `update-foundry-metadata` command
+pub async fn update_foundry_metadata_command(
+    account_handle: &AccountHandle,
+    token_id: String,
+    metadata: Vec<u8>,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    log::info!("Updating metadata of foundry of native token {token_id}.");
+
+    let prepared_transaction = account_handle
+        .prepare_update_foundry_metadata(TokenId::from_str(&token_id)?, metadata, None)
+        .await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
+
+    log::info!(
+        "Foundry metadata transaction sent:\n{:?}\n{:?}",
+        transaction.transaction_id,
+        transaction.block_id
+    );
+
+    transaction_to_value(&transaction)
 }
 
 pub async fn vote_command(
     account_handle: &AccountHandle,
     event_id: ParticipationEventId,
     answers: Vec<u8>,
-) -> Result<(), Error> {
-    let transaction = account_handle.vote(Some(event_id), Some(answers)).await?;
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    let prepared_transaction = account_handle.prepare_vote(Some(event_id), Some(answers)).await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Voting transaction sent:\n{:?}\n{:?}",
@@ -644,14 +1232,19 @@ pub async fn vote_command(
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
 pub async fn stop_participating_command(
     account_handle: &AccountHandle,
     event_id: ParticipationEventId,
-) -> Result<(), Error> {
-    let transaction = account_handle.stop_participating(event_id).await?;
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    let prepared_transaction = account_handle.prepare_stop_participating(event_id).await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Stop participating transaction sent:\n{:?}\n{:?}",
@@ -659,30 +1252,38 @@ pub async fn stop_participating_command(
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
 pub async fn participation_overview_command(
     account_handle: &AccountHandle,
     event_ids: Option<Vec<ParticipationEventId>>,
-) -> Result<(), Error> {
+) -> Result<Value, Error> {
     let participation_overview = account_handle.get_participation_overview(event_ids).await?;
 
     log::info!("Participation overview: {participation_overview:?}");
 
-    Ok(())
+    Ok(serde_json::to_value(participation_overview)?)
 }
 
-pub async fn voting_power_command(account_handle: &AccountHandle) -> Result<(), Error> {
+pub async fn voting_power_command(account_handle: &AccountHandle) -> Result<Value, Error> {
     let voting_power = account_handle.get_voting_power().await?;
 
     log::info!("Voting power: {voting_power}");
 
-    Ok(())
+    Ok(voting_power.into())
 }
 
-pub async fn increase_voting_power_command(account_handle: &AccountHandle, amount: u64) -> Result<(), Error> {
-    let transaction = account_handle.increase_voting_power(amount).await?;
+pub async fn increase_voting_power_command(
+    account_handle: &AccountHandle,
+    amount: u64,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    let prepared_transaction = account_handle.prepare_increase_voting_power(amount).await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Increase voting power transaction sent:\n{:?}\n{:?}",
@@ -690,11 +1291,19 @@ pub async fn increase_voting_power_command(account_handle: &AccountHandle, amoun
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
-pub async fn decrease_voting_power_command(account_handle: &AccountHandle, amount: u64) -> Result<(), Error> {
-    let transaction = account_handle.decrease_voting_power(amount).await?;
+pub async fn decrease_voting_power_command(
+    account_handle: &AccountHandle,
+    amount: u64,
+    confirmation: TransactionConfirmation,
+) -> Result<Value, Error> {
+    let prepared_transaction = account_handle.prepare_decrease_voting_power(amount).await?;
+    let transaction = match submit_after_preview(account_handle, prepared_transaction, confirmation).await? {
+        Submission::Submitted(transaction) => transaction,
+        Submission::Previewed(preview) => return Ok(preview),
+    };
 
     log::info!(
         "Decrease voting power transaction sent:\n{:?}\n{:?}",
@@ -702,15 +1311,238 @@ pub async fn decrease_voting_power_command(account_handle: &AccountHandle, amoun
         transaction.block_id
     );
 
-    Ok(())
+    transaction_to_value(&transaction)
 }
 
-pub async fn voting_output_command(account_handle: &AccountHandle) -> Result<(), Error> {
+pub async fn voting_output_command(account_handle: &AccountHandle) -> Result<Value, Error> {
     let output = account_handle.get_voting_output().await?;
 
     log::info!("Voting output: {output:?}");
 
-    Ok(())
+    Ok(serde_json::to_value(output.as_ref().map(OutputDataDto::from))?)
+}
+
+/// The outcome of a prepared transaction after its preview.
+enum Submission {
+    /// The transaction got signed and submitted.
+    Submitted(Transaction),
+    /// The transaction was a dry run or got declined, its inputs got unlocked again.
+    Previewed(Value),
+}
+
+// Shows a preview of the prepared transaction, then signs and submits it if confirmed.
+async fn submit_after_preview(
+    account_handle: &AccountHandle,
+    prepared_transaction: PreparedTransactionData,
+    confirmation: TransactionConfirmation,
+) -> Result<Submission, Error> {
+    let preview = transaction_preview(account_handle, &prepared_transaction).await?;
+
+    let submit = if confirmation.dry_run {
+        log::info!("Dry run, the transaction was not signed and submitted.");
+        false
+    } else if confirmation.yes {
+        true
+    } else if confirmation.non_interactive {
+        unlock_inputs(account_handle, &prepared_transaction).await?;
+        return Err(Error::Miscellaneous(
+            "signing and submitting transactions with `--output json` requires `--yes` or `--dry-run`".to_string(),
+        ));
+    } else {
+        let confirmed = Confirm::new()
+            .with_prompt("Sign and submit the transaction?")
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            log::info!("Transaction cancelled.");
+        }
+        confirmed
+    };
+
+    if !submit {
+        unlock_inputs(account_handle, &prepared_transaction).await?;
+
+        return Ok(Submission::Previewed(preview));
+    }
+
+    Ok(Submission::Submitted(
+        account_handle.sign_and_submit_transaction(prepared_transaction).await?,
+    ))
+}
+
+// The inputs got locked while preparing the transaction.
+pub async fn unlock_inputs(
+    account_handle: &AccountHandle,
+    prepared_transaction: &PreparedTransactionData,
+) -> Result<(), Error> {
+    let output_ids: Vec<OutputId> = prepared_transaction
+        .inputs_data
+        .iter()
+        .map(|input| *input.output_id())
+        .collect();
+
+    Ok(account_handle.unlock_outputs(&output_ids).await?)
+}
+
+// Logs the inputs, outputs, storage deposits, remainder and burned assets of a prepared transaction and returns them.
+pub async fn transaction_preview(
+    account_handle: &AccountHandle,
+    prepared_transaction: &PreparedTransactionData,
+) -> Result<Value, Error> {
+    let rent_structure = account_handle.client().get_rent_structure().await?;
+    let bech32_hrp = account_handle.client().get_bech32_hrp().await?;
+    let TransactionEssence::Regular(essence) = &prepared_transaction.essence;
+    // Input selection appends the remainder of the prepared transaction after the requested outputs, so only the last
+    // output that is equal to it is the remainder
+    let remainder_index = prepared_transaction
+        .remainder
+        .as_ref()
+        .and_then(|remainder| essence.outputs().iter().rposition(|output| output == &remainder.output));
+
+    let mut log = "Transaction preview:\nInputs:".to_string();
+    let mut inputs = Vec::new();
+    let mut input_amount = 0;
+    let mut input_chains = Vec::new();
+    let mut input_native_tokens: HashMap<TokenId, U256> = HashMap::new();
+
+    for input in &prepared_transaction.inputs_data {
+        let output_id = input.output_id();
+        let output = &input.output;
+
+        input_amount += output.amount();
+        match output {
+            Output::Alias(alias) => input_chains.push(("alias", alias.alias_id_non_null(output_id).to_string())),
+            Output::Foundry(foundry) => input_chains.push(("foundry", foundry.id().to_string())),
+            Output::Nft(nft) => input_chains.push(("nft", nft.nft_id_non_null(output_id).to_string())),
+            _ => {}
+        }
+        for native_token in output
+            .native_tokens()
+            .into_iter()
+            .flat_map(|native_tokens| native_tokens.iter())
+        {
+            *input_native_tokens.entry(*native_token.token_id()).or_default() += native_token.amount();
+        }
+
+        log = format!("{log}\n  {output_id} {} {}", output_kind(output), output.amount());
+        inputs.push(json!({
+            "outputId": output_id.to_string(),
+            "kind": output_kind(output),
+            "amount": output.amount().to_string(),
+        }));
+    }
+
+    log = format!("{log}\nOutputs:");
+    let mut outputs = Vec::new();
+    let mut output_amount = 0;
+    let mut storage_deposit = 0;
+    let mut output_chains = HashSet::new();
+    let mut output_native_tokens: HashMap<TokenId, U256> = HashMap::new();
+
+    for (index, output) in essence.outputs().iter().enumerate() {
+        let output_storage_deposit = output.rent_cost(&rent_structure);
+        let address = output_address(output).map(|address| address.to_bech32(&bech32_hrp));
+        let is_remainder = remainder_index == Some(index);
+
+        output_amount += output.amount();
+        storage_deposit += output_storage_deposit;
+        match output {
+            Output::Alias(alias) => output_chains.insert(alias.alias_id().to_string()),
+            Output::Foundry(foundry) => output_chains.insert(foundry.id().to_string()),
+            Output::Nft(nft) => output_chains.insert(nft.nft_id().to_string()),
+            _ => false,
+        };
+        let native_tokens: Vec<Value> = output
+            .native_tokens()
+            .into_iter()
+            .flat_map(|native_tokens| native_tokens.iter())
+            .map(|native_token| {
+                *output_native_tokens.entry(*native_token.token_id()).or_default() += native_token.amount();
+                json!({ "tokenId": native_token.token_id().to_string(), "amount": native_token.amount().to_string() })
+            })
+            .collect();
+
+        log = format!(
+            "{log}\n  {} {} {} (storage deposit {output_storage_deposit}{}{})",
+            output_kind(output),
+            address.as_deref().unwrap_or("-"),
+            output.amount(),
+            if native_tokens.is_empty() {
+                String::new()
+            } else {
+                format!(", {} native token(s)", native_tokens.len())
+            },
+            if is_remainder { ", remainder" } else { "" }
+        );
+        outputs.push(json!({
+            "kind": output_kind(output),
+            "address": address,
+            "amount": output.amount().to_string(),
+            "storageDeposit": output_storage_deposit.to_string(),
+            "nativeTokens": native_tokens,
+            "remainder": is_remainder,
+        }));
+    }
+
+    // Chain outputs and native tokens that are consumed without being transitioned get burned.
+    let burned_chains: Vec<Value> = input_chains
+        .into_iter()
+        .filter(|(_, id)| !output_chains.contains(id))
+        .map(|(kind, id)| {
+            log = format!("{log}\nBurned {kind}: {id}");
+            json!({ "kind": kind, "id": id })
+        })
+        .collect();
+    let burned_native_tokens: Vec<Value> = input_native_tokens
+        .into_iter()
+        .filter_map(|(token_id, input_amount)| {
+            let output_amount = output_native_tokens.get(&token_id).copied().unwrap_or_default();
+            (input_amount > output_amount).then(|| {
+                let amount = input_amount - output_amount;
+                log = format!("{log}\nBurned native token: {token_id} {amount}");
+                json!({ "tokenId": token_id.to_string(), "amount": amount.to_string() })
+            })
+        })
+        .collect();
+
+    log::info!("{log}\nTotal: inputs {input_amount}, outputs {output_amount}, storage deposit {storage_deposit}");
+
+    Ok(json!({
+        "inputs": inputs,
+        "outputs": outputs,
+        "inputAmount": input_amount.to_string(),
+        "outputAmount": output_amount.to_string(),
+        "storageDeposit": storage_deposit.to_string(),
+        "burned": {
+            "chains": burned_chains,
+            "nativeTokens": burned_native_tokens,
+        },
+    }))
+}
+
+fn output_kind(output: &Output) -> &'static str {
+    match output {
+        Output::Alias(_) => "alias",
+        Output::Basic(_) => "basic",
+        Output::Foundry(_) => "foundry",
+        Output::Nft(_) => "nft",
+        Output::Treasury(_) => "treasury",
+    }
+}
+
+fn output_address(output: &Output) -> Option<Address> {
+    match output {
+        Output::Alias(alias) => Some(*alias.state_controller_address()),
+        Output::Foundry(foundry) => Some(Address::Alias(*foundry.alias_address())),
+        _ => output
+            .unlock_conditions()
+            .and_then(UnlockConditions::address)
+            .map(|unlock_condition| *unlock_condition.address()),
+    }
+}
+
+fn transaction_to_value(transaction: &Transaction) -> Result<Value, Error> {
+    Ok(serde_json::to_value(TransactionDto::from(transaction))?)
 }
 
 async fn print_address(account_handle: &AccountHandle, address: &AccountAddress) -> Result<(), Error> {
@@ -3,7 +3,7 @@
 
 use std::{fs::File, io::prelude::*};
 
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use iota_wallet::{
     account_manager::AccountManager,
     iota_client::{constants::SHIMMER_COIN_TYPE, secret::SecretManager, utils::generate_mnemonic},
@@ -11,7 +11,11 @@ use iota_wallet::{
 };
 use log::LevelFilter;
 
-use crate::{error::Error, helper::get_password};
+use crate::{
+    command::account::{AccountCommand, TransactionConfirmation},
+    error::Error,
+    helper::get_password,
+};
 
 #[derive(Debug, Clone, Parser)]
 #[clap(version, long_about = None)]
@@ -22,14 +26,43 @@ pub struct AccountManagerCli {
     pub account: Option<String>,
     #[clap(short, long)]
     pub log_level: Option<LevelFilter>,
+    /// Format of the result of one-shot commands.
+    #[clap(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+    /// Read the stronghold password from the first line of this file descriptor instead of prompting for it.
+    #[clap(long)]
+    pub password_fd: Option<i32>,
+    #[clap(flatten)]
+    pub confirmation: TransactionConfirmation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable logs.
+    Text,
+    /// A single JSON document on stdout, logs are only written to the archive.
+    Json,
 }
 
 #[derive(Debug, Clone, Subcommand)]
 pub enum AccountManagerCommand {
+    /// Use an account by its alias, runs a single command without the account prompt if one is provided:
+    /// `account main send rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3 1000000`
+    Account {
+        alias: String,
+        #[clap(subcommand)]
+        command: Option<AccountCommand>,
+    },
     /// Create a stronghold backup file.
     Backup { path: String },
     /// Change the stronghold password.
     ChangePassword,
+    /// Show a live dashboard of all accounts.
+    Dashboard {
+        /// Interval of the background syncing in seconds.
+        #[clap(short, long, default_value_t = 30)]
+        sync_interval: u64,
+    },
     /// Parameters for the init command.
     Init(InitParameters),
     /// Generate a random mnemonic.
@@ -104,11 +137,14 @@ pub async fn init_command(
         "It is the only way to recover your account if you ever forget your password and/or lose the stronghold file."
     );
 
-    if let SecretManager::Stronghold(secret_manager) = &mut *account_manager.get_secret_manager().write().await {
-        secret_manager.store_mnemonic(mnemonic).await?;
-    } else {
+    if !matches!(
+        &*account_manager.get_secret_manager().read().await,
+        SecretManager::Stronghold(_)
+    ) {
         panic!("cli-wallet only supports Stronghold-backed secret managers at the moment.");
     }
+    // Also stores the seed to decrypt memos
+    account_manager.store_mnemonic(mnemonic).await?;
     log::info!("Mnemonic stored successfully");
 
     Ok(account_manager)
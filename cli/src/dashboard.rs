@@ -0,0 +1,426 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use dialoguer::console::{style, truncate_str, Key, Term};
+use iota_wallet::{
+    account::{
+        types::{AccountBalance, Transaction},
+        AccountHandle, OutputsToClaim,
+    },
+    account_manager::AccountManager,
+    events::types::{Event, WalletEvent},
+    iota_client::{api::PreparedTransactionData, block::output::OutputId},
+};
+use serde_json::Value;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+use crate::{
+    command::account::{transaction_preview, unlock_inputs},
+    error::Error,
+};
+
+// Interval at which the dashboard is redrawn if nothing happens, e.g. to show the changes of background syncing.
+const REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+const EVENT_LOG_LENGTH: usize = 8;
+const TRANSACTION_COUNT: usize = 8;
+
+enum Input {
+    Key(Key),
+    Wallet(Event),
+}
+
+struct AccountSummary {
+    index: u32,
+    alias: String,
+    balance: AccountBalance,
+}
+
+#[derive(Default)]
+struct Dashboard {
+    selected: usize,
+    accounts: Vec<AccountSummary>,
+    transactions: Vec<Transaction>,
+    claimable_outputs: Vec<OutputId>,
+    // Prepared claiming transactions with their preview, waiting for confirmation.
+    pending_claim: Option<(AccountHandle, Vec<(PreparedTransactionData, Value)>)>,
+    status: String,
+    // Timestamps in milliseconds and messages of the latest events, newest first.
+    events: VecDeque<(u128, String)>,
+}
+
+/// Shows a live dashboard of all accounts, updated by background syncing and wallet events, until `q` is pressed.
+pub async fn dashboard(account_manager: &AccountManager, sync_interval: Duration) -> Result<(), Error> {
+    let (sender, mut receiver) = unbounded_channel();
+
+    let event_sender = sender.clone();
+    account_manager
+        .listen(vec![], move |event| {
+            event_sender.send(Input::Wallet(event.clone())).ok();
+        })
+        .await;
+
+    // Reading a key blocks, so it's done on a thread that doesn't prevent the process from exiting.
+    std::thread::spawn(move || {
+        let term = Term::stdout();
+        while let Ok(key) = term.read_key() {
+            if sender.send(Input::Key(key)).is_err() {
+                break;
+            }
+        }
+    });
+
+    account_manager
+        .start_background_syncing(None, Some(sync_interval))
+        .await?;
+
+    let term = Term::stdout();
+    term.hide_cursor()?;
+
+    let mut dashboard = Dashboard {
+        status: format!("Background syncing every {}s", sync_interval.as_secs()),
+        ..Default::default()
+    };
+    let result = run_dashboard(account_manager, &term, &mut receiver, &mut dashboard).await;
+
+    account_manager.clear_listeners(vec![]).await;
+    account_manager.stop_background_syncing().await?;
+    term.clear_screen()?;
+    term.show_cursor()?;
+
+    result
+}
+
+async fn run_dashboard(
+    account_manager: &AccountManager,
+    term: &Term,
+    receiver: &mut UnboundedReceiver<Input>,
+    dashboard: &mut Dashboard,
+) -> Result<(), Error> {
+    loop {
+        dashboard.refresh(account_manager).await?;
+        dashboard.draw(term)?;
+
+        let input = match tokio::time::timeout(REDRAW_INTERVAL, receiver.recv()).await {
+            Ok(Some(input)) => input,
+            Ok(None) => return Ok(()),
+            Err(_) => continue,
+        };
+
+        match input {
+            Input::Wallet(event) => {
+                let message = dashboard.format_event(&event);
+                dashboard.log(message);
+            }
+            // Like the `claim` command, claiming transactions are only signed and submitted after their preview got
+            // confirmed
+            Input::Key(key) if dashboard.pending_claim.is_some() => {
+                if let Some((account_handle, prepared_transactions)) = dashboard.pending_claim.take() {
+                    dashboard.status = if key == Key::Char('y') {
+                        match submit_claim(&account_handle, prepared_transactions).await {
+                            Ok(count) => format!("Claiming transaction(s) sent: {count}"),
+                            Err(e) => format!("Claiming failed: {e}"),
+                        }
+                    } else {
+                        cancel_claim(&account_handle, &prepared_transactions).await?;
+                        "Claiming cancelled".to_string()
+                    };
+                }
+            }
+            Input::Key(Key::Char('q') | Key::Escape) => return Ok(()),
+            Input::Key(Key::ArrowUp | Key::Char('k')) => dashboard.selected = dashboard.selected.saturating_sub(1),
+            Input::Key(Key::ArrowDown | Key::Char('j')) => {
+                dashboard.selected = (dashboard.selected + 1).min(dashboard.accounts.len().saturating_sub(1))
+            }
+            Input::Key(Key::Char('s')) => {
+                dashboard.status = "Syncing...".to_string();
+                dashboard.draw(term)?;
+                dashboard.status = match account_manager.sync(None).await {
+                    Ok(_) => "Synced".to_string(),
+                    Err(e) => format!("Sync failed: {e}"),
+                };
+            }
+            Input::Key(Key::Char('c')) => {
+                if let Some(account_handle) = dashboard.selected_account(account_manager).await? {
+                    match prepare_claim(&account_handle, &dashboard.claimable_outputs).await {
+                        Ok(prepared_transactions) if prepared_transactions.is_empty() => {
+                            dashboard.status = "No outputs to claim".to_string();
+                        }
+                        Ok(prepared_transactions) => {
+                            dashboard.status = "Sign and submit the claiming transaction(s)? [y/N]".to_string();
+                            dashboard.pending_claim = Some((account_handle, prepared_transactions));
+                        }
+                        Err(e) => dashboard.status = format!("Claiming failed: {e}"),
+                    }
+                }
+            }
+            Input::Key(Key::Char('n')) => {
+                if let Some(account_handle) = dashboard.selected_account(account_manager).await? {
+                    match account_handle.generate_addresses(1, None).await {
+                        Ok(addresses) => {
+                            let message = format!("New address: {}", addresses[0].address().to_bech32());
+                            dashboard.log(message);
+                        }
+                        Err(e) => dashboard.status = format!("Address generation failed: {e}"),
+                    }
+                }
+            }
+            Input::Key(_) => {}
+        }
+    }
+}
+
+// Prepares the claiming transactions in chunks of outputs, see the `claim` command.
+async fn prepare_claim(
+    account_handle: &AccountHandle,
+    output_ids: &[OutputId],
+) -> Result<Vec<(PreparedTransactionData, Value)>, Error> {
+    let mut prepared_transactions = Vec::new();
+
+    for output_ids_chunk in output_ids.chunks(60) {
+        let prepared_transaction = match account_handle.prepare_claim_outputs(output_ids_chunk.to_vec()).await {
+            Ok(prepared_transaction) => prepared_transaction,
+            Err(e) => {
+                cancel_claim(account_handle, &prepared_transactions).await?;
+                return Err(e.into());
+            }
+        };
+        let preview = match transaction_preview(account_handle, &prepared_transaction).await {
+            Ok(preview) => preview,
+            Err(e) => {
+                unlock_inputs(account_handle, &prepared_transaction).await?;
+                cancel_claim(account_handle, &prepared_transactions).await?;
+                return Err(e);
+            }
+        };
+        prepared_transactions.push((prepared_transaction, preview));
+    }
+
+    Ok(prepared_transactions)
+}
+
+async fn submit_claim(
+    account_handle: &AccountHandle,
+    prepared_transactions: Vec<(PreparedTransactionData, Value)>,
+) -> Result<usize, Error> {
+    let count = prepared_transactions.len();
+    let mut prepared_transactions = prepared_transactions.into_iter();
+
+    while let Some((prepared_transaction, _)) = prepared_transactions.next() {
+        if let Err(e) = account_handle.sign_and_submit_transaction(prepared_transaction).await {
+            // The inputs of the transactions that won't be submitted anymore are unlocked again
+            cancel_claim(account_handle, &prepared_transactions.collect::<Vec<_>>()).await?;
+            return Err(e.into());
+        }
+    }
+
+    Ok(count)
+}
+
+async fn cancel_claim(
+    account_handle: &AccountHandle,
+    prepared_transactions: &[(PreparedTransactionData, Value)],
+) -> Result<(), Error> {
+    for (prepared_transaction, _) in prepared_transactions {
+        unlock_inputs(account_handle, prepared_transaction).await?;
+    }
+
+    Ok(())
+}
+
+impl Dashboard {
+    async fn refresh(&mut self, account_manager: &AccountManager) -> Result<(), Error> {
+        let mut accounts = Vec::new();
+
+        for account_handle in account_manager.get_accounts().await? {
+            let (index, alias) = {
+                let account = account_handle.read().await;
+                (*account.index(), account.alias().clone())
+            };
+            accounts.push(AccountSummary {
+                index,
+                alias,
+                balance: account_handle.balance().await?,
+            });
+        }
+        self.accounts = accounts;
+        self.selected = self.selected.min(self.accounts.len().saturating_sub(1));
+
+        if let Some(account_handle) = self.selected_account(account_manager).await? {
+            let mut transactions = account_handle.transactions().await?;
+            transactions.extend(account_handle.incoming_transactions().await?.into_values());
+            transactions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+            transactions.truncate(TRANSACTION_COUNT);
+            self.transactions = transactions;
+            self.claimable_outputs = account_handle
+                .get_unlockable_outputs_with_additional_unlock_conditions(OutputsToClaim::All)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn selected_account(&self, account_manager: &AccountManager) -> Result<Option<AccountHandle>, Error> {
+        match self.accounts.get(self.selected) {
+            Some(account) => Ok(Some(account_manager.get_account(account.index).await?)),
+            None => Ok(None),
+        }
+    }
+
+    fn log(&mut self, message: String) {
+        if self.events.len() == EVENT_LOG_LENGTH {
+            self.events.pop_back();
+        }
+        self.events.push_front((now_millis(), message));
+    }
+
+    fn format_event(&self, event: &Event) -> String {
+        let alias = self
+            .accounts
+            .iter()
+            .find(|account| account.index == event.account_index)
+            .map_or_else(|| event.account_index.to_string(), |account| account.alias.clone());
+        let message = match &event.event {
+            WalletEvent::ConsolidationRequired => "consolidation required".to_string(),
+            WalletEvent::NewOutput(new_output) => format!("new output {}", new_output.output.output_id),
+            WalletEvent::SpentOutput(spent_output) => format!("spent output {}", spent_output.output.output_id),
+            WalletEvent::TransactionInclusion(inclusion) => format!(
+                "transaction {} {:?}",
+                inclusion.transaction_id, inclusion.inclusion_state
+            ),
+            event => format!("{event:?}"),
+        };
+
+        format!("{alias}: {message}")
+    }
+
+    fn draw(&self, term: &Term) -> Result<(), Error> {
+        let (_rows, columns) = term.size();
+        let mut lines = vec![
+            format!(
+                "{} {}",
+                style("IOTA wallet dashboard").bold(),
+                style(format!("| {}", self.status)).dim()
+            ),
+            String::new(),
+            style("Accounts").bold().to_string(),
+        ];
+
+        for (position, account) in self.accounts.iter().enumerate() {
+            let line = format!(
+                "{} {} {}: {} available / {} total, {} native token(s), {} NFT(s)",
+                if position == self.selected { ">" } else { " " },
+                account.index,
+                account.alias,
+                account.balance.base_coin.available,
+                account.balance.base_coin.total,
+                account.balance.native_tokens.len(),
+                account.balance.nfts.len()
+            );
+            lines.push(if position == self.selected {
+                style(line).cyan().to_string()
+            } else {
+                line
+            });
+        }
+
+        if let Some(account) = self.accounts.get(self.selected) {
+            lines.push(String::new());
+            lines.push(style(format!("Account \"{}\"", account.alias)).bold().to_string());
+            for native_token in &account.balance.native_tokens {
+                let metadata = native_token.token_metadata.clone().unwrap_or_default();
+                let name = match metadata.symbol() {
+                    Some(symbol) if metadata.trusted => symbol.to_string(),
+                    Some(symbol) => format!("{symbol} (untrusted)"),
+                    None => native_token.token_id.to_string(),
+                };
+                lines.push(format!(
+                    "  Native token {name}: {} available / {} total",
+                    metadata.format_amount(native_token.available),
+                    metadata.format_amount(native_token.total)
+                ));
+            }
+            for nft_id in &account.balance.nfts {
+                lines.push(format!("  NFT {nft_id}"));
+            }
+            lines.push(format!("  Pending claims: {} output(s)", self.claimable_outputs.len()));
+
+            lines.push(String::new());
+            lines.push(style("Recent transactions").bold().to_string());
+            if self.transactions.is_empty() {
+                lines.push("  No transactions".to_string());
+            }
+            for transaction in &self.transactions {
+                lines.push(format!(
+                    "  {} {} {:?} {}",
+                    format_age(transaction.timestamp),
+                    if transaction.incoming { "incoming" } else { "outgoing" },
+                    transaction.inclusion_state,
+                    transaction.transaction_id
+                ));
+            }
+        }
+
+        if let Some((_, prepared_transactions)) = &self.pending_claim {
+            lines.push(String::new());
+            lines.push(style("Claiming preview").bold().to_string());
+            for (_, preview) in prepared_transactions {
+                let burned = preview["burned"]["chains"].as_array().map_or(0, Vec::len)
+                    + preview["burned"]["nativeTokens"].as_array().map_or(0, Vec::len);
+                lines.push(format!(
+                    "  {} input(s) {}, {} output(s) {}, storage deposit {}, {burned} burned asset(s)",
+                    preview["inputs"].as_array().map_or(0, Vec::len),
+                    preview["inputAmount"].as_str().unwrap_or_default(),
+                    preview["outputs"].as_array().map_or(0, Vec::len),
+                    preview["outputAmount"].as_str().unwrap_or_default(),
+                    preview["storageDeposit"].as_str().unwrap_or_default(),
+                ));
+            }
+        }
+
+        lines.push(String::new());
+        lines.push(style("Events").bold().to_string());
+        lines.extend(
+            self.events
+                .iter()
+                .map(|(timestamp, message)| format!("  {} {message}", format_age(*timestamp))),
+        );
+
+        lines.push(String::new());
+        lines.push(
+            style("[↑/↓] select account  [s] sync  [c] claim  [n] new address  [q] quit")
+                .dim()
+                .to_string(),
+        );
+
+        term.clear_screen()?;
+        for line in lines {
+            term.write_line(&truncate_str(&line, columns as usize, "…"))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time went backwards")
+        .as_millis()
+}
+
+// Formats a timestamp in milliseconds relative to now, e.g. `5m ago`.
+fn format_age(timestamp: u128) -> String {
+    let seconds = now_millis().saturating_sub(timestamp) / 1000;
+
+    match seconds {
+        0..=59 => format!("{seconds:>2}s ago"),
+        60..=3599 => format!("{:>2}m ago", seconds / 60),
+        3600..=86399 => format!("{:>2}h ago", seconds / 3600),
+        _ => format!("{:>2}d ago", seconds / 86400),
+    }
+}
@@ -4,7 +4,9 @@
 use fern_logger::Error as LoggerError;
 use iota_wallet::{
     error::Error as WalletError,
-    iota_client::{block::Error as BlockError, error::Error as ClientError},
+    iota_client::{
+        api::input_selection::Error as InputSelectionError, block::Error as BlockError, error::Error as ClientError,
+    },
 };
 use serde_json::Error as SerdeJsonError;
 
@@ -28,6 +30,42 @@ pub enum Error {
     Wallet(#[from] WalletError),
 }
 
+impl Error {
+    /// The exit code of the process when a command failed with this error.
+    ///
+    /// - `1`: generic failure;
+    /// - `2`: invalid arguments, also used by the argument parser;
+    /// - `3`: wallet error;
+    /// - `4`: node error;
+    /// - `5`: insufficient funds;
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Block(_) => 2,
+            Self::Client(error) => client_exit_code(error),
+            Self::Wallet(WalletError::Client(error)) => client_exit_code(error),
+            Self::Wallet(WalletError::InsufficientFunds { .. }) => 5,
+            Self::NoAddressForFaucet | Self::Wallet(_) => 3,
+            Self::Io(_) | Self::Logger(_) | Self::Miscellaneous(_) | Self::SerdeJson(_) => 1,
+        }
+    }
+}
+
+// Only errors of requests to the node are node errors, input selection and validation happen locally
+fn client_exit_code(error: &ClientError) -> i32 {
+    match error {
+        ClientError::InputSelection(
+            InputSelectionError::InsufficientAmount { .. } | InputSelectionError::InsufficientNativeTokenAmount { .. },
+        ) => 5,
+        ClientError::Block(_) | ClientError::InvalidMnemonic(_) | ClientError::Url(_) => 2,
+        ClientError::InputSelection(_)
+        | ClientError::ConsolidationRequired(_)
+        | ClientError::Crypto(_)
+        | ClientError::PlaceholderSecretManager
+        | ClientError::SecretManagerMismatch => 3,
+        _ => 4,
+    }
+}
+
 impl From<ClientError> for Error {
     fn from(error: ClientError) -> Self {
         Error::Client(Box::new(error))
@@ -1,6 +1,8 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::env::var_os;
+
 use dialoguer::{console::Term, theme::ColorfulTheme, Password, Select};
 use iota_wallet::account_manager::AccountManager;
 
@@ -18,6 +20,61 @@ pub fn get_password(prompt: &str, confirmation: bool) -> Result<String, Error> {
     Ok(password.interact()?)
 }
 
+/// Gets the stronghold password from the file descriptor if provided, then from the `WALLET_STRONGHOLD_PASSWORD`
+/// environment variable, and finally prompts for it.
+pub fn get_stronghold_password(password_fd: Option<i32>, confirmation: bool) -> Result<String, Error> {
+    if let Some(fd) = password_fd {
+        return read_password_from_fd(fd);
+    }
+
+    if let Some(password) = var_os("WALLET_STRONGHOLD_PASSWORD") {
+        return password
+            .into_string()
+            .map_err(|_| Error::Miscellaneous("invalid WALLET_STRONGHOLD_PASSWORD".to_string()));
+    }
+
+    get_password("Stronghold password", confirmation)
+}
+
+#[cfg(unix)]
+fn read_password_from_fd(fd: i32) -> Result<String, Error> {
+    use std::{
+        fs::File,
+        io::{BufRead, BufReader},
+        mem::ManuallyDrop,
+        os::unix::io::FromRawFd,
+    };
+
+    // Standard output and error are never a valid password source
+    if fd < 0 || fd == 1 || fd == 2 {
+        return Err(Error::Miscellaneous(format!("invalid password file descriptor {fd}")));
+    }
+    // SAFETY: `fcntl` with `F_GETFD` only queries the descriptor flags and fails with `EBADF` for a closed descriptor.
+    if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+        return Err(Error::Miscellaneous(format!(
+            "invalid password file descriptor {fd}: {}",
+            std::io::Error::last_os_error()
+        )));
+    }
+
+    // SAFETY: the descriptor was checked to be open above. It's read before the wallet opens any files, so it can't
+    // be owned by anything else in the process, and `ManuallyDrop` leaves closing it to the process exit, so it's
+    // never closed twice.
+    let file = ManuallyDrop::new(unsafe { File::from_raw_fd(fd) });
+    let mut password = String::new();
+
+    BufReader::new(&*file).read_line(&mut password)?;
+
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(not(unix))]
+fn read_password_from_fd(_fd: i32) -> Result<String, Error> {
+    Err(Error::Miscellaneous(
+        "reading the password from a file descriptor is only supported on unix".to_string(),
+    ))
+}
+
 pub async fn pick_account(manager: &AccountManager) -> Result<Option<u32>, Error> {
     let accounts = manager.get_accounts().await?;
 
@@ -5,20 +5,34 @@ mod account;
 mod account_history;
 mod account_manager;
 mod command;
+mod dashboard;
 mod error;
 mod helper;
 
+use std::time::Duration;
+
 use clap::Parser;
 use fern_logger::{LoggerConfigBuilder, LoggerOutputConfigBuilder};
 use log::LevelFilter;
+use serde_json::{json, Value};
 
 use self::{
-    account_manager::new_account_manager, command::account_manager::AccountManagerCli, error::Error,
+    account_manager::new_account_manager,
+    command::{
+        account::TransactionConfirmation,
+        account_manager::{AccountManagerCli, AccountManagerCommand, OutputFormat},
+    },
+    error::Error,
     helper::pick_account,
 };
 
 fn logger_init(cli: &AccountManagerCli) -> Result<(), Error> {
-    let stdout_level_filter = if let Some(log_level) = cli.log_level {
+    let stdout_level_filter = if cli.output == OutputFormat::Json
+        || matches!(cli.command, Some(AccountManagerCommand::Dashboard { .. }))
+    {
+        // Stdout is reserved for the JSON result or the dashboard.
+        LevelFilter::Off
+    } else if let Some(log_level) = cli.log_level {
         log_level
     } else {
         LevelFilter::Info
@@ -43,10 +57,38 @@ fn logger_init(cli: &AccountManagerCli) -> Result<(), Error> {
     Ok(())
 }
 
-async fn run(cli: AccountManagerCli) -> Result<(), Error> {
+async fn run(cli: AccountManagerCli) -> Result<Value, Error> {
+    let non_interactive = cli.output == OutputFormat::Json;
+
+    if non_interactive && matches!(cli.command, None | Some(AccountManagerCommand::Dashboard { .. })) {
+        return Err(Error::Miscellaneous(
+            "the account prompt and the dashboard are not available with `--output json`".to_string(),
+        ));
+    }
+
     let (account_manager, account) = new_account_manager(cli.clone()).await?;
 
     if let Some(account_manager) = account_manager {
+        match cli.command {
+            Some(AccountManagerCommand::Account {
+                alias,
+                command: Some(command),
+            }) => {
+                let account_handle = account_manager.get_account(alias).await?;
+                let confirmation = TransactionConfirmation {
+                    non_interactive,
+                    ..cli.confirmation
+                };
+                return account::account_command(&account_handle, command, confirmation).await;
+            }
+            Some(AccountManagerCommand::Dashboard { sync_interval }) => {
+                dashboard::dashboard(&account_manager, Duration::from_secs(sync_interval)).await?;
+                return Ok(Value::Null);
+            }
+            _ if non_interactive => return Ok(Value::Null),
+            _ => {}
+        }
+
         match cli.account.or(account) {
             Some(account) => account::account_prompt(account_manager.get_account(account).await?).await?,
             None => {
@@ -57,25 +99,35 @@ async fn run(cli: AccountManagerCli) -> Result<(), Error> {
         }
     }
 
-    Ok(())
+    Ok(Value::Null)
 }
 
 #[tokio::main]
 async fn main() {
     let cli = match AccountManagerCli::try_parse() {
         Ok(cli) => cli,
-        Err(e) => {
-            println!("{e}");
-            return;
-        }
+        Err(e) => e.exit(),
     };
 
     if let Err(e) = logger_init(&cli) {
         println!("{e}");
-        return;
+        std::process::exit(e.exit_code());
     }
 
-    if let Err(e) = run(cli).await {
-        log::error!("{e}");
+    let output = cli.output;
+
+    match run(cli).await {
+        Ok(result) => {
+            if output == OutputFormat::Json {
+                println!("{result}");
+            }
+        }
+        Err(e) => {
+            log::error!("{e}");
+            if output == OutputFormat::Json {
+                println!("{}", json!({ "error": e.to_string(), "exitCode": e.exit_code() }));
+            }
+            std::process::exit(e.exit_code());
+        }
     }
 }
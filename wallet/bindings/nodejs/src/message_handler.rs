@@ -6,7 +6,7 @@ use std::sync::Arc;
 use iota_wallet::{
     events::types::{Event, WalletEventType},
     message_interface::{
-        create_message_handler, init_logger as init_logger_rust, ManagerOptions, Message, Response,
+        create_message_handler, init_logger as init_logger_rust, parse_message, ManagerOptions, Response,
         WalletMessageHandler,
     },
     Result,
@@ -42,7 +42,7 @@ impl MessageHandler {
     }
 
     async fn send_message(&self, serialized_message: String) -> (String, bool) {
-        match serde_json::from_str::<Message>(&serialized_message) {
+        match parse_message(&serialized_message) {
             Ok(message) => {
                 let res = self.wallet_message_handler.send_message(message).await;
                 let mut is_err = matches!(res, Response::Error(_) | Response::Panic(_));
@@ -61,7 +61,7 @@ impl MessageHandler {
             Err(e) => {
                 log::debug!("{:?}", e);
                 (
-                    serde_json::to_string(&Response::Error(e.into()))
+                    serde_json::to_string(&Response::Error(e))
                         .expect("the response is generated manually, so unwrap is safe."),
                     true,
                 )
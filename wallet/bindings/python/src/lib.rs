@@ -7,7 +7,7 @@ use std::sync::Mutex;
 
 use ::iota_wallet::{
     events::types::WalletEventType,
-    message_interface::{init_logger as init_logger_rust, ManagerOptions, Message},
+    message_interface::{init_logger as init_logger_rust, parse_message, ManagerOptions},
 };
 use once_cell::sync::OnceCell;
 use pyo3::{prelude::*, wrap_pyfunction};
@@ -60,7 +60,7 @@ pub fn create_message_handler(options: Option<String>) -> Result<WalletMessageHa
 #[pyfunction]
 /// Send message through handler.
 pub fn send_message(handle: &WalletMessageHandler, message: String) -> Result<String> {
-    let message = match serde_json::from_str::<Message>(&message) {
+    let message = match parse_message(&message) {
         Ok(message) => message,
         Err(e) => {
             panic!("Wrong message! {e:?}");
@@ -11,7 +11,7 @@ use std::{
 
 use iota_wallet::{
     events::types::{Event, WalletEventType},
-    message_interface::{self, init_logger, ManagerOptions, Message, WalletMessageHandler},
+    message_interface::{self, init_logger, ManagerOptions, WalletMessageHandler},
 };
 use once_cell::sync::OnceCell;
 use tokio::runtime::Runtime;
@@ -131,7 +131,7 @@ pub unsafe extern "C" fn iota_send_message(
     };
 
     let message = c_message.to_str().unwrap();
-    let message = match serde_json::from_str::<Message>(message) {
+    let message = match message_interface::parse_message(message) {
         Ok(message) => message,
         Err(e) => {
             let error = CString::new(format!("{:?}", e)).unwrap();
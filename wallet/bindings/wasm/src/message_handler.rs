@@ -6,7 +6,7 @@ use std::{cell::RefCell, rc::Rc};
 use iota_wallet::{
     events::types::{Event, WalletEventType},
     message_interface::{
-        create_message_handler, init_logger as init_logger_rust, ManagerOptions, Message, Response,
+        create_message_handler, init_logger as init_logger_rust, parse_message, ManagerOptions, Response,
         WalletMessageHandler,
     },
 };
@@ -57,7 +57,7 @@ pub async fn init_logger(config: String) -> Result<(), JsValue> {
 #[allow(non_snake_case, clippy::await_holding_refcell_ref)]
 pub async fn send_message_async(message: String, message_handler: &MessageHandler) -> Result<String, JsValue> {
     let message_handler = message_handler.handler.borrow_mut();
-    let message: Message = serde_json::from_str(&message).map_err(|err| err.to_string())?;
+    let message = parse_message(&message).map_err(|err| err.to_string())?;
 
     let response = message_handler.as_ref().unwrap().send_message(message).await;
     match response {
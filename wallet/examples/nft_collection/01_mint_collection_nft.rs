@@ -8,11 +8,10 @@
 use std::{env, str::FromStr};
 
 use dotenv::dotenv;
-use iota_client::block::{
-    address::{Address, NftAddress},
-    output::NftId,
+use iota_client::block::output::NftId;
+use iota_wallet::{
+    account::types::Irc27Metadata, account_manager::AccountManager, NftCollectionMintingOptions, NftOptions, Result,
 };
-use iota_wallet::{account_manager::AccountManager, NftOptions, Result};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -36,40 +35,59 @@ async fn main() -> Result<()> {
         .set_stronghold_password(&env::var("STRONGHOLD_PASSWORD").unwrap())
         .await?;
 
-    let bech32_hrp = account.client().get_bech32_hrp().await?;
     let mut nft_options = Vec::new();
 
     // Create the metadata with another index for each
     for index in 0..nft_collection_size {
         nft_options.push(NftOptions {
             address: None,
-            immutable_metadata: Some(format!("{{\"standard\":\"IRC27\",\"version\":\"v1.0\",\"type\":\"video/mp4\",\"uri\":\"ipfs://wrongcVm9fx47YXNTkhpMEYSxCD3Bqh7PJYr7eo5Ywrong\",\"name\":\"Shimmer OG NFT #{index}\",\"description\":\"The Shimmer OG NFT was handed out 1337 times by the IOTA Foundation to celebrate the official launch of the Shimmer Network.\",\"issuerName\":\"IOTA Foundation\",\"collectionId\":\"{issuer_nft_id}\",\"collectionName\":\"Shimmer OG\" }}").as_bytes().to_vec()),
-            // The NFT address from the NFT we minted in mint_issuer_nft example
-            issuer: Some(Address::Nft(NftAddress::new(issuer_nft_id)).to_bech32(bech32_hrp.clone())),
+            immutable_metadata: Some(
+                Irc27Metadata::new(
+                    "video/mp4",
+                    "ipfs://wrongcVm9fx47YXNTkhpMEYSxCD3Bqh7PJYr7eo5Ywrong",
+                    format!("Shimmer OG NFT #{index}"),
+                )
+                .with_description(
+                    "The Shimmer OG NFT was handed out 1337 times by the IOTA Foundation to celebrate the official \
+                     launch of the Shimmer Network.",
+                )
+                .with_issuer_name("IOTA Foundation")
+                .with_collection_name("Shimmer OG")
+                .to_bytes()?,
+            ),
+            // The issuer is set to the NFT address from the NFT we minted in mint_issuer_nft example
+            issuer: None,
             metadata: None,
             sender: None,
             tag: None,
         });
     }
 
-    // Mint nfts in chunks, since the transaction size is limited
-    for nfts in nft_options.chunks(50) {
-        let transaction = account.mint_nfts(nfts.to_vec(), None).await?;
-
-        println!("Transaction: {}", transaction.transaction_id);
-        if let Some(block_id) = transaction.block_id {
-            println!(
-                "Block sent: {}/api/core/v2/blocks/{}",
-                &env::var("NODE_URL").unwrap(),
-                block_id
-            );
-        }
-        // Try to get the transaction confirmed
-        account
-            .retry_transaction_until_included(&transaction.transaction_id, None, None)
+    // Mint the nfts, they're split into multiple transactions since the transaction size is limited. Up to 3
+    // transactions are sent before waiting for their inclusion.
+    let minting_options = NftCollectionMintingOptions {
+        max_pending_transactions: Some(3),
+        ..Default::default()
+    };
+    let mut minting = account
+        .mint_nft_collection(issuer_nft_id, nft_options, Some(minting_options.clone()))
+        .await?;
+    // Resume minting a few times if it failed partway
+    for _ in 0..3 {
+        let Some(error) = &minting.error else {
+            break;
+        };
+        println!("Minting failed after {} NFTs: {error}", minting.minted);
+        minting = account
+            .resume_nft_collection_minting(minting, Some(minting_options.clone()))
             .await?;
-        // Sync so the new outputs are available again for new transactions
-        account.sync(None).await?;
+    }
+    if let Some(error) = &minting.error {
+        println!("Minting stopped after {} NFTs: {error}", minting.minted);
+    }
+
+    for transaction_id in &minting.transaction_ids {
+        println!("Transaction: {transaction_id}");
     }
 
     // After the NFTs are minted, the issuer nft can be sent to the so called "null address"
@@ -1,6 +1,8 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "stronghold")]
+use std::sync::atomic::AtomicU64;
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
@@ -21,7 +23,8 @@ use crate::storage::manager::StorageManagerHandle;
 use crate::{
     account::{
         handle::AccountHandle,
-        types::{address::AddressWrapper, AccountAddress},
+        operations::syncing::request_limiter::RequestLimiter,
+        types::{address::AddressWrapper, AccountAddress, AccountMetadata},
         Account,
     },
     ClientOptions, Error,
@@ -35,7 +38,10 @@ pub struct AccountBuilder {
     client_options: Arc<RwLock<ClientOptions>>,
     coin_type: u32,
     secret_manager: Arc<RwLock<SecretManager>>,
+    request_limiter: RequestLimiter,
     accounts: Arc<RwLock<Vec<AccountHandle>>>,
+    #[cfg(feature = "stronghold")]
+    backup_changes: Arc<AtomicU64>,
     #[cfg(feature = "events")]
     event_emitter: Arc<Mutex<EventEmitter>>,
     #[cfg(feature = "storage")]
@@ -49,6 +55,8 @@ impl AccountBuilder {
         client_options: Arc<RwLock<ClientOptions>>,
         coin_type: u32,
         secret_manager: Arc<RwLock<SecretManager>>,
+        request_limiter: RequestLimiter,
+        #[cfg(feature = "stronghold")] backup_changes: Arc<AtomicU64>,
         #[cfg(feature = "events")] event_emitter: Arc<Mutex<EventEmitter>>,
         #[cfg(feature = "storage")] storage_manager: StorageManagerHandle,
     ) -> Self {
@@ -59,7 +67,10 @@ impl AccountBuilder {
             client_options,
             coin_type,
             secret_manager,
+            request_limiter,
             accounts,
+            #[cfg(feature = "stronghold")]
+            backup_changes,
             #[cfg(feature = "events")]
             event_emitter,
             #[cfg(feature = "storage")]
@@ -169,6 +180,7 @@ impl AccountBuilder {
                     key_index: 0,
                     internal: false,
                     used: false,
+                    label: None,
                 };
 
                 vec![first_public_account_address]
@@ -189,13 +201,25 @@ impl AccountBuilder {
             pending_transactions: HashSet::new(),
             incoming_transactions: HashMap::new(),
             inaccessible_incoming_transactions: HashSet::new(),
+            undecryptable_memo_transactions: HashSet::new(),
             native_token_foundries: HashMap::new(),
+            metadata: AccountMetadata::default(),
+            payment_requests: HashMap::new(),
+            next_payment_request_id: 0,
+            highest_used_public_address_index: None,
+            highest_used_internal_address_index: None,
+            sync_checkpoint: None,
+            balance_snapshots: Vec::new(),
+            native_token_metadata: HashMap::new(),
         };
 
         let account_handle = AccountHandle::new(
             account,
             client,
             self.secret_manager.clone(),
+            self.request_limiter.clone(),
+            #[cfg(feature = "stronghold")]
+            self.backup_changes.clone(),
             #[cfg(feature = "events")]
             self.event_emitter.clone(),
             #[cfg(feature = "storage")]
@@ -204,6 +228,7 @@ impl AccountBuilder {
         #[cfg(feature = "storage")]
         account_handle.save(None).await?;
         accounts.push(account_handle.clone());
+        account_handle.count_backup_change();
 
         Ok(account_handle)
     }
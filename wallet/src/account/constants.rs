@@ -8,8 +8,20 @@ pub(crate) const DEFAULT_OUTPUT_CONSOLIDATION_THRESHOLD: usize = 100;
 #[cfg(feature = "ledger_nano")]
 pub(crate) const DEFAULT_LEDGER_OUTPUT_CONSOLIDATION_THRESHOLD: usize = 15;
 
-/// Amount of API request that can be sent in parallel during syncing
-pub(crate) const PARALLEL_REQUESTS_AMOUNT: usize = 500;
+/// Default amount of API request that can be sent in parallel during syncing
+pub(crate) const DEFAULT_PARALLEL_REQUESTS_AMOUNT: usize = 500;
+
+/// Seconds after which a sync checkpoint isn't used anymore and all outputs are requested again, one week
+pub(crate) const MAX_SYNC_CHECKPOINT_AGE: u32 = 604800;
+
+/// Seconds an incremental sync overlaps with the sync checkpoint, so outputs that weren't indexed yet are requested
+pub(crate) const SYNC_CHECKPOINT_OVERLAP: u32 = 60;
+
+/// Maximum amount of balance snapshots stored in an account, the oldest ones are removed first
+pub(crate) const MAX_BALANCE_SNAPSHOTS: usize = 1000;
+
+/// Amount of outputs with native tokens requested per page when listing the holders of a native token
+pub(crate) const NATIVE_TOKEN_HOLDERS_PAGE_SIZE: usize = 100;
 
 /// ms before an account actually syncs with the network, before it just returns the previous syncing result
 /// this is done to prevent unnecessary simultaneous synchronizations
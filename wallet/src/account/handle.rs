@@ -1,6 +1,8 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "stronghold")]
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::{collections::HashMap, ops::Deref, sync::Arc};
 
 use iota_client::{
@@ -20,9 +22,10 @@ use crate::events::EventEmitter;
 use crate::storage::manager::StorageManagerHandle;
 use crate::{
     account::{
+        operations::syncing::{options::SyncCancellationToken, request_limiter::RequestLimiter, SyncOptions},
         types::{
-            address::{AccountAddress, AddressWithUnspentOutputs},
-            OutputData, Transaction,
+            address::{AccountAddress, AddressWithUnspentOutputs, AddressWrapper},
+            LabelSearchResult, OutputData, Transaction,
         },
         Account,
     },
@@ -49,10 +52,20 @@ pub struct AccountHandle {
     account: Arc<RwLock<Account>>,
     pub(crate) client: Client,
     pub(crate) secret_manager: Arc<RwLock<SecretManager>>,
+    // limits for the requests during syncing, shared with the other accounts of the account manager
+    pub(crate) request_limiter: RequestLimiter,
     // mutex to prevent multiple sync calls at the same or almost the same time, the u128 is a timestamp
     // if the last synced time was < `MIN_SYNC_INTERVAL` second ago, we don't sync, but only calculate the balance
     // again, because sending transactions can change that
     pub(crate) last_synced: Arc<Mutex<u128>>,
+    // the cancellation token of the running sync, used by `cancel_sync()`
+    pub(crate) sync_cancellation_token: Arc<Mutex<Option<SyncCancellationToken>>>,
+    // addresses derived by the address discovery after the account addresses, by internal flag and key index, so they
+    // don't need to be derived again on every sync
+    pub(crate) discovery_addresses: Arc<Mutex<HashMap<(bool, u32), AddressWrapper>>>,
+    // counter of the changes that require a new automatic backup, shared with the account manager
+    #[cfg(feature = "stronghold")]
+    pub(crate) backup_changes: Arc<AtomicU64>,
     #[cfg(feature = "events")]
     pub(crate) event_emitter: Arc<Mutex<EventEmitter>>,
     #[cfg(feature = "storage")]
@@ -65,6 +78,8 @@ impl AccountHandle {
         account: Account,
         client: Client,
         secret_manager: Arc<RwLock<SecretManager>>,
+        request_limiter: RequestLimiter,
+        #[cfg(feature = "stronghold")] backup_changes: Arc<AtomicU64>,
         #[cfg(feature = "events")] event_emitter: Arc<Mutex<EventEmitter>>,
         #[cfg(feature = "storage")] storage_manager: StorageManagerHandle,
     ) -> Self {
@@ -72,7 +87,12 @@ impl AccountHandle {
             account: Arc::new(RwLock::new(account)),
             client,
             secret_manager,
+            request_limiter,
             last_synced: Default::default(),
+            sync_cancellation_token: Default::default(),
+            discovery_addresses: Default::default(),
+            #[cfg(feature = "stronghold")]
+            backup_changes,
             #[cfg(feature = "events")]
             event_emitter,
             #[cfg(feature = "storage")]
@@ -89,6 +109,19 @@ impl AccountHandle {
         &self.client
     }
 
+    // Count a new account, address or transaction, so the automatic backup creates a new backup
+    pub(crate) fn count_backup_change(&self) {
+        #[cfg(feature = "stronghold")]
+        self.backup_changes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Get the request limiter for a sync, a new one if the options have their own limits, otherwise the shared one
+    pub(crate) fn sync_request_limiter(&self, options: &SyncOptions) -> RequestLimiter {
+        options
+            .request_options
+            .map_or_else(|| self.request_limiter.clone(), RequestLimiter::new)
+    }
+
     /// Get the [`OutputData`] of an output stored in the account
     pub async fn get_output(&self, output_id: &OutputId) -> Option<OutputData> {
         let account = self.read().await;
@@ -207,6 +240,33 @@ impl AccountHandle {
         Ok(outputs)
     }
 
+    /// Returns the addresses and outputs with a label that contains the query, ignoring the case.
+    pub async fn search_labels(&self, query: &str) -> LabelSearchResult {
+        let query = query.to_lowercase();
+        let matches = |label: &Option<String>| {
+            label
+                .as_ref()
+                .map_or(false, |label| label.to_lowercase().contains(&query))
+        };
+
+        let account = self.read().await;
+        LabelSearchResult {
+            addresses: account
+                .public_addresses
+                .iter()
+                .chain(account.internal_addresses.iter())
+                .filter(|address| matches(&address.label))
+                .cloned()
+                .collect(),
+            output_ids: account
+                .outputs
+                .values()
+                .filter(|output_data| matches(&output_data.label))
+                .map(|output_data| output_data.output_id)
+                .collect(),
+        }
+    }
+
     /// Returns all incoming transactions of the account
     pub async fn incoming_transactions(&self) -> Result<HashMap<TransactionId, Transaction>> {
         Ok(self.read().await.incoming_transactions.clone())
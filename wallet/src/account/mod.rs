@@ -24,7 +24,7 @@ use instant::SystemTime;
 use iota_client::{
     api_types::core::response::OutputWithMetadataResponse,
     block::{
-        output::{FoundryId, FoundryOutput, OutputId},
+        output::{FoundryId, FoundryOutput, OutputId, TokenId},
         payload::{
             transaction::{TransactionEssence, TransactionId},
             TransactionPayload,
@@ -36,7 +36,8 @@ use serde::{de, Deserialize, Deserializer, Serialize};
 
 use self::types::{
     address::{AccountAddress, AddressWithUnspentOutputs},
-    AccountBalance, OutputData, Transaction,
+    AccountBalance, AccountMetadata, BalanceSnapshot, NativeTokenMetadata, OutputData, PaymentRequest, SyncCheckpoint,
+    Transaction,
 };
 pub use self::{
     handle::{AccountHandle, FilterOptions},
@@ -44,11 +45,18 @@ pub use self::{
         address_generation::AddressGenerationOptions,
         output_claiming::OutputsToClaim,
         syncing::{
-            options::{AccountSyncOptions, AliasSyncOptions, NftSyncOptions},
+            options::{
+                AccountSyncOptions, AliasSyncOptions, NftSyncOptions, SyncCancellationToken, SyncRequestOptions,
+            },
             SyncOptions,
         },
         transaction::{
-            high_level::minting::mint_native_token::MintTokenTransactionDto,
+            high_level::{
+                minting::mint_native_token::{
+                    MintTokenTransaction, MintTokenTransactionDto, PreparedMintTokenTransaction,
+                },
+                update_alias::{AliasGovernanceUpdate, AliasGovernanceUpdateDto},
+            },
             prepare_output::{Assets, Features, OutputOptions, ReturnStrategy, StorageDeposit, Unlocks},
             RemainderValueStrategy, TransactionOptions, TransactionOptionsDto,
         },
@@ -102,9 +110,37 @@ pub struct Account {
     /// set, because another node might still have them.
     #[serde(default)]
     inaccessible_incoming_transactions: HashSet<TransactionId>,
+    /// Transactions with a memo that the account can't decrypt, because it's for another messaging key or invalid, so
+    /// decrypting it isn't tried again on every sync.
+    #[serde(default)]
+    undecryptable_memo_transactions: HashSet<TransactionId>,
     /// Foundries for native tokens in outputs
     #[serde(default)]
     native_token_foundries: HashMap<FoundryId, FoundryOutput>,
+    /// User defined metadata
+    #[serde(default)]
+    pub(crate) metadata: AccountMetadata,
+    /// Payment requests by id
+    #[serde(default)]
+    payment_requests: HashMap<u32, PaymentRequest>,
+    /// The id of the next payment request, ids aren't reused when a request is cancelled
+    #[serde(default)]
+    next_payment_request_id: u32,
+    /// The highest public address index with outputs found by the address discovery during syncing
+    #[serde(default)]
+    highest_used_public_address_index: Option<u32>,
+    /// The highest internal address index with outputs found by the address discovery during syncing
+    #[serde(default)]
+    highest_used_internal_address_index: Option<u32>,
+    /// The checkpoint of the latest sync of all addresses, used to only request new outputs
+    #[serde(default)]
+    sync_checkpoint: Option<SyncCheckpoint>,
+    /// Balance snapshots recorded after syncs when the balance changed, ordered by timestamp
+    #[serde(default)]
+    balance_snapshots: Vec<BalanceSnapshot>,
+    /// Metadata of native tokens, parsed from the foundries and set by the user
+    #[serde(default)]
+    native_token_metadata: HashMap<TokenId, NativeTokenMetadata>,
 }
 
 // Custom deserialization to stay backwards compatible
@@ -159,6 +195,7 @@ pub(crate) fn build_transaction_from_payload_and_inputs(
         incoming: true,
         note: None,
         inputs,
+        memo: None,
     })
 }
 
@@ -234,6 +271,7 @@ fn serialize() {
         incoming: false,
         note: None,
         inputs: Vec::new(),
+        memo: None,
     };
 
     let mut incoming_transactions = HashMap::new();
@@ -256,7 +294,16 @@ fn serialize() {
         pending_transactions: HashSet::new(),
         incoming_transactions,
         inaccessible_incoming_transactions: HashSet::new(),
+        undecryptable_memo_transactions: HashSet::new(),
         native_token_foundries: HashMap::new(),
+        metadata: AccountMetadata::default(),
+        payment_requests: HashMap::new(),
+        next_payment_request_id: 0,
+        highest_used_public_address_index: None,
+        highest_used_internal_address_index: None,
+        sync_checkpoint: None,
+        balance_snapshots: Vec::new(),
+        native_token_metadata: HashMap::new(),
     };
 
     serde_json::from_str::<Account>(&serde_json::to_string(&account).unwrap()).unwrap();
@@ -274,6 +274,7 @@ impl AccountHandle {
             native_tokens_balance.push(NativeTokensBalance {
                 token_id: *native_token.token_id(),
                 metadata,
+                token_metadata: account.native_token_metadata.get(native_token.token_id()).cloned(),
                 total: native_token.amount(),
                 available: native_token.amount() - *locked_amount.unwrap_or(&U256::from(0u8)),
             })
@@ -322,6 +323,7 @@ pub(crate) fn add_balances(balances: Vec<AccountBalance>) -> crate::Result<Accou
                 total_balance.native_tokens.push(NativeTokensBalance {
                     token_id: native_token_balance.token_id,
                     metadata: native_token_balance.metadata.clone(),
+                    token_metadata: native_token_balance.token_metadata.clone(),
                     total: native_token_balance.total,
                     available: native_token_balance.available,
                 })
@@ -0,0 +1,363 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use iota_client::block::{
+    input::Input,
+    output::{NativeToken, Output, OutputId, TokenId},
+    payload::transaction::TransactionEssence,
+};
+use primitive_types::U256;
+
+use crate::account::{
+    constants::MAX_BALANCE_SNAPSHOTS,
+    handle::AccountHandle,
+    types::{BalanceSnapshot, InclusionState},
+    Account,
+};
+
+impl AccountHandle {
+    /// Get the balance of the account at a point in time. The balance is calculated from the latest balance snapshot
+    /// before the timestamp and the outputs that were received and spent after it, see [`BalanceSnapshot`].
+    pub async fn balance_at(&self, timestamp: u32) -> crate::Result<BalanceSnapshot> {
+        let network_id = self.client.get_network_id().await?;
+        let account = self.read().await;
+        balance_at(
+            &snapshots_for_network(&account, network_id),
+            &balance_changes(&account, network_id),
+            network_id,
+            timestamp,
+        )
+    }
+
+    /// Get the balance of the account after every change between `start` and `end`, both unix timestamps in seconds.
+    /// With a `start` the first entry is the balance at that time, consecutive entries with the same balance are
+    /// merged.
+    pub async fn balance_history(&self, start: Option<u32>, end: Option<u32>) -> crate::Result<Vec<BalanceSnapshot>> {
+        let network_id = self.client.get_network_id().await?;
+        let account = self.read().await;
+        balance_history(
+            &snapshots_for_network(&account, network_id),
+            &balance_changes(&account, network_id),
+            network_id,
+            start,
+            end,
+        )
+    }
+
+    /// Store a snapshot of the current balance if it changed since the latest one. The snapshot gets the timestamp of
+    /// the confirmed milestone, like the balance changes of the outputs, so it needs to be recorded after syncing, when
+    /// all outputs booked up to that milestone are known.
+    pub(crate) async fn record_balance_snapshot(&self) -> crate::Result<()> {
+        let network_id = self.client.get_network_id().await?;
+        let milestone_timestamp = match self
+            .client
+            .get_info()
+            .await?
+            .node_info
+            .status
+            .confirmed_milestone
+            .timestamp
+        {
+            Some(milestone_timestamp) => milestone_timestamp,
+            None => return Ok(()),
+        };
+        let mut account = self.write().await;
+
+        let mut balance = Balance::default();
+        for output_data in account.unspent_outputs.values() {
+            if output_data.network_id == network_id {
+                balance.apply(&BalanceChange::new(0, &output_data.output, true));
+            }
+        }
+        let snapshot = balance.into_snapshot(milestone_timestamp, network_id)?;
+
+        if account
+            .balance_snapshots
+            .last()
+            .map_or(false, |latest| latest.has_same_balance(&snapshot))
+        {
+            return Ok(());
+        }
+        log::debug!("[SYNC] recording balance snapshot {snapshot:?}");
+        account.balance_snapshots.push(snapshot);
+        if account.balance_snapshots.len() > MAX_BALANCE_SNAPSHOTS {
+            let excess = account.balance_snapshots.len() - MAX_BALANCE_SNAPSHOTS;
+            account.balance_snapshots.drain(..excess);
+        }
+
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+
+        Ok(())
+    }
+}
+
+// A change of the balance by an output that was received or spent
+#[derive(Debug, Clone)]
+struct BalanceChange {
+    timestamp: u32,
+    amount: u64,
+    native_tokens: Vec<NativeToken>,
+    received: bool,
+}
+
+impl BalanceChange {
+    fn new(timestamp: u32, output: &Output, received: bool) -> Self {
+        Self {
+            timestamp,
+            amount: output.amount(),
+            native_tokens: output
+                .native_tokens()
+                .map(|native_tokens| native_tokens.iter().cloned().collect())
+                .unwrap_or_default(),
+            received,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Balance {
+    base_coin: u64,
+    native_tokens: BTreeMap<TokenId, U256>,
+}
+
+impl Balance {
+    fn from_snapshot(snapshot: &BalanceSnapshot) -> Self {
+        Self {
+            base_coin: snapshot.base_coin,
+            native_tokens: snapshot
+                .native_tokens
+                .iter()
+                .map(|native_token| (*native_token.token_id(), *native_token.amount()))
+                .collect(),
+        }
+    }
+
+    fn apply(&mut self, change: &BalanceChange) {
+        if change.received {
+            self.base_coin = self.base_coin.saturating_add(change.amount);
+        } else {
+            self.base_coin = self.base_coin.saturating_sub(change.amount);
+        }
+        for native_token in &change.native_tokens {
+            let amount = self.native_tokens.entry(*native_token.token_id()).or_default();
+            if change.received {
+                *amount = amount.saturating_add(*native_token.amount());
+            } else {
+                *amount = amount.saturating_sub(*native_token.amount());
+            }
+        }
+    }
+
+    fn into_snapshot(self, timestamp: u32, network_id: u64) -> crate::Result<BalanceSnapshot> {
+        Ok(BalanceSnapshot {
+            timestamp,
+            network_id,
+            base_coin: self.base_coin,
+            native_tokens: self
+                .native_tokens
+                .into_iter()
+                .filter(|(_, amount)| !amount.is_zero())
+                .map(|(token_id, amount)| NativeToken::new(token_id, amount))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+// The recorded balance snapshots of the network, ordered by timestamp
+fn snapshots_for_network(account: &Account, network_id: u64) -> Vec<BalanceSnapshot> {
+    account
+        .balance_snapshots
+        .iter()
+        .filter(|snapshot| snapshot.network_id == network_id)
+        .cloned()
+        .collect()
+}
+
+// The changes of the balance by the outputs of the network, ordered by timestamp. Outputs are received when they got
+// booked and spent with the milestone that spent them, or if that's unknown, when the transaction of the account that
+// spent them was created. Spent outputs without a known time are only corrected by the next snapshot.
+fn balance_changes(account: &Account, network_id: u64) -> Vec<BalanceChange> {
+    let mut spending_timestamps: HashMap<OutputId, u32> = HashMap::new();
+    for transaction in account.transactions.values() {
+        if transaction.network_id != network_id || transaction.inclusion_state != InclusionState::Confirmed {
+            continue;
+        }
+        let TransactionEssence::Regular(essence) = transaction.payload.essence();
+        for input in essence.inputs() {
+            if let Input::Utxo(input) = input {
+                spending_timestamps.insert(*input.output_id(), (transaction.timestamp / 1000) as u32);
+            }
+        }
+    }
+
+    let mut changes = Vec::new();
+    for output_data in account.outputs.values() {
+        if output_data.network_id != network_id {
+            continue;
+        }
+        changes.push(BalanceChange::new(
+            output_data.metadata.milestone_timestamp_booked,
+            &output_data.output,
+            true,
+        ));
+        if output_data.is_spent {
+            if let Some(timestamp) = output_data
+                .metadata
+                .milestone_timestamp_spent
+                .or_else(|| spending_timestamps.get(&output_data.output_id).copied())
+            {
+                changes.push(BalanceChange::new(timestamp, &output_data.output, false));
+            }
+        }
+    }
+    // Received outputs first, so a change can't go below zero if an output is received and spent in the same second
+    changes.sort_by_key(|change| (change.timestamp, !change.received));
+    changes
+}
+
+// The balance at the timestamp, starting from the latest snapshot at or before it and adding the later changes
+fn balance_at(
+    snapshots: &[BalanceSnapshot],
+    changes: &[BalanceChange],
+    network_id: u64,
+    timestamp: u32,
+) -> crate::Result<BalanceSnapshot> {
+    let snapshot = snapshots.iter().rev().find(|snapshot| snapshot.timestamp <= timestamp);
+    let mut balance = snapshot.map(Balance::from_snapshot).unwrap_or_default();
+    for change in changes {
+        if change.timestamp > timestamp {
+            break;
+        }
+        if snapshot.map_or(true, |snapshot| change.timestamp > snapshot.timestamp) {
+            balance.apply(change);
+        }
+    }
+    balance.into_snapshot(timestamp, network_id)
+}
+
+fn balance_history(
+    snapshots: &[BalanceSnapshot],
+    changes: &[BalanceChange],
+    network_id: u64,
+    start: Option<u32>,
+    end: Option<u32>,
+) -> crate::Result<Vec<BalanceSnapshot>> {
+    let in_range =
+        |timestamp: &u32| start.map_or(true, |start| *timestamp >= start) && end.map_or(true, |end| *timestamp <= end);
+    let mut timestamps = snapshots
+        .iter()
+        .map(|snapshot| snapshot.timestamp)
+        .chain(changes.iter().map(|change| change.timestamp))
+        .chain(start)
+        .filter(in_range)
+        .collect::<Vec<_>>();
+    timestamps.sort_unstable();
+    timestamps.dedup();
+
+    let mut history: Vec<BalanceSnapshot> = Vec::new();
+    for timestamp in timestamps {
+        let balance = balance_at(snapshots, changes, network_id, timestamp)?;
+        if history
+            .last()
+            .map_or(true, |previous| !previous.has_same_balance(&balance))
+        {
+            history.push(balance);
+        }
+    }
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NETWORK_ID: u64 = 1;
+
+    fn change(timestamp: u32, amount: u64, native_token_amount: u64, received: bool) -> BalanceChange {
+        BalanceChange {
+            timestamp,
+            amount,
+            native_tokens: if native_token_amount == 0 {
+                Vec::new()
+            } else {
+                vec![NativeToken::new(TokenId::null(), U256::from(native_token_amount)).unwrap()]
+            },
+            received,
+        }
+    }
+
+    fn snapshot(timestamp: u32, base_coin: u64) -> BalanceSnapshot {
+        BalanceSnapshot {
+            timestamp,
+            network_id: NETWORK_ID,
+            base_coin,
+            native_tokens: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn balance_from_changes() {
+        let changes = [
+            change(10, 1_000_000, 0, true),
+            change(20, 500_000, 100, true),
+            change(30, 1_000_000, 0, false),
+        ];
+
+        assert_eq!(balance_at(&[], &changes, NETWORK_ID, 5).unwrap().base_coin, 0);
+        assert_eq!(balance_at(&[], &changes, NETWORK_ID, 10).unwrap().base_coin, 1_000_000);
+        let balance = balance_at(&[], &changes, NETWORK_ID, 25).unwrap();
+        assert_eq!(balance.base_coin, 1_500_000);
+        assert_eq!(balance.native_tokens[0].amount(), &U256::from(100));
+        assert_eq!(balance_at(&[], &changes, NETWORK_ID, 30).unwrap().base_coin, 500_000);
+    }
+
+    #[test]
+    fn balance_from_snapshot() {
+        // The output received at 10 was spent without a known time, the snapshot at 40 doesn't contain it anymore
+        let changes = [change(10, 1_000_000, 0, true), change(50, 200_000, 0, true)];
+        let snapshots = [snapshot(40, 0)];
+
+        assert_eq!(
+            balance_at(&snapshots, &changes, NETWORK_ID, 30).unwrap().base_coin,
+            1_000_000
+        );
+        assert_eq!(balance_at(&snapshots, &changes, NETWORK_ID, 40).unwrap().base_coin, 0);
+        assert_eq!(
+            balance_at(&snapshots, &changes, NETWORK_ID, 60).unwrap().base_coin,
+            200_000
+        );
+    }
+
+    #[test]
+    fn history() {
+        let changes = [
+            change(10, 1_000_000, 0, true),
+            change(20, 1_000_000, 0, false),
+            change(20, 1_000_000, 0, true),
+            change(30, 500_000, 0, true),
+        ];
+        let snapshots = [snapshot(25, 1_000_000)];
+
+        let history = balance_history(&snapshots, &changes, NETWORK_ID, None, None).unwrap();
+        // The balance didn't change at 20 and 25
+        assert_eq!(
+            history
+                .iter()
+                .map(|balance| (balance.timestamp, balance.base_coin))
+                .collect::<Vec<_>>(),
+            [(10, 1_000_000), (30, 1_500_000)]
+        );
+
+        let history = balance_history(&snapshots, &changes, NETWORK_ID, Some(15), Some(29)).unwrap();
+        assert_eq!(
+            history
+                .iter()
+                .map(|balance| (balance.timestamp, balance.base_coin))
+                .collect::<Vec<_>>(),
+            [(15, 1_000_000)]
+        );
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::{
+    block::{
+        address::{Address, AliasAddress},
+        output::{AliasOutput, BasicOutput, FoundryId, FoundryOutput, NftOutput, Output, OutputId, TokenId},
+    },
+    node_api::indexer::query_parameters::QueryParameter,
+};
+use primitive_types::U256;
+
+use crate::{
+    account::{
+        constants::NATIVE_TOKEN_HOLDERS_PAGE_SIZE,
+        handle::AccountHandle,
+        types::{NativeTokenHolder, NativeTokenHolders, NativeTokenSupply},
+        Account,
+    },
+    Error,
+};
+
+impl AccountHandle {
+    /// Get the current supply of a native token. The foundry is taken from the unspent outputs if the account controls
+    /// it, otherwise it's requested from the node.
+    pub async fn native_token_supply(&self, token_id: &TokenId) -> crate::Result<NativeTokenSupply> {
+        let foundry_id = FoundryId::from(*token_id);
+        if let Some(foundry) = controlled_foundry(&*self.read().await, foundry_id) {
+            return Ok(NativeTokenSupply::new(&foundry, true));
+        }
+
+        let foundry = match self.client.foundry_output_id(foundry_id).await {
+            Ok(output_id) => self.client.get_output(&output_id).await?,
+            Err(iota_client::Error::NotFound(_)) => return Err(Error::FoundryNotFound(*token_id)),
+            Err(e) => return Err(e.into()),
+        };
+        let foundry = match Output::try_from_dto(&foundry.output, self.client.get_token_supply().await?)? {
+            Output::Foundry(foundry) => foundry,
+            _ => return Err(Error::FoundryNotFound(*token_id)),
+        };
+
+        Ok(NativeTokenSupply::new(&foundry, false))
+    }
+
+    /// Get the supply of all native tokens known to the account. The supply of tokens with a foundry controlled by
+    /// the account is current, the one of other tokens is from when their foundry was first requested.
+    pub async fn native_token_supplies(&self) -> Vec<NativeTokenSupply> {
+        let account = self.read().await;
+        let mut supplies = controlled_foundries(&account)
+            .map(|foundry| NativeTokenSupply::new(foundry, true))
+            .collect::<Vec<_>>();
+        for foundry in account.native_token_foundries.values() {
+            if !supplies.iter().any(|supply| supply.foundry_id == foundry.id()) {
+                supplies.push(NativeTokenSupply::new(foundry, false));
+            }
+        }
+        supplies.sort_by_key(|supply| supply.token_id);
+        supplies
+    }
+
+    /// Get a page of the addresses that hold a native token with a foundry controlled by the account. The indexer
+    /// can't filter by token id, so the unspent basic, alias and NFT outputs with native tokens are paged through one
+    /// after another. Start without cursor and pass the returned cursor to get the next page, until it's `None`.
+    /// The amounts are only summed within a page, an address with outputs in several pages is a holder in each of
+    /// them, so the pages need to be merged with [`NativeTokenHolder::aggregate()`] to get the total per address.
+    pub async fn native_token_holders(
+        &self,
+        token_id: &TokenId,
+        cursor: Option<String>,
+    ) -> crate::Result<NativeTokenHolders> {
+        log::debug!("[native_token_holders] {token_id} {cursor:?}");
+        if controlled_foundry(&*self.read().await, FoundryId::from(*token_id)).is_none() {
+            return Err(Error::FoundryNotControlled(*token_id));
+        }
+
+        let (output_kind, indexer_cursor) = match cursor {
+            Some(cursor) => parse_holders_cursor(&cursor)?,
+            None => (BasicOutput::KIND, String::new()),
+        };
+        let query_parameters = vec![QueryParameter::HasNativeTokens(true)];
+        let page_size = NATIVE_TOKEN_HOLDERS_PAGE_SIZE;
+        let response = match output_kind {
+            BasicOutput::KIND => {
+                self.request_limiter
+                    .request_output_ids_page(query_parameters, page_size, indexer_cursor, |query_parameters| {
+                        self.client.basic_output_ids(query_parameters)
+                    })
+                    .await?
+            }
+            AliasOutput::KIND => {
+                self.request_limiter
+                    .request_output_ids_page(query_parameters, page_size, indexer_cursor, |query_parameters| {
+                        self.client.alias_output_ids(query_parameters)
+                    })
+                    .await?
+            }
+            // The cursor was parsed, so the only other kind is an NFT output
+            _ => {
+                self.request_limiter
+                    .request_output_ids_page(query_parameters, page_size, indexer_cursor, |query_parameters| {
+                        self.client.nft_output_ids(query_parameters)
+                    })
+                    .await?
+            }
+        };
+
+        let token_supply = self.client.get_token_supply().await?;
+        let mut amounts = Vec::new();
+        for output_ids in response.items.chunks(self.request_limiter.max_parallel_requests()) {
+            let outputs = self.request_limiter.get_outputs(&self.client, output_ids).await?;
+            for output_response in outputs {
+                let output = Output::try_from_dto(&output_response.output, token_supply)?;
+                let output_id = output_response.metadata.output_id()?;
+                if let Some(holding) = token_holding(&output, &output_id, token_id) {
+                    amounts.push(holding);
+                }
+            }
+        }
+
+        Ok(NativeTokenHolders {
+            holders: NativeTokenHolder::from_amounts(amounts),
+            cursor: next_holders_cursor(output_kind, response.cursor),
+        })
+    }
+}
+
+// A holders cursor is the output kind and the indexer cursor for it, separated by a dot. An empty indexer cursor
+// requests the first page of the output kind.
+fn parse_holders_cursor(cursor: &str) -> crate::Result<(u8, String)> {
+    let (output_kind, indexer_cursor) = cursor
+        .split_once('.')
+        .ok_or_else(|| Error::InvalidOutputKind(cursor.to_string()))?;
+    match output_kind.parse::<u8>() {
+        Ok(output_kind @ (BasicOutput::KIND | AliasOutput::KIND | NftOutput::KIND)) => {
+            Ok((output_kind, indexer_cursor.to_string()))
+        }
+        _ => Err(Error::InvalidOutputKind(cursor.to_string())),
+    }
+}
+
+// Continue with the next page of the output kind, or the first page of the next output kind
+fn next_holders_cursor(output_kind: u8, indexer_cursor: Option<String>) -> Option<String> {
+    match (output_kind, indexer_cursor) {
+        (output_kind, Some(indexer_cursor)) => Some(format!("{output_kind}.{indexer_cursor}")),
+        (BasicOutput::KIND, None) => Some(format!("{}.", AliasOutput::KIND)),
+        (AliasOutput::KIND, None) => Some(format!("{}.", NftOutput::KIND)),
+        _ => None,
+    }
+}
+
+// The unspent foundry outputs whose alias is also an unspent output of the account, with an account address as state
+// controller
+fn controlled_foundries(account: &Account) -> impl Iterator<Item = &FoundryOutput> {
+    account
+        .unspent_outputs
+        .values()
+        .filter_map(|output_data| match &output_data.output {
+            Output::Foundry(foundry)
+                if account
+                    .unspent_outputs
+                    .values()
+                    .any(|alias_data| match &alias_data.output {
+                        Output::Alias(alias) => {
+                            alias.alias_id_non_null(&alias_data.output_id) == *foundry.alias_address().alias_id()
+                                && is_account_address(account, alias.state_controller_address())
+                        }
+                        _ => false,
+                    }) =>
+            {
+                Some(foundry)
+            }
+            _ => None,
+        })
+}
+
+pub(crate) fn controlled_foundry(account: &Account, foundry_id: FoundryId) -> Option<FoundryOutput> {
+    controlled_foundries(account)
+        .find(|foundry| foundry.id() == foundry_id)
+        .cloned()
+}
+
+fn is_account_address(account: &Account, address: &Address) -> bool {
+    account
+        .public_addresses
+        .iter()
+        .chain(account.internal_addresses.iter())
+        .any(|account_address| account_address.address.inner == *address)
+}
+
+// The address that can unlock the output and the amount of the token it holds
+fn token_holding(output: &Output, output_id: &OutputId, token_id: &TokenId) -> Option<(Address, U256)> {
+    let amount = output
+        .native_tokens()?
+        .iter()
+        .find(|native_token| native_token.token_id() == token_id)?
+        .amount();
+    let address = match output {
+        Output::Alias(alias) => Address::Alias(AliasAddress::from(alias.alias_id_non_null(output_id))),
+        _ => *output.unlock_conditions()?.address()?.address(),
+    };
+    Some((address, amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holders_cursor() {
+        assert_eq!(
+            parse_holders_cursor("3.0x1234.100").unwrap(),
+            (BasicOutput::KIND, "0x1234.100".to_string())
+        );
+        assert_eq!(parse_holders_cursor("4.").unwrap(), (AliasOutput::KIND, String::new()));
+        assert!(parse_holders_cursor("5.").is_err());
+        assert!(parse_holders_cursor("3").is_err());
+
+        assert_eq!(
+            next_holders_cursor(BasicOutput::KIND, Some("0x1234.100".to_string())).as_deref(),
+            Some("3.0x1234.100")
+        );
+        assert_eq!(next_holders_cursor(BasicOutput::KIND, None).as_deref(), Some("4."));
+        assert_eq!(next_holders_cursor(AliasOutput::KIND, None).as_deref(), Some("6."));
+        assert_eq!(next_holders_cursor(NftOutput::KIND, None), None);
+    }
+}
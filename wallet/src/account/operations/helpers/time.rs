@@ -1,10 +1,24 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use instant::SystemTime;
 use iota_client::block::{address::Address, output::AliasTransition};
 
 use crate::account::types::{AddressWithUnspentOutputs, OutputData};
 
+/// The current unix timestamp of the system in seconds
+pub(crate) fn unix_timestamp() -> u32 {
+    (unix_timestamp_millis() / 1000) as u32
+}
+
+/// The current unix timestamp of the system in milliseconds
+pub(crate) fn unix_timestamp_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("time went backwards")
+        .as_millis()
+}
+
 // Check if an output can be unlocked by one of the account addresses at the current time
 pub(crate) fn can_output_be_unlocked_now(
     // We use the addresses with unspent outputs, because other addresses of the account without unspent outputs can't
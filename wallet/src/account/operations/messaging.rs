@@ -0,0 +1,333 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use crypto::{
+    hashes::{blake2b::Blake2b256, Digest},
+    keys::{slip10::Chain, x25519},
+};
+use iota_client::{
+    api::PreparedTransactionData,
+    block::{
+        address::{Address, Ed25519Address},
+        output::{
+            feature::{Feature, TagFeature},
+            unlock_condition::AddressUnlockCondition,
+            BasicOutputBuilder, Output, UnlockCondition,
+        },
+        payload::{transaction::TransactionEssence, Payload, TaggedDataPayload},
+        signature::Signature,
+        unlock::Unlock,
+        BlockId,
+    },
+    secret::{SecretManage, SecretManager},
+};
+#[cfg(feature = "stronghold")]
+use iota_client::{storage::StorageProvider, stronghold::StrongholdAdapter};
+use zeroize::Zeroizing;
+
+use crate::{
+    account::{
+        handle::AccountHandle,
+        operations::syncing::request_limiter::RequestLimiter,
+        types::{
+            memo::{parse_messaging_key, MemoEnvelope},
+            Memo, Transaction, MEMO_TAG,
+        },
+        TransactionOptions,
+    },
+    Error,
+};
+
+// The change index of the chain the messaging key of an account is derived from, next to the public (0) and internal
+// (1) addresses, so it's never the key of an address
+const MESSAGING_KEY_CHANGE: u32 = 2;
+// The message that is signed with the key of the messaging chain, the messaging key is derived from the signature
+const MESSAGING_KEY_MESSAGE: &str = "WALLET_MESSAGING_KEY";
+// The key of the seed that the first version of the messaging stored in the Stronghold store, outside of the vault
+#[cfg(feature = "stronghold")]
+const MESSAGING_SEED_KEY: &str = "messaging_seed";
+
+impl AccountHandle {
+    /// Get the messaging key of the account, hex encoded. It's derived in the secret manager from the key of a
+    /// dedicated chain, so it's only available with an unlocked Stronghold or a mnemonic, other secret managers
+    /// return [`Error::MessagingKeyUnavailable`]. Others learn it from the memos the account sends in transactions.
+    pub async fn messaging_key(&self) -> crate::Result<String> {
+        Ok(prefix_hex::encode(
+            self.messaging_secret_key().await?.public_key().to_bytes(),
+        ))
+    }
+
+    /// Get the messaging key of an address from the memos in the transactions the account sent or received that the
+    /// address signed, the signature unlock of the address proves that the memo is from it.
+    pub async fn messaging_key_of(&self, address: &str) -> crate::Result<Option<String>> {
+        let (_, address) = Address::try_from_bech32(address)?;
+        let account = self.read().await;
+        Ok(account
+            .transactions
+            .values()
+            .chain(account.incoming_transactions.values())
+            .filter(|transaction| is_signed_by(transaction, &address))
+            .find_map(|transaction| memo_envelope(transaction)?.sender_key().map(ToString::to_string)))
+    }
+
+    /// Encrypt a memo to a messaging key, the tagged data payload can be attached to any transaction with
+    /// [`TransactionOptions::tagged_data_payload`]. A copy is encrypted to the messaging key of the account, if it's
+    /// available, so the account can read the memos it sent.
+    pub async fn prepare_memo(&self, messaging_key: &str, text: &str) -> crate::Result<TaggedDataPayload> {
+        let recipient_key = parse_messaging_key(messaging_key)?;
+        let sender_key = match self.messaging_secret_key().await {
+            Ok(secret_key) => Some(secret_key.public_key()),
+            Err(e) => {
+                log::debug!("[TRANSACTION] memo without copy for the sender: {e}");
+                None
+            }
+        };
+        MemoEnvelope::encrypt(&recipient_key, sender_key.as_ref(), text)?.to_payload()
+    }
+
+    /// Send an encrypted memo to an address, with an output of the minimum storage deposit. Without a messaging key
+    /// the key of the address is taken from the memos it sent in its transactions with the account.
+    /// ```ignore
+    /// let transaction = account
+    ///     .send_memo(
+    ///         "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu".to_string(),
+    ///         "thanks for the coffee".to_string(),
+    ///         None,
+    ///         None,
+    ///     )
+    ///     .await?;
+    /// println!("Transaction: {}", transaction.transaction_id);
+    /// ```
+    pub async fn send_memo(
+        &self,
+        address: String,
+        text: String,
+        messaging_key: Option<String>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<Transaction> {
+        let prepared_transaction = self.prepare_send_memo(address, text, messaging_key, options).await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.send_memo()](crate::account::handle::AccountHandle.send_memo)
+    pub async fn prepare_send_memo(
+        &self,
+        address: String,
+        text: String,
+        messaging_key: Option<String>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_send_memo");
+        let messaging_key = match messaging_key {
+            Some(messaging_key) => messaging_key,
+            None => self
+                .messaging_key_of(&address)
+                .await?
+                .ok_or_else(|| Error::MessagingKeyNotFound(address.clone()))?,
+        };
+        let tagged_data_payload = self.prepare_memo(&messaging_key, &text).await?;
+
+        let rent_structure = self.client.get_rent_structure().await?;
+        let token_supply = self.client.get_token_supply().await?;
+        // The tag marks the output, so the recipient requests the transaction with the memo during syncing
+        let output = BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure)?
+            .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(
+                Address::try_from_bech32(&address)?.1,
+            )))
+            .add_feature(Feature::Tag(TagFeature::new(MEMO_TAG.as_bytes().to_vec())?))
+            .finish_output(token_supply)?;
+
+        let options = TransactionOptions {
+            tagged_data_payload: Some(tagged_data_payload),
+            ..options.unwrap_or_default()
+        };
+        self.prepare_transaction(vec![output], Some(options)).await
+    }
+
+    /// Send an encrypted memo in a block without a transaction. Data blocks aren't signed, so the recipient can't
+    /// know which address sent it.
+    pub async fn send_memo_block(&self, messaging_key: &str, text: &str) -> crate::Result<BlockId> {
+        let tagged_data_payload = self.prepare_memo(messaging_key, text).await?;
+        Ok(self
+            .client
+            .block()
+            .finish_block(Some(Payload::TaggedData(Box::new(tagged_data_payload))))
+            .await?
+            .id())
+    }
+
+    /// Decrypt the memo of a tagged data payload, the account has to be the recipient or the sender. Memos can only be
+    /// decrypted while the messaging key is available, see [`AccountHandle::messaging_key()`].
+    pub async fn read_memo(&self, tagged_data_payload: &TaggedDataPayload) -> crate::Result<Memo> {
+        let envelope =
+            MemoEnvelope::from_payload(tagged_data_payload).ok_or(Error::Messaging("payload has no memo"))?;
+        envelope.decrypt(&self.messaging_secret_key().await?)
+    }
+
+    /// Read an encrypted memo from a block, the account has to be the sender or the recipient.
+    pub async fn read_memo_block(&self, block_id: &BlockId) -> crate::Result<Memo> {
+        let block = self.client.get_block(block_id).await?;
+        match block.payload() {
+            Some(Payload::TaggedData(tagged_data_payload)) => self.read_memo(tagged_data_payload).await,
+            _ => Err(Error::Messaging("block has no memo")),
+        }
+    }
+
+    /// Decrypt the memos of the transactions the account sent or received. The transactions of unspent outputs with
+    /// the memo tag are requested first, so memos are also found without syncing incoming transactions. Memos the
+    /// account can't decrypt are recorded, so they aren't tried again. If the messaging key isn't available it's only
+    /// logged, so syncing works with a locked Stronghold or another secret manager, and the memos are decrypted by a
+    /// later sync.
+    pub(crate) async fn decrypt_memos(&self, request_limiter: &RequestLimiter) -> crate::Result<()> {
+        let memo_transaction_ids = {
+            let account = self.read().await;
+            account
+                .unspent_outputs
+                .values()
+                .filter(|output_data| has_memo_tag(&output_data.output))
+                .map(|output_data| *output_data.output_id.transaction_id())
+                .collect::<HashSet<_>>()
+        };
+        if !memo_transaction_ids.is_empty() {
+            // Known transactions aren't requested again
+            self.request_incoming_transaction_data(memo_transaction_ids.into_iter().collect(), request_limiter)
+                .await?;
+        }
+
+        let envelopes = {
+            let account = self.read().await;
+            account
+                .transactions
+                .values()
+                .chain(account.incoming_transactions.values())
+                .filter(|transaction| {
+                    transaction.memo.is_none()
+                        && !account
+                            .undecryptable_memo_transactions
+                            .contains(&transaction.transaction_id)
+                })
+                .filter_map(|transaction| Some((transaction.transaction_id, memo_envelope(transaction)?)))
+                .collect::<Vec<_>>()
+        };
+        if envelopes.is_empty() {
+            return Ok(());
+        }
+
+        let secret_key = match self.messaging_secret_key().await {
+            Ok(secret_key) => secret_key,
+            Err(e) => {
+                log::debug!("[SYNC] can't decrypt memos: {e}");
+                return Ok(());
+            }
+        };
+        let mut memos = Vec::new();
+        let mut undecryptable_transaction_ids = Vec::new();
+        for (transaction_id, envelope) in envelopes {
+            match envelope.decrypt(&secret_key) {
+                Ok(memo) => memos.push((transaction_id, memo)),
+                Err(e) => {
+                    log::debug!("[SYNC] can't decrypt the memo of {transaction_id}: {e}");
+                    undecryptable_transaction_ids.push(transaction_id);
+                }
+            }
+        }
+
+        let mut account = self.write().await;
+        for (transaction_id, memo) in memos {
+            if let Some(transaction) = account.transactions.get_mut(&transaction_id) {
+                transaction.memo = Some(memo.clone());
+            }
+            if let Some(transaction) = account.incoming_transactions.get_mut(&transaction_id) {
+                transaction.memo = Some(memo);
+            }
+        }
+        account
+            .undecryptable_memo_transactions
+            .extend(undecryptable_transaction_ids);
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+
+        Ok(())
+    }
+
+    // The secret key of the messaging key of the account. The secret manager signs a fixed message with the key of a
+    // dedicated chain and the secret key is the hash of the signature, so the private key never leaves the Stronghold
+    // vault. It isn't kept, so it's not available after the Stronghold password was cleared.
+    async fn messaging_secret_key(&self) -> crate::Result<x25519::SecretKey> {
+        let chain = {
+            let account = self.read().await;
+            Chain::from_u32_hardened(vec![44, account.coin_type, account.index, MESSAGING_KEY_CHANGE, 0])
+        };
+        let message: [u8; 32] = Blake2b256::digest(MESSAGING_KEY_MESSAGE.as_bytes()).into();
+        let unlock = {
+            let secret_manager = self.secret_manager.read().await;
+            match &*secret_manager {
+                #[cfg(feature = "stronghold")]
+                SecretManager::Stronghold(_) => {}
+                SecretManager::Mnemonic(_) => {}
+                // The Ledger Nano only signs transactions
+                _ => return Err(Error::MessagingKeyUnavailable),
+            }
+            secret_manager.signature_unlock(&message, &chain).await?
+        };
+        let signature = match unlock {
+            Unlock::Signature(signature_unlock) => {
+                let Signature::Ed25519(signature) = signature_unlock.signature();
+                Zeroizing::new(*signature.signature())
+            }
+            _ => return Err(Error::MessagingKeyUnavailable),
+        };
+
+        #[cfg(feature = "stronghold")]
+        if let SecretManager::Stronghold(stronghold) = &mut *self.secret_manager.write().await {
+            remove_messaging_seed(stronghold).await?;
+        }
+
+        let mut hasher = Blake2b256::new();
+        hasher.update(MESSAGING_KEY_MESSAGE.as_bytes());
+        hasher.update(signature.as_slice());
+        let secret = Zeroizing::new(<[u8; 32]>::from(hasher.finalize()));
+        Ok(x25519::SecretKey::from_bytes(*secret))
+    }
+}
+
+// Removes the seed of wallets whose mnemonic was stored with the first version of the messaging, it was readable by
+// anyone who could open the Stronghold store
+#[cfg(feature = "stronghold")]
+async fn remove_messaging_seed(stronghold: &mut StrongholdAdapter) -> crate::Result<()> {
+    if stronghold.delete(MESSAGING_SEED_KEY.as_bytes()).await?.is_some() {
+        stronghold.write_stronghold_snapshot(None).await?;
+    }
+    Ok(())
+}
+
+fn memo_envelope(transaction: &Transaction) -> Option<MemoEnvelope> {
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    match essence.payload() {
+        Some(Payload::TaggedData(tagged_data_payload)) => MemoEnvelope::from_payload(tagged_data_payload),
+        _ => None,
+    }
+}
+
+fn has_memo_tag(output: &Output) -> bool {
+    matches!(output, Output::Basic(_))
+        && output
+            .features()
+            .and_then(|features| features.tag())
+            .map_or(false, |tag| tag.tag() == MEMO_TAG.as_bytes())
+}
+
+// Whether the address signed one of the inputs of a transaction
+fn is_signed_by(transaction: &Transaction, address: &Address) -> bool {
+    transaction.payload.unlocks().iter().any(|unlock| match unlock {
+        Unlock::Signature(signature_unlock) => {
+            let Signature::Ed25519(signature) = signature_unlock.signature();
+            let signer = Address::Ed25519(Ed25519Address::new(Blake2b256::digest(signature.public_key()).into()));
+            signer == *address
+        }
+        _ => false,
+    })
+}
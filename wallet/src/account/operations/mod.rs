@@ -5,8 +5,18 @@
 pub(crate) mod address_generation;
 /// The module to get the accounts balance
 pub(crate) mod balance;
+/// The module for the balance history
+pub(crate) mod balance_history;
+/// The module to inspect native token foundries
+pub(crate) mod foundry;
 /// Helper functions
 pub(crate) mod helpers;
+/// The module for encrypted memos
+pub(crate) mod messaging;
+/// The module for the native token metadata
+pub(crate) mod native_token_metadata;
+/// The module to query NFTs with their metadata
+pub(crate) mod nft;
 /// The module for claiming of outputs with
 /// [`UnlockCondition`](iota_client::block::output::UnlockCondition)s that aren't only
 /// [`AddressUnlockCondition`](iota_client::block::output::unlock_condition::AddressUnlockCondition)
@@ -15,6 +25,8 @@ pub(crate) mod output_claiming;
 pub(crate) mod output_consolidation;
 /// The module to find additional addresses with unspent outputs
 pub(crate) mod output_finder;
+/// The module for payment requests
+pub(crate) mod payment_request;
 /// The module for participation
 #[cfg(feature = "participation")]
 pub(crate) mod participation;
@@ -0,0 +1,97 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use iota_client::block::output::TokenId;
+
+use crate::account::{
+    handle::AccountHandle,
+    types::{Irc30Metadata, NativeTokenMetadata, NativeTokenMetadataOverrides, MAX_NATIVE_TOKEN_DECIMALS},
+    Account,
+};
+
+impl AccountHandle {
+    /// Get the metadata of a native token, parsed from its foundry during syncing and set by the user.
+    pub async fn native_token_metadata(&self, token_id: &TokenId) -> Option<NativeTokenMetadata> {
+        self.read().await.native_token_metadata.get(token_id).cloned()
+    }
+
+    /// Get the metadata of all native tokens known to the account.
+    pub async fn native_tokens_metadata(&self) -> HashMap<TokenId, NativeTokenMetadata> {
+        self.read().await.native_token_metadata.clone()
+    }
+
+    /// Set values that replace the ones of the IRC30 metadata of a native token, also for tokens without a known
+    /// foundry. Default overrides remove the previous ones. Returns an error if the decimals are more than
+    /// [`MAX_NATIVE_TOKEN_DECIMALS`].
+    pub async fn set_native_token_metadata_overrides(
+        &self,
+        token_id: TokenId,
+        overrides: NativeTokenMetadataOverrides,
+    ) -> crate::Result<NativeTokenMetadata> {
+        log::debug!("[set_native_token_metadata_overrides] {token_id}");
+        if let Some(decimals) = overrides
+            .decimals
+            .filter(|decimals| *decimals > MAX_NATIVE_TOKEN_DECIMALS)
+        {
+            return Err(crate::Error::InvalidNativeTokenDecimals(decimals));
+        }
+        self.update_native_token_metadata(token_id, |metadata| metadata.overrides = overrides)
+            .await
+    }
+
+    /// Mark a native token as trusted or untrusted.
+    pub async fn set_native_token_trusted(
+        &self,
+        token_id: TokenId,
+        trusted: bool,
+    ) -> crate::Result<NativeTokenMetadata> {
+        log::debug!("[set_native_token_trusted] {token_id}: {trusted}");
+        self.update_native_token_metadata(token_id, |metadata| metadata.trusted = trusted)
+            .await
+    }
+
+    async fn update_native_token_metadata(
+        &self,
+        token_id: TokenId,
+        update: impl FnOnce(&mut NativeTokenMetadata) + Send,
+    ) -> crate::Result<NativeTokenMetadata> {
+        let mut account = self.write().await;
+        let metadata = account.native_token_metadata.entry(token_id).or_default();
+        update(metadata);
+        let metadata = metadata.clone();
+
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+
+        Ok(metadata)
+    }
+}
+
+// Parse the IRC30 metadata of the native token foundries, keeping the user defined values
+pub(crate) fn update_irc30_metadata(account: &mut Account) {
+    let Account {
+        native_token_foundries,
+        native_token_metadata,
+        ..
+    } = account;
+    for foundry in native_token_foundries.values() {
+        let irc30 = Irc30Metadata::from_foundry(foundry);
+        let token_id = TokenId::from(foundry.id());
+        match native_token_metadata.get_mut(&token_id) {
+            Some(metadata) => metadata.irc30 = irc30,
+            None => {
+                if irc30.is_some() {
+                    native_token_metadata.insert(
+                        token_id,
+                        NativeTokenMetadata {
+                            irc30,
+                            ..Default::default()
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::block::output::{NftId, Output};
+
+use crate::account::{
+    handle::AccountHandle,
+    types::{Irc27Metadata, NftCollection, OwnedNft},
+    Account,
+};
+
+impl AccountHandle {
+    /// Get the unspent NFTs of the account with their decoded metadata.
+    pub async fn nfts(&self) -> crate::Result<Vec<OwnedNft>> {
+        let network_id = self.client.get_network_id().await?;
+        Ok(owned_nfts(&*self.read().await, network_id))
+    }
+
+    /// Get an unspent NFT of the account with its decoded metadata.
+    pub async fn nft(&self, nft_id: &NftId) -> crate::Result<Option<OwnedNft>> {
+        Ok(self.nfts().await?.into_iter().find(|nft| nft.nft_id == *nft_id))
+    }
+
+    /// Get the unspent NFTs of the account grouped by their issuer. For NFTs issued by a collection NFT, the IRC27
+    /// metadata of the collection NFT is added if the account knows an output of it.
+    pub async fn nft_collections(&self) -> crate::Result<Vec<NftCollection>> {
+        let network_id = self.client.get_network_id().await?;
+        let account = self.read().await;
+        Ok(group_by_issuer(owned_nfts(&account, network_id), |collection_id| {
+            collection_metadata(&account, collection_id)
+        }))
+    }
+}
+
+fn owned_nfts(account: &Account, network_id: u64) -> Vec<OwnedNft> {
+    let mut nfts = account
+        .unspent_outputs
+        .values()
+        .filter(|output_data| output_data.network_id == network_id)
+        .filter_map(|output_data| match &output_data.output {
+            Output::Nft(nft) => Some(OwnedNft::new(output_data.output_id, nft)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    nfts.sort_by_key(|nft| nft.nft_id);
+    nfts
+}
+
+// The IRC27 metadata of the latest known output of the collection NFT, it's immutable so spent outputs are fine
+fn collection_metadata(account: &Account, collection_id: &NftId) -> Option<Irc27Metadata> {
+    account
+        .outputs
+        .values()
+        .filter_map(|output_data| match &output_data.output {
+            Output::Nft(nft) if nft.nft_id_non_null(&output_data.output_id) == *collection_id => Some(nft),
+            _ => None,
+        })
+        .find_map(Irc27Metadata::from_nft)
+}
+
+// Group the NFTs by their issuer, keeping the order in which the issuers are found
+fn group_by_issuer(
+    nfts: Vec<OwnedNft>,
+    collection_metadata: impl Fn(&NftId) -> Option<Irc27Metadata>,
+) -> Vec<NftCollection> {
+    let mut collections: Vec<NftCollection> = Vec::new();
+    for nft in nfts {
+        match collections
+            .iter_mut()
+            .find(|collection| collection.issuer == nft.issuer)
+        {
+            Some(collection) => collection.nfts.push(nft),
+            None => collections.push(NftCollection {
+                issuer: nft.issuer,
+                collection_id: nft.collection_id,
+                collection_metadata: nft.collection_id.as_ref().and_then(&collection_metadata),
+                nfts: vec![nft],
+            }),
+        }
+    }
+    collections
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_client::block::{
+        address::{Address, Ed25519Address, NftAddress},
+        output::OutputId,
+        payload::transaction::TransactionId,
+    };
+
+    use super::*;
+
+    fn nft(index: u16, issuer: Option<Address>) -> OwnedNft {
+        let output_id = OutputId::new(TransactionId::new([1; 32]), index).unwrap();
+        OwnedNft {
+            nft_id: NftId::from(&output_id),
+            output_id,
+            issuer,
+            collection_id: match issuer {
+                Some(Address::Nft(nft_address)) => Some(*nft_address.nft_id()),
+                _ => None,
+            },
+            metadata: None,
+            immutable_metadata: None,
+            irc27_metadata: None,
+        }
+    }
+
+    #[test]
+    fn group_nfts_by_issuer() {
+        let collection_id = NftId::new([2; 32]);
+        let collection_issuer = Address::Nft(NftAddress::new(collection_id));
+        let ed25519_issuer = Address::Ed25519(Ed25519Address::new([3; 32]));
+        let nfts = vec![
+            nft(0, Some(collection_issuer)),
+            nft(1, None),
+            nft(2, Some(ed25519_issuer)),
+            nft(3, Some(collection_issuer)),
+        ];
+
+        let collections = group_by_issuer(nfts, |nft_id| {
+            (*nft_id == collection_id).then(|| Irc27Metadata::new("image/png", "https://x.y/og.png", "Shimmer OG"))
+        });
+
+        assert_eq!(collections.len(), 3);
+        assert_eq!(collections[0].collection_id, Some(collection_id));
+        assert_eq!(
+            collections[0]
+                .collection_metadata
+                .as_ref()
+                .map(|metadata| metadata.name.as_str()),
+            Some("Shimmer OG")
+        );
+        assert_eq!(collections[0].nfts.len(), 2);
+        assert_eq!(collections[1].issuer, None);
+        assert_eq!(collections[2].issuer, Some(ed25519_issuer));
+        assert_eq!(collections[2].collection_id, None);
+        assert!(collections[2].collection_metadata.is_none());
+    }
+}
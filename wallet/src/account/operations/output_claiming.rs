@@ -4,7 +4,7 @@
 use std::collections::{HashMap, HashSet};
 
 use iota_client::{
-    api::input_selection::minimum_storage_deposit_basic_output,
+    api::{input_selection::minimum_storage_deposit_basic_output, PreparedTransactionData},
     block::{
         address::Address,
         output::{
@@ -182,17 +182,35 @@ impl AccountHandle {
     /// from [`AccountHandle::get_unlockable_outputs_with_additional_unlock_conditions()`].
     pub async fn claim_outputs(&self, output_ids_to_claim: Vec<OutputId>) -> crate::Result<Transaction> {
         log::debug!("[OUTPUT_CLAIMING] claim_outputs");
+        let prepared_transaction = self.prepare_claim_outputs(output_ids_to_claim).await?;
+        let claim_tx = self.sign_and_submit_transaction(prepared_transaction).await?;
+
+        log::debug!(
+            "[OUTPUT_CLAIMING] Claiming transaction created: block_id: {:?} tx_id: {:?}",
+            claim_tx.block_id,
+            claim_tx.transaction_id
+        );
+        Ok(claim_tx)
+    }
+
+    /// Function to prepare the transaction for [`AccountHandle::claim_outputs()`]
+    pub async fn prepare_claim_outputs(
+        &self,
+        output_ids_to_claim: Vec<OutputId>,
+    ) -> crate::Result<PreparedTransactionData> {
         let basic_outputs = self.get_basic_outputs_for_additional_inputs().await?;
-        self.claim_outputs_internal(output_ids_to_claim, basic_outputs).await
+        self.prepare_claim_outputs_internal(output_ids_to_claim, basic_outputs)
+            .await
     }
 
-    /// Try to claim basic outputs that have additional unlock conditions to their [AddressUnlockCondition].
-    pub(crate) async fn claim_outputs_internal(
+    /// Prepare a transaction that claims basic outputs that have additional unlock conditions to their
+    /// [AddressUnlockCondition].
+    pub(crate) async fn prepare_claim_outputs_internal(
         &self,
         output_ids_to_claim: Vec<OutputId>,
         mut possible_additional_inputs: Vec<OutputData>,
-    ) -> crate::Result<Transaction> {
-        log::debug!("[OUTPUT_CLAIMING] claim_outputs_internal");
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[OUTPUT_CLAIMING] prepare_claim_outputs_internal");
 
         let current_time = self.client.get_time_checked().await?;
         let rent_structure = self.client.get_rent_structure().await?;
@@ -374,29 +392,21 @@ impl AccountHandle {
             })?;
         }
 
-        let claim_tx = self
-            .finish_transaction(
-                outputs_to_send,
-                Some(TransactionOptions {
-                    custom_inputs: Some(
-                        outputs_to_claim
-                            .iter()
-                            .map(|o| o.output_id)
-                            // add additional inputs
-                            .chain(additional_inputs)
-                            .collect::<Vec<OutputId>>(),
-                    ),
-                    ..Default::default()
-                }),
-            )
-            .await?;
-
-        log::debug!(
-            "[OUTPUT_CLAIMING] Claiming transaction created: block_id: {:?} tx_id: {:?}",
-            claim_tx.block_id,
-            claim_tx.transaction_id
-        );
-        Ok(claim_tx)
+        self.prepare_transaction(
+            outputs_to_send,
+            Some(TransactionOptions {
+                custom_inputs: Some(
+                    outputs_to_claim
+                        .iter()
+                        .map(|o| o.output_id)
+                        // add additional inputs
+                        .chain(additional_inputs)
+                        .collect::<Vec<OutputId>>(),
+                ),
+                ..Default::default()
+            }),
+        )
+        .await
     }
 }
 
@@ -1,15 +1,18 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_client::block::{
-    input::INPUT_COUNT_MAX,
-    output::{
-        unlock_condition::{AddressUnlockCondition, UnlockCondition},
-        BasicOutputBuilder, NativeTokens, NativeTokensBuilder, Output,
-    },
-};
 #[cfg(feature = "ledger_nano")]
 use iota_client::secret::SecretManager;
+use iota_client::{
+    api::PreparedTransactionData,
+    block::{
+        input::INPUT_COUNT_MAX,
+        output::{
+            unlock_condition::{AddressUnlockCondition, UnlockCondition},
+            BasicOutputBuilder, NativeTokens, NativeTokensBuilder, Output,
+        },
+    },
+};
 
 // Constants for the calculation of the amount of inputs we can use with a ledger nano
 #[cfg(feature = "ledger_nano")]
@@ -71,6 +74,26 @@ impl AccountHandle {
         force: bool,
         output_consolidation_threshold: Option<usize>,
     ) -> Result<Transaction> {
+        let prepared_transaction = self
+            .prepare_consolidate_outputs(force, output_consolidation_threshold)
+            .await?;
+        let consolidation_tx = self.sign_and_submit_transaction(prepared_transaction).await?;
+
+        log::debug!(
+            "[OUTPUT_CONSOLIDATION] consolidation transaction created: block_id: {:?} tx_id: {:?}",
+            consolidation_tx.block_id,
+            consolidation_tx.transaction_id
+        );
+
+        Ok(consolidation_tx)
+    }
+
+    /// Function to prepare the transaction for [`AccountHandle::consolidate_outputs()`]
+    pub async fn prepare_consolidate_outputs(
+        &self,
+        force: bool,
+        output_consolidation_threshold: Option<usize>,
+    ) -> Result<PreparedTransactionData> {
         log::debug!("[OUTPUT_CONSOLIDATION] consolidating outputs if needed");
         #[cfg(feature = "participation")]
         let voting_output = self.get_voting_output().await?;
@@ -171,22 +194,13 @@ impl AccountHandle {
                 .finish_output(token_supply)?,
         ];
 
-        let consolidation_tx = self
-            .finish_transaction(
-                consolidation_output,
-                Some(TransactionOptions {
-                    custom_inputs: Some(custom_inputs),
-                    ..Default::default()
-                }),
-            )
-            .await?;
-
-        log::debug!(
-            "[OUTPUT_CONSOLIDATION] consolidation transaction created: block_id: {:?} tx_id: {:?}",
-            consolidation_tx.block_id,
-            consolidation_tx.transaction_id
-        );
-
-        Ok(consolidation_tx)
+        self.prepare_transaction(
+            consolidation_output,
+            Some(TransactionOptions {
+                custom_inputs: Some(custom_inputs),
+                ..Default::default()
+            }),
+        )
+        .await
     }
 }
@@ -8,6 +8,8 @@ use crate::account::{
     operations::{address_generation::AddressGenerationOptions, syncing::SyncOptions},
     types::AddressWithUnspentOutputs,
 };
+#[cfg(feature = "events")]
+use crate::events::types::SyncProgressEvent;
 
 impl AccountHandle {
     /// Search addresses with unspent outputs
@@ -82,6 +84,10 @@ impl AccountHandle {
 
         let mut address_gap_limit_internal = address_gap_limit;
 
+        // The indexes are shadowed in the loop, but needed to clean up if syncing fails
+        let (old_highest_public_address_index, old_highest_internal_address_index) =
+            (highest_public_address_index, highest_internal_address_index);
+
         let mut latest_outputs_count = 0;
         loop {
             // Also needs to be in the loop so it gets updated every round for internal use without modifying the values
@@ -100,6 +106,12 @@ impl AccountHandle {
             log::debug!(
                 "[search_addresses_with_outputs] address_gap_limit: {address_gap_limit}, address_gap_limit_internal: {address_gap_limit_internal}"
             );
+            #[cfg(feature = "events")]
+            self.emit_sync_progress(SyncProgressEvent::SearchingAddresses {
+                address_gap_limit,
+                address_gap_limit_internal,
+            })
+            .await;
             // generate public and internal addresses
             let addresses = self
                 .generate_addresses(
@@ -141,6 +153,8 @@ impl AccountHandle {
                     sync_options.force_syncing = true;
                     sync_options.address_start_index = address_start_index;
                     sync_options.address_start_index_internal = address_start_index_internal;
+                    // The addresses are already generated here
+                    sync_options.address_gap_limit = None;
                     Some(sync_options.clone())
                 }
                 None => Some(SyncOptions {
@@ -152,7 +166,12 @@ impl AccountHandle {
                 }),
             };
 
-            self.sync(sync_options).await?;
+            if let Err(error) = self.sync(sync_options).await {
+                // Don't keep the generated addresses if syncing failed or got cancelled
+                self.clean_account_after_recovery(old_highest_public_address_index, old_highest_internal_address_index)
+                    .await;
+                return Err(error);
+            }
 
             let output_count = self.read().await.unspent_outputs.len();
 
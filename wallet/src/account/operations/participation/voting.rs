@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use iota_client::{
+    api::PreparedTransactionData,
     api_types::plugins::participation::types::{
         Participation, ParticipationEventId, Participations, PARTICIPATION_TAG,
     },
@@ -34,6 +35,16 @@ impl AccountHandle {
     /// This is an add OR update function, not just add.
     /// This should use regular client options, NOT specific node for the event.
     pub async fn vote(&self, event_id: Option<ParticipationEventId>, answers: Option<Vec<u8>>) -> Result<Transaction> {
+        let prepared_transaction = self.prepare_vote(event_id, answers).await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for [`AccountHandle::vote()`]
+    pub async fn prepare_vote(
+        &self,
+        event_id: Option<ParticipationEventId>,
+        answers: Option<Vec<u8>>,
+    ) -> Result<PreparedTransactionData> {
         if let Some(event_id) = event_id {
             let event_status = self.get_participation_event_status(&event_id).await?;
 
@@ -91,7 +102,7 @@ impl AccountHandle {
             ])
             .finish_output(self.client().get_token_supply().await?)?;
 
-        self.send(
+        self.prepare_transaction(
             vec![new_output],
             Some(TransactionOptions {
                 // Only use previous voting output as input.
@@ -116,6 +127,12 @@ impl AccountHandle {
     /// If multiple outputs contain metadata for this event, removes all of them.
     /// If NOT already voting for this event, throws an error (e.g. output with this event ID not found).
     pub async fn stop_participating(&self, event_id: ParticipationEventId) -> Result<Transaction> {
+        let prepared_transaction = self.prepare_stop_participating(event_id).await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for [`AccountHandle::stop_participating()`]
+    pub async fn prepare_stop_participating(&self, event_id: ParticipationEventId) -> Result<PreparedTransactionData> {
         let voting_output = self
             .get_voting_output()
             .await?
@@ -161,7 +178,7 @@ impl AccountHandle {
             ])
             .finish_output(self.client().get_token_supply().await?)?;
 
-        self.send(
+        self.prepare_transaction(
             vec![new_output],
             Some(TransactionOptions {
                 // Only use previous voting output as input.
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use iota_client::{
+    api::PreparedTransactionData,
     api_types::plugins::participation::types::{Participations, PARTICIPATION_TAG},
     block::{
         output::{
@@ -39,6 +40,12 @@ impl AccountHandle {
     /// Prioritizes consuming outputs that are designated for voting but don't have any metadata (only possible if user
     /// increases voting power then increases again immediately after).
     pub async fn increase_voting_power(&self, amount: u64) -> Result<Transaction> {
+        let prepared_transaction = self.prepare_increase_voting_power(amount).await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for [`AccountHandle::increase_voting_power()`]
+    pub async fn prepare_increase_voting_power(&self, amount: u64) -> Result<PreparedTransactionData> {
         let token_supply = self.client().get_token_supply().await?;
 
         let (new_output, tx_options) = match self.get_voting_output().await? {
@@ -77,7 +84,7 @@ impl AccountHandle {
             ),
         };
 
-        self.send(vec![new_output], tx_options).await
+        self.prepare_transaction(vec![new_output], tx_options).await
     }
 
     /// Reduces an account's "voting power" by a given amount.
@@ -90,6 +97,12 @@ impl AccountHandle {
     /// Prioritizes consuming outputs that are designated for voting but don't have any metadata (only possible if user
     /// increases voting power then decreases immediately after).
     pub async fn decrease_voting_power(&self, amount: u64) -> Result<Transaction> {
+        let prepared_transaction = self.prepare_decrease_voting_power(amount).await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for [`AccountHandle::decrease_voting_power()`]
+    pub async fn prepare_decrease_voting_power(&self, amount: u64) -> Result<PreparedTransactionData> {
         let token_supply = self.client().get_token_supply().await?;
         let current_output_data = self
             .get_voting_output()
@@ -115,7 +128,7 @@ impl AccountHandle {
             (new_output, Some(tagged_data_payload))
         };
 
-        self.send(
+        self.prepare_transaction(
             vec![new_output],
             Some(TransactionOptions {
                 // Use the previous voting output and additionally others for possible additional required amount for
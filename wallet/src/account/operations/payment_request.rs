@@ -0,0 +1,197 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use primitive_types::U256;
+
+use crate::account::{
+    handle::AccountHandle,
+    operations::helpers::time::unix_timestamp,
+    types::{OutputData, PaymentRequest, PaymentRequestOptions, PaymentRequestStatus, PaymentUri},
+    Account,
+};
+
+impl AccountHandle {
+    /// Create a payment request. Without a tag, a new public address is generated, so every payment to it belongs to
+    /// the request. With a tag, the request is bound to the first public address and only outputs with this tag are
+    /// counted, so only one open request can have the same tag. The status is updated when the account is synced.
+    pub async fn create_payment_request(&self, options: PaymentRequestOptions) -> crate::Result<PaymentRequest> {
+        log::debug!("[create_payment_request]");
+
+        let address = match options.tag {
+            Some(_) => self
+                .read()
+                .await
+                .public_addresses
+                .first()
+                .ok_or_else(|| crate::Error::AddressNotFoundInAccount("first public address".to_string()))?
+                .address
+                .clone(),
+            None => self.generate_addresses(1, None).await?[0].address.clone(),
+        };
+
+        let mut account = self.write().await;
+        if let Some(tag) = &options.tag {
+            if account
+                .payment_requests
+                .values()
+                .any(|payment_request| payment_request.is_open() && payment_request.tag.as_ref() == Some(tag))
+            {
+                return Err(crate::Error::PaymentRequestTagInUse(prefix_hex::encode(tag)));
+            }
+        }
+        let id = account.next_payment_request_id;
+        account.next_payment_request_id += 1;
+        let payment_request = PaymentRequest {
+            id,
+            address,
+            tag: options.tag,
+            amount: options.amount,
+            native_token: options.native_token,
+            memo: options.memo,
+            created_at: unix_timestamp(),
+            expiration: options.expiration,
+            status: PaymentRequestStatus::Pending,
+            received_amount: 0,
+            received_native_token_amount: U256::zero(),
+            output_ids: Vec::new(),
+        };
+        account
+            .payment_requests
+            .insert(payment_request.id, payment_request.clone());
+
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+
+        Ok(payment_request)
+    }
+
+    /// Get a payment request by its id.
+    pub async fn get_payment_request(&self, id: u32) -> crate::Result<PaymentRequest> {
+        self.read()
+            .await
+            .payment_requests
+            .get(&id)
+            .cloned()
+            .ok_or(crate::Error::PaymentRequestNotFound(id))
+    }
+
+    /// Returns all payment requests of the account, ordered by id.
+    pub async fn payment_requests(&self) -> Vec<PaymentRequest> {
+        let mut payment_requests: Vec<PaymentRequest> = self.read().await.payment_requests.values().cloned().collect();
+        payment_requests.sort_by_key(|payment_request| payment_request.id);
+        payment_requests
+    }
+
+    /// Remove a payment request, received outputs aren't affected.
+    pub async fn cancel_payment_request(&self, id: u32) -> crate::Result<()> {
+        let mut account = self.write().await;
+        account
+            .payment_requests
+            .remove(&id)
+            .ok_or(crate::Error::PaymentRequestNotFound(id))?;
+
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+
+        Ok(())
+    }
+
+    /// Parse a payment URI and check that the address is for the network of the client.
+    pub async fn parse_payment_uri(&self, uri: &str) -> crate::Result<PaymentUri> {
+        let bech32_hrp = self.client.get_bech32_hrp().await?;
+        PaymentUri::parse(uri, &bech32_hrp)
+    }
+}
+
+/// Adds the new outputs that were booked before the expiration to the open payment requests they pay and expires the
+/// unpaid ones, once the `milestone_timestamp` of the node reached the expiration. Returns the payment requests with a
+/// changed status.
+pub(crate) fn update_payment_requests(
+    account: &mut Account,
+    new_outputs: &[OutputData],
+    milestone_timestamp: Option<u32>,
+) -> Vec<PaymentRequest> {
+    let mut changed_payment_requests = Vec::new();
+
+    for payment_request in account.payment_requests.values_mut() {
+        if !payment_request.is_open() {
+            continue;
+        }
+        let previous_status = payment_request.status;
+
+        for output_data in new_outputs {
+            // Ignore our own transactions, they could have the same tag
+            if output_data.remainder
+                || account
+                    .transactions
+                    .contains_key(output_data.output_id.transaction_id())
+                || payment_request.output_ids.contains(&output_data.output_id)
+                || payment_request.expiration.map_or(false, |expiration| {
+                    output_data.metadata.milestone_timestamp_booked >= expiration
+                })
+            {
+                continue;
+            }
+            if let Some(amount) = payment_amount(payment_request, output_data) {
+                payment_request.received_amount += amount;
+                if let (Some(requested_native_token), Some(native_tokens)) =
+                    (&payment_request.native_token, output_data.output.native_tokens())
+                {
+                    for native_token in native_tokens.iter() {
+                        if native_token.token_id() == requested_native_token.token_id() {
+                            payment_request.received_native_token_amount += *native_token.amount();
+                        }
+                    }
+                }
+                payment_request.output_ids.push(output_data.output_id);
+            }
+        }
+
+        payment_request.status = payment_request.status_from_received();
+        if payment_request.is_open()
+            && matches!(
+                (payment_request.expiration, milestone_timestamp),
+                (Some(expiration), Some(milestone_timestamp)) if milestone_timestamp >= expiration
+            )
+        {
+            payment_request.status = PaymentRequestStatus::Expired;
+        }
+
+        if payment_request.status != previous_status {
+            log::debug!(
+                "[SYNC] payment request {} changed to {:?}",
+                payment_request.id,
+                payment_request.status
+            );
+            changed_payment_requests.push(payment_request.clone());
+        }
+    }
+
+    changed_payment_requests
+}
+
+// The base coin amount an output pays to a request, without a storage deposit that needs to be returned. Outputs with
+// an expiration or timelock aren't counted, because the funds aren't available for sure.
+fn payment_amount(payment_request: &PaymentRequest, output_data: &OutputData) -> Option<u64> {
+    let unlock_conditions = output_data.output.unlock_conditions()?;
+    if unlock_conditions.address()?.address() != &payment_request.address.inner
+        || unlock_conditions.expiration().is_some()
+        || unlock_conditions.timelock().is_some()
+    {
+        return None;
+    }
+
+    if let Some(tag) = &payment_request.tag {
+        let output_tag = output_data
+            .output
+            .features()
+            .and_then(|features| features.tag())
+            .map(|tag_feature| tag_feature.tag());
+        if output_tag != Some(tag.as_slice()) {
+            return None;
+        }
+    }
+
+    let storage_deposit_return = unlock_conditions.storage_deposit_return().map_or(0, |sdr| sdr.amount());
+    Some(output_data.output.amount().saturating_sub(storage_deposit_return))
+}
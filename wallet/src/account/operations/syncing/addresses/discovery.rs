@@ -0,0 +1,264 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashSet, future::Future, ops::Range};
+
+use iota_client::{
+    block::address::Address,
+    secret::{SecretManage, SecretManager},
+};
+
+use crate::account::{
+    handle::AccountHandle,
+    operations::{
+        address_generation::AddressGenerationOptions,
+        syncing::{request_limiter::RequestLimiter, SyncOptions},
+    },
+    types::address::{AddressWithUnspentOutputs, AddressWrapper},
+    Account,
+};
+#[cfg(feature = "events")]
+use crate::events::types::SyncProgressEvent;
+
+impl AccountHandle {
+    /// Probe `address_gap_limit` addresses after the highest used address on the public and internal chain for
+    /// outputs, so funds on addresses that were generated by another wallet instance with the same seed are found.
+    /// Probed addresses are only derived and kept in memory, the account gets addresses up to the highest one with
+    /// outputs.
+    pub(crate) async fn discover_addresses(
+        &self,
+        address_gap_limit: u32,
+        options: &SyncOptions,
+        request_limiter: &RequestLimiter,
+    ) -> crate::Result<()> {
+        log::debug!("[SYNC] discover_addresses with address_gap_limit {address_gap_limit}");
+        if address_gap_limit == 0 {
+            return Ok(());
+        }
+
+        #[cfg(feature = "events")]
+        self.emit_sync_progress(SyncProgressEvent::DiscoveringAddresses).await;
+
+        for internal in [false, true] {
+            // Only addresses with outputs are added to the account, so stopping in between leaves no probed addresses
+            // behind
+            options.check_cancelled()?;
+            self.discover_addresses_on_chain(address_gap_limit, internal, options, request_limiter)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn discover_addresses_on_chain(
+        &self,
+        address_gap_limit: u32,
+        internal: bool,
+        options: &SyncOptions,
+        request_limiter: &RequestLimiter,
+    ) -> crate::Result<()> {
+        let highest_used_index = highest_used_key_index(&mut *self.write().await, internal);
+
+        let highest_used_index =
+            discover_highest_used_index(highest_used_index, address_gap_limit, move |probe_range| async move {
+                let addresses_to_probe = self.probe_addresses(internal, probe_range).await?;
+                let (addresses_with_outputs, _) = self
+                    .get_output_ids_for_addresses(options, addresses_to_probe, None, request_limiter)
+                    .await?;
+                Ok(addresses_with_outputs.iter().map(|a| a.key_index).max())
+            })
+            .await?;
+
+        // Add the addresses up to the highest used one to the account, so their outputs get synced
+        if let Some(highest_used_index) = highest_used_index {
+            let address_amount = {
+                let account = self.read().await;
+                if internal {
+                    account.internal_addresses.len() as u32
+                } else {
+                    account.public_addresses.len() as u32
+                }
+            };
+            if address_amount <= highest_used_index {
+                log::debug!("[SYNC] discovered used address with key_index {highest_used_index}, internal: {internal}");
+                self.generate_addresses(
+                    highest_used_index + 1 - address_amount,
+                    Some(AddressGenerationOptions {
+                        internal,
+                        options: None,
+                    }),
+                )
+                .await?;
+            }
+        }
+
+        let mut account = self.write().await;
+        if internal {
+            account.highest_used_internal_address_index = highest_used_index;
+        } else {
+            account.highest_used_public_address_index = highest_used_index;
+        }
+
+        Ok(())
+    }
+
+    // The addresses of the account in the key index range and the derived ones after the account addresses, without
+    // adding them to the account. Derived addresses are cached, so they aren't derived again on every sync.
+    async fn probe_addresses(
+        &self,
+        internal: bool,
+        key_index_range: Range<u32>,
+    ) -> crate::Result<Vec<AddressWithUnspentOutputs>> {
+        let account = self.read().await;
+        let addresses = if internal {
+            &account.internal_addresses
+        } else {
+            &account.public_addresses
+        };
+        let mut probe_addresses: Vec<AddressWithUnspentOutputs> = addresses
+            .iter()
+            .filter(|a| key_index_range.contains(&a.key_index))
+            .map(|a| AddressWithUnspentOutputs {
+                address: a.address.clone(),
+                key_index: a.key_index,
+                internal: a.internal,
+                output_ids: Vec::new(),
+            })
+            .collect();
+
+        let first_derived_index = key_index_range.start.max(addresses.len() as u32);
+        let mut discovery_addresses = self.discovery_addresses.lock().await;
+        // Addresses below the range are either in the account now or won't be probed again
+        discovery_addresses.retain(|(address_internal, key_index), _| {
+            *address_internal != internal || *key_index >= first_derived_index
+        });
+        let missing_indexes = (first_derived_index..key_index_range.end)
+            .filter(|key_index| !discovery_addresses.contains_key(&(internal, *key_index)))
+            .collect::<Vec<_>>();
+        if let (Some(first_missing_index), Some(last_missing_index)) = (missing_indexes.first(), missing_indexes.last())
+        {
+            let bech32_hrp = match account.public_addresses.first() {
+                Some(address) => address.address.bech32_hrp.to_string(),
+                None => self.client.get_bech32_hrp().await?,
+            };
+            let derived_addresses = match &*self.secret_manager.read().await {
+                // Without a secret manager that can derive them, only the addresses of the account are probed
+                SecretManager::Placeholder(_) => Vec::new(),
+                secret_manager => {
+                    secret_manager
+                        .generate_addresses(
+                            account.coin_type,
+                            account.index,
+                            *first_missing_index..*last_missing_index + 1,
+                            internal,
+                            None,
+                        )
+                        .await?
+                }
+            };
+            for (key_index, address) in (*first_missing_index..).zip(derived_addresses) {
+                discovery_addresses.insert((internal, key_index), AddressWrapper::new(address, bech32_hrp.clone()));
+            }
+        }
+
+        probe_addresses.extend((first_derived_index..key_index_range.end).filter_map(|key_index| {
+            discovery_addresses
+                .get(&(internal, key_index))
+                .map(|address| AddressWithUnspentOutputs {
+                    address: address.clone(),
+                    key_index,
+                    internal,
+                    output_ids: Vec::new(),
+                })
+        }));
+
+        Ok(probe_addresses)
+    }
+}
+
+// The highest key index of the chain that had outputs, from the previous discovery, the used flag of the addresses and
+// the known unspent and spent outputs. Addresses with known outputs are marked as used.
+fn highest_used_key_index(account: &mut Account, internal: bool) -> Option<u32> {
+    let addresses_with_outputs = account
+        .outputs
+        .values()
+        .map(|output_data| output_data.address)
+        .chain(account.addresses_with_unspent_outputs.iter().map(|a| a.address.inner))
+        .collect::<HashSet<Address>>();
+    let (addresses, mut highest_used_index) = if internal {
+        (
+            &mut account.internal_addresses,
+            account.highest_used_internal_address_index,
+        )
+    } else {
+        (&mut account.public_addresses, account.highest_used_public_address_index)
+    };
+
+    for address in addresses.iter_mut() {
+        if addresses_with_outputs.contains(&address.address.inner) {
+            address.used = true;
+        }
+        if address.used {
+            highest_used_index = highest_used_index.max(Some(address.key_index));
+        }
+    }
+
+    highest_used_index
+}
+
+// Probe the `address_gap_limit` key indexes after the highest used one with `probe`, which returns the highest key
+// index with outputs in the range, until all of them are unused. Returns the highest used key index.
+async fn discover_highest_used_index<F, Fut>(
+    mut highest_used_index: Option<u32>,
+    address_gap_limit: u32,
+    mut probe: F,
+) -> crate::Result<Option<u32>>
+where
+    F: FnMut(Range<u32>) -> Fut,
+    Fut: Future<Output = crate::Result<Option<u32>>>,
+{
+    loop {
+        let probe_start_index = highest_used_index.map_or(0, |index| index + 1);
+        let probe_range = probe_start_index..probe_start_index + address_gap_limit;
+        match probe(probe_range).await? {
+            Some(key_index) if highest_used_index.map_or(true, |index| key_index > index) => {
+                highest_used_index = Some(key_index);
+            }
+            _ => return Ok(highest_used_index),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn discover(used_indexes: &[u32], address_gap_limit: u32) -> (Option<u32>, Vec<Range<u32>>) {
+        let mut probed_ranges = Vec::new();
+        let highest_used_index = discover_highest_used_index(None, address_gap_limit, |probe_range| {
+            probed_ranges.push(probe_range.clone());
+            let highest_used = used_indexes
+                .iter()
+                .filter(|index| probe_range.contains(*index))
+                .max()
+                .copied();
+            async move { Ok(highest_used) }
+        })
+        .await
+        .unwrap();
+        (highest_used_index, probed_ranges)
+    }
+
+    #[tokio::test]
+    async fn gap_limit_discovery() {
+        // Nothing used, only the first range is probed
+        assert_eq!(discover(&[], 5).await, (None, vec![0..5]));
+
+        // Used addresses within the gap limit are found, the probing continues after the highest one
+        assert_eq!(discover(&[0, 3, 7], 5).await, (Some(7), vec![0..5, 4..9, 8..13]));
+
+        // A gap of `address_gap_limit` unused addresses stops the discovery
+        assert_eq!(discover(&[1, 7], 5).await, (Some(1), vec![0..5, 2..7]));
+        assert_eq!(discover(&[1, 7], 6).await, (Some(7), vec![0..6, 2..8, 8..14]));
+    }
+}
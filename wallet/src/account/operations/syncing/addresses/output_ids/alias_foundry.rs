@@ -14,8 +14,9 @@ use iota_client::{
     node_api::indexer::query_parameters::QueryParameter,
 };
 
+use super::with_created_after;
 use crate::{
-    account::{handle::AccountHandle, SyncOptions},
+    account::{handle::AccountHandle, operations::syncing::request_limiter::RequestLimiter, SyncOptions},
     task,
 };
 
@@ -25,6 +26,8 @@ impl AccountHandle {
         &self,
         bech32_address: &str,
         sync_options: SyncOptions,
+        created_after: Option<u32>,
+        request_limiter: &RequestLimiter,
     ) -> crate::Result<Vec<OutputId>> {
         log::debug!("[SYNC] get_alias_and_foundry_output_ids");
         let client = self.client();
@@ -34,14 +37,26 @@ impl AccountHandle {
         #[cfg(target_family = "wasm")]
         {
             output_ids.extend(
-                client
-                    .alias_output_ids(vec![QueryParameter::Governor(bech32_address.to_string())])
+                request_limiter
+                    .request_output_ids(
+                        with_created_after(
+                            vec![QueryParameter::Governor(bech32_address.to_string())],
+                            created_after,
+                        ),
+                        |query_parameters| client.alias_output_ids(query_parameters),
+                    )
                     .await?
                     .items,
             );
             output_ids.extend(
-                client
-                    .alias_output_ids(vec![QueryParameter::StateController(bech32_address.to_string())])
+                request_limiter
+                    .request_output_ids(
+                        with_created_after(
+                            vec![QueryParameter::StateController(bech32_address.to_string())],
+                            created_after,
+                        ),
+                        |query_parameters| client.alias_output_ids(query_parameters),
+                    )
                     .await?
                     .items,
             );
@@ -54,9 +69,16 @@ impl AccountHandle {
                 async move {
                     let bech32_address_ = bech32_address.to_string();
                     let client = client.clone();
+                    let request_limiter = request_limiter.clone();
                     task::spawn(async move {
-                        client
-                            .alias_output_ids(vec![QueryParameter::Governor(bech32_address_)])
+                        request_limiter
+                            .request_output_ids(
+                                with_created_after(
+                                    vec![QueryParameter::Governor(bech32_address_.clone())],
+                                    created_after,
+                                ),
+                                |query_parameters| client.alias_output_ids(query_parameters),
+                            )
                             .await
                             .map_err(From::from)
                     })
@@ -67,9 +89,16 @@ impl AccountHandle {
                 async move {
                     let bech32_address_ = bech32_address.to_string();
                     let client = client.clone();
+                    let request_limiter = request_limiter.clone();
                     task::spawn(async move {
-                        client
-                            .alias_output_ids(vec![QueryParameter::StateController(bech32_address_)])
+                        request_limiter
+                            .request_output_ids(
+                                with_created_after(
+                                    vec![QueryParameter::StateController(bech32_address_.clone())],
+                                    created_after,
+                                ),
+                                |query_parameters| client.alias_output_ids(query_parameters),
+                            )
                             .await
                             .map_err(From::from)
                     })
@@ -88,7 +117,7 @@ impl AccountHandle {
 
         // Get all results
         if sync_options.alias.foundry_outputs {
-            let foundry_output_ids = self.get_foundry_output_ids(&output_ids).await?;
+            let foundry_output_ids = self.get_foundry_output_ids(&output_ids, request_limiter).await?;
             output_ids.extend(foundry_output_ids);
         }
 
@@ -99,10 +128,13 @@ impl AccountHandle {
     pub(crate) async fn get_foundry_output_ids(
         &self,
         alias_output_ids: &HashSet<OutputId>,
+        request_limiter: &RequestLimiter,
     ) -> crate::Result<Vec<OutputId>> {
         log::debug!("[SYNC] get_foundry_output_ids");
         // Get alias outputs, so we can then get the foundry outputs with the alias addresses
-        let alias_output_responses = self.get_outputs(alias_output_ids.iter().cloned().collect()).await?;
+        let alias_output_responses = self
+            .get_outputs(alias_output_ids.iter().cloned().collect(), request_limiter)
+            .await?;
 
         let bech32_hrp = self.client.get_bech32_hrp().await?;
         let token_supply = self.client.get_token_supply().await?;
@@ -116,9 +148,13 @@ impl AccountHandle {
                 let alias_address = AliasAddress::from(alias_output.alias_id_non_null(&output_id));
                 let alias_bech32_address = Address::Alias(alias_address).to_bech32(bech32_hrp.clone());
                 let client = self.client.clone();
+                let request_limiter = request_limiter.clone();
                 tasks.push(Box::pin(task::spawn(async move {
-                    client
-                        .foundry_output_ids(vec![QueryParameter::AliasAddress(alias_bech32_address)])
+                    request_limiter
+                        .request_output_ids(
+                            vec![QueryParameter::AliasAddress(alias_bech32_address.clone())],
+                            |query_parameters| client.foundry_output_ids(query_parameters),
+                        )
                         .await
                         .map_err(From::from)
                 })));
@@ -10,23 +10,31 @@ use futures::FutureExt;
 use iota_client::api_types::plugins::indexer::OutputIdsResponse;
 use iota_client::{block::output::OutputId, node_api::indexer::query_parameters::QueryParameter};
 
-use crate::account::handle::AccountHandle;
+use super::with_created_after;
+use crate::account::{handle::AccountHandle, operations::syncing::request_limiter::RequestLimiter};
 
 impl AccountHandle {
     /// Returns output ids of basic outputs that have only the address unlock condition
     pub(crate) async fn get_basic_output_ids_with_address_unlock_condition_only(
         &self,
         bech32_address: String,
+        created_after: Option<u32>,
+        request_limiter: &RequestLimiter,
     ) -> iota_client::Result<Vec<OutputId>> {
         // Only request basic outputs with `AddressUnlockCondition` only
-        Ok(self
-            .client
-            .basic_output_ids(vec![
-                QueryParameter::Address(bech32_address),
-                QueryParameter::HasExpiration(false),
-                QueryParameter::HasTimelock(false),
-                QueryParameter::HasStorageDepositReturn(false),
-            ])
+        Ok(request_limiter
+            .request_output_ids(
+                with_created_after(
+                    vec![
+                        QueryParameter::Address(bech32_address.clone()),
+                        QueryParameter::HasExpiration(false),
+                        QueryParameter::HasTimelock(false),
+                        QueryParameter::HasStorageDepositReturn(false),
+                    ],
+                    created_after,
+                ),
+                |query_parameters| self.client().basic_output_ids(query_parameters),
+            )
             .await?
             .items)
     }
@@ -36,30 +44,43 @@ impl AccountHandle {
     pub(crate) async fn get_basic_output_ids_with_any_unlock_condition(
         &self,
         bech32_address: &str,
+        created_after: Option<u32>,
+        request_limiter: &RequestLimiter,
     ) -> crate::Result<Vec<OutputId>> {
         // aliases and foundries
         #[cfg(target_family = "wasm")]
         {
             let mut output_ids = vec![];
             output_ids.extend(
-                self.client()
-                    .basic_output_ids(vec![QueryParameter::Address(bech32_address.to_string())])
+                request_limiter
+                    .request_output_ids(
+                        with_created_after(vec![QueryParameter::Address(bech32_address.to_string())], created_after),
+                        |query_parameters| self.client().basic_output_ids(query_parameters),
+                    )
                     .await?
                     .items,
             );
             output_ids.extend(
-                self.client()
-                    .basic_output_ids(vec![QueryParameter::StorageDepositReturnAddress(
-                        bech32_address.to_string(),
-                    )])
+                request_limiter
+                    .request_output_ids(
+                        with_created_after(
+                            vec![QueryParameter::StorageDepositReturnAddress(bech32_address.to_string())],
+                            created_after,
+                        ),
+                        |query_parameters| self.client().basic_output_ids(query_parameters),
+                    )
                     .await?
                     .items,
             );
             output_ids.extend(
-                self.client()
-                    .basic_output_ids(vec![QueryParameter::ExpirationReturnAddress(
-                        bech32_address.to_string(),
-                    )])
+                request_limiter
+                    .request_output_ids(
+                        with_created_after(
+                            vec![QueryParameter::ExpirationReturnAddress(bech32_address.to_string())],
+                            created_after,
+                        ),
+                        |query_parameters| self.client().basic_output_ids(query_parameters),
+                    )
                     .await?
                     .items,
             );
@@ -75,9 +96,16 @@ impl AccountHandle {
                 async move {
                     let bech32_address = bech32_address.to_string();
                     let client = client.clone();
+                    let request_limiter = request_limiter.clone();
                     tokio::spawn(async move {
-                        client
-                            .basic_output_ids(vec![QueryParameter::Address(bech32_address)])
+                        request_limiter
+                            .request_output_ids(
+                                with_created_after(
+                                    vec![QueryParameter::Address(bech32_address.clone())],
+                                    created_after,
+                                ),
+                                |query_parameters| client.basic_output_ids(query_parameters),
+                            )
                             .await
                             .map_err(From::from)
                     })
@@ -88,9 +116,16 @@ impl AccountHandle {
                 async move {
                     let bech32_address = bech32_address.to_string();
                     let client = client.clone();
+                    let request_limiter = request_limiter.clone();
                     tokio::spawn(async move {
-                        client
-                            .basic_output_ids(vec![QueryParameter::StorageDepositReturnAddress(bech32_address)])
+                        request_limiter
+                            .request_output_ids(
+                                with_created_after(
+                                    vec![QueryParameter::StorageDepositReturnAddress(bech32_address.clone())],
+                                    created_after,
+                                ),
+                                |query_parameters| client.basic_output_ids(query_parameters),
+                            )
                             .await
                             .map_err(From::from)
                     })
@@ -101,9 +136,16 @@ impl AccountHandle {
                 async move {
                     let bech32_address = bech32_address.to_string();
                     let client = client.clone();
+                    let request_limiter = request_limiter.clone();
                     tokio::spawn(async move {
-                        client
-                            .basic_output_ids(vec![QueryParameter::ExpirationReturnAddress(bech32_address)])
+                        request_limiter
+                            .request_output_ids(
+                                with_created_after(
+                                    vec![QueryParameter::ExpirationReturnAddress(bech32_address.clone())],
+                                    created_after,
+                                ),
+                                |query_parameters| client.basic_output_ids(query_parameters),
+                            )
                             .await
                             .map_err(From::from)
                     })
@@ -5,33 +5,45 @@ mod alias_foundry;
 mod basic;
 mod nft;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 #[cfg(not(target_family = "wasm"))]
 use futures::FutureExt;
 use instant::Instant;
-use iota_client::block::{address::Address, output::OutputId};
+use iota_client::{
+    block::{address::Address, output::OutputId},
+    node_api::indexer::query_parameters::QueryParameter,
+};
 
 use crate::account::{
-    constants::PARALLEL_REQUESTS_AMOUNT, handle::AccountHandle, operations::syncing::SyncOptions,
-    types::address::AddressWithUnspentOutputs,
+    handle::AccountHandle,
+    operations::syncing::{request_limiter::RequestLimiter, SyncOptions},
+    types::{address::AddressWithUnspentOutputs, SyncCheckpoint},
 };
+#[cfg(feature = "events")]
+use crate::events::types::SyncProgressEvent;
 
 impl AccountHandle {
     /// Returns output ids for outputs that are directly (Ed25519 address in AddressUnlockCondition) or indirectly
     /// (alias/nft address in AddressUnlockCondition and the alias/nft output is controlled with the Ed25519 address)
-    /// connected to
+    /// connected to. With `created_after`, only outputs that were created after this timestamp are returned.
     pub(crate) async fn get_output_ids_for_address(
         &self,
         address: Address,
         sync_options: &SyncOptions,
+        created_after: Option<u32>,
+        request_limiter: &RequestLimiter,
     ) -> crate::Result<Vec<OutputId>> {
         let bech32_hrp = self.client.get_bech32_hrp().await?;
         let bech32_address = &address.to_bech32(bech32_hrp);
 
         if sync_options.sync_only_most_basic_outputs {
             let output_ids = self
-                .get_basic_output_ids_with_address_unlock_condition_only(bech32_address.to_string())
+                .get_basic_output_ids_with_address_unlock_condition_only(
+                    bech32_address.to_string(),
+                    created_after,
+                    request_limiter,
+                )
                 .await?;
             return Ok(output_ids);
         }
@@ -50,8 +62,12 @@ impl AccountHandle {
             #[cfg(target_family = "wasm")]
             {
                 results.push(
-                    self.get_basic_output_ids_with_any_unlock_condition(&bech32_address)
-                        .await,
+                    self.get_basic_output_ids_with_any_unlock_condition(
+                        &bech32_address,
+                        created_after,
+                        request_limiter,
+                    )
+                    .await,
                 )
             }
 
@@ -61,9 +77,14 @@ impl AccountHandle {
                     async move {
                         let account_handle = self.clone();
                         let bech32_address = bech32_address.clone();
+                        let request_limiter = request_limiter.clone();
                         tokio::spawn(async move {
                             account_handle
-                                .get_basic_output_ids_with_any_unlock_condition(&bech32_address)
+                                .get_basic_output_ids_with_any_unlock_condition(
+                                    &bech32_address,
+                                    created_after,
+                                    &request_limiter,
+                                )
                                 .await
                         })
                         .await
@@ -80,7 +101,10 @@ impl AccountHandle {
             // nfts
             #[cfg(target_family = "wasm")]
             {
-                results.push(self.get_nft_output_ids_with_any_unlock_condition(&bech32_address).await)
+                results.push(
+                    self.get_nft_output_ids_with_any_unlock_condition(&bech32_address, created_after, request_limiter)
+                        .await,
+                )
             }
 
             #[cfg(not(target_family = "wasm"))]
@@ -89,9 +113,14 @@ impl AccountHandle {
                     async move {
                         let bech32_address_ = bech32_address.clone();
                         let account_handle = self.clone();
+                        let request_limiter = request_limiter.clone();
                         tokio::spawn(async move {
                             account_handle
-                                .get_nft_output_ids_with_any_unlock_condition(&bech32_address_)
+                                .get_nft_output_ids_with_any_unlock_condition(
+                                    &bech32_address_,
+                                    created_after,
+                                    &request_limiter,
+                                )
                                 .await
                         })
                         .await
@@ -109,8 +138,13 @@ impl AccountHandle {
             #[cfg(target_family = "wasm")]
             {
                 results.push(
-                    self.get_alias_and_foundry_output_ids(&bech32_address, sync_options.clone())
-                        .await,
+                    self.get_alias_and_foundry_output_ids(
+                        &bech32_address,
+                        sync_options.clone(),
+                        created_after,
+                        request_limiter,
+                    )
+                    .await,
                 )
             }
 
@@ -121,9 +155,15 @@ impl AccountHandle {
                         let bech32_address = bech32_address.clone();
                         let sync_options = sync_options.clone();
                         let account_handle = self.clone();
+                        let request_limiter = request_limiter.clone();
                         tokio::spawn(async move {
                             account_handle
-                                .get_alias_and_foundry_output_ids(&bech32_address, sync_options)
+                                .get_alias_and_foundry_output_ids(
+                                    &bech32_address,
+                                    sync_options,
+                                    created_after,
+                                    &request_limiter,
+                                )
                                 .await
                         })
                         .await
@@ -148,28 +188,57 @@ impl AccountHandle {
 
     /// Get the current output ids for provided addresses and only returns addresses that have unspent outputs and
     /// return spent outputs separated
+    /// With a `checkpoint`, only outputs created after it are requested for the addresses that were synced with it and
+    /// their known unspent outputs are checked for being spent instead
     pub(crate) async fn get_output_ids_for_addresses(
         &self,
         options: &SyncOptions,
         addresses_with_unspent_outputs: Vec<AddressWithUnspentOutputs>,
+        checkpoint: Option<&SyncCheckpoint>,
+        request_limiter: &RequestLimiter,
     ) -> crate::Result<(Vec<AddressWithUnspentOutputs>, Vec<OutputId>)> {
         log::debug!("[SYNC] start get_output_ids_for_addresses");
         let address_output_ids_start_time = Instant::now();
 
+        let mut known_output_ids = match checkpoint {
+            Some(checkpoint) => {
+                self.get_known_unspent_output_ids(&addresses_with_unspent_outputs, checkpoint, request_limiter)
+                    .await?
+            }
+            None => HashMap::new(),
+        };
+
         let mut addresses_with_outputs = Vec::new();
+        #[cfg(feature = "events")]
+        let mut addresses_processed = 0;
         // spent outputs or alias/nft/foundries that don't get synced anymore, because of other sync options
         let mut spent_or_not_anymore_synced_outputs = Vec::new();
         // We split the addresses into chunks so we don't get timeouts if we have thousands
         for addresses_chunk in &mut addresses_with_unspent_outputs
-            .chunks(PARALLEL_REQUESTS_AMOUNT)
+            .chunks(request_limiter.max_parallel_requests())
             .map(|x: &[AddressWithUnspentOutputs]| x.to_vec())
         {
+            options.check_cancelled()?;
+            #[cfg(feature = "events")]
+            self.emit_sync_progress(SyncProgressEvent::RequestingOutputIds {
+                processed: addresses_processed,
+                total: addresses_with_unspent_outputs.len(),
+            })
+            .await;
+            #[cfg(feature = "events")]
+            {
+                addresses_processed += addresses_chunk.len();
+            }
+
             let results;
             #[cfg(target_family = "wasm")]
             {
                 let mut tasks = Vec::new();
                 for address in addresses_chunk {
-                    let output_ids = self.get_output_ids_for_address(address.address.inner, &options).await?;
+                    let created_after = created_after(checkpoint, &address);
+                    let output_ids = self
+                        .get_output_ids_for_address(address.address.inner, &options, created_after, request_limiter)
+                        .await?;
                     tasks.push(crate::Result::Ok((address, output_ids)));
                 }
                 results = tasks;
@@ -181,10 +250,17 @@ impl AccountHandle {
                 for address in addresses_chunk {
                     let account_handle = self.clone();
                     let sync_options = options.clone();
+                    let created_after = created_after(checkpoint, &address);
+                    let request_limiter = request_limiter.clone();
                     tasks.push(async move {
                         tokio::spawn(async move {
                             let output_ids = account_handle
-                                .get_output_ids_for_address(address.address.inner, &sync_options)
+                                .get_output_ids_for_address(
+                                    address.address.inner,
+                                    &sync_options,
+                                    created_after,
+                                    &request_limiter,
+                                )
                                 .await?;
                             crate::Result::Ok((address, output_ids))
                         })
@@ -196,7 +272,15 @@ impl AccountHandle {
             }
 
             for res in results {
-                let (mut address, output_ids): (AddressWithUnspentOutputs, Vec<OutputId>) = res?;
+                let (mut address, mut output_ids): (AddressWithUnspentOutputs, Vec<OutputId>) = res?;
+                // Only new outputs were requested, so add the known ones that are still unspent
+                if let Some(known_unspent_output_ids) = known_output_ids.remove(&address.address.inner) {
+                    for output_id in known_unspent_output_ids {
+                        if !output_ids.contains(&output_id) {
+                            output_ids.push(output_id);
+                        }
+                    }
+                }
                 // only return addresses with outputs
                 if !output_ids.is_empty() {
                     // outputs we had before, but now not anymore, got spent or are alias/nft/foundries that don't get
@@ -226,4 +310,74 @@ impl AccountHandle {
         );
         Ok((addresses_with_outputs, spent_or_not_anymore_synced_outputs))
     }
+
+    // Returns the known unspent output ids that are still unspent for the addresses that were synced with the
+    // checkpoint. Outputs owned by alias or nft addresses are skipped, they are requested again with their alias or nft
+    // output.
+    async fn get_known_unspent_output_ids(
+        &self,
+        addresses_with_unspent_outputs: &[AddressWithUnspentOutputs],
+        checkpoint: &SyncCheckpoint,
+        request_limiter: &RequestLimiter,
+    ) -> crate::Result<HashMap<Address, Vec<OutputId>>> {
+        let mut known_output_ids = HashMap::new();
+        {
+            let account = self.read().await;
+            for address in addresses_with_unspent_outputs {
+                if !checkpoint.contains_address(address.key_index, address.internal) {
+                    continue;
+                }
+                let output_ids: Vec<OutputId> = address
+                    .output_ids
+                    .iter()
+                    .filter(|output_id| {
+                        account.unspent_outputs.get(output_id).map_or(false, |output_data| {
+                            output_data
+                                .output
+                                .unlock_conditions()
+                                .and_then(|unlock_conditions| unlock_conditions.address())
+                                .map_or(true, |address_unlock_condition| {
+                                    address_unlock_condition.address().is_ed25519()
+                                })
+                        })
+                    })
+                    .copied()
+                    .collect();
+                known_output_ids.insert(address.address.inner, output_ids);
+            }
+        }
+
+        let output_ids_to_check: Vec<OutputId> = known_output_ids.values().flatten().copied().collect();
+        let mut unspent_output_ids = HashSet::new();
+        // A failed request fails the sync, otherwise unspent outputs would be removed from the account
+        let outputs_metadata = request_limiter
+            .get_outputs_metadata_skip_not_found(&self.client, &output_ids_to_check)
+            .await?;
+        for output_metadata in outputs_metadata {
+            if !output_metadata.is_spent {
+                unspent_output_ids.insert(output_metadata.output_id()?);
+            }
+        }
+        // Outputs without metadata got spent and pruned
+        for output_ids in known_output_ids.values_mut() {
+            output_ids.retain(|output_id| unspent_output_ids.contains(output_id));
+        }
+
+        Ok(known_output_ids)
+    }
+}
+
+// The timestamp after which outputs need to be requested for the address, if it was synced with the checkpoint.
+fn created_after(checkpoint: Option<&SyncCheckpoint>, address: &AddressWithUnspentOutputs) -> Option<u32> {
+    checkpoint
+        .filter(|checkpoint| checkpoint.contains_address(address.key_index, address.internal))
+        .map(SyncCheckpoint::created_after)
+}
+
+// Adds the `CreatedAfter` query parameter, to only get outputs that were created after the timestamp.
+fn with_created_after(mut query_parameters: Vec<QueryParameter>, created_after: Option<u32>) -> Vec<QueryParameter> {
+    if let Some(created_after) = created_after {
+        query_parameters.push(QueryParameter::CreatedAfter(created_after));
+    }
+    query_parameters
 }
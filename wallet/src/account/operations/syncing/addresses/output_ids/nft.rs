@@ -10,36 +10,50 @@ use futures::FutureExt;
 use iota_client::api_types::plugins::indexer::OutputIdsResponse;
 use iota_client::{block::output::OutputId, node_api::indexer::query_parameters::QueryParameter};
 
-use crate::account::handle::AccountHandle;
+use super::with_created_after;
+use crate::account::{handle::AccountHandle, operations::syncing::request_limiter::RequestLimiter};
 
 impl AccountHandle {
     /// Returns output ids of nft outputs that have the address in any unlock condition
     pub(crate) async fn get_nft_output_ids_with_any_unlock_condition(
         &self,
         bech32_address: &str,
+        created_after: Option<u32>,
+        request_limiter: &RequestLimiter,
     ) -> crate::Result<Vec<OutputId>> {
         #[cfg(target_family = "wasm")]
         {
             let mut output_ids = vec![];
             output_ids.extend(
-                self.client()
-                    .nft_output_ids(vec![QueryParameter::Address(bech32_address.to_string())])
+                request_limiter
+                    .request_output_ids(
+                        with_created_after(vec![QueryParameter::Address(bech32_address.to_string())], created_after),
+                        |query_parameters| self.client().nft_output_ids(query_parameters),
+                    )
                     .await?
                     .items,
             );
             output_ids.extend(
-                self.client()
-                    .nft_output_ids(vec![QueryParameter::StorageDepositReturnAddress(
-                        bech32_address.to_string(),
-                    )])
+                request_limiter
+                    .request_output_ids(
+                        with_created_after(
+                            vec![QueryParameter::StorageDepositReturnAddress(bech32_address.to_string())],
+                            created_after,
+                        ),
+                        |query_parameters| self.client().nft_output_ids(query_parameters),
+                    )
                     .await?
                     .items,
             );
             output_ids.extend(
-                self.client()
-                    .nft_output_ids(vec![QueryParameter::ExpirationReturnAddress(
-                        bech32_address.to_string(),
-                    )])
+                request_limiter
+                    .request_output_ids(
+                        with_created_after(
+                            vec![QueryParameter::ExpirationReturnAddress(bech32_address.to_string())],
+                            created_after,
+                        ),
+                        |query_parameters| self.client().nft_output_ids(query_parameters),
+                    )
                     .await?
                     .items,
             );
@@ -53,10 +67,17 @@ impl AccountHandle {
                 async move {
                     let bech32_address_ = bech32_address.to_string();
                     let client = client.clone();
+                    let request_limiter = request_limiter.clone();
                     tokio::spawn(async move {
                         // Get nft outputs where the address is in the address unlock condition
-                        client
-                            .nft_output_ids(vec![QueryParameter::Address(bech32_address_)])
+                        request_limiter
+                            .request_output_ids(
+                                with_created_after(
+                                    vec![QueryParameter::Address(bech32_address_.clone())],
+                                    created_after,
+                                ),
+                                |query_parameters| client.nft_output_ids(query_parameters),
+                            )
                             .await
                             .map_err(From::from)
                     })
@@ -66,10 +87,17 @@ impl AccountHandle {
                 async move {
                     let bech32_address_ = bech32_address.to_string();
                     let client = client.clone();
+                    let request_limiter = request_limiter.clone();
                     tokio::spawn(async move {
                         // Get outputs where the address is in the storage deposit return unlock condition
-                        client
-                            .nft_output_ids(vec![QueryParameter::StorageDepositReturnAddress(bech32_address_)])
+                        request_limiter
+                            .request_output_ids(
+                                with_created_after(
+                                    vec![QueryParameter::StorageDepositReturnAddress(bech32_address_.clone())],
+                                    created_after,
+                                ),
+                                |query_parameters| client.nft_output_ids(query_parameters),
+                            )
                             .await
                             .map_err(From::from)
                     })
@@ -79,10 +107,17 @@ impl AccountHandle {
                 async move {
                     let bech32_address_ = bech32_address.to_string();
                     let client = client.clone();
+                    let request_limiter = request_limiter.clone();
                     tokio::spawn(async move {
                         // Get outputs where the address is in the expiration unlock condition
-                        client
-                            .nft_output_ids(vec![QueryParameter::ExpirationReturnAddress(bech32_address_)])
+                        request_limiter
+                            .request_output_ids(
+                                with_created_after(
+                                    vec![QueryParameter::ExpirationReturnAddress(bech32_address_.clone())],
+                                    created_after,
+                                ),
+                                |query_parameters| client.nft_output_ids(query_parameters),
+                            )
                             .await
                             .map_err(From::from)
                     })
@@ -3,9 +3,13 @@
 
 use instant::Instant;
 
+#[cfg(feature = "events")]
+use crate::events::types::SyncProgressEvent;
 use crate::{
     account::{
-        constants::PARALLEL_REQUESTS_AMOUNT, handle::AccountHandle, types::address::AddressWithUnspentOutputs,
+        handle::AccountHandle,
+        operations::syncing::{request_limiter::RequestLimiter, SyncOptions},
+        types::address::AddressWithUnspentOutputs,
         OutputData,
     },
     task,
@@ -16,6 +20,8 @@ impl AccountHandle {
     pub(crate) async fn get_outputs_from_address_output_ids(
         &self,
         addresses_with_unspent_outputs: Vec<AddressWithUnspentOutputs>,
+        options: &SyncOptions,
+        request_limiter: &RequestLimiter,
     ) -> crate::Result<(Vec<AddressWithUnspentOutputs>, Vec<OutputData>)> {
         log::debug!("[SYNC] start get_outputs_from_address_output_ids");
         let address_outputs_start_time = Instant::now();
@@ -25,15 +31,27 @@ impl AccountHandle {
 
         // We split the addresses into chunks so we don't get timeouts if we have thousands
         for addresses_chunk in &mut addresses_with_unspent_outputs
-            .chunks(PARALLEL_REQUESTS_AMOUNT)
+            .chunks(request_limiter.max_parallel_requests())
             .map(|x: &[AddressWithUnspentOutputs]| x.to_vec())
         {
+            options.check_cancelled()?;
+            #[cfg(feature = "events")]
+            self.emit_sync_progress(SyncProgressEvent::RequestingOutputs {
+                processed: addresses_with_outputs.len(),
+                total: addresses_with_unspent_outputs.len(),
+                outputs: outputs_data.len(),
+            })
+            .await;
+
             let mut tasks = Vec::new();
             for address in addresses_chunk {
                 let account_handle = self.clone();
+                let request_limiter = request_limiter.clone();
                 tasks.push(async move {
                     task::spawn(async move {
-                        let output_responses = account_handle.get_outputs(address.output_ids.clone()).await?;
+                        let output_responses = account_handle
+                            .get_outputs(address.output_ids.clone(), &request_limiter)
+                            .await?;
 
                         let outputs = account_handle
                             .output_response_to_output_data(output_responses, &address)
@@ -0,0 +1,78 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account::{handle::AccountHandle, operations::syncing::SyncOptions, types::SyncCheckpoint};
+
+/// The confirmed milestone and pruning index of the node when a sync started
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NodeSyncState {
+    pub(crate) network_id: u64,
+    pub(crate) milestone_index: u32,
+    pub(crate) milestone_timestamp: u32,
+    pub(crate) pruning_index: u32,
+}
+
+impl AccountHandle {
+    /// Get the state of the node to check and create sync checkpoints
+    pub(crate) async fn get_node_sync_state(&self) -> crate::Result<Option<NodeSyncState>> {
+        let status = self.client.get_info().await?.node_info.status;
+        Ok(match status.confirmed_milestone.timestamp {
+            Some(milestone_timestamp) => Some(NodeSyncState {
+                network_id: self.client.get_network_id().await?,
+                milestone_index: status.confirmed_milestone.index,
+                milestone_timestamp,
+                pruning_index: status.pruning_index,
+            }),
+            None => None,
+        })
+    }
+
+    /// Returns the sync checkpoint of the account if it can be used to only sync new outputs, otherwise all outputs
+    /// need to be synced
+    pub(crate) async fn get_valid_sync_checkpoint(
+        &self,
+        options: &SyncOptions,
+        node_sync_state: &NodeSyncState,
+    ) -> Option<SyncCheckpoint> {
+        let checkpoint = self.read().await.sync_checkpoint.clone()?;
+        if checkpoint.is_valid_for(
+            options,
+            node_sync_state.network_id,
+            node_sync_state.pruning_index,
+            node_sync_state.milestone_timestamp,
+        ) {
+            log::debug!("[SYNC] syncing outputs after milestone {}", checkpoint.milestone_index);
+            Some(checkpoint)
+        } else {
+            log::debug!("[SYNC] sync checkpoint can't be used, syncing all outputs");
+            None
+        }
+    }
+
+    /// Store the new sync checkpoint after all addresses were synced
+    pub(crate) async fn update_sync_checkpoint(
+        &self,
+        options: &SyncOptions,
+        node_sync_state: &NodeSyncState,
+        public_address_count: u32,
+        internal_address_count: u32,
+    ) -> crate::Result<()> {
+        let mut account = self.write().await;
+        account.sync_checkpoint = Some(SyncCheckpoint {
+            network_id: node_sync_state.network_id,
+            milestone_index: node_sync_state.milestone_index,
+            milestone_timestamp: node_sync_state.milestone_timestamp,
+            public_address_count,
+            internal_address_count,
+            account: options.account.clone(),
+            alias: options.alias.clone(),
+            nft: options.nft.clone(),
+            sync_only_most_basic_outputs: options.sync_only_most_basic_outputs,
+        });
+
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+
+        Ok(())
+    }
+}
@@ -5,10 +5,20 @@ use std::collections::HashSet;
 
 use iota_client::block::output::{FoundryId, Output};
 
-use crate::{account::handle::AccountHandle, task};
+use crate::{
+    account::{
+        handle::AccountHandle,
+        operations::{native_token_metadata::update_irc30_metadata, syncing::request_limiter::RequestLimiter},
+    },
+    task,
+};
 
 impl AccountHandle {
-    pub(crate) async fn request_and_store_foundry_outputs(&self, foundry_ids: HashSet<FoundryId>) -> crate::Result<()> {
+    pub(crate) async fn request_and_store_foundry_outputs(
+        &self,
+        foundry_ids: HashSet<FoundryId>,
+        request_limiter: &RequestLimiter,
+    ) -> crate::Result<()> {
         log::debug!("[SYNC] request_and_store_foundry_outputs");
 
         let mut foundries = self.read().await.native_token_foundries().clone();
@@ -21,10 +31,11 @@ impl AccountHandle {
             }
 
             let client = self.client.clone();
+            let request_limiter = request_limiter.clone();
             tasks.push(async move {
                 task::spawn(async move {
-                    match client.foundry_output_id(foundry_id).await {
-                        Ok(output_id) => Ok(Some(client.get_output(&output_id).await?)),
+                    match request_limiter.request(|| client.foundry_output_id(foundry_id)).await {
+                        Ok(output_id) => Ok(Some(request_limiter.request(|| client.get_output(&output_id)).await?)),
                         Err(iota_client::Error::NotFound(_)) => Ok(None),
                         Err(e) => Err(crate::Error::Client(e.into())),
                     }
@@ -48,6 +59,7 @@ impl AccountHandle {
 
         let mut account = self.write().await;
         account.native_token_foundries = foundries;
+        update_irc30_metadata(&mut account);
 
         Ok(())
     }
@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod addresses;
+pub(crate) mod checkpoint;
 pub(crate) mod foundries;
 pub(crate) mod options;
 pub(crate) mod outputs;
+pub(crate) mod request_limiter;
 pub(crate) mod transactions;
 
 use std::collections::{HashMap, HashSet};
@@ -16,18 +18,21 @@ use iota_client::block::{
 };
 
 pub use self::options::SyncOptions;
+use self::{options::SyncCancellationToken, request_limiter::RequestLimiter};
 use crate::account::{
     constants::MIN_SYNC_INTERVAL,
     handle::AccountHandle,
-    types::{AddressWithUnspentOutputs, OutputData},
+    types::{AddressWithUnspentOutputs, OutputData, SyncCheckpoint},
     AccountBalance,
 };
+#[cfg(feature = "events")]
+use crate::events::types::{SyncProgressEvent, WalletEvent};
 
 impl AccountHandle {
     /// Sync the account by fetching new information from the nodes. Will also retry pending transactions
     /// if necessary.
     pub async fn sync(&self, options: Option<SyncOptions>) -> crate::Result<AccountBalance> {
-        let options = options.unwrap_or_default();
+        let mut options = options.unwrap_or_default();
         log::debug!("[SYNC] start syncing with {:?}", options);
         let syc_start_time = Instant::now();
 
@@ -48,31 +53,119 @@ impl AccountHandle {
             return self.balance().await;
         }
 
-        self.sync_internal(&options).await?;
+        // Keep the token of the running sync, so it can be cancelled with `cancel_sync()`
+        let cancellation_token = options
+            .cancellation_token
+            .get_or_insert_with(SyncCancellationToken::new)
+            .clone();
+        self.sync_cancellation_token.lock().await.replace(cancellation_token);
+        let sync_result = self.sync_account(&options).await;
+        self.sync_cancellation_token.lock().await.take();
+        #[cfg(feature = "events")]
+        if let Err(crate::Error::SyncCancelled) = sync_result {
+            self.emit_sync_progress(SyncProgressEvent::Cancelled).await;
+        }
+        sync_result?;
+
+        let account_balance = self.balance().await?;
+        // Update last_synced mutex
+        let time_now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_millis();
+        *last_synced = time_now;
+        log::debug!("[SYNC] finished syncing in {:.2?}", syc_start_time.elapsed());
+        #[cfg(feature = "events")]
+        self.emit_sync_progress(SyncProgressEvent::Finished).await;
+        Ok(account_balance)
+    }
+
+    /// Cancel the running sync of the account, if there is one. Like cancelling the
+    /// [`SyncOptions::cancellation_token`], the sync is only stopped before the account gets updated.
+    pub async fn cancel_sync(&self) {
+        if let Some(cancellation_token) = &*self.sync_cancellation_token.lock().await {
+            cancellation_token.cancel();
+        }
+    }
+
+    // Sync the addresses, outputs and pending transactions, cancellation is checked until the account gets updated
+    async fn sync_account(&self, options: &SyncOptions) -> crate::Result<()> {
+        let request_limiter = self.sync_request_limiter(options);
+
+        if let Some(address_gap_limit) = options.address_gap_limit {
+            if options.addresses.is_empty() {
+                self.discover_addresses(address_gap_limit, options, &request_limiter)
+                    .await?;
+            }
+        }
+
+        // With a valid checkpoint from the latest sync only new outputs are requested
+        let node_sync_state = if options.incremental {
+            self.get_node_sync_state().await?
+        } else {
+            None
+        };
+        let checkpoint = match &node_sync_state {
+            Some(node_sync_state) => self.get_valid_sync_checkpoint(options, node_sync_state).await,
+            None => None,
+        };
+        let (public_address_count, internal_address_count) = {
+            let account = self.read().await;
+            (
+                account.public_addresses.len() as u32,
+                account.internal_addresses.len() as u32,
+            )
+        };
+
+        self.sync_internal(options, checkpoint.as_ref(), &request_limiter)
+            .await?;
+
+        // The account is updated now, so the remaining steps aren't cancelled anymore and the sync is completed
+        let options = &SyncOptions {
+            cancellation_token: None,
+            ..options.clone()
+        };
 
         // Sync transactions after updating account with outputs, so we can use them to check the transaction
         // status
         if options.sync_pending_transactions {
+            #[cfg(feature = "events")]
+            self.emit_sync_progress(SyncProgressEvent::SyncingPendingTransactions)
+                .await;
             let confirmed_tx_with_unknown_output = self.sync_pending_transactions().await?;
             // Sync again if we don't know the output yet, to prevent having no unspent outputs after syncing
             if confirmed_tx_with_unknown_output {
                 log::debug!("[SYNC] a transaction for which no output is known got confirmed, syncing outputs again");
-                self.sync_internal(&options).await?;
+                self.sync_internal(options, checkpoint.as_ref(), &request_limiter)
+                    .await?;
             }
         };
 
-        let account_balance = self.balance().await?;
-        // Update last_synced mutex
-        let time_now = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .expect("time went backwards")
-            .as_millis();
-        *last_synced = time_now;
-        log::debug!("[SYNC] finished syncing in {:.2?}", syc_start_time.elapsed());
-        Ok(account_balance)
+        // Decrypt the memos of new transactions, after outgoing and incoming transactions are updated
+        self.decrypt_memos(&request_limiter).await?;
+
+        // Only a sync of all addresses can be used as checkpoint
+        if let Some(node_sync_state) = &node_sync_state {
+            if options.addresses.is_empty()
+                && options.address_start_index == 0
+                && options.address_start_index_internal == 0
+            {
+                self.update_sync_checkpoint(options, node_sync_state, public_address_count, internal_address_count)
+                    .await?;
+            }
+        }
+
+        self.record_balance_snapshot().await?;
+
+        Ok(())
     }
 
-    async fn sync_internal(&self, options: &SyncOptions) -> crate::Result<()> {
+    async fn sync_internal(
+        &self,
+        options: &SyncOptions,
+        checkpoint: Option<&SyncCheckpoint>,
+        request_limiter: &RequestLimiter,
+    ) -> crate::Result<()> {
         log::debug!("[SYNC] sync_internal");
 
         let addresses_to_sync = self.get_addresses_to_sync(options).await?;
@@ -82,14 +175,21 @@ impl AccountHandle {
             Vec<OutputId>,
             Vec<AddressWithUnspentOutputs>,
             Vec<OutputData>,
-        ) = self.request_outputs_recursively(addresses_to_sync, options).await?;
+        ) = self
+            .request_outputs_recursively(addresses_to_sync, options, checkpoint, request_limiter)
+            .await?;
 
         // Request possible spent outputs
         log::debug!("[SYNC] spent_or_not_synced_outputs: {spent_or_not_synced_output_ids:?}");
-        let spent_or_unsynced_output_metadata_responses = self
-            .client
-            .try_get_outputs_metadata(spent_or_not_synced_output_ids.clone())
-            .await?;
+        options.check_cancelled()?;
+        #[cfg(feature = "events")]
+        self.emit_sync_progress(SyncProgressEvent::CheckingSpentOutputs {
+            outputs: spent_or_not_synced_output_ids.len(),
+        })
+        .await;
+        let spent_or_unsynced_output_metadata_responses = request_limiter
+            .try_get_outputs_metadata(&self.client, &spent_or_not_synced_output_ids)
+            .await;
 
         // Add the output response to the output ids, the output response is optional, because an output could be
         // pruned and then we can't get the metadata
@@ -101,15 +201,24 @@ impl AccountHandle {
         }
 
         if options.sync_incoming_transactions {
+            options.check_cancelled()?;
+            #[cfg(feature = "events")]
+            self.emit_sync_progress(SyncProgressEvent::SyncingIncomingTransactions)
+                .await;
             let transaction_ids = outputs_data
                 .iter()
                 .map(|output| *output.output_id.transaction_id())
                 .collect();
             // Request and store transaction payload for newly received unspent outputs
-            self.request_incoming_transaction_data(transaction_ids).await?;
+            self.request_incoming_transaction_data(transaction_ids, request_limiter)
+                .await?;
         }
 
         if options.sync_native_token_foundries {
+            options.check_cancelled()?;
+            #[cfg(feature = "events")]
+            self.emit_sync_progress(SyncProgressEvent::SyncingNativeTokenFoundries)
+                .await;
             let native_token_foundry_ids = outputs_data
                 .iter()
                 .filter_map(|output| output.output.native_tokens())
@@ -121,9 +230,15 @@ impl AccountHandle {
                 .collect::<HashSet<_>>();
 
             // Request and store foundry outputs
-            self.request_and_store_foundry_outputs(native_token_foundry_ids).await?;
+            self.request_and_store_foundry_outputs(native_token_foundry_ids, request_limiter)
+                .await?;
         }
 
+        // Last chance to cancel, the account is updated at once afterwards
+        options.check_cancelled()?;
+        #[cfg(feature = "events")]
+        self.emit_sync_progress(SyncProgressEvent::UpdatingAccount).await;
+
         // Updates account with balances, output ids, outputs
         self.update_account(
             addresses_with_unspent_outputs,
@@ -141,6 +256,8 @@ impl AccountHandle {
         &self,
         addresses_to_sync: Vec<AddressWithUnspentOutputs>,
         options: &SyncOptions,
+        checkpoint: Option<&SyncCheckpoint>,
+        request_limiter: &RequestLimiter,
     ) -> crate::Result<(Vec<OutputId>, Vec<AddressWithUnspentOutputs>, Vec<OutputData>)> {
         // Cache the alias and nft address with the related ed2559 address, so we can update the account address with
         // the new output ids
@@ -152,21 +269,29 @@ impl AccountHandle {
             let new_outputs_data = if new_alias_and_nft_addresses.is_empty() {
                 // Get outputs for addresses and add them also the the addresses_with_unspent_outputs
                 let (addresses_with_output_ids, spent_or_not_synced_output_ids_inner) = self
-                    .get_output_ids_for_addresses(options, addresses_to_sync.clone())
+                    .get_output_ids_for_addresses(options, addresses_to_sync.clone(), checkpoint, request_limiter)
                     .await?;
                 spent_or_not_synced_output_ids = spent_or_not_synced_output_ids_inner;
                 // Get outputs for addresses and add them also the the addresses_with_unspent_outputs
                 let (addresses_with_unspent_outputs_inner, outputs_data_inner) = self
-                    .get_outputs_from_address_output_ids(addresses_with_output_ids)
+                    .get_outputs_from_address_output_ids(addresses_with_output_ids, options, request_limiter)
                     .await?;
                 addresses_with_unspent_outputs = addresses_with_unspent_outputs_inner;
                 outputs_data.extend(outputs_data_inner.clone().into_iter());
                 outputs_data_inner
             } else {
+                options.check_cancelled()?;
+                #[cfg(feature = "events")]
+                self.emit_sync_progress(SyncProgressEvent::RequestingAliasAndNftOutputs {
+                    addresses: new_alias_and_nft_addresses.len(),
+                })
+                .await;
                 let bech32_hrp = self.client().get_bech32_hrp().await?;
                 let mut new_outputs_data = Vec::new();
                 for (alias_or_nft_address, ed25519_address) in new_alias_and_nft_addresses {
-                    let output_ids = self.get_output_ids_for_address(alias_or_nft_address, options).await?;
+                    let output_ids = self
+                        .get_output_ids_for_address(alias_or_nft_address, options, None, request_limiter)
+                        .await?;
 
                     // Update address with unspent outputs
                     let address_with_unspent_outputs = addresses_with_unspent_outputs
@@ -177,7 +302,7 @@ impl AccountHandle {
                         })?;
                     address_with_unspent_outputs.output_ids.extend(output_ids.clone());
 
-                    let new_outputs_data_inner = self.get_outputs(output_ids).await?;
+                    let new_outputs_data_inner = self.get_outputs(output_ids, request_limiter).await?;
 
                     let outputs_data_inner = self
                         .output_response_to_output_data(new_outputs_data_inner, address_with_unspent_outputs)
@@ -227,4 +352,14 @@ impl AccountHandle {
             outputs_data,
         ))
     }
+
+    /// Emit a sync progress event for the account
+    #[cfg(feature = "events")]
+    pub(crate) async fn emit_sync_progress(&self, event: SyncProgressEvent) {
+        let account_index = self.read().await.index;
+        self.event_emitter
+            .lock()
+            .await
+            .emit(account_index, WalletEvent::SyncProgress(event));
+    }
 }
@@ -1,14 +1,26 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::{
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
 use serde::{Deserialize, Serialize};
 
+use crate::account::constants::DEFAULT_PARALLEL_REQUESTS_AMOUNT;
+
 const DEFAULT_ADDRESS_START_INDEX: u32 = 0;
 const DEFAULT_FORCE_SYNCING: bool = false;
 const DEFAULT_SYNC_INCOMING_TRANSACTIONS: bool = false;
 const DEFAULT_SYNC_ONLY_MOST_BASIC_OUTPUTS: bool = false;
 const DEFAULT_SYNC_PENDING_TRANSACTIONS: bool = true;
 const DEFAULT_SYNC_NATIVE_TOKEN_FOUNDRIES: bool = false;
+const DEFAULT_INCREMENTAL: bool = false;
+const DEFAULT_RETRY_DELAY_IN_MILLISECONDS: u64 = 500;
 
 /// The synchronization options
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -56,6 +68,38 @@ pub struct SyncOptions {
     /// Sync native token foundries, so their metadata can be returned in the balance.
     #[serde(rename = "syncNativeTokenFoundries", default = "default_sync_native_token_foundries")]
     pub sync_native_token_foundries: bool,
+    /// The number of addresses after the highest used one, on the public and on the internal chain, that are checked
+    /// for outputs before syncing. Addresses with outputs are generated, so funds on addresses that were generated by
+    /// another wallet instance with the same seed are found. `None` by default, ignored if `addresses` are provided.
+    #[serde(rename = "addressGapLimit", default)]
+    pub address_gap_limit: Option<u32>,
+    /// Only request outputs that were created after the checkpoint of the latest sync of all addresses and check the
+    /// known unspent outputs for being spent. All outputs are requested if there is no checkpoint or if it's too old,
+    /// pruned by the node or was created with other output options. Checkpoints are only created by incremental syncs.
+    /// `false` by default.
+    #[serde(rename = "incremental", default = "default_incremental")]
+    pub incremental: bool,
+    /// Token to stop the sync before the account is updated with the synced outputs, the account then isn't changed
+    /// apart from addresses with outputs found by the address discovery and
+    /// [`Error::SyncCancelled`](crate::Error::SyncCancelled) is returned. Once the account is updated, the sync isn't
+    /// stopped anymore. A running sync can also be cancelled with
+    /// [`AccountHandle::cancel_sync()`](crate::account::AccountHandle::cancel_sync).
+    #[serde(skip)]
+    pub cancellation_token: Option<SyncCancellationToken>,
+    /// Limits for the requests of this sync, instead of the ones from the account manager, which are shared between
+    /// all accounts.
+    #[serde(rename = "requestOptions", default)]
+    pub request_options: Option<SyncRequestOptions>,
+}
+
+impl SyncOptions {
+    // Returns an error if the sync got cancelled
+    pub(crate) fn check_cancelled(&self) -> crate::Result<()> {
+        match &self.cancellation_token {
+            Some(cancellation_token) if cancellation_token.is_cancelled() => Err(crate::Error::SyncCancelled),
+            _ => Ok(()),
+        }
+    }
 }
 
 fn default_address_start_index() -> u32 {
@@ -82,6 +126,10 @@ fn default_sync_native_token_foundries() -> bool {
     DEFAULT_SYNC_NATIVE_TOKEN_FOUNDRIES
 }
 
+fn default_incremental() -> bool {
+    DEFAULT_INCREMENTAL
+}
+
 impl Default for SyncOptions {
     fn default() -> Self {
         Self {
@@ -96,10 +144,89 @@ impl Default for SyncOptions {
             sync_only_most_basic_outputs: default_sync_only_most_basic_outputs(),
             sync_native_token_foundries: default_sync_native_token_foundries(),
             force_syncing: default_force_syncing(),
+            address_gap_limit: None,
+            incremental: default_incremental(),
+            cancellation_token: None,
+            request_options: None,
+        }
+    }
+}
+
+/// Limits for the requests that are sent to the node during syncing, to not overload it or run into rate limits.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncRequestOptions {
+    /// The maximum number of requests that are sent in parallel, 500 by default.
+    #[serde(default = "default_max_parallel_requests")]
+    pub max_parallel_requests: usize,
+    /// The maximum number of requests that are sent per second, unlimited by default.
+    #[serde(default)]
+    pub requests_per_second: Option<u32>,
+    /// How often a failed request is retried, 0 by default. Only timeouts, connection errors, too many requests and
+    /// server errors are retried.
+    #[serde(default)]
+    pub max_retries: u32,
+    /// The delay before the first retry in milliseconds, doubled for every further retry, 500 by default.
+    #[serde(default = "default_retry_delay_in_milliseconds")]
+    pub retry_delay_in_milliseconds: u64,
+}
+
+fn default_max_parallel_requests() -> usize {
+    DEFAULT_PARALLEL_REQUESTS_AMOUNT
+}
+
+fn default_retry_delay_in_milliseconds() -> u64 {
+    DEFAULT_RETRY_DELAY_IN_MILLISECONDS
+}
+
+impl Default for SyncRequestOptions {
+    fn default() -> Self {
+        Self {
+            max_parallel_requests: default_max_parallel_requests(),
+            requests_per_second: None,
+            max_retries: 0,
+            retry_delay_in_milliseconds: default_retry_delay_in_milliseconds(),
         }
     }
 }
 
+/// A token to cancel a running sync, all clones of a token share the cancellation. A cancelled token can't be reset, a
+/// new one needs to be created for the next sync.
+#[derive(Debug, Clone, Default)]
+pub struct SyncCancellationToken(Arc<AtomicBool>);
+
+impl SyncCancellationToken {
+    /// Create a new token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the syncs that use this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns true if the token was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+// Tokens are equal if they are clones of each other
+impl PartialEq for SyncCancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SyncCancellationToken {}
+
+impl Hash for SyncCancellationToken {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(Arc::as_ptr(&self.0), state)
+    }
+}
+
 /// Sync options for Ed25519 addresses from the account
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
@@ -149,3 +276,24 @@ pub struct NftSyncOptions {
     pub nft_outputs: bool,
     pub alias_outputs: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancellation_token() {
+        let cancellation_token = SyncCancellationToken::new();
+        let options = SyncOptions {
+            cancellation_token: Some(cancellation_token.clone()),
+            ..Default::default()
+        };
+        assert!(options.check_cancelled().is_ok());
+        assert_eq!(options.clone(), options);
+        assert_ne!(options, SyncOptions::default());
+
+        cancellation_token.cancel();
+        assert!(matches!(options.check_cancelled(), Err(crate::Error::SyncCancelled)));
+        assert!(SyncOptions::default().check_cancelled().is_ok());
+    }
+}
@@ -20,7 +20,8 @@ use iota_client::{
 
 use crate::{
     account::{
-        build_transaction_from_payload_and_inputs, handle::AccountHandle, types::OutputData, AddressWithUnspentOutputs,
+        build_transaction_from_payload_and_inputs, handle::AccountHandle,
+        operations::syncing::request_limiter::RequestLimiter, types::OutputData, AddressWithUnspentOutputs,
     },
     task,
 };
@@ -67,6 +68,7 @@ impl AccountHandle {
                 network_id,
                 remainder,
                 chain: Some(chain),
+                label: None,
             });
         }
 
@@ -78,6 +80,7 @@ impl AccountHandle {
     pub(crate) async fn get_outputs(
         &self,
         output_ids: Vec<OutputId>,
+        request_limiter: &RequestLimiter,
     ) -> crate::Result<Vec<OutputWithMetadataResponse>> {
         log::debug!("[SYNC] start get_outputs");
         let get_outputs_start_time = Instant::now();
@@ -109,7 +112,7 @@ impl AccountHandle {
         drop(account);
 
         if !unknown_outputs.is_empty() {
-            outputs.extend(self.client.get_outputs(unknown_outputs).await?);
+            outputs.extend(request_limiter.get_outputs(&self.client, &unknown_outputs).await?);
         }
 
         log::debug!(
@@ -126,11 +129,15 @@ impl AccountHandle {
     pub(crate) async fn request_incoming_transaction_data(
         &self,
         transaction_ids: Vec<TransactionId>,
+        request_limiter: &RequestLimiter,
     ) -> crate::Result<()> {
         log::debug!("[SYNC] request_incoming_transaction_data");
 
-        // Limit parallel requests to 100, to avoid timeouts
-        for transaction_ids_chunk in transaction_ids.chunks(100).map(|x: &[TransactionId]| x.to_vec()) {
+        // Limit parallel requests, to avoid timeouts
+        for transaction_ids_chunk in transaction_ids
+            .chunks(request_limiter.max_parallel_requests())
+            .map(|x: &[TransactionId]| x.to_vec())
+        {
             let mut tasks = Vec::new();
             let account = self.read().await;
 
@@ -144,13 +151,21 @@ impl AccountHandle {
                 }
 
                 let client = self.client.clone();
+                let request_limiter = request_limiter.clone();
                 tasks.push(async move {
                     task::spawn(async move {
-                        match client.get_included_block(&transaction_id).await {
+                        match request_limiter
+                            .request(|| client.get_included_block(&transaction_id))
+                            .await
+                        {
                             Ok(block) => {
                                 if let Some(Payload::Transaction(transaction_payload)) = block.payload() {
-                                    let inputs =
-                                        get_inputs_for_transaction_payload(&client, transaction_payload).await?;
+                                    let inputs = get_inputs_for_transaction_payload(
+                                        &client,
+                                        transaction_payload,
+                                        &request_limiter,
+                                    )
+                                    .await?;
 
                                     let transaction = build_transaction_from_payload_and_inputs(
                                         transaction_id,
@@ -179,7 +194,13 @@ impl AccountHandle {
             for res in results {
                 match res? {
                     (transaction_id, Some(transaction)) => {
-                        account.incoming_transactions.insert(transaction_id, transaction);
+                        if account
+                            .incoming_transactions
+                            .insert(transaction_id, transaction)
+                            .is_none()
+                        {
+                            self.count_backup_change();
+                        }
                     }
                     (transaction_id, None) => {
                         log::debug!("[SYNC] adding {transaction_id} to inaccessible_incoming_transactions");
@@ -199,6 +220,7 @@ impl AccountHandle {
 pub(crate) async fn get_inputs_for_transaction_payload(
     client: &Client,
     transaction_payload: &TransactionPayload,
+    request_limiter: &RequestLimiter,
 ) -> crate::Result<Vec<OutputWithMetadataResponse>> {
     let TransactionEssence::Regular(essence) = transaction_payload.essence();
     let mut output_ids = Vec::new();
@@ -209,5 +231,5 @@ pub(crate) async fn get_inputs_for_transaction_payload(
         }
     }
 
-    client.try_get_outputs(output_ids).await.map_err(|e| e.into())
+    Ok(request_limiter.try_get_outputs(client, &output_ids).await)
 }
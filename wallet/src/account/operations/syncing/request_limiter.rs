@@ -0,0 +1,380 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{future::Future, sync::Arc, time::Duration};
+
+use instant::Instant;
+use iota_client::{
+    api_types::{core::response::OutputWithMetadataResponse, plugins::indexer::OutputIdsResponse},
+    block::output::{dto::OutputMetadataDto, OutputId},
+    node_api::indexer::query_parameters::QueryParameter,
+    Client,
+};
+use tokio::sync::{Mutex, Semaphore};
+
+use crate::account::operations::syncing::options::SyncRequestOptions;
+
+/// The page size for indexer queries, the pages are requested one by one, so each counts as a request
+pub(crate) const INDEXER_PAGE_SIZE: usize = 1000;
+
+/// Limits the parallel requests and the requests per second to the node and retries failed requests. Clones share the
+/// limits, so the ones of the account manager apply to all its accounts together. Every method sends one HTTP request
+/// per permit and rate limit token, batches are split into single requests.
+#[derive(Debug, Clone)]
+pub(crate) struct RequestLimiter {
+    pub(crate) options: SyncRequestOptions,
+    semaphore: Arc<Semaphore>,
+    // The earliest time at which the next request can be sent
+    next_request: Arc<Mutex<Option<Instant>>>,
+}
+
+impl RequestLimiter {
+    pub(crate) fn new(options: SyncRequestOptions) -> Self {
+        Self {
+            options,
+            semaphore: Arc::new(Semaphore::new(options.max_parallel_requests.max(1))),
+            next_request: Default::default(),
+        }
+    }
+
+    /// The amount of requests that can be sent in parallel, also used to split addresses and outputs into chunks
+    pub(crate) fn max_parallel_requests(&self) -> usize {
+        self.options.max_parallel_requests.max(1)
+    }
+
+    /// Sends the request once the limits allow it and retries it if it failed, a new future is created for every retry
+    pub(crate) async fn request<T, F, Fut>(&self, mut request: F) -> iota_client::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = iota_client::Result<T>>,
+    {
+        // The semaphore is never closed
+        let _permit = self.semaphore.acquire().await.expect("request semaphore closed");
+        let mut retries = 0;
+        loop {
+            self.wait_for_rate_limit().await;
+            match request().await {
+                Err(err) if retries < self.options.max_retries && is_retryable(&err) => {
+                    let delay = retry_delay(self.options.retry_delay_in_milliseconds, retries);
+                    log::debug!("[SYNC] request failed, retrying in {delay:?}: {err}");
+                    sleep(delay).await;
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Sends an indexer query with [`INDEXER_PAGE_SIZE`] and returns the output ids of all pages. The pages are
+    /// requested one after another with the cursor, so every page takes a permit and a rate limit token.
+    pub(crate) async fn request_output_ids<F, Fut>(
+        &self,
+        query_parameters: Vec<QueryParameter>,
+        mut request: F,
+    ) -> iota_client::Result<OutputIdsResponse>
+    where
+        F: FnMut(Vec<QueryParameter>) -> Fut,
+        Fut: Future<Output = iota_client::Result<OutputIdsResponse>>,
+    {
+        let mut output_ids_response = self
+            .request_output_ids_page(query_parameters.clone(), INDEXER_PAGE_SIZE, String::new(), &mut request)
+            .await?;
+        while let Some(cursor) = output_ids_response.cursor.take() {
+            let page = self
+                .request_output_ids_page(query_parameters.clone(), INDEXER_PAGE_SIZE, cursor, &mut request)
+                .await?;
+            output_ids_response.ledger_index = page.ledger_index;
+            output_ids_response.cursor = page.cursor;
+            output_ids_response.items.extend(page.items);
+        }
+        Ok(output_ids_response)
+    }
+
+    /// Sends an indexer query for a single page, the client returns early if a cursor is provided. An empty cursor
+    /// requests the first page.
+    pub(crate) async fn request_output_ids_page<F, Fut>(
+        &self,
+        mut query_parameters: Vec<QueryParameter>,
+        page_size: usize,
+        cursor: String,
+        mut request: F,
+    ) -> iota_client::Result<OutputIdsResponse>
+    where
+        F: FnMut(Vec<QueryParameter>) -> Fut,
+        Fut: Future<Output = iota_client::Result<OutputIdsResponse>>,
+    {
+        query_parameters.push(QueryParameter::PageSize(page_size));
+        query_parameters.push(QueryParameter::Cursor(cursor));
+        self.request(|| request(query_parameters.clone())).await
+    }
+
+    /// Gets the outputs with one request per output, instead of a single batch that would bypass the limits.
+    pub(crate) async fn get_outputs(
+        &self,
+        client: &Client,
+        output_ids: &[OutputId],
+    ) -> iota_client::Result<Vec<OutputWithMetadataResponse>> {
+        futures::future::try_join_all(
+            output_ids
+                .iter()
+                .map(|output_id| self.request(|| client.get_output(output_id))),
+        )
+        .await
+    }
+
+    /// Like [`Self::get_outputs()`], but outputs that couldn't be requested are skipped.
+    pub(crate) async fn try_get_outputs(
+        &self,
+        client: &Client,
+        output_ids: &[OutputId],
+    ) -> Vec<OutputWithMetadataResponse> {
+        futures::future::join_all(
+            output_ids
+                .iter()
+                .map(|output_id| self.request(|| client.get_output(output_id))),
+        )
+        .await
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect()
+    }
+
+    /// Gets the metadata of the outputs with one request per output, outputs that couldn't be requested, because they
+    /// got pruned for example, are skipped.
+    pub(crate) async fn try_get_outputs_metadata(
+        &self,
+        client: &Client,
+        output_ids: &[OutputId],
+    ) -> Vec<OutputMetadataDto> {
+        futures::future::join_all(
+            output_ids
+                .iter()
+                .map(|output_id| self.request(|| client.get_output_metadata(output_id))),
+        )
+        .await
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect()
+    }
+
+    /// Gets the metadata of the outputs with one request per output, outputs the node doesn't have, because they got
+    /// spent and pruned, are skipped. Other errors are returned, because the outputs could still be unspent.
+    pub(crate) async fn get_outputs_metadata_skip_not_found(
+        &self,
+        client: &Client,
+        output_ids: &[OutputId],
+    ) -> iota_client::Result<Vec<OutputMetadataDto>> {
+        let mut outputs_metadata = Vec::new();
+        for result in futures::future::join_all(
+            output_ids
+                .iter()
+                .map(|output_id| self.request(|| client.get_output_metadata(output_id))),
+        )
+        .await
+        {
+            match result {
+                Ok(output_metadata) => outputs_metadata.push(output_metadata),
+                Err(iota_client::Error::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(outputs_metadata)
+    }
+
+    // Waits until the next request can be sent without exceeding the requests per second
+    async fn wait_for_rate_limit(&self) {
+        if let Some(send_at) = self.reserve_request().await {
+            let now = Instant::now();
+            if send_at > now {
+                sleep(send_at - now).await;
+            }
+        }
+    }
+
+    // Reserves the time slot for a request, so the following requests are delayed accordingly. Returns the time at
+    // which it can be sent. `None` if the requests per second aren't limited.
+    async fn reserve_request(&self) -> Option<Instant> {
+        let requests_per_second = self.options.requests_per_second?;
+        let request_interval = Duration::from_secs(1) / requests_per_second.max(1);
+        let mut next_request = self.next_request.lock().await;
+        let now = Instant::now();
+        let send_at = next_request.map_or(now, |next_request| next_request.max(now));
+        *next_request = Some(send_at + request_interval);
+        Some(send_at)
+    }
+}
+
+impl Default for RequestLimiter {
+    fn default() -> Self {
+        Self::new(SyncRequestOptions::default())
+    }
+}
+
+// Only timeouts, errors sending the request, like connection errors, too many requests and server errors can be gone
+// with a retry, other responses like data the node doesn't have or an invalid request won't change
+fn is_retryable(error: &iota_client::Error) -> bool {
+    match error {
+        iota_client::Error::ResponseError { code, .. } => *code == 429 || *code >= 500,
+        iota_client::Error::Reqwest(error) => error.is_timeout() || error.is_request(),
+        _ => false,
+    }
+}
+
+// The delay is doubled for every retry
+fn retry_delay(retry_delay_in_milliseconds: u64, retries: u32) -> Duration {
+    Duration::from_millis(retry_delay_in_milliseconds.saturating_mul(2u64.saturating_pow(retries)))
+}
+
+async fn sleep(duration: Duration) {
+    #[cfg(target_family = "wasm")]
+    gloo_timers::future::TimeoutFuture::new(duration.as_millis() as u32).await;
+    #[cfg(not(target_family = "wasm"))]
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+    use iota_client::block::payload::transaction::TransactionId;
+
+    use super::*;
+
+    #[test]
+    fn retry_delays() {
+        assert_eq!(retry_delay(500, 0), Duration::from_millis(500));
+        assert_eq!(retry_delay(500, 1), Duration::from_millis(1000));
+        assert_eq!(retry_delay(500, 3), Duration::from_millis(4000));
+        assert_eq!(retry_delay(500, 100), Duration::from_millis(u64::MAX));
+    }
+
+    #[tokio::test]
+    async fn retries() {
+        let request_limiter = RequestLimiter::new(SyncRequestOptions {
+            max_retries: 2,
+            retry_delay_in_milliseconds: 1,
+            ..Default::default()
+        });
+
+        // Fails twice, succeeds with the last retry
+        let attempts = AtomicU32::new(0);
+        let result = request_limiter
+            .request(|| async {
+                match attempts.fetch_add(1, Ordering::Relaxed) {
+                    0 => Err(response_error(429)),
+                    1 => Err(response_error(503)),
+                    _ => Ok(()),
+                }
+            })
+            .await;
+        assert!(result.is_ok());
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+
+        // Not found and other client errors aren't retried
+        let not_retryable: [fn() -> iota_client::Error; 2] = [
+            || iota_client::Error::NotFound("output".to_string()),
+            || response_error(400),
+        ];
+        for error in not_retryable {
+            let attempts = AtomicU32::new(0);
+            let result: iota_client::Result<()> = request_limiter
+                .request(|| async {
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    Err(error())
+                })
+                .await;
+            assert!(result.is_err());
+            assert_eq!(attempts.load(Ordering::Relaxed), 1);
+        }
+    }
+
+    fn response_error(code: u16) -> iota_client::Error {
+        iota_client::Error::ResponseError {
+            code,
+            text: String::new(),
+            url: "http://localhost:14265".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_per_second() {
+        let request_limiter = RequestLimiter::new(SyncRequestOptions {
+            requests_per_second: Some(100),
+            ..Default::default()
+        });
+
+        let start = Instant::now();
+        for _ in 0..11 {
+            request_limiter.request(|| async { Ok(()) }).await.unwrap();
+        }
+        // The first request is sent immediately, the others every 10ms
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn budget_holds_for_parallel_requests() {
+        let request_limiter = RequestLimiter::new(SyncRequestOptions {
+            max_parallel_requests: 3,
+            requests_per_second: Some(100),
+            ..Default::default()
+        });
+
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+        let start = Instant::now();
+        futures::future::try_join_all((0..21).map(|_| {
+            request_limiter.request(|| async {
+                let requests = in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+                max_in_flight.fetch_max(requests, Ordering::Relaxed);
+                sleep(Duration::from_millis(5)).await;
+                in_flight.fetch_sub(1, Ordering::Relaxed);
+                Ok(())
+            })
+        }))
+        .await
+        .unwrap();
+        // Never more than the parallel requests at once and not faster than one request every 10ms
+        assert!(max_in_flight.load(Ordering::Relaxed) <= 3);
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn pages_are_counted() {
+        let request_limiter = RequestLimiter::new(SyncRequestOptions {
+            requests_per_second: Some(100),
+            ..Default::default()
+        });
+        let output_id = OutputId::new(TransactionId::new([1; 32]), 0).unwrap();
+
+        let start = Instant::now();
+        let response = request_limiter
+            .request_output_ids(Vec::new(), |query_parameters| {
+                assert!(query_parameters
+                    .iter()
+                    .any(|parameter| matches!(parameter, QueryParameter::PageSize(INDEXER_PAGE_SIZE))));
+                // Every page is requested with the cursor of the previous one, the first with an empty one
+                let page = query_parameters
+                    .iter()
+                    .find_map(|parameter| match parameter {
+                        QueryParameter::Cursor(cursor) => Some(cursor.parse::<usize>().unwrap_or_default()),
+                        _ => None,
+                    })
+                    .unwrap();
+                async move {
+                    Ok(OutputIdsResponse {
+                        ledger_index: 0,
+                        cursor: (page < 2).then(|| (page + 1).to_string()),
+                        items: vec![output_id; if page < 2 { INDEXER_PAGE_SIZE } else { 1 }],
+                    })
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.items.len(), 2 * INDEXER_PAGE_SIZE + 1);
+        assert!(response.cursor.is_none());
+        // The query needed 3 pages, which were sent 10ms after each other, so the next request is sent 30ms after the
+        // first page
+        request_limiter.request(|| async { Ok(()) }).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+}
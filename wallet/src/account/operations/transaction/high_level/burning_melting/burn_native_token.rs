@@ -7,7 +7,7 @@ use std::{
 };
 
 use iota_client::{
-    api::input_selection::Burn,
+    api::{input_selection::Burn, PreparedTransactionData},
     block::{
         input::INPUT_COUNT_MAX,
         output::{
@@ -56,7 +56,19 @@ impl AccountHandle {
         burn_amount: U256,
         options: Option<TransactionOptions>,
     ) -> crate::Result<Transaction> {
-        log::debug!("[TRANSACTION] burn_native_token");
+        let prepared_transaction = self.prepare_burn_native_token(token_id, burn_amount, options).await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.burn_native_token()](crate::account::handle::AccountHandle.burn_native_token)
+    pub async fn prepare_burn_native_token(
+        &self,
+        token_id: TokenId,
+        burn_amount: U256,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_burn_native_token");
 
         let StrippedOutputAggregate {
             custom_inputs,
@@ -78,7 +90,7 @@ impl AccountHandle {
             }),
         };
 
-        self.send(outputs, options).await
+        self.prepare_transaction(outputs, options).await
     }
 
     // Get inputs with the required native token amount and create new outputs, just with the to be burned native token
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use iota_client::{
-    api::input_selection::Burn,
+    api::{input_selection::Burn, PreparedTransactionData},
     block::{
         address::{Address, NftAddress},
         output::{
@@ -24,7 +24,18 @@ use crate::{
 impl AccountHandle {
     /// Function to burn an nft output.
     pub async fn burn_nft(&self, nft_id: NftId, options: Option<TransactionOptions>) -> crate::Result<Transaction> {
-        log::debug!("[TRANSACTION] burn_nft");
+        let prepared_transaction = self.prepare_burn_nft(nft_id, options).await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.burn_nft()](crate::account::handle::AccountHandle.burn_nft)
+    pub async fn prepare_burn_nft(
+        &self,
+        nft_id: NftId,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_burn_nft");
 
         let current_time = self.client().get_time_checked().await?;
 
@@ -68,7 +79,7 @@ impl AccountHandle {
             }),
         };
 
-        self.send(outputs, options).await
+        self.prepare_transaction(outputs, options).await
     }
 
     // Get the current output id for the nft and build a basic output with the amount, native tokens and
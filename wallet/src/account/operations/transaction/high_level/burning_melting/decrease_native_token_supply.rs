@@ -1,8 +1,11 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_client::block::output::{
-    AliasOutputBuilder, FoundryId, FoundryOutputBuilder, Output, SimpleTokenScheme, TokenId, TokenScheme,
+use iota_client::{
+    api::PreparedTransactionData,
+    block::output::{
+        AliasOutputBuilder, FoundryId, FoundryOutputBuilder, Output, SimpleTokenScheme, TokenId, TokenScheme,
+    },
 };
 use primitive_types::U256;
 
@@ -18,7 +21,20 @@ impl AccountHandle {
         melt_amount: U256,
         options: Option<TransactionOptions>,
     ) -> crate::Result<Transaction> {
-        log::debug!("[TRANSACTION] decrease_native_token_supply");
+        let prepared_transaction = self
+            .prepare_decrease_native_token_supply(token_id, melt_amount, options)
+            .await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for [`AccountHandle::decrease_native_token_supply()`]
+    pub async fn prepare_decrease_native_token_supply(
+        &self,
+        token_id: TokenId,
+        melt_amount: U256,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_decrease_native_token_supply");
 
         let foundry_id = FoundryId::from(token_id);
         let alias_id = *foundry_id.alias_address().alias_id();
@@ -51,7 +67,7 @@ impl AccountHandle {
                     .finish_output(token_supply)?,
             ];
             // Input selection will detect that we're melting native tokens and add the required inputs if available
-            self.send(outputs, options).await
+            self.prepare_transaction(outputs, options).await
         } else {
             unreachable!("We checked if it's an alias output before")
         }
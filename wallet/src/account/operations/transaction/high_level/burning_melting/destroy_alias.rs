@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use iota_client::{
-    api::input_selection::Burn,
+    api::{input_selection::Burn, PreparedTransactionData},
     block::{
         address::{Address, AliasAddress},
         output::{
@@ -27,7 +27,18 @@ impl AccountHandle {
         alias_id: AliasId,
         options: Option<TransactionOptions>,
     ) -> crate::Result<Transaction> {
-        log::debug!("[TRANSACTION] destroy_alias");
+        let prepared_transaction = self.prepare_destroy_alias(alias_id, options).await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.destroy_alias()](crate::account::handle::AccountHandle.destroy_alias)
+    pub async fn prepare_destroy_alias(
+        &self,
+        alias_id: AliasId,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_destroy_alias");
 
         let current_time = self.client().get_time_checked().await?;
 
@@ -75,7 +86,7 @@ impl AccountHandle {
             }),
         };
 
-        self.send(outputs, options).await
+        self.prepare_transaction(outputs, options).await
     }
 
     // Get the current output id for the alias and build a basic output with the amount, native tokens and
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use iota_client::{
-    api::input_selection::Burn,
+    api::{input_selection::Burn, PreparedTransactionData},
     block::output::{AliasId, AliasOutputBuilder, FoundryId, NativeTokensBuilder, Output, TokenScheme},
 };
 
@@ -19,7 +19,18 @@ impl AccountHandle {
         foundry_id: FoundryId,
         options: Option<TransactionOptions>,
     ) -> crate::Result<Transaction> {
-        log::debug!("[TRANSACTION] destroy_foundry");
+        let prepared_transaction = self.prepare_destroy_foundry(foundry_id, options).await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.destroy_foundry()](crate::account::handle::AccountHandle.destroy_foundry)
+    pub async fn prepare_destroy_foundry(
+        &self,
+        foundry_id: FoundryId,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_destroy_foundry");
 
         let token_supply = self.client.get_token_supply().await?;
         let alias_id = *foundry_id.alias_address().alias_id();
@@ -70,7 +81,7 @@ impl AccountHandle {
             _ => unreachable!("We checked if it's an alias output before"),
         };
 
-        self.send(outputs, options).await
+        self.prepare_transaction(outputs, options).await
     }
 
     /// Find and return unspent `OutputData` for given `alias_id` and `foundry_id`
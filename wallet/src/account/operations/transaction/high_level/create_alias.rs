@@ -103,7 +103,7 @@ impl AccountHandle {
         self.sign_and_submit_transaction(prepared_transaction).await
     }
 
-    pub(crate) async fn prepare_create_alias_output(
+    pub async fn prepare_create_alias_output(
         &self,
         alias_output_options: Option<AliasOutputOptions>,
         options: Option<TransactionOptions>,
@@ -9,7 +9,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     account::{
-        handle::AccountHandle, operations::transaction::high_level::minting::mint_native_token::MintTokenTransaction,
+        handle::AccountHandle,
+        operations::transaction::high_level::minting::mint_native_token::{
+            MintTokenTransaction, PreparedMintTokenTransaction,
+        },
         TransactionOptions,
     },
     Error,
@@ -50,10 +53,26 @@ impl AccountHandle {
         &self,
         token_id: TokenId,
         mint_amount: U256,
-        _increase_native_token_supply_options: Option<IncreaseNativeTokenSupplyOptions>,
+        increase_native_token_supply_options: Option<IncreaseNativeTokenSupplyOptions>,
         options: Option<TransactionOptions>,
     ) -> crate::Result<MintTokenTransaction> {
-        log::debug!("[TRANSACTION] increase_native_token_supply");
+        let prepared_transaction = self
+            .prepare_increase_native_token_supply(token_id, mint_amount, increase_native_token_supply_options, options)
+            .await?;
+        self.sign_and_submit_transaction(prepared_transaction.transaction)
+            .await
+            .map(|transaction| MintTokenTransaction { token_id, transaction })
+    }
+
+    /// Function to prepare the transaction for [`AccountHandle::increase_native_token_supply()`]
+    pub async fn prepare_increase_native_token_supply(
+        &self,
+        token_id: TokenId,
+        mint_amount: U256,
+        _increase_native_token_supply_options: Option<IncreaseNativeTokenSupplyOptions>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedMintTokenTransaction> {
+        log::debug!("[TRANSACTION] prepare_increase_native_token_supply");
 
         let account = self.read().await;
         let token_supply = self.client.get_token_supply().await?;
@@ -130,8 +149,8 @@ impl AccountHandle {
             // Native Tokens will be added automatically in the remainder output in try_select_inputs()
         ];
 
-        self.send(outputs, options)
+        self.prepare_transaction(outputs, options)
             .await
-            .map(|transaction| MintTokenTransaction { token_id, transaction })
+            .map(|transaction| PreparedMintTokenTransaction { token_id, transaction })
     }
 }
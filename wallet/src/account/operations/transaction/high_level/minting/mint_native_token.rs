@@ -1,16 +1,20 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_client::block::{
-    address::AliasAddress,
-    dto::U256Dto,
-    output::{
-        dto::AliasIdDto,
-        feature::{Feature, MetadataFeature},
-        unlock_condition::{ImmutableAliasAddressUnlockCondition, UnlockCondition},
-        AliasId, AliasOutputBuilder, FoundryId, FoundryOutputBuilder, Output, SimpleTokenScheme, TokenId, TokenScheme,
+use iota_client::{
+    api::PreparedTransactionData,
+    block::{
+        address::AliasAddress,
+        dto::U256Dto,
+        output::{
+            dto::AliasIdDto,
+            feature::{Feature, MetadataFeature},
+            unlock_condition::{ImmutableAliasAddressUnlockCondition, UnlockCondition},
+            AliasId, AliasOutputBuilder, FoundryId, FoundryOutputBuilder, Output, SimpleTokenScheme, TokenId,
+            TokenScheme,
+        },
+        DtoError,
     },
-    DtoError,
 };
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
@@ -86,6 +90,13 @@ pub struct MintTokenTransaction {
     pub transaction: Transaction,
 }
 
+/// The prepared transaction of a minting native token transaction
+#[derive(Debug)]
+pub struct PreparedMintTokenTransaction {
+    pub token_id: TokenId,
+    pub transaction: PreparedTransactionData,
+}
+
 /// Dto for MintTokenTransaction
 #[derive(Debug, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -127,7 +138,23 @@ impl AccountHandle {
         native_token_options: NativeTokenOptions,
         options: Option<TransactionOptions>,
     ) -> crate::Result<MintTokenTransaction> {
-        log::debug!("[TRANSACTION] mint_native_token");
+        let prepared_transaction = self.prepare_mint_native_token(native_token_options, options).await?;
+        self.sign_and_submit_transaction(prepared_transaction.transaction)
+            .await
+            .map(|transaction| MintTokenTransaction {
+                token_id: prepared_transaction.token_id,
+                transaction,
+            })
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.mint_native_token()](crate::account::handle::AccountHandle.mint_native_token)
+    pub async fn prepare_mint_native_token(
+        &self,
+        native_token_options: NativeTokenOptions,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedMintTokenTransaction> {
+        log::debug!("[TRANSACTION] prepare_mint_native_token");
         let rent_structure = self.client.get_rent_structure().await?;
         let token_supply = self.client.get_token_supply().await?;
 
@@ -175,9 +202,9 @@ impl AccountHandle {
                     foundry_builder.finish_output(token_supply)?
                 }, // Native Tokens will be added automatically in the remainder output in try_select_inputs()
             ];
-            self.send(outputs, options)
+            self.prepare_transaction(outputs, options)
                 .await
-                .map(|transaction| MintTokenTransaction { token_id, transaction })
+                .map(|transaction| PreparedMintTokenTransaction { token_id, transaction })
         } else {
             unreachable!("We checked if it's an alias output before")
         }
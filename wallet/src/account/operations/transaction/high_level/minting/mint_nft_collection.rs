@@ -0,0 +1,574 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use crypto::keys::slip10::Chain;
+use iota_client::{
+    api::{input_selection::minimum_storage_deposit_basic_output, PreparedTransactionData},
+    block::{
+        address::{Address, NftAddress},
+        output::{dto::OutputMetadataDto, NftId, Output, OutputId, OUTPUT_COUNT_MAX},
+        payload::transaction::{TransactionEssence, TransactionId},
+        Block,
+    },
+};
+use packable::PackableExt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{
+        handle::AccountHandle,
+        operations::transaction::high_level::minting::mint_nfts::{nft_output, NftOptions, NftOptionsDto},
+        types::{InclusionState, OutputData, Transaction},
+        TransactionOptions,
+    },
+    Error,
+};
+
+/// Options for [`AccountHandle::mint_nft_collection()`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NftCollectionMintingOptions {
+    /// The maximum number of NFTs minted in one transaction, lower values than the one given by the output count and
+    /// block size limits can be used to keep transactions small.
+    pub max_nfts_per_transaction: Option<usize>,
+    /// The maximum number of transactions that are sent before waiting for their inclusion, every transaction spends
+    /// the issuer NFT and remainder outputs of the previous one. 1 by default, so every transaction is included before
+    /// the next one is sent.
+    pub max_pending_transactions: Option<usize>,
+    /// Options for every transaction, custom and mandatory inputs aren't supported.
+    pub transaction_options: Option<TransactionOptions>,
+}
+
+/// A submitted transaction of an NFT collection minting and the NFTs it mints.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NftCollectionTransaction {
+    /// The id of the transaction.
+    pub transaction_id: TransactionId,
+    /// The options of the NFTs minted by the transaction.
+    pub nfts: Vec<NftOptions>,
+}
+
+/// Dto for [`NftCollectionTransaction`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NftCollectionTransactionDto {
+    /// The id of the transaction.
+    pub transaction_id: TransactionId,
+    /// The options of the NFTs minted by the transaction.
+    pub nfts: Vec<NftOptionsDto>,
+}
+
+impl From<&NftCollectionTransaction> for NftCollectionTransactionDto {
+    fn from(value: &NftCollectionTransaction) -> Self {
+        Self {
+            transaction_id: value.transaction_id,
+            nfts: value.nfts.iter().map(NftOptionsDto::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<&NftCollectionTransactionDto> for NftCollectionTransaction {
+    type Error = crate::Error;
+
+    fn try_from(value: &NftCollectionTransactionDto) -> crate::Result<Self> {
+        Ok(Self {
+            transaction_id: value.transaction_id,
+            nfts: value
+                .nfts
+                .iter()
+                .map(NftOptions::try_from)
+                .collect::<crate::Result<_>>()?,
+        })
+    }
+}
+
+/// The state of minting an NFT collection. If minting failed partway, it contains the error and can be passed to
+/// [`AccountHandle::resume_nft_collection_minting()`] to mint the remaining NFTs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NftCollectionMinting {
+    /// The id of the issuer NFT of the collection.
+    pub issuer_nft_id: NftId,
+    /// The options of the NFTs that aren't sent yet.
+    pub pending: Vec<NftOptions>,
+    /// The submitted transactions that aren't known to be included yet, in the order they were sent.
+    pub unconfirmed: Vec<NftCollectionTransaction>,
+    /// The included transactions that minted NFTs.
+    pub transaction_ids: Vec<TransactionId>,
+    /// The number of minted NFTs.
+    pub minted: usize,
+    /// The error that stopped minting, `None` if all NFTs are minted.
+    pub error: Option<String>,
+}
+
+impl NftCollectionMinting {
+    /// Returns true if all NFTs of the collection are minted.
+    pub fn is_finished(&self) -> bool {
+        self.pending.is_empty() && self.unconfirmed.is_empty()
+    }
+}
+
+/// Dto for [`NftCollectionMinting`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NftCollectionMintingDto {
+    /// The id of the issuer NFT of the collection.
+    pub issuer_nft_id: NftId,
+    /// The options of the NFTs that aren't sent yet.
+    pub pending: Vec<NftOptionsDto>,
+    /// The submitted transactions that aren't known to be included yet, in the order they were sent.
+    pub unconfirmed: Vec<NftCollectionTransactionDto>,
+    /// The included transactions that minted NFTs.
+    pub transaction_ids: Vec<TransactionId>,
+    /// The number of minted NFTs.
+    pub minted: usize,
+    /// The error that stopped minting, `None` if all NFTs are minted.
+    pub error: Option<String>,
+}
+
+impl From<&NftCollectionMinting> for NftCollectionMintingDto {
+    fn from(value: &NftCollectionMinting) -> Self {
+        Self {
+            issuer_nft_id: value.issuer_nft_id,
+            pending: value.pending.iter().map(NftOptionsDto::from).collect(),
+            unconfirmed: value
+                .unconfirmed
+                .iter()
+                .map(NftCollectionTransactionDto::from)
+                .collect(),
+            transaction_ids: value.transaction_ids.clone(),
+            minted: value.minted,
+            error: value.error.clone(),
+        }
+    }
+}
+
+impl TryFrom<&NftCollectionMintingDto> for NftCollectionMinting {
+    type Error = crate::Error;
+
+    fn try_from(value: &NftCollectionMintingDto) -> crate::Result<Self> {
+        Ok(Self {
+            issuer_nft_id: value.issuer_nft_id,
+            pending: value
+                .pending
+                .iter()
+                .map(NftOptions::try_from)
+                .collect::<crate::Result<_>>()?,
+            unconfirmed: value
+                .unconfirmed
+                .iter()
+                .map(NftCollectionTransaction::try_from)
+                .collect::<crate::Result<_>>()?,
+            transaction_ids: value.transaction_ids.clone(),
+            minted: value.minted,
+            error: value.error.clone(),
+        })
+    }
+}
+
+impl AccountHandle {
+    /// Mint any number of NFTs with the issuer NFT of a collection as issuer. The issuer NFT needs to be unlocked in
+    /// every transaction, so the NFTs are split into transactions that respect the output count, block size and
+    /// available funds. Transactions are sent one after another, or with
+    /// [`NftCollectionMintingOptions::max_pending_transactions`] chained without waiting for the inclusion of the
+    /// previous ones. NFTs count as minted once their transaction is included.
+    /// An error after the first checks is returned in the job state instead, which can be resumed with
+    /// [`AccountHandle::resume_nft_collection_minting()`].
+    /// ```ignore
+    /// let nfts_options = (0..150)
+    ///     .map(|index| {
+    ///         Ok(NftOptions {
+    ///             address: None,
+    ///             sender: None,
+    ///             metadata: None,
+    ///             tag: None,
+    ///             issuer: None,
+    ///             immutable_metadata: Some(
+    ///                 Irc27Metadata::new("image/png", format!("https://mysite.com/{index}.png"), format!("#{index}"))
+    ///                     .to_bytes()?,
+    ///             ),
+    ///         })
+    ///     })
+    ///     .collect::<Result<Vec<_>>>()?;
+    ///
+    /// let mut minting = account.mint_nft_collection(issuer_nft_id, nfts_options, None).await?;
+    /// // Resume a few times if minting failed partway, the state can also be stored and resumed later
+    /// for _ in 0..3 {
+    ///     let Some(error) = &minting.error else { break };
+    ///     println!("Minting failed after {} NFTs: {error}", minting.minted);
+    ///     minting = account.resume_nft_collection_minting(minting, None).await?;
+    /// }
+    /// ```
+    pub async fn mint_nft_collection(
+        &self,
+        issuer_nft_id: NftId,
+        nfts_options: Vec<NftOptions>,
+        options: Option<NftCollectionMintingOptions>,
+    ) -> crate::Result<NftCollectionMinting> {
+        log::debug!("[TRANSACTION] mint_nft_collection");
+        let bech32_hrp = self.client.get_bech32_hrp().await?;
+        let issuer = Address::Nft(NftAddress::new(issuer_nft_id));
+        let pending = nfts_options
+            .into_iter()
+            .map(|mut nft_options| {
+                if let Some(other_issuer) = &nft_options.issuer {
+                    if Address::try_from_bech32(other_issuer)?.1 != issuer {
+                        return Err(Error::MintingFailed(format!(
+                            "issuer {other_issuer} doesn't match the collection issuer NFT {issuer_nft_id}"
+                        )));
+                    }
+                }
+                nft_options.issuer.replace(issuer.to_bech32(&bech32_hrp));
+                Ok(nft_options)
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        self.resume_nft_collection_minting(
+            NftCollectionMinting {
+                issuer_nft_id,
+                pending,
+                unconfirmed: Vec::new(),
+                transaction_ids: Vec::new(),
+                minted: 0,
+                error: None,
+            },
+            options,
+        )
+        .await
+    }
+
+    /// Continue minting an NFT collection with the NFTs that weren't minted yet. Unconfirmed transactions of the
+    /// previous attempt are awaited first, their NFTs are sent again if the transactions won't be included.
+    pub async fn resume_nft_collection_minting(
+        &self,
+        mut minting: NftCollectionMinting,
+        options: Option<NftCollectionMintingOptions>,
+    ) -> crate::Result<NftCollectionMinting> {
+        log::debug!("[TRANSACTION] resume_nft_collection_minting");
+        let options = options.unwrap_or_default();
+        if let Some(transaction_options) = &options.transaction_options {
+            if transaction_options.custom_inputs.is_some() || transaction_options.mandatory_inputs.is_some() {
+                return Err(Error::MintingFailed(
+                    "custom and mandatory inputs can't be used to mint an nft collection".to_string(),
+                ));
+            }
+        }
+        // Room for the issuer NFT and the remainder output
+        let max_nfts = options
+            .max_nfts_per_transaction
+            .unwrap_or(usize::MAX)
+            .min(OUTPUT_COUNT_MAX as usize - 2);
+        let max_pending_transactions = options.max_pending_transactions.unwrap_or(1).max(1);
+        minting.error = None;
+
+        while !minting.is_finished() {
+            if let Err(err) = self
+                .mint_next_collection_nfts(&mut minting, &options, max_nfts, max_pending_transactions)
+                .await
+            {
+                log::debug!("[TRANSACTION] nft collection minting stopped: {err}");
+                minting.error.replace(err.to_string());
+                break;
+            }
+        }
+
+        Ok(minting)
+    }
+
+    // Send up to `max_pending_transactions` chained transactions with the pending NFTs and wait until they're
+    // included. Unconfirmed transactions of a previous attempt are awaited before new ones are sent.
+    async fn mint_next_collection_nfts(
+        &self,
+        minting: &mut NftCollectionMinting,
+        options: &NftCollectionMintingOptions,
+        max_nfts: usize,
+        max_pending_transactions: usize,
+    ) -> crate::Result<()> {
+        if minting.unconfirmed.is_empty() {
+            while minting.unconfirmed.len() < max_pending_transactions && !minting.pending.is_empty() {
+                let transaction = self
+                    .send_next_collection_transaction(minting, options, max_nfts)
+                    .await?;
+                // The next transaction spends the outputs of this one, which is only possible once it's submitted
+                if minting.unconfirmed.len() == max_pending_transactions
+                    || minting.pending.is_empty()
+                    || !self
+                        .add_unconfirmed_collection_outputs(&transaction, minting.issuer_nft_id)
+                        .await?
+                {
+                    break;
+                }
+            }
+        }
+
+        self.confirm_collection_transactions(minting).await?;
+
+        // Sync so the new issuer NFT output and the remainder are available for the next transactions
+        self.sync(None).await?;
+
+        Ok(())
+    }
+
+    // Send a transaction with as many of the pending NFTs as fit into it, they're moved to the unconfirmed ones
+    async fn send_next_collection_transaction(
+        &self,
+        minting: &mut NftCollectionMinting,
+        options: &NftCollectionMintingOptions,
+        max_nfts: usize,
+    ) -> crate::Result<Transaction> {
+        let rent_structure = self.client.get_rent_structure().await?;
+        let token_supply = self.client.get_token_supply().await?;
+        let default_address = self.default_nft_address().await?;
+
+        let issuer_output = self
+            .unspent_outputs(None)
+            .await?
+            .into_iter()
+            .find_map(|output_data| match output_data.output {
+                Output::Nft(nft) if nft.nft_id_non_null(&output_data.output_id) == minting.issuer_nft_id => {
+                    Some(Output::Nft(nft))
+                }
+                _ => None,
+            })
+            .ok_or(Error::NftNotFoundInUnspentOutputs)?;
+        let outputs = minting
+            .pending
+            .iter()
+            .take(max_nfts)
+            .map(|nft_options| {
+                nft_output(
+                    nft_options.clone(),
+                    default_address,
+                    rent_structure.clone(),
+                    token_supply,
+                )
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        // Upper bound from the outputs alone, the remainder needs its storage deposit as well
+        let estimated_count = chunk_len(
+            outputs.iter().map(|output| (output.amount(), output.packed_len())),
+            self.balance()
+                .await?
+                .base_coin
+                .available
+                .saturating_sub(minimum_storage_deposit_basic_output(
+                    &rent_structure,
+                    &None,
+                    token_supply,
+                )?),
+            Block::LENGTH_MAX.saturating_sub(issuer_output.packed_len()),
+        );
+        let (nfts_count, prepared_transaction_data) = self
+            .prepare_collection_transaction(&outputs, estimated_count, options.transaction_options.clone())
+            .await?;
+        log::debug!("[TRANSACTION] minting {nfts_count} nfts of the collection");
+
+        let transaction = self.sign_and_submit_transaction(prepared_transaction_data).await?;
+        minting.unconfirmed.push(NftCollectionTransaction {
+            transaction_id: transaction.transaction_id,
+            nfts: minting.pending.drain(..nfts_count).collect(),
+        });
+
+        Ok(transaction)
+    }
+
+    // Prepare the transaction with the most of the outputs from the start, up to the estimated count. Input selection
+    // adds the remainder with its storage deposit and the length of the essence is validated, so the count is reduced
+    // until the transaction can be prepared.
+    async fn prepare_collection_transaction(
+        &self,
+        outputs: &[Output],
+        estimated_count: usize,
+        transaction_options: Option<TransactionOptions>,
+    ) -> crate::Result<(usize, PreparedTransactionData)> {
+        let mut prepared: Option<(usize, PreparedTransactionData)> = None;
+        let mut last_error = None;
+        // Binary search between the largest count that could be prepared and the smallest one that couldn't
+        let (mut fitting, mut not_fitting) = (0, estimated_count + 1);
+        let mut count = estimated_count;
+        while count > fitting {
+            match self
+                .prepare_transaction(outputs[..count].to_vec(), transaction_options.clone())
+                .await
+            {
+                Ok(prepared_transaction_data) => {
+                    // Only the inputs of the transaction that gets sent stay locked
+                    if let Some((_, smaller)) = prepared.replace((count, prepared_transaction_data)) {
+                        self.unlock_outputs(
+                            &smaller
+                                .inputs_data
+                                .iter()
+                                .map(|input| *input.output_id())
+                                .collect::<Vec<_>>(),
+                        )
+                        .await?;
+                    }
+                    fitting = count;
+                }
+                Err(err) => {
+                    log::debug!("[TRANSACTION] {count} nfts of the collection don't fit into a transaction: {err}");
+                    not_fitting = count;
+                    last_error.replace(err);
+                }
+            }
+            count = (fitting + not_fitting) / 2;
+        }
+
+        prepared.ok_or_else(|| {
+            last_error.unwrap_or_else(|| {
+                Error::MintingFailed("the next nft doesn't fit into a transaction with the available funds".to_string())
+            })
+        })
+    }
+
+    // Add the issuer NFT output and the basic outputs of a submitted transaction that belong to the account to the
+    // unspent outputs, so the next transaction can spend them before this one is included. Outputs of transactions
+    // that don't get included are removed by the next sync. Returns false if the block wasn't submitted yet, then
+    // the outputs can't be spent.
+    async fn add_unconfirmed_collection_outputs(
+        &self,
+        transaction: &Transaction,
+        issuer_nft_id: NftId,
+    ) -> crate::Result<bool> {
+        let Some(block_id) = transaction.block_id else {
+            return Ok(false);
+        };
+        let TransactionEssence::Regular(essence) = transaction.payload.essence();
+
+        let mut account = self.write().await;
+        let account_addresses = account
+            .public_addresses
+            .iter()
+            .chain(account.internal_addresses.iter())
+            .map(|address| (address.address.inner, (address.internal, address.key_index)))
+            .collect::<HashMap<_, _>>();
+        for (output_index, output) in essence.outputs().iter().enumerate() {
+            // New NFTs aren't needed as inputs
+            let spendable = match output {
+                Output::Nft(nft) => *nft.nft_id() == issuer_nft_id,
+                Output::Basic(_) => true,
+                _ => false,
+            };
+            let Some(address) = output
+                .unlock_conditions()
+                .and_then(|unlock_conditions| unlock_conditions.address())
+                .map(|address_unlock_condition| *address_unlock_condition.address())
+            else {
+                continue;
+            };
+            let Some((internal, key_index)) = account_addresses.get(&address).copied().filter(|_| spendable) else {
+                continue;
+            };
+
+            let output_id = OutputId::new(transaction.transaction_id, output_index as u16)?;
+            let output_data = OutputData {
+                output_id,
+                metadata: OutputMetadataDto {
+                    block_id: block_id.to_string(),
+                    transaction_id: transaction.transaction_id.to_string(),
+                    output_index: output_index as u16,
+                    is_spent: false,
+                    milestone_index_spent: None,
+                    milestone_timestamp_spent: None,
+                    transaction_id_spent: None,
+                    milestone_index_booked: 0,
+                    milestone_timestamp_booked: 0,
+                    ledger_index: 0,
+                },
+                output: output.clone(),
+                is_spent: false,
+                address,
+                network_id: transaction.network_id,
+                remainder: true,
+                chain: Some(Chain::from_u32_hardened(vec![
+                    44,
+                    account.coin_type,
+                    account.index,
+                    internal as u32,
+                    key_index,
+                ])),
+                label: None,
+            };
+            account.outputs.insert(output_id, output_data.clone());
+            account.unspent_outputs.insert(output_id, output_data);
+        }
+
+        Ok(true)
+    }
+
+    // Wait until the unconfirmed transactions are included, in the order they were sent, because every one spends
+    // outputs of the previous. If a transaction won't be included, the chained ones won't be either and their NFTs are
+    // pending again.
+    async fn confirm_collection_transactions(&self, minting: &mut NftCollectionMinting) -> crate::Result<()> {
+        while let Some(transaction_id) = minting
+            .unconfirmed
+            .first()
+            .map(|transaction| transaction.transaction_id)
+        {
+            if let Err(err) = self.retry_transaction_until_included(&transaction_id, None, None).await {
+                // Sync to know if the transaction is still pending or won't be included anymore
+                self.sync(None).await?;
+                let inclusion_state = self
+                    .read()
+                    .await
+                    .transactions
+                    .get(&transaction_id)
+                    .map(|transaction| transaction.inclusion_state);
+                match inclusion_state {
+                    Some(InclusionState::Confirmed) => {}
+                    Some(InclusionState::Pending) => return Err(err),
+                    _ => {
+                        let mut pending = minting
+                            .unconfirmed
+                            .drain(..)
+                            .flat_map(|transaction| transaction.nfts)
+                            .collect::<Vec<_>>();
+                        pending.append(&mut minting.pending);
+                        minting.pending = pending;
+                        return Err(err);
+                    }
+                }
+            }
+            let transaction = minting.unconfirmed.remove(0);
+            minting.minted += transaction.nfts.len();
+            minting.transaction_ids.push(transaction.transaction_id);
+        }
+
+        Ok(())
+    }
+}
+
+// The number of NFT outputs, given by their amount and packed length, from the start that fit into one transaction
+fn chunk_len(outputs: impl Iterator<Item = (u64, usize)>, available_amount: u64, max_length: usize) -> usize {
+    let mut amount = 0u64;
+    let mut length = 0;
+    let mut count = 0;
+    for (output_amount, output_length) in outputs {
+        amount = amount.saturating_add(output_amount);
+        length += output_length;
+        if amount > available_amount || length > max_length {
+            break;
+        }
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_by_amount_and_length() {
+        let outputs = [(100_000, 1_000); 10];
+
+        assert_eq!(chunk_len(outputs.into_iter(), 10_000_000, 32_000), 10);
+        assert_eq!(chunk_len(outputs.into_iter(), 350_000, 32_000), 3);
+        assert_eq!(chunk_len(outputs.into_iter(), 10_000_000, 4_500), 4);
+        assert_eq!(chunk_len(outputs.into_iter(), 50_000, 32_000), 0);
+    }
+}
@@ -8,7 +8,7 @@ use iota_client::{
         output::{
             feature::{Feature, IssuerFeature, MetadataFeature, SenderFeature, TagFeature},
             unlock_condition::{AddressUnlockCondition, UnlockCondition},
-            NftId, NftOutputBuilder,
+            NftId, NftOutputBuilder, Output, RentStructure,
         },
         DtoError,
     },
@@ -16,7 +16,7 @@ use iota_client::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    account::{handle::AccountHandle, operations::transaction::Transaction, TransactionOptions},
+    account::{handle::AccountHandle, operations::transaction::Transaction, types::Irc27Metadata, TransactionOptions},
     Error,
 };
 
@@ -34,7 +34,8 @@ pub struct NftOptions {
     pub tag: Option<Vec<u8>>,
     /// NFT issuer feature.
     pub issuer: Option<String>,
-    /// NFT immutable metadata feature.
+    /// NFT immutable metadata feature. Metadata that claims to follow the IRC27 standard is validated, it can be
+    /// created with [`Irc27Metadata::to_bytes()`].
     #[serde(rename = "immutableMetadata")]
     pub immutable_metadata: Option<Vec<u8>>,
 }
@@ -58,6 +59,19 @@ pub struct NftOptionsDto {
     pub immutable_metadata: Option<String>,
 }
 
+impl From<&NftOptions> for NftOptionsDto {
+    fn from(value: &NftOptions) -> Self {
+        Self {
+            address: value.address.clone(),
+            sender: value.sender.clone(),
+            metadata: value.metadata.as_ref().map(prefix_hex::encode),
+            tag: value.tag.as_ref().map(prefix_hex::encode),
+            issuer: value.issuer.clone(),
+            immutable_metadata: value.immutable_metadata.as_ref().map(prefix_hex::encode),
+        }
+    }
+}
+
 impl TryFrom<&NftOptionsDto> for NftOptions {
     type Error = crate::Error;
 
@@ -100,7 +114,11 @@ impl AccountHandle {
     ///     metadata: Some(b"some nft metadata".to_vec()),
     ///     tag: None,
     ///     issuer: None,
-    ///     immutable_metadata: Some(b"some immutable nft metadata".to_vec()),
+    ///     immutable_metadata: Some(
+    ///         Irc27Metadata::new("image/png", "https://mysite.com/my-nft.png", "My NFT")
+    ///             .with_description("some immutable nft metadata")
+    ///             .to_bytes()?,
+    ///     ),
     /// }];
     ///
     /// let transaction = account.mint_nfts(nft_options, None).await?;
@@ -121,7 +139,7 @@ impl AccountHandle {
 
     /// Function to prepare the transaction for
     /// [AccountHandle.mint_nfts()](crate::account::handle::AccountHandle.mint_nfts)
-    async fn prepare_mint_nfts(
+    pub async fn prepare_mint_nfts(
         &self,
         nfts_options: Vec<NftOptions>,
         options: Option<TransactionOptions>,
@@ -129,54 +147,67 @@ impl AccountHandle {
         log::debug!("[TRANSACTION] prepare_mint_nfts");
         let rent_structure = self.client.get_rent_structure().await?;
         let token_supply = self.client.get_token_supply().await?;
-        let account_addresses = self.addresses().await?;
-        let mut outputs = Vec::new();
-
-        for nft_options in nfts_options {
-            let address = match nft_options.address {
-                Some(address) => Address::try_from_bech32(address)?.1,
-                // todo other error message
-                None => {
-                    account_addresses
-                        .first()
-                        .ok_or(Error::FailedToGetRemainder)?
-                        .address
-                        .inner
-                }
-            };
-
-            // NftId needs to be set to 0 for the creation
-            let mut nft_builder =
-                NftOutputBuilder::new_with_minimum_storage_deposit(rent_structure.clone(), NftId::null())?
-                    // Address which will own the nft
-                    .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)));
-
-            if let Some(sender) = nft_options.sender {
-                nft_builder =
-                    nft_builder.add_feature(Feature::Sender(SenderFeature::new(Address::try_from_bech32(sender)?.1)));
-            }
-
-            if let Some(metadata) = nft_options.metadata {
-                nft_builder = nft_builder.add_feature(Feature::Metadata(MetadataFeature::new(metadata)?));
-            }
-
-            if let Some(tag) = nft_options.tag {
-                nft_builder = nft_builder.add_feature(Feature::Tag(TagFeature::new(tag)?));
-            }
-
-            if let Some(issuer) = nft_options.issuer {
-                nft_builder = nft_builder
-                    .add_immutable_feature(Feature::Issuer(IssuerFeature::new(Address::try_from_bech32(issuer)?.1)));
-            }
-
-            if let Some(immutable_metadata) = nft_options.immutable_metadata {
-                nft_builder =
-                    nft_builder.add_immutable_feature(Feature::Metadata(MetadataFeature::new(immutable_metadata)?));
-            }
-
-            outputs.push(nft_builder.finish_output(token_supply)?);
-        }
+        let default_address = self.default_nft_address().await?;
+
+        let outputs = nfts_options
+            .into_iter()
+            .map(|nft_options| nft_output(nft_options, default_address, rent_structure.clone(), token_supply))
+            .collect::<crate::Result<Vec<_>>>()?;
 
         self.prepare_transaction(outputs, options).await
     }
+
+    // The address to which NFTs are minted if the options don't contain one
+    pub(crate) async fn default_nft_address(&self) -> crate::Result<Address> {
+        // todo other error message
+        Ok(self
+            .addresses()
+            .await?
+            .first()
+            .ok_or(Error::FailedToGetRemainder)?
+            .address
+            .inner)
+    }
+}
+
+// Build the output for an NFT to mint
+pub(crate) fn nft_output(
+    nft_options: NftOptions,
+    default_address: Address,
+    rent_structure: RentStructure,
+    token_supply: u64,
+) -> crate::Result<Output> {
+    let address = match nft_options.address {
+        Some(address) => Address::try_from_bech32(address)?.1,
+        None => default_address,
+    };
+
+    // NftId needs to be set to 0 for the creation
+    let mut nft_builder = NftOutputBuilder::new_with_minimum_storage_deposit(rent_structure, NftId::null())?
+        // Address which will own the nft
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)));
+
+    if let Some(sender) = nft_options.sender {
+        nft_builder = nft_builder.add_feature(Feature::Sender(SenderFeature::new(Address::try_from_bech32(sender)?.1)));
+    }
+
+    if let Some(metadata) = nft_options.metadata {
+        nft_builder = nft_builder.add_feature(Feature::Metadata(MetadataFeature::new(metadata)?));
+    }
+
+    if let Some(tag) = nft_options.tag {
+        nft_builder = nft_builder.add_feature(Feature::Tag(TagFeature::new(tag)?));
+    }
+
+    if let Some(issuer) = nft_options.issuer {
+        nft_builder =
+            nft_builder.add_immutable_feature(Feature::Issuer(IssuerFeature::new(Address::try_from_bech32(issuer)?.1)));
+    }
+
+    if let Some(immutable_metadata) = nft_options.immutable_metadata {
+        Irc27Metadata::validate_claimed(&immutable_metadata)?;
+        nft_builder = nft_builder.add_immutable_feature(Feature::Metadata(MetadataFeature::new(immutable_metadata)?));
+    }
+
+    Ok(nft_builder.finish_output(token_supply)?)
 }
@@ -3,4 +3,6 @@
 
 pub(crate) mod increase_native_token_supply;
 pub(crate) mod mint_native_token;
+pub(crate) mod mint_nft_collection;
 pub(crate) mod mint_nfts;
+pub(crate) mod update_foundry_metadata;
@@ -0,0 +1,89 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::{
+    api::PreparedTransactionData,
+    block::output::{
+        feature::{Feature, MetadataFeature},
+        AliasOutputBuilder, FoundryId, FoundryOutputBuilder, Output, Rent, TokenId,
+    },
+};
+
+use crate::{
+    account::{handle::AccountHandle, operations::foundry::controlled_foundry, types::Transaction, TransactionOptions},
+    Error,
+};
+
+impl AccountHandle {
+    /// Function to replace the mutable metadata feature of a foundry controlled by this account, empty metadata
+    /// removes it. The alias that controls the foundry is state transitioned in the same transaction.
+    /// ```ignore
+    /// let transaction = account
+    ///     .update_foundry_metadata(token_id, b"some foundry metadata".to_vec(), None)
+    ///     .await?;
+    /// println!("Transaction: {}", transaction.transaction_id);
+    /// ```
+    pub async fn update_foundry_metadata(
+        &self,
+        token_id: TokenId,
+        metadata: Vec<u8>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<Transaction> {
+        let prepared_transaction = self
+            .prepare_update_foundry_metadata(token_id, metadata, options)
+            .await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.update_foundry_metadata()](crate::account::handle::AccountHandle.update_foundry_metadata)
+    pub async fn prepare_update_foundry_metadata(
+        &self,
+        token_id: TokenId,
+        metadata: Vec<u8>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_update_foundry_metadata");
+        let rent_structure = self.client.get_rent_structure().await?;
+        let token_supply = self.client.get_token_supply().await?;
+
+        let foundry_output = controlled_foundry(&*self.read().await, FoundryId::from(token_id))
+            .ok_or(Error::FoundryNotControlled(token_id))?;
+        let alias_id = *foundry_output.alias_address().alias_id();
+        let alias_output = match self.get_alias_output(Some(alias_id)).await {
+            Some((_, output_data)) => match output_data.output {
+                Output::Alias(alias_output) => alias_output,
+                _ => unreachable!("get_alias_output only returns alias outputs"),
+            },
+            None => return Err(Error::FoundryNotControlled(token_id)),
+        };
+
+        // The foundry can only be changed together with a state transition of its alias
+        let alias_output = AliasOutputBuilder::from(&alias_output)
+            .with_alias_id(alias_id)
+            .with_state_index(alias_output.state_index() + 1)
+            .finish_output(token_supply)?;
+
+        // The metadata feature is the only mutable feature of a foundry
+        let features = if metadata.is_empty() {
+            Vec::new()
+        } else {
+            vec![Feature::Metadata(MetadataFeature::new(metadata)?)]
+        };
+        let foundry_output = FoundryOutputBuilder::from(&foundry_output)
+            .with_features(features)
+            .finish_output(token_supply)?;
+        let required_storage_deposit = foundry_output.rent_cost(&rent_structure);
+        let foundry_output = match &foundry_output {
+            Output::Foundry(foundry) if foundry.amount() < required_storage_deposit => {
+                FoundryOutputBuilder::from(foundry)
+                    .with_amount(required_storage_deposit)?
+                    .finish_output(token_supply)?
+            }
+            _ => foundry_output,
+        };
+
+        self.prepare_transaction(vec![alias_output, foundry_output], options)
+            .await
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::{
+    api::PreparedTransactionData,
+    block::{
+        address::Address,
+        output::{
+            feature::{Feature, MetadataFeature},
+            unlock_condition::{
+                GovernorAddressUnlockCondition, StateControllerAddressUnlockCondition, UnlockCondition,
+            },
+            AliasId, AliasOutput, AliasOutputBuilder, Output, Rent,
+        },
+        DtoError,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{handle::AccountHandle, types::Transaction, TransactionOptions},
+    Error,
+};
+
+/// Changes of an alias for `update_alias_governance()`, fields that are `None` stay the same
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AliasGovernanceUpdate {
+    /// Bech32 encoded address of the new state controller
+    #[serde(rename = "stateController")]
+    pub state_controller: Option<String>,
+    /// Bech32 encoded address of the new governor
+    pub governor: Option<String>,
+    /// New alias metadata, empty metadata removes the metadata feature
+    pub metadata: Option<Vec<u8>>,
+}
+
+/// Dto for AliasGovernanceUpdate
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AliasGovernanceUpdateDto {
+    /// Bech32 encoded address of the new state controller
+    #[serde(rename = "stateController")]
+    pub state_controller: Option<String>,
+    /// Bech32 encoded address of the new governor
+    pub governor: Option<String>,
+    /// New alias metadata, hex encoded bytes
+    pub metadata: Option<String>,
+}
+
+impl TryFrom<&AliasGovernanceUpdateDto> for AliasGovernanceUpdate {
+    type Error = crate::Error;
+
+    fn try_from(value: &AliasGovernanceUpdateDto) -> crate::Result<Self> {
+        Ok(Self {
+            state_controller: value.state_controller.clone(),
+            governor: value.governor.clone(),
+            metadata: match &value.metadata {
+                Some(metadata) => Some(prefix_hex::decode(metadata).map_err(|_| DtoError::InvalidField("metadata"))?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl AccountHandle {
+    /// Function to update the state metadata of an alias with a state transition, signed by the state controller.
+    /// The amount of the alias is increased if the new state metadata requires a higher storage deposit.
+    /// ```ignore
+    /// let transaction = account
+    ///     .update_alias_state(alias_id, b"new state".to_vec(), None)
+    ///     .await?;
+    /// println!("Transaction: {}", transaction.transaction_id);
+    /// ```
+    pub async fn update_alias_state(
+        &self,
+        alias_id: AliasId,
+        state_metadata: Vec<u8>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<Transaction> {
+        let prepared_transaction = self
+            .prepare_update_alias_state(alias_id, state_metadata, options)
+            .await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.update_alias_state()](crate::account::handle::AccountHandle.update_alias_state)
+    pub async fn prepare_update_alias_state(
+        &self,
+        alias_id: AliasId,
+        state_metadata: Vec<u8>,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_update_alias_state");
+        let rent_structure = self.client.get_rent_structure().await?;
+        let token_supply = self.client.get_token_supply().await?;
+        let alias_output = self.unspent_alias_output(alias_id).await?;
+
+        // A state transition requires the state index to be incremented
+        let alias_output = AliasOutputBuilder::from(&alias_output)
+            .with_alias_id(alias_id)
+            .with_state_index(alias_output.state_index() + 1)
+            .with_state_metadata(state_metadata)
+            .finish_output(token_supply)?;
+        let required_storage_deposit = alias_output.rent_cost(&rent_structure);
+        let alias_output = match &alias_output {
+            Output::Alias(alias) if alias.amount() < required_storage_deposit => AliasOutputBuilder::from(alias)
+                .with_amount(required_storage_deposit)?
+                .finish_output(token_supply)?,
+            _ => alias_output,
+        };
+
+        self.prepare_transaction(vec![alias_output], options).await
+    }
+
+    /// Function to change the state controller, governor or metadata of an alias with a governance transition,
+    /// signed by the governor. The amount, native tokens and state of the alias stay the same.
+    /// ```ignore
+    /// let update = AliasGovernanceUpdate {
+    ///     state_controller: Some("rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu".to_string()),
+    ///     governor: None,
+    ///     metadata: Some(b"some alias metadata".to_vec()),
+    /// };
+    ///
+    /// let transaction = account.update_alias_governance(alias_id, update, None).await?;
+    /// println!("Transaction: {}", transaction.transaction_id);
+    /// ```
+    pub async fn update_alias_governance(
+        &self,
+        alias_id: AliasId,
+        update: AliasGovernanceUpdate,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<Transaction> {
+        let prepared_transaction = self.prepare_update_alias_governance(alias_id, update, options).await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.update_alias_governance()](crate::account::handle::AccountHandle.update_alias_governance)
+    pub async fn prepare_update_alias_governance(
+        &self,
+        alias_id: AliasId,
+        update: AliasGovernanceUpdate,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_update_alias_governance");
+        let token_supply = self.client.get_token_supply().await?;
+        let alias_output = self.unspent_alias_output(alias_id).await?;
+
+        let state_controller = match update.state_controller {
+            Some(bech32_address) => Address::try_from_bech32(bech32_address)?.1,
+            None => *alias_output.state_controller_address(),
+        };
+        let governor = match update.governor {
+            Some(bech32_address) => Address::try_from_bech32(bech32_address)?.1,
+            None => *alias_output.governor_address(),
+        };
+
+        // The state index isn't changed, so it's a governance transition
+        let mut alias_output_builder = AliasOutputBuilder::from(&alias_output)
+            .with_alias_id(alias_id)
+            .with_unlock_conditions(vec![
+                UnlockCondition::StateControllerAddress(StateControllerAddressUnlockCondition::new(state_controller)),
+                UnlockCondition::GovernorAddress(GovernorAddressUnlockCondition::new(governor)),
+            ]);
+        if let Some(metadata) = update.metadata {
+            let mut features = alias_output
+                .features()
+                .iter()
+                .filter(|feature| !matches!(feature, Feature::Metadata(_)))
+                .cloned()
+                .collect::<Vec<_>>();
+            if !metadata.is_empty() {
+                features.push(Feature::Metadata(MetadataFeature::new(metadata)?));
+            }
+            alias_output_builder = alias_output_builder.with_features(features);
+        }
+
+        let outputs = vec![alias_output_builder.finish_output(token_supply)?];
+
+        self.prepare_transaction(outputs, options).await
+    }
+
+    /// Function to transfer an alias to another address, which becomes its state controller and governor.
+    /// ```ignore
+    /// let transaction = account
+    ///     .transfer_alias(
+    ///         alias_id,
+    ///         "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu".to_string(),
+    ///         None,
+    ///     )
+    ///     .await?;
+    /// println!("Transaction: {}", transaction.transaction_id);
+    /// ```
+    pub async fn transfer_alias(
+        &self,
+        alias_id: AliasId,
+        address: String,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<Transaction> {
+        let prepared_transaction = self.prepare_transfer_alias(alias_id, address, options).await?;
+        self.sign_and_submit_transaction(prepared_transaction).await
+    }
+
+    /// Function to prepare the transaction for
+    /// [AccountHandle.transfer_alias()](crate::account::handle::AccountHandle.transfer_alias)
+    pub async fn prepare_transfer_alias(
+        &self,
+        alias_id: AliasId,
+        address: String,
+        options: Option<TransactionOptions>,
+    ) -> crate::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_transfer_alias");
+        self.prepare_update_alias_governance(
+            alias_id,
+            AliasGovernanceUpdate {
+                state_controller: Some(address.clone()),
+                governor: Some(address),
+                metadata: None,
+            },
+            options,
+        )
+        .await
+    }
+
+    // Get the unspent output of an alias
+    async fn unspent_alias_output(&self, alias_id: AliasId) -> crate::Result<AliasOutput> {
+        match self.get_alias_output(Some(alias_id)).await {
+            Some((_, output_data)) => match output_data.output {
+                Output::Alias(alias_output) => Ok(alias_output),
+                _ => unreachable!("get_alias_output only returns alias outputs"),
+            },
+            None => Err(Error::AliasNotFoundInUnspentOutputs),
+        }
+    }
+}
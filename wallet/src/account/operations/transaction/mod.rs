@@ -17,7 +17,7 @@ use iota_client::{
     block::{
         output::{
             dto::{OutputDto, OutputMetadataDto},
-            Output,
+            Output, OutputId,
         },
         payload::transaction::TransactionPayload,
         semantic::ConflictReason,
@@ -172,12 +172,14 @@ impl AccountHandle {
             incoming: false,
             note: None,
             inputs,
+            memo: None,
         };
 
         let mut account = self.write().await;
 
         account.transactions.insert(transaction_id, transaction.clone());
         account.pending_transactions.insert(transaction_id);
+        self.count_backup_change();
         #[cfg(feature = "storage")]
         {
             log::debug!("[TRANSACTION] storing account {}", account.index());
@@ -187,6 +189,19 @@ impl AccountHandle {
         Ok(transaction)
     }
 
+    /// Unlock outputs that were locked as inputs for a prepared transaction which won't be submitted, so they are
+    /// available for new transactions again.
+    pub async fn unlock_outputs(&self, output_ids: &[OutputId]) -> crate::Result<()> {
+        let mut account = self.write().await;
+        for output_id in output_ids {
+            account.locked_outputs.remove(output_id);
+            log::debug!("[TRANSACTION] Unlocked output {}", output_id);
+        }
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+
     // unlock outputs
     async fn unlock_inputs(&self, inputs: Vec<InputSigningData>) -> crate::Result<()> {
         let mut account = self.write().await;
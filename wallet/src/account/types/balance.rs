@@ -5,11 +5,17 @@ use std::collections::HashMap;
 
 use iota_client::block::{
     dto::U256Dto,
-    output::{dto::TokenIdDto, feature::MetadataFeature, AliasId, FoundryId, NftId, OutputId, TokenId},
+    output::{
+        dto::{NativeTokenDto, TokenIdDto},
+        feature::MetadataFeature,
+        AliasId, FoundryId, NativeToken, NftId, OutputId, TokenId,
+    },
 };
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
 
+use crate::account::types::native_token_metadata::{format_native_token_amount, NativeTokenMetadata};
+
 /// The balance of an account, returned from [`crate::account::handle::AccountHandle::sync()`] and
 /// [`crate::account::handle::AccountHandle::balance()`].
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
@@ -176,6 +182,9 @@ pub struct NativeTokensBalance {
     pub token_id: TokenId,
     /// Token foundry immutable metadata
     pub metadata: Option<MetadataFeature>,
+    /// Parsed IRC30 metadata and user defined values of the token
+    #[serde(rename = "tokenMetadata", default, skip_serializing_if = "Option::is_none")]
+    pub token_metadata: Option<NativeTokenMetadata>,
     /// Total amount
     pub total: U256,
     /// Balance that can currently be spent
@@ -187,6 +196,7 @@ impl Default for NativeTokensBalance {
         Self {
             token_id: TokenId::null(),
             metadata: None,
+            token_metadata: None,
             total: U256::from(0u8),
             available: U256::from(0u8),
         }
@@ -201,19 +211,83 @@ pub struct NativeTokensBalanceDto {
     pub token_id: TokenIdDto,
     /// Token foundry immutable metadata
     pub metadata: Option<String>,
+    /// Parsed IRC30 metadata and user defined values of the token
+    #[serde(rename = "tokenMetadata", default, skip_serializing_if = "Option::is_none")]
+    pub token_metadata: Option<NativeTokenMetadata>,
     /// Total amount
     pub total: U256Dto,
     /// Balance that can currently be spent
     pub available: U256Dto,
+    /// Total amount formatted with the decimals of the token, if they're known
+    #[serde(rename = "formattedTotal", default, skip_serializing_if = "Option::is_none")]
+    pub formatted_total: Option<String>,
+    /// Available amount formatted with the decimals of the token, if they're known
+    #[serde(rename = "formattedAvailable", default, skip_serializing_if = "Option::is_none")]
+    pub formatted_available: Option<String>,
 }
 
 impl From<&NativeTokensBalance> for NativeTokensBalanceDto {
     fn from(value: &NativeTokensBalance) -> Self {
+        let decimals = value.token_metadata.as_ref().and_then(NativeTokenMetadata::decimals);
         Self {
             token_id: TokenIdDto::from(&value.token_id),
             metadata: value.metadata.as_ref().map(|m| prefix_hex::encode(m.data())),
+            token_metadata: value.token_metadata.clone(),
             total: U256Dto::from(&value.total),
             available: U256Dto::from(&value.available),
+            formatted_total: decimals.map(|decimals| format_native_token_amount(value.total, decimals)),
+            formatted_available: decimals.map(|decimals| format_native_token_amount(value.available, decimals)),
+        }
+    }
+}
+
+/// The total balance of an account at a point in time, returned from
+/// [`crate::account::handle::AccountHandle::balance_at()`] and
+/// [`crate::account::handle::AccountHandle::balance_history()`]. It contains the amounts of all outputs the account
+/// owned, including outputs with additional unlock conditions that weren't claimed yet.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceSnapshot {
+    /// Unix timestamp in seconds.
+    pub timestamp: u32,
+    /// The network id of the outputs.
+    pub network_id: u64,
+    /// Total amount of the base coin.
+    pub base_coin: u64,
+    /// Total amounts of the native tokens, ordered by token id. Tokens without balance aren't included.
+    pub native_tokens: Vec<NativeToken>,
+}
+
+impl BalanceSnapshot {
+    /// Returns true if the amounts are the same as the ones of the other snapshot, ignoring the timestamp.
+    pub fn has_same_balance(&self, other: &Self) -> bool {
+        self.network_id == other.network_id
+            && self.base_coin == other.base_coin
+            && self.native_tokens == other.native_tokens
+    }
+}
+
+/// Dto for [`BalanceSnapshot`]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceSnapshotDto {
+    /// Unix timestamp in seconds.
+    pub timestamp: u32,
+    /// The network id of the outputs.
+    pub network_id: String,
+    /// Total amount of the base coin.
+    pub base_coin: String,
+    /// Total amounts of the native tokens, ordered by token id.
+    pub native_tokens: Vec<NativeTokenDto>,
+}
+
+impl From<&BalanceSnapshot> for BalanceSnapshotDto {
+    fn from(value: &BalanceSnapshot) -> Self {
+        Self {
+            timestamp: value.timestamp,
+            network_id: value.network_id.to_string(),
+            base_coin: value.base_coin.to_string(),
+            native_tokens: value.native_tokens.iter().map(NativeTokenDto::from).collect(),
         }
     }
 }
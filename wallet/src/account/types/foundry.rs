@@ -0,0 +1,224 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use iota_client::block::{
+    address::{dto::AddressDto, Address},
+    dto::U256Dto,
+    output::{dto::TokenIdDto, AliasId, FoundryId, FoundryOutput, TokenId, TokenScheme},
+};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+/// The supply of a native token, given by the token scheme of its foundry.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NativeTokenSupply {
+    /// The id of the native token.
+    pub token_id: TokenId,
+    /// The id of the foundry.
+    pub foundry_id: FoundryId,
+    /// The id of the alias that controls the foundry.
+    pub alias_id: AliasId,
+    /// The amount of minted tokens.
+    pub minted_tokens: U256,
+    /// The amount of melted tokens.
+    pub melted_tokens: U256,
+    /// The minted tokens minus the melted ones, burned tokens are included.
+    pub circulating_supply: U256,
+    /// The maximum supply.
+    pub maximum_supply: U256,
+    /// The data of the mutable metadata feature of the foundry.
+    pub metadata: Option<Vec<u8>>,
+    /// If the state controller of the controlling alias is an address of the account, so its supply and metadata can
+    /// be changed.
+    pub controlled: bool,
+}
+
+impl NativeTokenSupply {
+    pub(crate) fn new(foundry: &FoundryOutput, controlled: bool) -> Self {
+        let TokenScheme::Simple(token_scheme) = foundry.token_scheme();
+        let foundry_id = foundry.id();
+        Self {
+            token_id: TokenId::from(foundry_id),
+            foundry_id,
+            alias_id: *foundry.alias_address().alias_id(),
+            minted_tokens: token_scheme.minted_tokens(),
+            melted_tokens: token_scheme.melted_tokens(),
+            circulating_supply: token_scheme.circulating_supply(),
+            maximum_supply: token_scheme.maximum_supply(),
+            metadata: foundry.features().metadata().map(|metadata| metadata.data().to_vec()),
+            controlled,
+        }
+    }
+}
+
+/// Dto for a [`NativeTokenSupply`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeTokenSupplyDto {
+    /// The id of the native token.
+    pub token_id: TokenIdDto,
+    /// The id of the foundry.
+    pub foundry_id: FoundryId,
+    /// The id of the alias that controls the foundry.
+    pub alias_id: AliasId,
+    /// The amount of minted tokens.
+    pub minted_tokens: U256Dto,
+    /// The amount of melted tokens.
+    pub melted_tokens: U256Dto,
+    /// The minted tokens minus the melted ones, burned tokens are included.
+    pub circulating_supply: U256Dto,
+    /// The maximum supply.
+    pub maximum_supply: U256Dto,
+    /// The data of the mutable metadata feature of the foundry, hex encoded.
+    pub metadata: Option<String>,
+    /// If the state controller of the controlling alias is an address of the account, so its supply and metadata can
+    /// be changed.
+    pub controlled: bool,
+}
+
+impl From<&NativeTokenSupply> for NativeTokenSupplyDto {
+    fn from(value: &NativeTokenSupply) -> Self {
+        Self {
+            token_id: TokenIdDto::from(&value.token_id),
+            foundry_id: value.foundry_id,
+            alias_id: value.alias_id,
+            minted_tokens: U256Dto::from(&value.minted_tokens),
+            melted_tokens: U256Dto::from(&value.melted_tokens),
+            circulating_supply: U256Dto::from(&value.circulating_supply),
+            maximum_supply: U256Dto::from(&value.maximum_supply),
+            metadata: value.metadata.as_ref().map(prefix_hex::encode),
+            controlled: value.controlled,
+        }
+    }
+}
+
+/// An address holding a native token in unspent outputs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NativeTokenHolder {
+    /// The address that can unlock the outputs, the alias address for tokens held by an alias.
+    pub address: Address,
+    /// The amount of the token held by the address.
+    pub amount: U256,
+    /// The number of outputs of the address that hold the token.
+    pub output_count: usize,
+}
+
+impl NativeTokenHolder {
+    /// Merge the holders of several pages, so every address is listed once with the sum of its amounts and outputs.
+    /// The holders with the largest amounts come first.
+    pub fn aggregate(holders: impl IntoIterator<Item = Self>) -> Vec<Self> {
+        let mut aggregated: HashMap<Address, Self> = HashMap::new();
+        for holder in holders {
+            let aggregated_holder = aggregated.entry(holder.address).or_insert(Self {
+                address: holder.address,
+                amount: U256::zero(),
+                output_count: 0,
+            });
+            aggregated_holder.amount = aggregated_holder.amount.saturating_add(holder.amount);
+            aggregated_holder.output_count += holder.output_count;
+        }
+        let mut holders = aggregated.into_values().collect::<Vec<_>>();
+        holders.sort_by(|a, b| b.amount.cmp(&a.amount).then_with(|| a.address.cmp(&b.address)));
+        holders
+    }
+
+    // Sum the token amounts of the outputs per address
+    pub(crate) fn from_amounts(amounts: impl IntoIterator<Item = (Address, U256)>) -> Vec<Self> {
+        Self::aggregate(amounts.into_iter().map(|(address, amount)| Self {
+            address,
+            amount,
+            output_count: 1,
+        }))
+    }
+}
+
+/// Dto for a [`NativeTokenHolder`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeTokenHolderDto {
+    /// The address that can unlock the outputs, the alias address for tokens held by an alias.
+    pub address: AddressDto,
+    /// The amount of the token held by the address.
+    pub amount: U256Dto,
+    /// The number of outputs of the address that hold the token.
+    pub output_count: usize,
+}
+
+impl From<&NativeTokenHolder> for NativeTokenHolderDto {
+    fn from(value: &NativeTokenHolder) -> Self {
+        Self {
+            address: AddressDto::from(&value.address),
+            amount: U256Dto::from(&value.amount),
+            output_count: value.output_count,
+        }
+    }
+}
+
+/// A page of the holders of a native token, from the outputs of a single indexer page.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NativeTokenHolders {
+    /// The holders in the outputs of this page, an address can also hold the token in outputs of other pages.
+    pub holders: Vec<NativeTokenHolder>,
+    /// The cursor to request the next page, `None` if this was the last one.
+    pub cursor: Option<String>,
+}
+
+/// Dto for [`NativeTokenHolders`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeTokenHoldersDto {
+    /// The holders in the outputs of this page, an address can also hold the token in outputs of other pages.
+    pub holders: Vec<NativeTokenHolderDto>,
+    /// The cursor to request the next page, `None` if this was the last one.
+    pub cursor: Option<String>,
+}
+
+impl From<&NativeTokenHolders> for NativeTokenHoldersDto {
+    fn from(value: &NativeTokenHolders) -> Self {
+        Self {
+            holders: value.holders.iter().map(NativeTokenHolderDto::from).collect(),
+            cursor: value.cursor.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_client::block::address::Ed25519Address;
+
+    use super::*;
+
+    #[test]
+    fn sum_holder_amounts() {
+        let first = Address::Ed25519(Ed25519Address::new([1; 32]));
+        let second = Address::Ed25519(Ed25519Address::new([2; 32]));
+
+        let holders = NativeTokenHolder::from_amounts([
+            (first, U256::from(10)),
+            (second, U256::from(25)),
+            (first, U256::from(20)),
+        ]);
+
+        assert_eq!(holders.len(), 2);
+        assert_eq!(holders[0].address, first);
+        assert_eq!(holders[0].amount, U256::from(30));
+        assert_eq!(holders[0].output_count, 2);
+        assert_eq!(holders[1].address, second);
+        assert_eq!(holders[1].amount, U256::from(25));
+        assert!(NativeTokenHolder::from_amounts([]).is_empty());
+
+        // Holders of different pages are merged
+        let holders = NativeTokenHolder::aggregate(
+            holders
+                .into_iter()
+                .chain(NativeTokenHolder::from_amounts([(second, U256::from(10))])),
+        );
+        assert_eq!(holders.len(), 2);
+        assert_eq!(holders[0].address, second);
+        assert_eq!(holders[0].amount, U256::from(35));
+        assert_eq!(holders[0].output_count, 2);
+        assert_eq!(holders[1].amount, U256::from(30));
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use iota_client::block::{address::Address, output::NftOutput};
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// The standard name of IRC27 NFT metadata.
+pub const IRC27_STANDARD: &str = "IRC27";
+/// The supported version of the IRC27 standard.
+pub const IRC27_VERSION: &str = "v1.0";
+
+/// NFT metadata following the IRC27 standard, stored in the immutable metadata feature of the NFT.
+/// IOTA NFT Standard - TIP27: <https://github.com/iotaledger/tips/blob/main/tips/TIP-0027/tip-0027.md>
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Irc27Metadata {
+    /// The standard, always `IRC27`.
+    pub standard: String,
+    /// The version of the standard.
+    pub version: String,
+    /// The MIME type of the NFT media, e.g. `image/png`.
+    #[serde(rename = "type")]
+    pub media_type: String,
+    /// The URI of the NFT media.
+    pub uri: String,
+    /// The name of the NFT.
+    pub name: String,
+    /// The name of the collection the NFT belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection_name: Option<String>,
+    /// The share of the royalties per bech32 encoded address, between 0 and 1.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub royalties: BTreeMap<String, f64>,
+    /// The name of the issuer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer_name: Option<String>,
+    /// A description of the NFT.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The attributes of the NFT.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<Irc27Attribute>,
+}
+
+/// An attribute of an IRC27 NFT.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Irc27Attribute {
+    /// The name of the attribute.
+    pub trait_type: String,
+    /// The value of the attribute, a string or a number.
+    pub value: serde_json::Value,
+}
+
+impl Irc27Metadata {
+    /// Create IRC27 metadata with the MIME type and the URI of the media and the name of the NFT.
+    pub fn new(media_type: impl Into<String>, uri: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            standard: IRC27_STANDARD.to_string(),
+            version: IRC27_VERSION.to_string(),
+            media_type: media_type.into(),
+            uri: uri.into(),
+            name: name.into(),
+            collection_name: None,
+            royalties: BTreeMap::new(),
+            issuer_name: None,
+            description: None,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Set the name of the collection.
+    pub fn with_collection_name(mut self, collection_name: impl Into<String>) -> Self {
+        self.collection_name.replace(collection_name.into());
+        self
+    }
+
+    /// Add a royalty share for a bech32 encoded address.
+    pub fn add_royalty(mut self, address: impl Into<String>, share: f64) -> Self {
+        self.royalties.insert(address.into(), share);
+        self
+    }
+
+    /// Set the name of the issuer.
+    pub fn with_issuer_name(mut self, issuer_name: impl Into<String>) -> Self {
+        self.issuer_name.replace(issuer_name.into());
+        self
+    }
+
+    /// Set the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description.replace(description.into());
+        self
+    }
+
+    /// Add an attribute.
+    pub fn add_attribute(mut self, trait_type: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.attributes.push(Irc27Attribute {
+            trait_type: trait_type.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Check that the metadata follows the IRC27 standard.
+    pub fn validate(&self) -> crate::Result<()> {
+        let invalid = |message: String| Err(Error::InvalidIrc27Metadata(message));
+
+        if self.standard != IRC27_STANDARD {
+            return invalid(format!("unsupported standard {}", self.standard));
+        }
+        if self.version != IRC27_VERSION {
+            return invalid(format!("unsupported version {}", self.version));
+        }
+        if !self.media_type.contains('/') {
+            return invalid(format!("type {} is no MIME type", self.media_type));
+        }
+        if self.uri.is_empty() {
+            return invalid("empty uri".to_string());
+        }
+        if self.name.is_empty() {
+            return invalid("empty name".to_string());
+        }
+        for (address, share) in &self.royalties {
+            if Address::try_from_bech32(address).is_err() {
+                return invalid(format!("invalid royalty address {address}"));
+            }
+            if share.is_nan() || *share <= 0.0 || *share > 1.0 {
+                return invalid(format!("royalty share {share} of {address} isn't between 0 and 1"));
+            }
+        }
+        if self.royalties.values().sum::<f64>() > 1.0 {
+            return invalid("royalty shares add up to more than 1".to_string());
+        }
+        for attribute in &self.attributes {
+            if attribute.trait_type.is_empty() {
+                return invalid("empty attribute trait type".to_string());
+            }
+            if !attribute.value.is_string() && !attribute.value.is_number() {
+                return invalid(format!(
+                    "value of attribute {} isn't a string or number",
+                    attribute.trait_type
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate the metadata and serialize it to JSON bytes for the immutable metadata feature.
+    pub fn to_bytes(&self) -> crate::Result<Vec<u8>> {
+        self.validate()?;
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    /// Parses IRC27 metadata from JSON bytes, returns `None` if they aren't valid IRC27 metadata.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice::<Self>(bytes)
+            .ok()
+            .filter(|metadata| metadata.validate().is_ok())
+    }
+
+    /// Parses the IRC27 metadata from the immutable metadata feature of an NFT.
+    pub fn from_nft(nft: &NftOutput) -> Option<Self> {
+        nft.immutable_features()
+            .metadata()
+            .and_then(|metadata| Self::from_bytes(metadata.data()))
+    }
+
+    /// Check metadata that is about to be minted. Bytes that claim to follow the IRC27 standard need to be valid IRC27
+    /// metadata, other bytes aren't checked.
+    pub(crate) fn validate_claimed(bytes: &[u8]) -> crate::Result<()> {
+        let claims_irc27 = serde_json::from_slice::<serde_json::Value>(bytes)
+            .map_or(false, |value| value["standard"] == IRC27_STANDARD);
+        if claims_irc27 {
+            serde_json::from_slice::<Self>(bytes)
+                .map_err(|e| Error::InvalidIrc27Metadata(e.to_string()))?
+                .validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROYALTY_ADDRESS: &str = "rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3";
+
+    #[test]
+    fn build_and_parse() {
+        let metadata = Irc27Metadata::new(
+            "image/png",
+            "ipfs://QmdRj4Q2VSgd8Vb2gTd1hNqB1vhCSbNpPHpWE8ZVBNJyuU",
+            "OG #1",
+        )
+        .with_collection_name("Shimmer OG")
+        .add_royalty(ROYALTY_ADDRESS, 0.05)
+        .add_attribute("background", "blue")
+        .add_attribute("rank", 1);
+
+        let bytes = metadata.to_bytes().unwrap();
+        let json = serde_json::from_slice::<serde_json::Value>(&bytes).unwrap();
+        assert_eq!(json["type"], "image/png");
+        assert_eq!(json["collectionName"], "Shimmer OG");
+        assert_eq!(json["attributes"][1]["trait_type"], "rank");
+
+        assert_eq!(Irc27Metadata::from_bytes(&bytes), Some(metadata));
+        assert!(
+            Irc27Metadata::from_bytes(br#"{"standard":"IRC30","name":"Token","symbol":"T","decimals":0}"#).is_none()
+        );
+        assert!(Irc27Metadata::from_bytes(b"some immutable nft metadata").is_none());
+    }
+
+    #[test]
+    fn validate() {
+        let metadata = Irc27Metadata::new("image/png", "https://x.y/nft.png", "NFT");
+        assert!(metadata.validate().is_ok());
+
+        assert!(Irc27Metadata::new("png", "https://x.y/nft.png", "NFT")
+            .validate()
+            .is_err());
+        assert!(Irc27Metadata::new("image/png", "", "NFT").validate().is_err());
+        assert!(metadata.clone().add_royalty("no address", 0.1).validate().is_err());
+        assert!(metadata.clone().add_royalty(ROYALTY_ADDRESS, 1.5).validate().is_err());
+        assert!(metadata
+            .add_attribute("list", serde_json::json!([1, 2]))
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn validate_claimed() {
+        assert!(Irc27Metadata::validate_claimed(b"some immutable nft metadata").is_ok());
+        assert!(Irc27Metadata::validate_claimed(br#"{"standard":"IRC30"}"#).is_ok());
+        assert!(Irc27Metadata::validate_claimed(br#"{"standard":"IRC27","version":"v1.0","name":"NFT"}"#).is_err());
+    }
+}
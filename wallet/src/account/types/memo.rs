@@ -0,0 +1,191 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::{
+    ciphers::chacha,
+    hashes::{blake2b::Blake2b256, Digest},
+    keys::x25519,
+};
+use iota_client::block::payload::TaggedDataPayload;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::Error;
+
+/// The tag of tagged data payloads with an encrypted memo.
+pub const MEMO_TAG: &str = "WALLET_MEMO";
+/// The maximum length of a memo text in bytes.
+pub const MEMO_MAX_LENGTH: usize = 1024;
+const MEMO_VERSION: u8 = 1;
+
+/// A decrypted memo.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Memo {
+    /// The text of the memo.
+    pub text: String,
+    /// The messaging key of the recipient, hex encoded.
+    pub recipient_key: String,
+}
+
+/// The encrypted memo in the data of a tagged data payload. The key is derived from the Diffie-Hellman secret of a new
+/// ephemeral key and the messaging key of the recipient, so only the recipient can decrypt it. A second copy is
+/// encrypted to the messaging key of the sender, if it's known, so the sender can read its memos after a restore too.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MemoEnvelope {
+    version: u8,
+    ephemeral_key: String,
+    recipient_key: String,
+    ciphertext: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sender_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sender_ciphertext: Option<String>,
+}
+
+impl MemoEnvelope {
+    pub(crate) fn encrypt(
+        recipient_key: &x25519::PublicKey,
+        sender_key: Option<&x25519::PublicKey>,
+        text: &str,
+    ) -> crate::Result<Self> {
+        if text.len() > MEMO_MAX_LENGTH {
+            return Err(Error::Messaging("memo is too long"));
+        }
+        let ephemeral_secret_key = x25519::SecretKey::generate()?;
+        let encrypt_to = |key: &x25519::PublicKey| -> crate::Result<String> {
+            let mut memo_key = memo_key(&ephemeral_secret_key, key);
+            let ciphertext = chacha::aead_encrypt(&memo_key, text.as_bytes());
+            memo_key.zeroize();
+            Ok(prefix_hex::encode(ciphertext?))
+        };
+
+        Ok(Self {
+            version: MEMO_VERSION,
+            ephemeral_key: prefix_hex::encode(ephemeral_secret_key.public_key().to_bytes()),
+            recipient_key: prefix_hex::encode(recipient_key.to_bytes()),
+            ciphertext: encrypt_to(recipient_key)?,
+            sender_key: sender_key.map(|sender_key| prefix_hex::encode(sender_key.to_bytes())),
+            sender_ciphertext: sender_key.map(encrypt_to).transpose()?,
+        })
+    }
+
+    /// Decrypts the memo, if the public key of the secret key is the recipient or sender key.
+    pub(crate) fn decrypt(&self, secret_key: &x25519::SecretKey) -> crate::Result<Memo> {
+        if self.version != MEMO_VERSION {
+            return Err(Error::Messaging("unsupported memo version"));
+        }
+        let ephemeral_key = parse_messaging_key(&self.ephemeral_key)?;
+        let own_key = prefix_hex::encode(secret_key.public_key().to_bytes());
+        let ciphertext = if own_key == self.recipient_key {
+            &self.ciphertext
+        } else if Some(&own_key) == self.sender_key.as_ref() {
+            self.sender_ciphertext
+                .as_ref()
+                .ok_or(Error::Messaging("invalid memo ciphertext"))?
+        } else {
+            return Err(Error::Messaging("memo isn't for this messaging key"));
+        };
+
+        let ciphertext: Vec<u8> =
+            prefix_hex::decode(ciphertext).map_err(|_| Error::Messaging("invalid memo ciphertext"))?;
+        let mut key = memo_key(secret_key, &ephemeral_key);
+        let plaintext = chacha::aead_decrypt(&key, &ciphertext);
+        key.zeroize();
+        let text = String::from_utf8(plaintext.map_err(|_| Error::Messaging("memo decryption failed"))?)
+            .map_err(|_| Error::Messaging("memo isn't valid UTF-8"))?;
+
+        Ok(Memo {
+            text,
+            recipient_key: self.recipient_key.clone(),
+        })
+    }
+
+    /// The messaging key of the sender, hex encoded, if the sender could derive it.
+    pub(crate) fn sender_key(&self) -> Option<&str> {
+        self.sender_key.as_deref()
+    }
+
+    pub(crate) fn to_payload(&self) -> crate::Result<TaggedDataPayload> {
+        Ok(TaggedDataPayload::new(
+            MEMO_TAG.as_bytes().to_vec(),
+            serde_json::to_vec(self)?,
+        )?)
+    }
+
+    /// Reads the envelope from a tagged data payload, returns `None` for payloads without a memo.
+    pub(crate) fn from_payload(payload: &TaggedDataPayload) -> Option<Self> {
+        if payload.tag() != MEMO_TAG.as_bytes() {
+            return None;
+        }
+        serde_json::from_slice(payload.data()).ok()
+    }
+}
+
+/// Parses a hex encoded messaging key.
+pub(crate) fn parse_messaging_key(key: &str) -> crate::Result<x25519::PublicKey> {
+    let bytes: [u8; x25519::PUBLIC_KEY_LENGTH] =
+        prefix_hex::decode(key).map_err(|_| Error::Messaging("invalid messaging key"))?;
+    Ok(x25519::PublicKey::from_bytes(bytes))
+}
+
+// The shared secret is bound to both public keys, the ephemeral key and the key the copy is encrypted to
+fn memo_key(secret_key: &x25519::SecretKey, other_key: &x25519::PublicKey) -> [u8; 32] {
+    let shared_secret = secret_key.diffie_hellman(other_key);
+    let (mut first_key, mut second_key) = (secret_key.public_key().to_bytes(), other_key.to_bytes());
+    // The same order on both sides
+    if first_key > second_key {
+        std::mem::swap(&mut first_key, &mut second_key);
+    }
+    let mut hasher = Blake2b256::new();
+    hasher.update(MEMO_TAG.as_bytes());
+    hasher.update(shared_secret.as_bytes());
+    hasher.update(first_key);
+    hasher.update(second_key);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_and_decrypt_memo() {
+        let sender = x25519::SecretKey::from_bytes([1; 32]);
+        let recipient = x25519::SecretKey::from_bytes([2; 32]);
+        let other = x25519::SecretKey::from_bytes([3; 32]);
+
+        let envelope = MemoEnvelope::encrypt(
+            &recipient.public_key(),
+            Some(&sender.public_key()),
+            "thanks for the coffee",
+        )
+        .unwrap();
+        let payload = envelope.to_payload().unwrap();
+        let envelope = MemoEnvelope::from_payload(&payload).unwrap();
+
+        let memo = envelope.decrypt(&recipient).unwrap();
+        assert_eq!(memo.text, "thanks for the coffee");
+        assert_eq!(
+            memo.recipient_key,
+            prefix_hex::encode(recipient.public_key().to_bytes())
+        );
+        assert_eq!(envelope.decrypt(&sender).unwrap(), memo);
+        assert!(envelope.decrypt(&other).is_err());
+        assert_eq!(
+            envelope.sender_key(),
+            Some(prefix_hex::encode(sender.public_key().to_bytes()).as_str())
+        );
+
+        // Without the key of the sender only the recipient can decrypt it
+        let envelope = MemoEnvelope::encrypt(&recipient.public_key(), None, "thanks for the coffee").unwrap();
+        assert_eq!(envelope.decrypt(&recipient).unwrap(), memo);
+        assert!(envelope.decrypt(&sender).is_err());
+        assert!(envelope.sender_key().is_none());
+
+        let payload = TaggedDataPayload::new(b"OTHER".to_vec(), payload.data().to_vec()).unwrap();
+        assert!(MemoEnvelope::from_payload(&payload).is_none());
+        assert!(MemoEnvelope::encrypt(&recipient.public_key(), None, &"a".repeat(MEMO_MAX_LENGTH + 1)).is_err());
+    }
+}
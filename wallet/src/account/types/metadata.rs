@@ -0,0 +1,71 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use iota_client::block::output::OutputId;
+use serde::{Deserialize, Serialize};
+
+use crate::account::types::address::AccountAddress;
+
+/// User defined metadata of an account.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountMetadata {
+    /// A description of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// A colour to display the account with, e.g. `#ff0000`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// If the account is archived.
+    #[serde(default)]
+    pub archived: bool,
+    /// Arbitrary key/value metadata.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, String>,
+}
+
+impl AccountMetadata {
+    /// Checks if the description or one of the property keys or values contains the lowercase query.
+    pub(crate) fn matches(&self, query: &str) -> bool {
+        self.description
+            .as_ref()
+            .map_or(false, |description| description.to_lowercase().contains(query))
+            || self
+                .properties
+                .iter()
+                .any(|(key, value)| key.to_lowercase().contains(query) || value.to_lowercase().contains(query))
+    }
+}
+
+/// The addresses and outputs with a label that matches a query, returned by
+/// [`AccountHandle::search_labels()`](crate::account::AccountHandle::search_labels).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelSearchResult {
+    /// Addresses with a matching label.
+    pub addresses: Vec<AccountAddress>,
+    /// Ids of the outputs with a matching label.
+    pub output_ids: Vec<OutputId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches() {
+        let metadata = AccountMetadata {
+            description: Some("Savings for the Holidays".to_string()),
+            properties: BTreeMap::from([("category".to_string(), "Personal".to_string())]),
+            ..Default::default()
+        };
+
+        assert!(metadata.matches("holiday"));
+        assert!(metadata.matches("categ"));
+        assert!(metadata.matches("personal"));
+        assert!(!metadata.matches("business"));
+        assert!(!AccountMetadata::default().matches("holiday"));
+    }
+}
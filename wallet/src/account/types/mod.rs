@@ -6,8 +6,17 @@ pub(crate) mod address;
 /// Custom de/serialization for [`address::AddressWrapper`]
 pub(crate) mod address_serde;
 pub(crate) mod balance;
+pub(crate) mod foundry;
+pub(crate) mod irc27;
+pub(crate) mod memo;
+pub(crate) mod metadata;
+pub(crate) mod native_token_metadata;
+pub(crate) mod nft;
 #[cfg(feature = "participation")]
 pub mod participation;
+pub(crate) mod payment_request;
+pub(crate) mod payment_uri;
+pub(crate) mod sync_checkpoint;
 
 use std::str::FromStr;
 
@@ -30,9 +39,26 @@ use serde::{Deserialize, Deserializer, Serialize};
 pub use self::{
     address::{AccountAddress, AddressWithUnspentOutputs, AddressWrapper},
     balance::{
-        AccountBalance, AccountBalanceDto, BaseCoinBalance, BaseCoinBalanceDto, NativeTokensBalance,
-        NativeTokensBalanceDto, RequiredStorageDeposit,
+        AccountBalance, AccountBalanceDto, BalanceSnapshot, BalanceSnapshotDto, BaseCoinBalance, BaseCoinBalanceDto,
+        NativeTokensBalance, NativeTokensBalanceDto, RequiredStorageDeposit,
     },
+    foundry::{
+        NativeTokenHolder, NativeTokenHolderDto, NativeTokenHolders, NativeTokenHoldersDto, NativeTokenSupply,
+        NativeTokenSupplyDto,
+    },
+    irc27::{Irc27Attribute, Irc27Metadata, IRC27_STANDARD, IRC27_VERSION},
+    memo::{Memo, MEMO_MAX_LENGTH, MEMO_TAG},
+    metadata::{AccountMetadata, LabelSearchResult},
+    native_token_metadata::{
+        format_native_token_amount, Irc30Metadata, NativeTokenMetadata, NativeTokenMetadataOverrides, IRC30_STANDARD,
+        MAX_NATIVE_TOKEN_DECIMALS,
+    },
+    nft::{NftCollection, NftCollectionDto, OwnedNft, OwnedNftDto},
+    payment_request::{
+        PaymentRequest, PaymentRequestDto, PaymentRequestOptions, PaymentRequestOptionsDto, PaymentRequestStatus,
+    },
+    payment_uri::{PaymentUri, PAYMENT_URI_SCHEME},
+    sync_checkpoint::SyncCheckpoint,
 };
 use crate::account::Account;
 
@@ -56,6 +82,9 @@ pub struct OutputData {
     pub remainder: bool,
     // bip32 path
     pub chain: Option<Chain>,
+    /// A user defined label.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
 }
 
 impl OutputData {
@@ -122,6 +151,9 @@ pub struct OutputDataDto {
     pub remainder: bool,
     /// Bip32 path
     pub chain: Option<Chain>,
+    /// A user defined label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
 }
 
 impl From<&OutputData> for OutputDataDto {
@@ -135,6 +167,7 @@ impl From<&OutputData> for OutputDataDto {
             network_id: value.network_id.to_string(),
             remainder: value.remainder,
             chain: value.chain.clone(),
+            label: value.label.clone(),
         }
     }
 }
@@ -162,6 +195,9 @@ pub struct Transaction {
     // serde(default) is needed so it doesn't break with old dbs
     #[serde(default)]
     pub inputs: Vec<OutputWithMetadataResponse>,
+    /// The decrypted memo of the tagged data payload, set during syncing if the account is the sender or recipient.
+    #[serde(default)]
+    pub memo: Option<Memo>,
 }
 
 /// Dto for a transaction with metadata
@@ -186,6 +222,8 @@ pub struct TransactionDto {
     pub incoming: bool,
     pub note: Option<String>,
     pub inputs: Vec<OutputWithMetadataResponse>,
+    /// The decrypted memo of the tagged data payload
+    pub memo: Option<Memo>,
 }
 
 impl From<&Transaction> for TransactionDto {
@@ -200,6 +238,7 @@ impl From<&Transaction> for TransactionDto {
             incoming: value.incoming,
             note: value.note.clone(),
             inputs: value.inputs.clone(),
+            memo: value.memo.clone(),
         }
     }
 }
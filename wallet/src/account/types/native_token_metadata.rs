@@ -0,0 +1,216 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::block::output::FoundryOutput;
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+/// The standard name of IRC30 native token metadata.
+pub const IRC30_STANDARD: &str = "IRC30";
+/// The maximum number of decimals of a native token, the largest amount has 78 digits.
+pub const MAX_NATIVE_TOKEN_DECIMALS: u32 = 77;
+
+/// Native token metadata following the IRC30 standard, stored in the immutable metadata feature of the foundry.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Irc30Metadata {
+    /// The standard, always `IRC30`.
+    pub standard: String,
+    /// The name of the token.
+    pub name: String,
+    /// The symbol of the token.
+    pub symbol: String,
+    /// The number of decimals of the token amounts.
+    pub decimals: u32,
+    /// A description of the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// A URL pointing to further information about the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// A URL pointing to the logo of the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo_url: Option<String>,
+    /// The logo of the token as SVG string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo: Option<String>,
+}
+
+impl Irc30Metadata {
+    /// Parses IRC30 metadata from JSON bytes, returns `None` if they aren't valid IRC30 metadata or have more than
+    /// [`MAX_NATIVE_TOKEN_DECIMALS`].
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice::<Self>(bytes)
+            .ok()
+            .filter(|metadata| metadata.standard == IRC30_STANDARD && metadata.decimals <= MAX_NATIVE_TOKEN_DECIMALS)
+    }
+
+    /// Parses the IRC30 metadata from the immutable metadata feature of a foundry.
+    pub fn from_foundry(foundry: &FoundryOutput) -> Option<Self> {
+        foundry
+            .immutable_features()
+            .metadata()
+            .and_then(|metadata| Self::from_bytes(metadata.data()))
+    }
+}
+
+/// User defined values that replace the ones of the IRC30 metadata of a native token.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeTokenMetadataOverrides {
+    /// The name of the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The symbol of the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// The number of decimals of the token amounts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u32>,
+    /// A description of the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// A URL pointing to the logo of the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo_url: Option<String>,
+}
+
+/// The metadata of a native token known to the account, parsed from its foundry and set by the user.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeTokenMetadata {
+    /// The IRC30 metadata of the foundry, if it's valid.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub irc30: Option<Irc30Metadata>,
+    /// User defined values that replace the IRC30 ones.
+    #[serde(default)]
+    pub overrides: NativeTokenMetadataOverrides,
+    /// If the user marked the token as trusted. Metadata can be set by anyone who creates a foundry, so the name and
+    /// symbol of untrusted tokens might imitate other tokens.
+    #[serde(default)]
+    pub trusted: bool,
+}
+
+impl NativeTokenMetadata {
+    /// The name of the token.
+    pub fn name(&self) -> Option<&str> {
+        self.overrides
+            .name
+            .as_deref()
+            .or_else(|| self.irc30.as_ref().map(|irc30| irc30.name.as_str()))
+    }
+
+    /// The symbol of the token.
+    pub fn symbol(&self) -> Option<&str> {
+        self.overrides
+            .symbol
+            .as_deref()
+            .or_else(|| self.irc30.as_ref().map(|irc30| irc30.symbol.as_str()))
+    }
+
+    /// The number of decimals of the token amounts.
+    pub fn decimals(&self) -> Option<u32> {
+        self.overrides
+            .decimals
+            .or_else(|| self.irc30.as_ref().map(|irc30| irc30.decimals))
+    }
+
+    /// A description of the token.
+    pub fn description(&self) -> Option<&str> {
+        self.overrides
+            .description
+            .as_deref()
+            .or_else(|| self.irc30.as_ref().and_then(|irc30| irc30.description.as_deref()))
+    }
+
+    /// A URL pointing to the logo of the token.
+    pub fn logo_url(&self) -> Option<&str> {
+        self.overrides
+            .logo_url
+            .as_deref()
+            .or_else(|| self.irc30.as_ref().and_then(|irc30| irc30.logo_url.as_deref()))
+    }
+
+    /// Formats an amount of the token with its decimals, or as integer if they're unknown.
+    pub fn format_amount(&self, amount: U256) -> String {
+        format_native_token_amount(amount, self.decimals().unwrap_or(0))
+    }
+}
+
+/// Formats a native token amount with the decimals, without trailing zeros, e.g. `1234500` with 6 decimals is `1.2345`.
+/// Decimals are capped at [`MAX_NATIVE_TOKEN_DECIMALS`].
+pub fn format_native_token_amount(amount: U256, decimals: u32) -> String {
+    let digits = amount.to_string();
+    let decimals = decimals.min(MAX_NATIVE_TOKEN_DECIMALS) as usize;
+    if decimals == 0 {
+        return digits;
+    }
+    let digits = format!("{digits:0>width$}", width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{integer}.{fraction}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_irc30() {
+        let metadata = Irc30Metadata::from_bytes(
+            br#"{"standard":"IRC30","name":"Token","symbol":"SHT","decimals":6,"logoUrl":"https://x.y/logo.png"}"#,
+        )
+        .unwrap();
+        assert_eq!(metadata.symbol, "SHT");
+        assert_eq!(metadata.decimals, 6);
+        assert_eq!(metadata.logo_url.as_deref(), Some("https://x.y/logo.png"));
+
+        assert!(Irc30Metadata::from_bytes(br#"{"standard":"IRC27","name":"NFT","symbol":"N","decimals":0}"#).is_none());
+        assert!(Irc30Metadata::from_bytes(b"not json").is_none());
+
+        // More decimals than the digits of the largest amount
+        assert!(
+            Irc30Metadata::from_bytes(br#"{"standard":"IRC30","name":"Token","symbol":"T","decimals":77}"#).is_some()
+        );
+        assert!(
+            Irc30Metadata::from_bytes(br#"{"standard":"IRC30","name":"Token","symbol":"T","decimals":78}"#).is_none()
+        );
+    }
+
+    #[test]
+    fn overrides() {
+        let mut metadata = NativeTokenMetadata {
+            irc30: Irc30Metadata::from_bytes(br#"{"standard":"IRC30","name":"Token","symbol":"TOK","decimals":2}"#),
+            ..Default::default()
+        };
+        assert_eq!(metadata.symbol(), Some("TOK"));
+        assert_eq!(metadata.format_amount(U256::from(1050)), "10.5");
+
+        metadata.overrides.symbol = Some("MY".to_string());
+        metadata.overrides.decimals = Some(0);
+        assert_eq!(metadata.symbol(), Some("MY"));
+        assert_eq!(metadata.name(), Some("Token"));
+        assert_eq!(metadata.format_amount(U256::from(1050)), "1050");
+    }
+
+    #[test]
+    fn format_amounts() {
+        assert_eq!(format_native_token_amount(U256::from(1_234_500), 6), "1.2345");
+        assert_eq!(format_native_token_amount(U256::from(1_000_000), 6), "1");
+        assert_eq!(format_native_token_amount(U256::from(5), 3), "0.005");
+        assert_eq!(format_native_token_amount(U256::from(0), 3), "0");
+        assert_eq!(format_native_token_amount(U256::from(42), 0), "42");
+        assert_eq!(
+            format_native_token_amount(U256::MAX, MAX_NATIVE_TOKEN_DECIMALS),
+            format!("1.{}", &U256::MAX.to_string()[1..])
+        );
+        assert_eq!(
+            format_native_token_amount(U256::from(5), u32::MAX),
+            format_native_token_amount(U256::from(5), MAX_NATIVE_TOKEN_DECIMALS)
+        );
+    }
+}
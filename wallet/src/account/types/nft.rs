@@ -0,0 +1,122 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::block::{
+    address::{dto::AddressDto, Address},
+    output::{NftId, NftOutput, OutputId},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::account::types::Irc27Metadata;
+
+/// An unspent NFT of the account with its decoded metadata.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedNft {
+    /// The id of the NFT.
+    pub nft_id: NftId,
+    /// The id of the output that holds the NFT.
+    pub output_id: OutputId,
+    /// The address of the issuer feature.
+    pub issuer: Option<Address>,
+    /// The id of the collection NFT, if the issuer is an NFT address.
+    pub collection_id: Option<NftId>,
+    /// The data of the metadata feature.
+    pub metadata: Option<Vec<u8>>,
+    /// The data of the immutable metadata feature.
+    pub immutable_metadata: Option<Vec<u8>>,
+    /// The immutable metadata, if it's valid IRC27 metadata.
+    pub irc27_metadata: Option<Irc27Metadata>,
+}
+
+impl OwnedNft {
+    pub(crate) fn new(output_id: OutputId, nft: &NftOutput) -> Self {
+        let issuer = nft.immutable_features().issuer().map(|issuer| *issuer.address());
+        Self {
+            nft_id: nft.nft_id_non_null(&output_id),
+            output_id,
+            issuer,
+            collection_id: match issuer {
+                Some(Address::Nft(nft_address)) => Some(*nft_address.nft_id()),
+                _ => None,
+            },
+            metadata: nft.features().metadata().map(|metadata| metadata.data().to_vec()),
+            immutable_metadata: nft
+                .immutable_features()
+                .metadata()
+                .map(|metadata| metadata.data().to_vec()),
+            irc27_metadata: Irc27Metadata::from_nft(nft),
+        }
+    }
+}
+
+/// Dto for an [`OwnedNft`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnedNftDto {
+    /// The id of the NFT.
+    pub nft_id: NftId,
+    /// The id of the output that holds the NFT.
+    pub output_id: OutputId,
+    /// The address of the issuer feature.
+    pub issuer: Option<AddressDto>,
+    /// The id of the collection NFT, if the issuer is an NFT address.
+    pub collection_id: Option<NftId>,
+    /// The data of the metadata feature, hex encoded.
+    pub metadata: Option<String>,
+    /// The data of the immutable metadata feature, hex encoded.
+    pub immutable_metadata: Option<String>,
+    /// The immutable metadata, if it's valid IRC27 metadata.
+    pub irc27_metadata: Option<Irc27Metadata>,
+}
+
+impl From<&OwnedNft> for OwnedNftDto {
+    fn from(value: &OwnedNft) -> Self {
+        Self {
+            nft_id: value.nft_id,
+            output_id: value.output_id,
+            issuer: value.issuer.as_ref().map(AddressDto::from),
+            collection_id: value.collection_id,
+            metadata: value.metadata.as_ref().map(prefix_hex::encode),
+            immutable_metadata: value.immutable_metadata.as_ref().map(prefix_hex::encode),
+            irc27_metadata: value.irc27_metadata.clone(),
+        }
+    }
+}
+
+/// The unspent NFTs of the account with the same issuer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NftCollection {
+    /// The common issuer of the NFTs, `None` for NFTs without issuer feature.
+    pub issuer: Option<Address>,
+    /// The id of the collection NFT, if the issuer is an NFT address.
+    pub collection_id: Option<NftId>,
+    /// The IRC27 metadata of the collection NFT, if it's known to the account.
+    pub collection_metadata: Option<Irc27Metadata>,
+    /// The NFTs of the collection.
+    pub nfts: Vec<OwnedNft>,
+}
+
+/// Dto for an [`NftCollection`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NftCollectionDto {
+    /// The common issuer of the NFTs, `None` for NFTs without issuer feature.
+    pub issuer: Option<AddressDto>,
+    /// The id of the collection NFT, if the issuer is an NFT address.
+    pub collection_id: Option<NftId>,
+    /// The IRC27 metadata of the collection NFT, if it's known to the account.
+    pub collection_metadata: Option<Irc27Metadata>,
+    /// The NFTs of the collection.
+    pub nfts: Vec<OwnedNftDto>,
+}
+
+impl From<&NftCollection> for NftCollectionDto {
+    fn from(value: &NftCollection) -> Self {
+        Self {
+            issuer: value.issuer.as_ref().map(AddressDto::from),
+            collection_id: value.collection_id,
+            collection_metadata: value.collection_metadata.clone(),
+            nfts: value.nfts.iter().map(OwnedNftDto::from).collect(),
+        }
+    }
+}
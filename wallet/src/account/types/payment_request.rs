@@ -0,0 +1,248 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use iota_client::block::{
+    dto::U256Dto,
+    output::{dto::NativeTokenDto, NativeToken, OutputId},
+    DtoError,
+};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::account::types::address::AddressWrapper;
+
+/// The status of a [`PaymentRequest`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum PaymentRequestStatus {
+    /// Nothing was received yet.
+    Pending,
+    /// Less than the requested amount was received.
+    PartiallyPaid,
+    /// Exactly the requested amount was received.
+    Paid,
+    /// More than the requested amount was received.
+    Overpaid,
+    /// The request expired before it was paid.
+    Expired,
+}
+
+/// A request for a payment to a dedicated address or with a dedicated tag.
+/// Outputs that are found during syncing are matched against the open requests and update the received amounts.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRequest {
+    /// The id of the request, unique in the account.
+    pub id: u32,
+    /// The address the payment has to be sent to.
+    #[serde(with = "crate::account::types::address_serde")]
+    pub address: AddressWrapper,
+    /// The tag the outputs need to have, if the request isn't bound to a dedicated address.
+    pub tag: Option<Vec<u8>>,
+    /// The requested base coin amount. If a native token is requested, this is the minimum amount and more isn't
+    /// counted as overpaid, because the storage deposit depends on the sender.
+    pub amount: u64,
+    /// The requested native token.
+    pub native_token: Option<NativeToken>,
+    /// A memo for the request, e.g. an order number.
+    pub memo: Option<String>,
+    /// Unix timestamp in seconds when the request was created.
+    pub created_at: u32,
+    /// Unix timestamp in seconds after which the request expires, if it isn't paid. It's compared with milestone
+    /// timestamps, outputs booked after it don't count.
+    pub expiration: Option<u32>,
+    /// The current status.
+    pub status: PaymentRequestStatus,
+    /// The received base coin amount.
+    pub received_amount: u64,
+    /// The received amount of the requested native token.
+    pub received_native_token_amount: U256,
+    /// The outputs that paid the request.
+    pub output_ids: Vec<OutputId>,
+}
+
+impl PaymentRequest {
+    /// Returns true if outputs can still be received for the request.
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self.status,
+            PaymentRequestStatus::Pending | PaymentRequestStatus::PartiallyPaid
+        )
+    }
+
+    // The status based on the received amounts, without the expiration.
+    pub(crate) fn status_from_received(&self) -> PaymentRequestStatus {
+        let requested_native_token_amount = self
+            .native_token
+            .as_ref()
+            .map_or_else(U256::zero, |native_token| *native_token.amount());
+
+        if self.received_amount == 0 && self.received_native_token_amount.is_zero() {
+            PaymentRequestStatus::Pending
+        } else if self.received_amount < self.amount
+            || self.received_native_token_amount < requested_native_token_amount
+        {
+            PaymentRequestStatus::PartiallyPaid
+        } else if self.received_native_token_amount > requested_native_token_amount
+            || (self.native_token.is_none() && self.received_amount > self.amount)
+        {
+            PaymentRequestStatus::Overpaid
+        } else {
+            PaymentRequestStatus::Paid
+        }
+    }
+}
+
+/// Options for [`AccountHandle::create_payment_request()`](crate::account::AccountHandle::create_payment_request).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRequestOptions {
+    /// The requested base coin amount.
+    pub amount: u64,
+    /// The requested native token.
+    pub native_token: Option<NativeToken>,
+    /// Bind the request to a tag on the first public address, instead of generating a new address.
+    pub tag: Option<Vec<u8>>,
+    /// A memo for the request.
+    pub memo: Option<String>,
+    /// Unix timestamp in seconds after which the request expires.
+    pub expiration: Option<u32>,
+}
+
+/// Dto for PaymentRequestOptions
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRequestOptionsDto {
+    /// The requested base coin amount.
+    pub amount: String,
+    /// The requested native token.
+    pub native_token: Option<NativeTokenDto>,
+    /// Hex encoded tag.
+    pub tag: Option<String>,
+    /// A memo for the request.
+    pub memo: Option<String>,
+    /// Unix timestamp in seconds after which the request expires.
+    pub expiration: Option<u32>,
+}
+
+impl TryFrom<&PaymentRequestOptionsDto> for PaymentRequestOptions {
+    type Error = crate::Error;
+
+    fn try_from(value: &PaymentRequestOptionsDto) -> crate::Result<Self> {
+        Ok(Self {
+            amount: u64::from_str(&value.amount)
+                .map_err(|_| iota_client::Error::InvalidAmount(value.amount.clone()))?,
+            native_token: match &value.native_token {
+                Some(native_token) => Some(NativeToken::try_from(native_token)?),
+                None => None,
+            },
+            tag: match &value.tag {
+                Some(tag) => Some(prefix_hex::decode(tag).map_err(|_| DtoError::InvalidField("tag"))?),
+                None => None,
+            },
+            memo: value.memo.clone(),
+            expiration: value.expiration,
+        })
+    }
+}
+
+/// Dto for PaymentRequest
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRequestDto {
+    /// The id of the request, unique in the account.
+    pub id: u32,
+    /// The address the payment has to be sent to.
+    #[serde(with = "crate::account::types::address_serde")]
+    pub address: AddressWrapper,
+    /// Hex encoded tag the outputs need to have.
+    pub tag: Option<String>,
+    /// The requested base coin amount.
+    pub amount: String,
+    /// The requested native token.
+    pub native_token: Option<NativeTokenDto>,
+    /// A memo for the request.
+    pub memo: Option<String>,
+    /// Unix timestamp in seconds when the request was created.
+    pub created_at: u32,
+    /// Unix timestamp in seconds after which the request expires.
+    pub expiration: Option<u32>,
+    /// The current status.
+    pub status: PaymentRequestStatus,
+    /// The received base coin amount.
+    pub received_amount: String,
+    /// The received amount of the requested native token.
+    pub received_native_token_amount: U256Dto,
+    /// The outputs that paid the request.
+    pub output_ids: Vec<OutputId>,
+}
+
+impl From<&PaymentRequest> for PaymentRequestDto {
+    fn from(value: &PaymentRequest) -> Self {
+        Self {
+            id: value.id,
+            address: value.address.clone(),
+            tag: value.tag.as_ref().map(prefix_hex::encode),
+            amount: value.amount.to_string(),
+            native_token: value.native_token.as_ref().map(NativeTokenDto::from),
+            memo: value.memo.clone(),
+            created_at: value.created_at,
+            expiration: value.expiration,
+            status: value.status,
+            received_amount: value.received_amount.to_string(),
+            received_native_token_amount: U256Dto::from(&value.received_native_token_amount),
+            output_ids: value.output_ids.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_client::block::{
+        address::{Address, Ed25519Address},
+        output::TokenId,
+    };
+
+    use super::*;
+
+    fn payment_request(amount: u64, native_token: Option<NativeToken>) -> PaymentRequest {
+        PaymentRequest {
+            id: 0,
+            address: AddressWrapper::new(Address::Ed25519(Ed25519Address::new([0; 32])), "rms".to_string()),
+            tag: None,
+            amount,
+            native_token,
+            memo: None,
+            created_at: 0,
+            expiration: None,
+            status: PaymentRequestStatus::Pending,
+            received_amount: 0,
+            received_native_token_amount: U256::zero(),
+            output_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn status_from_received() {
+        let mut request = payment_request(1_000_000, None);
+        assert_eq!(request.status_from_received(), PaymentRequestStatus::Pending);
+        request.received_amount = 500_000;
+        assert_eq!(request.status_from_received(), PaymentRequestStatus::PartiallyPaid);
+        request.received_amount = 1_000_000;
+        assert_eq!(request.status_from_received(), PaymentRequestStatus::Paid);
+        request.received_amount = 1_500_000;
+        assert_eq!(request.status_from_received(), PaymentRequestStatus::Overpaid);
+
+        let native_token = NativeToken::new(TokenId::null(), U256::from(100)).unwrap();
+        let mut request = payment_request(0, Some(native_token));
+        // More base coins don't overpay a native token request
+        request.received_amount = 50_000;
+        request.received_native_token_amount = U256::from(40);
+        assert_eq!(request.status_from_received(), PaymentRequestStatus::PartiallyPaid);
+        request.received_native_token_amount = U256::from(100);
+        assert_eq!(request.status_from_received(), PaymentRequestStatus::Paid);
+        request.received_native_token_amount = U256::from(101);
+        assert_eq!(request.status_from_received(), PaymentRequestStatus::Overpaid);
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt, str::FromStr};
+
+use iota_client::{
+    block::{
+        address::Address,
+        output::{NativeToken, TokenId},
+    },
+    Url,
+};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::account::{
+    operations::transaction::prepare_output::{Assets, Features, OutputOptions},
+    types::PaymentRequest,
+};
+
+/// The scheme of payment URIs.
+pub const PAYMENT_URI_SCHEME: &str = "iota";
+
+/// A payment URI to pass a payment between apps and devices, for example as QR code, in the format
+/// `iota:<bech32 address>?<parameters>` with the optional parameters:
+/// - `amount`: the base coin amount
+/// - `token` and `tokenAmount`: the id and decimal amount of a native token
+/// - `tag` and `metadata`: hex encoded features of the output
+/// - `expiration`: unix timestamp in seconds after which the payment shouldn't be made anymore
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentUri {
+    /// Bech32 encoded address of the recipient.
+    pub address: String,
+    /// The base coin amount.
+    pub amount: Option<u64>,
+    /// The native token to send.
+    pub native_token: Option<NativeToken>,
+    /// The tag feature of the output.
+    pub tag: Option<Vec<u8>>,
+    /// The metadata feature of the output.
+    pub metadata: Option<Vec<u8>>,
+    /// Unix timestamp in seconds after which the payment shouldn't be made anymore.
+    pub expiration: Option<u32>,
+}
+
+impl PaymentUri {
+    /// Create a payment URI for an address without any parameters.
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            amount: None,
+            native_token: None,
+            tag: None,
+            metadata: None,
+            expiration: None,
+        }
+    }
+
+    /// Parse a payment URI, the address needs to have the `bech32_hrp` of the current network.
+    pub fn parse(uri: &str, bech32_hrp: &str) -> crate::Result<Self> {
+        let invalid = |reason: &str| crate::Error::InvalidPaymentUri(reason.to_string());
+
+        let url = Url::parse(uri).map_err(|e| invalid(&e.to_string()))?;
+        if url.scheme() != PAYMENT_URI_SCHEME {
+            return Err(invalid(&format!("scheme needs to be {PAYMENT_URI_SCHEME}")));
+        }
+
+        let address = url.path().to_string();
+        let (address_bech32_hrp, _) =
+            Address::try_from_bech32(&address).map_err(|_| invalid(&format!("invalid address {address}")))?;
+        if address_bech32_hrp != bech32_hrp {
+            return Err(invalid(&format!(
+                "address has bech32 hrp {address_bech32_hrp}, expected {bech32_hrp}"
+            )));
+        }
+
+        let mut payment_uri = Self::new(address);
+        let mut token_id = None;
+        let mut token_amount = None;
+        let mut parameters = Vec::new();
+
+        for (key, value) in url.query_pairs() {
+            if parameters.contains(&key) {
+                return Err(invalid(&format!("duplicate parameter {key}")));
+            }
+            match key.as_ref() {
+                "amount" => {
+                    payment_uri.amount = Some(u64::from_str(&value).map_err(|_| invalid("invalid amount"))?);
+                }
+                "token" => token_id = Some(TokenId::from_str(&value).map_err(|_| invalid("invalid token"))?),
+                "tokenAmount" => {
+                    token_amount = Some(U256::from_dec_str(&value).map_err(|_| invalid("invalid tokenAmount"))?);
+                }
+                "tag" => {
+                    payment_uri.tag = Some(prefix_hex::decode(value.as_ref()).map_err(|_| invalid("invalid tag"))?);
+                }
+                "metadata" => {
+                    payment_uri.metadata =
+                        Some(prefix_hex::decode(value.as_ref()).map_err(|_| invalid("invalid metadata"))?);
+                }
+                "expiration" => {
+                    payment_uri.expiration = Some(u32::from_str(&value).map_err(|_| invalid("invalid expiration"))?);
+                }
+                _ => return Err(invalid(&format!("unknown parameter {key}"))),
+            }
+            parameters.push(key);
+        }
+
+        payment_uri.native_token = match (token_id, token_amount) {
+            (Some(token_id), Some(token_amount)) => Some(NativeToken::new(token_id, token_amount)?),
+            (None, None) => None,
+            _ => return Err(invalid("token and tokenAmount need to be provided together")),
+        };
+
+        Ok(payment_uri)
+    }
+
+    /// Returns true if the expiration is reached at the `current_time` in seconds.
+    pub fn is_expired(&self, current_time: u32) -> bool {
+        self.expiration.map_or(false, |expiration| current_time >= expiration)
+    }
+
+    /// Convert the payment to [`OutputOptions`] for
+    /// [`AccountHandle::prepare_output()`](crate::account::AccountHandle::prepare_output). Without an amount, the
+    /// minimum storage deposit is used.
+    pub fn to_output_options(&self) -> OutputOptions {
+        let features = (self.tag.is_some() || self.metadata.is_some()).then(|| Features {
+            tag: self.tag.as_ref().map(prefix_hex::encode),
+            metadata: self.metadata.as_ref().map(prefix_hex::encode),
+            ..Default::default()
+        });
+
+        OutputOptions {
+            recipient_address: self.address.clone(),
+            amount: self.amount.unwrap_or_default(),
+            assets: self.native_token.as_ref().map(|native_token| Assets {
+                native_tokens: Some(vec![native_token.clone()]),
+                nft_id: None,
+            }),
+            features,
+            unlocks: None,
+            storage_deposit: None,
+        }
+    }
+}
+
+/// The memo of the payment request is added as metadata, so the payment carries it, for example an order number.
+impl From<&PaymentRequest> for PaymentUri {
+    fn from(payment_request: &PaymentRequest) -> Self {
+        Self {
+            address: payment_request.address.to_bech32(),
+            amount: (payment_request.amount > 0).then_some(payment_request.amount),
+            native_token: payment_request.native_token.clone(),
+            tag: payment_request.tag.clone(),
+            metadata: payment_request.memo.as_ref().map(|memo| memo.as_bytes().to_vec()),
+            expiration: payment_request.expiration,
+        }
+    }
+}
+
+impl fmt::Display for PaymentUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parameters = Vec::new();
+        if let Some(amount) = self.amount {
+            parameters.push(("amount", amount.to_string()));
+        }
+        if let Some(native_token) = &self.native_token {
+            parameters.push(("token", native_token.token_id().to_string()));
+            parameters.push(("tokenAmount", native_token.amount().to_string()));
+        }
+        if let Some(tag) = &self.tag {
+            parameters.push(("tag", prefix_hex::encode(tag)));
+        }
+        if let Some(metadata) = &self.metadata {
+            parameters.push(("metadata", prefix_hex::encode(metadata)));
+        }
+        if let Some(expiration) = self.expiration {
+            parameters.push(("expiration", expiration.to_string()));
+        }
+
+        write!(f, "{PAYMENT_URI_SCHEME}:{}", self.address)?;
+        // All values are numbers or hex encoded, so they don't need to be percent encoded
+        for (position, (key, value)) in parameters.iter().enumerate() {
+            write!(f, "{}{key}={value}", if position == 0 { '?' } else { '&' })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_client::block::address::Ed25519Address;
+
+    use super::*;
+    use crate::account::types::{AddressWrapper, PaymentRequestStatus};
+
+    const ADDRESS: &str = "rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3";
+
+    #[test]
+    fn round_trip() {
+        let payment_uri = PaymentUri {
+            amount: Some(1_000_000),
+            native_token: Some(NativeToken::new(TokenId::null(), U256::from(50)).unwrap()),
+            tag: Some(b"order-42".to_vec()),
+            metadata: Some(vec![1, 2, 3]),
+            expiration: Some(1_700_000_000),
+            ..PaymentUri::new(ADDRESS)
+        };
+
+        let uri = payment_uri.to_string();
+        assert!(uri.starts_with(&format!("iota:{ADDRESS}?amount=1000000&token=")));
+        assert_eq!(PaymentUri::parse(&uri, "rms").unwrap(), payment_uri);
+
+        let uri = PaymentUri::new(ADDRESS).to_string();
+        assert_eq!(uri, format!("iota:{ADDRESS}"));
+        assert_eq!(PaymentUri::parse(&uri, "rms").unwrap(), PaymentUri::new(ADDRESS));
+    }
+
+    #[test]
+    fn from_payment_request() {
+        let payment_request = PaymentRequest {
+            id: 0,
+            address: AddressWrapper::new(Address::Ed25519(Ed25519Address::new([0; 32])), "rms".to_string()),
+            tag: Some(b"order-42".to_vec()),
+            amount: 1_000_000,
+            native_token: None,
+            memo: Some("Order 42".to_string()),
+            created_at: 0,
+            expiration: None,
+            status: PaymentRequestStatus::Pending,
+            received_amount: 0,
+            received_native_token_amount: U256::zero(),
+            output_ids: Vec::new(),
+        };
+
+        let payment_uri = PaymentUri::from(&payment_request);
+        assert_eq!(payment_uri.metadata.as_deref(), Some("Order 42".as_bytes()));
+        assert_eq!(PaymentUri::parse(&payment_uri.to_string(), "rms").unwrap(), payment_uri);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(PaymentUri::parse(&format!("iota:{ADDRESS}"), "smr").is_err());
+        assert!(PaymentUri::parse(&format!("http:{ADDRESS}"), "rms").is_err());
+        assert!(PaymentUri::parse(&format!("iota:{ADDRESS}?amount=-1"), "rms").is_err());
+        assert!(PaymentUri::parse(&format!("iota:{ADDRESS}?amount=1&amount=2"), "rms").is_err());
+        assert!(PaymentUri::parse(&format!("iota:{ADDRESS}?unknown=1"), "rms").is_err());
+        assert!(PaymentUri::parse(&format!("iota:{ADDRESS}?tokenAmount=1"), "rms").is_err());
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::account::{
+    constants::{MAX_SYNC_CHECKPOINT_AGE, SYNC_CHECKPOINT_OVERLAP},
+    operations::syncing::{
+        options::{AccountSyncOptions, AliasSyncOptions, NftSyncOptions},
+        SyncOptions,
+    },
+};
+
+/// The state of the latest sync of all addresses of an account. Following syncs only request outputs that were created
+/// after it and check the known unspent outputs for being spent.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncCheckpoint {
+    /// The network id of the node the account was synced with.
+    pub network_id: u64,
+    /// The index of the confirmed milestone when the sync started.
+    pub milestone_index: u32,
+    /// The timestamp of the confirmed milestone when the sync started.
+    pub milestone_timestamp: u32,
+    /// The amount of public addresses that were synced.
+    pub public_address_count: u32,
+    /// The amount of internal addresses that were synced.
+    pub internal_address_count: u32,
+    /// The outputs that were synced for the ed25519 addresses.
+    pub account: AccountSyncOptions,
+    /// The outputs that were synced for alias addresses.
+    pub alias: AliasSyncOptions,
+    /// The outputs that were synced for nft addresses.
+    pub nft: NftSyncOptions,
+    /// If only basic outputs with an AddressUnlockCondition alone were synced.
+    pub sync_only_most_basic_outputs: bool,
+}
+
+impl SyncCheckpoint {
+    /// Returns true if the outputs of the address were synced with the checkpoint.
+    pub fn contains_address(&self, key_index: u32, internal: bool) -> bool {
+        if internal {
+            key_index < self.internal_address_count
+        } else {
+            key_index < self.public_address_count
+        }
+    }
+
+    /// The timestamp after which created outputs need to be requested. It overlaps with the checkpoint, so outputs that
+    /// weren't indexed yet when the checkpoint was created aren't missed.
+    pub fn created_after(&self) -> u32 {
+        self.milestone_timestamp.saturating_sub(SYNC_CHECKPOINT_OVERLAP)
+    }
+
+    // Checks if the checkpoint can be used for syncing with the options. The checkpoint can't be used if the network
+    // changed, the node pruned the milestone, it's too old or other outputs were synced.
+    pub(crate) fn is_valid_for(
+        &self,
+        options: &SyncOptions,
+        network_id: u64,
+        pruning_index: u32,
+        milestone_timestamp: u32,
+    ) -> bool {
+        self.network_id == network_id
+            && self.milestone_index > pruning_index
+            && milestone_timestamp.saturating_sub(self.milestone_timestamp) < MAX_SYNC_CHECKPOINT_AGE
+            && self.account == options.account
+            && self.alias == options.alias
+            && self.nft == options.nft
+            && self.sync_only_most_basic_outputs == options.sync_only_most_basic_outputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint() -> SyncCheckpoint {
+        let options = SyncOptions::default();
+        SyncCheckpoint {
+            network_id: 1,
+            milestone_index: 100,
+            milestone_timestamp: 1_000_000,
+            public_address_count: 2,
+            internal_address_count: 1,
+            account: options.account,
+            alias: options.alias,
+            nft: options.nft,
+            sync_only_most_basic_outputs: options.sync_only_most_basic_outputs,
+        }
+    }
+
+    #[test]
+    fn is_valid_for() {
+        let checkpoint = checkpoint();
+        let options = SyncOptions::default();
+
+        assert!(checkpoint.is_valid_for(&options, 1, 50, 1_000_100));
+        // Other network
+        assert!(!checkpoint.is_valid_for(&options, 2, 50, 1_000_100));
+        // Pruned
+        assert!(!checkpoint.is_valid_for(&options, 1, 100, 1_000_100));
+        // Too old
+        assert!(!checkpoint.is_valid_for(&options, 1, 50, 1_000_000 + MAX_SYNC_CHECKPOINT_AGE));
+        // Other outputs
+        let options = SyncOptions {
+            sync_only_most_basic_outputs: true,
+            ..Default::default()
+        };
+        assert!(!checkpoint.is_valid_for(&options, 1, 50, 1_000_100));
+    }
+
+    #[test]
+    fn contains_address() {
+        let checkpoint = checkpoint();
+
+        assert!(checkpoint.contains_address(1, false));
+        assert!(!checkpoint.contains_address(2, false));
+        assert!(checkpoint.contains_address(0, true));
+        assert!(!checkpoint.contains_address(1, true));
+    }
+}
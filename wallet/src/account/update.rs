@@ -10,13 +10,13 @@ use iota_client::{
 
 use crate::account::{
     handle::AccountHandle,
-    operations::syncing::options::SyncOptions,
-    types::{address::AddressWithUnspentOutputs, InclusionState, OutputData, Transaction},
+    operations::{payment_request::update_payment_requests, syncing::options::SyncOptions},
+    types::{address::AddressWithUnspentOutputs, AccountMetadata, InclusionState, OutputData, Transaction},
     AccountAddress,
 };
 #[cfg(feature = "events")]
 use crate::{
-    account::types::OutputDataDto,
+    account::types::{OutputDataDto, PaymentRequestDto},
     events::types::{NewOutputEvent, SpentOutputEvent, TransactionInclusionEvent, WalletEvent},
     iota_client::api_types::core::response::OutputWithMetadataResponse,
     iota_client::block::payload::transaction::dto::TransactionPayloadDto,
@@ -32,6 +32,46 @@ impl AccountHandle {
         Ok(())
     }
 
+    /// Set a label for an address of the account, `None` removes it.
+    pub async fn set_address_label(&self, address: &str, label: Option<String>) -> crate::Result<()> {
+        let mut account = self.write().await;
+        let account_address = account
+            .public_addresses
+            .iter_mut()
+            .chain(account.internal_addresses.iter_mut())
+            .find(|account_address| account_address.address.to_bech32() == address)
+            .ok_or_else(|| crate::Error::AddressNotFoundInAccount(address.to_string()))?;
+        account_address.label = label;
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+
+    /// Set a label for an output of the account, `None` removes it.
+    pub async fn set_output_label(&self, output_id: OutputId, label: Option<String>) -> crate::Result<()> {
+        let mut account = self.write().await;
+        let output_data = account
+            .outputs
+            .get_mut(&output_id)
+            .ok_or(crate::Error::OutputNotFound(output_id))?;
+        output_data.label = label.clone();
+        if let Some(unspent_output_data) = account.unspent_outputs.get_mut(&output_id) {
+            unspent_output_data.label = label;
+        }
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+
+    /// Set the user defined metadata of the account.
+    pub async fn set_metadata(&self, metadata: AccountMetadata) -> crate::Result<()> {
+        let mut account = self.write().await;
+        account.metadata = metadata;
+        #[cfg(feature = "storage")]
+        self.save(Some(&account)).await?;
+        Ok(())
+    }
+
     /// Update account with newly synced data and emit events for outputs
     pub(crate) async fn update_account(
         &self,
@@ -43,6 +83,24 @@ impl AccountHandle {
         log::debug!("[SYNC] Update account with new synced transactions");
 
         let network_id = self.client.get_network_id().await?;
+        // Payment requests expire by the milestone time, only requested if one of them can expire
+        let milestone_timestamp = if self
+            .read()
+            .await
+            .payment_requests
+            .values()
+            .any(|payment_request| payment_request.is_open() && payment_request.expiration.is_some())
+        {
+            self.client
+                .get_info()
+                .await?
+                .node_info
+                .status
+                .confirmed_milestone
+                .timestamp
+        } else {
+            None
+        };
         let mut account = self.write().await;
         #[cfg(feature = "events")]
         let account_index = account.index;
@@ -135,13 +193,19 @@ impl AccountHandle {
         }
 
         // Add new synced outputs
-        for output_data in unspent_outputs {
+        let mut new_outputs = Vec::new();
+        for mut output_data in unspent_outputs {
+            // Keep the label of known outputs
+            if let Some(known_output_data) = account.outputs.get(&output_data.output_id) {
+                output_data.label = known_output_data.label.clone();
+            }
             // Insert output, if it's unknown emit the NewOutputEvent
             if account
                 .outputs
                 .insert(output_data.output_id, output_data.clone())
                 .is_none()
             {
+                new_outputs.push(output_data.clone());
                 #[cfg(feature = "events")]
                 {
                     let transaction = account
@@ -168,6 +232,16 @@ impl AccountHandle {
             }
         }
 
+        #[cfg_attr(not(feature = "events"), allow(unused_variables))]
+        let changed_payment_requests = update_payment_requests(&mut account, &new_outputs, milestone_timestamp);
+        #[cfg(feature = "events")]
+        for payment_request in changed_payment_requests {
+            self.event_emitter.lock().await.emit(
+                account_index,
+                WalletEvent::PaymentRequest(Box::new(PaymentRequestDto::from(&payment_request))),
+            );
+        }
+
         #[cfg(feature = "storage")]
         {
             log::debug!("[SYNC] storing account {} with new synced data", account.alias());
@@ -209,9 +283,13 @@ impl AccountHandle {
                 }
                 _ => {}
             }
-            account
+            if account
                 .transactions
-                .insert(transaction.payload.id(), transaction.clone());
+                .insert(transaction.payload.id(), transaction.clone())
+                .is_none()
+            {
+                self.count_backup_change();
+            }
         }
 
         for output_to_unlock in &spent_output_ids {
@@ -258,6 +336,7 @@ impl AccountHandle {
         } else {
             account.public_addresses.extend(new_addresses);
         };
+        self.count_backup_change();
 
         #[cfg(feature = "storage")]
         {
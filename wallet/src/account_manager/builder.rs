@@ -1,9 +1,14 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::{
-    atomic::{AtomicU32, AtomicUsize},
-    Arc,
+#[cfg(feature = "stronghold")]
+use std::sync::atomic::AtomicU64;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, AtomicUsize},
+        Arc,
+    },
 };
 #[cfg(feature = "storage")]
 use std::{path::PathBuf, sync::atomic::Ordering};
@@ -20,7 +25,14 @@ use crate::events::EventEmitter;
 use crate::storage::adapter::memory::Memory;
 #[cfg(feature = "storage")]
 use crate::storage::{constants::default_storage_path, manager::ManagerStorage};
-use crate::{account::handle::AccountHandle, account_manager::AccountManager, ClientOptions};
+use crate::{
+    account::{
+        handle::AccountHandle,
+        operations::syncing::{options::SyncRequestOptions, request_limiter::RequestLimiter},
+    },
+    account_manager::AccountManager,
+    ClientOptions,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 /// Builder for the account manager.
@@ -29,6 +41,8 @@ pub struct AccountManagerBuilder {
     coin_type: Option<u32>,
     #[cfg(feature = "storage")]
     storage_options: Option<StorageOptions>,
+    #[serde(default)]
+    sync_request_options: Option<SyncRequestOptions>,
     #[serde(default, skip_serializing, skip_deserializing)]
     pub(crate) secret_manager: Option<Arc<RwLock<SecretManager>>>,
 }
@@ -89,6 +103,13 @@ impl AccountManagerBuilder {
         self
     }
 
+    /// Set the limits for the requests that are sent during syncing, they're shared between all accounts and can be
+    /// replaced for a single sync with [`SyncOptions::request_options`](crate::account::SyncOptions).
+    pub fn with_sync_request_options(mut self, sync_request_options: SyncRequestOptions) -> Self {
+        self.sync_request_options.replace(sync_request_options);
+        self
+    }
+
     /// Set the storage path to be used.
     #[cfg(feature = "storage")]
     #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
@@ -163,6 +184,11 @@ impl AccountManagerBuilder {
             self.secret_manager.replace(secret_manager);
         }
 
+        if self.sync_request_options.is_none() {
+            // Update self so it gets used and stored again
+            self.sync_request_options = read_manager_builder.as_ref().and_then(|data| data.sync_request_options);
+        }
+
         if self.coin_type.is_none() {
             let coin_type = read_manager_builder
                 .and_then(|data| data.coin_type)
@@ -185,6 +211,10 @@ impl AccountManagerBuilder {
         #[cfg(feature = "events")]
         let event_emitter = Arc::new(Mutex::new(EventEmitter::new()));
 
+        let request_limiter = RequestLimiter::new(self.sync_request_options.unwrap_or_default());
+        #[cfg(feature = "stronghold")]
+        let backup_changes = Arc::new(AtomicU64::new(0));
+
         #[cfg(feature = "storage")]
         let accounts = storage_manager.lock().await.get_accounts().await.unwrap_or_default();
         #[cfg(not(feature = "storage"))]
@@ -198,6 +228,9 @@ impl AccountManagerBuilder {
                     self.secret_manager
                         .clone()
                         .expect("secret_manager needs to be provided"),
+                    request_limiter.clone(),
+                    #[cfg(feature = "stronghold")]
+                    backup_changes.clone(),
                     #[cfg(feature = "events")]
                     event_emitter.clone(),
                     #[cfg(feature = "storage")]
@@ -216,7 +249,13 @@ impl AccountManagerBuilder {
 
         Ok(AccountManager {
             accounts: Arc::new(RwLock::new(account_handles)),
+            #[cfg(feature = "stronghold")]
+            auto_backup_status: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "stronghold")]
+            backup_changes,
             background_syncing_status: Arc::new(AtomicUsize::new(0)),
+            background_syncing_options: Arc::new(RwLock::new(HashMap::new())),
+            background_syncing_statuses: Arc::new(RwLock::new(HashMap::new())),
             client_options: Arc::new(RwLock::new(
                 self.client_options
                     .ok_or(crate::Error::MissingParameter("client_options"))?,
@@ -225,9 +264,12 @@ impl AccountManagerBuilder {
                 self.coin_type
                     .ok_or(crate::Error::MissingParameter("coin_type (IOTA: 4218, Shimmer: 4219)"))?,
             )),
+            #[cfg(feature = "mqtt")]
+            output_notifications_status: Arc::new(AtomicUsize::new(0)),
             secret_manager: self
                 .secret_manager
                 .ok_or(crate::Error::MissingParameter("secret_manager"))?,
+            request_limiter,
             #[cfg(feature = "events")]
             event_emitter,
             #[cfg(feature = "storage")]
@@ -243,6 +285,7 @@ impl AccountManagerBuilder {
             client_options: Some(account_manager.client_options.read().await.clone()),
             coin_type: Some(account_manager.coin_type.load(Ordering::Relaxed)),
             storage_options: Some(account_manager.storage_options.clone()),
+            sync_request_options: Some(account_manager.request_limiter.options),
             secret_manager: Some(account_manager.secret_manager.clone()),
         }
     }
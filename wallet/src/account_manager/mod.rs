@@ -4,9 +4,14 @@
 pub(crate) mod builder;
 pub(crate) mod operations;
 
-use std::sync::{
-    atomic::{AtomicU32, AtomicUsize, Ordering},
-    Arc,
+#[cfg(feature = "stronghold")]
+use std::sync::atomic::AtomicU64;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 use iota_client::{secret::SecretManager, Client};
@@ -28,23 +33,45 @@ use crate::{
     account::{
         builder::AccountBuilder,
         handle::AccountHandle,
-        operations::{balance::add_balances, syncing::SyncOptions},
+        operations::{
+            balance::add_balances,
+            syncing::{request_limiter::RequestLimiter, SyncOptions},
+        },
         types::AccountBalance,
     },
     ClientOptions,
 };
 
+pub use self::operations::background_syncing::{AccountBackgroundSyncOptions, AccountBackgroundSyncStatus};
+#[cfg(feature = "stronghold")]
+pub use self::operations::stronghold_backup::{
+    restore_options::{MergedAccount, RestoreOptions, RestoreReport},
+    verify::{BackupAccountSummary, BackupSummary},
+};
+
 /// The account manager, used to create and get accounts. One account manager can hold many accounts, but they should
 /// all share the same secret_manager type with the same seed/mnemonic.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AccountManager {
     // should we use a hashmap instead of a vec like in wallet.rs?
     pub(crate) accounts: Arc<RwLock<Vec<AccountHandle>>>,
     // 0 = not running, 1 = running, 2 = stopping
+    #[cfg(feature = "stronghold")]
+    pub(crate) auto_backup_status: Arc<AtomicUsize>,
+    // counter of the new accounts, addresses and transactions, a new automatic backup is created when it changes
+    #[cfg(feature = "stronghold")]
+    pub(crate) backup_changes: Arc<AtomicU64>,
+    // 0 = not running, 1 = running, 2 = stopping
     pub(crate) background_syncing_status: Arc<AtomicUsize>,
+    pub(crate) background_syncing_options: Arc<RwLock<HashMap<u32, AccountBackgroundSyncOptions>>>,
+    pub(crate) background_syncing_statuses: Arc<RwLock<HashMap<u32, AccountBackgroundSyncStatus>>>,
     pub(crate) client_options: Arc<RwLock<ClientOptions>>,
     pub(crate) coin_type: Arc<AtomicU32>,
+    // 0 = not running, 1 = running, 2 = stopping
+    #[cfg(feature = "mqtt")]
+    pub(crate) output_notifications_status: Arc<AtomicUsize>,
     pub(crate) secret_manager: Arc<RwLock<SecretManager>>,
+    pub(crate) request_limiter: RequestLimiter,
     #[cfg(feature = "events")]
     pub(crate) event_emitter: Arc<Mutex<EventEmitter>>,
     #[cfg(feature = "storage")]
@@ -67,6 +94,9 @@ impl AccountManager {
             self.client_options.clone(),
             self.coin_type.load(Ordering::Relaxed),
             self.secret_manager.clone(),
+            self.request_limiter.clone(),
+            #[cfg(feature = "stronghold")]
+            self.backup_changes.clone(),
             #[cfg(feature = "events")]
             self.event_emitter.clone(),
             #[cfg(feature = "storage")]
@@ -100,6 +130,8 @@ impl AccountManager {
                 if let Some(account) = accounts.get(i) {
                     if *account.read().await.index() == largest_account_index {
                         let _ = accounts.remove(i);
+                        #[cfg(feature = "stronghold")]
+                        self.backup_changes.fetch_add(1, Ordering::Relaxed);
 
                         #[cfg(feature = "storage")]
                         self.storage_manager
@@ -0,0 +1,148 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::Ordering,
+    time::Duration,
+};
+
+use instant::{Instant, SystemTime};
+use tokio::time::sleep;
+use zeroize::Zeroizing;
+
+#[cfg(feature = "events")]
+use crate::events::types::{AutoBackupEvent, WalletEvent};
+use crate::{account_manager::AccountManager, task::spawn_background_process};
+
+/// The default number of backups that are kept
+pub(crate) const DEFAULT_AUTO_BACKUP_KEEP: usize = 5;
+// Interval in which the accounts are checked for changes that require a new backup
+const AUTO_BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const AUTO_BACKUP_FILE_PREFIX: &str = "wallet-backup-";
+const AUTO_BACKUP_FILE_EXTENSION: &str = ".stronghold";
+
+impl AccountManager {
+    /// Start creating backups automatically in the `directory`, after accounts, addresses or transactions were added
+    /// and additionally in the `interval`, if provided. Only the latest `keep` backups are kept, by default 5.
+    /// Backups are created with [`AccountManager::backup()`], so the stronghold_password must be the current one when
+    /// Stronghold is used as SecretManager. With the `events` feature, a [`WalletEvent::AutoBackup`] is emitted with
+    /// account index 0 after every backup.
+    pub async fn start_auto_backup(
+        &self,
+        directory: PathBuf,
+        stronghold_password: String,
+        keep: Option<usize>,
+        interval: Option<Duration>,
+    ) -> crate::Result<()> {
+        log::debug!("[start_auto_backup]");
+        std::fs::create_dir_all(&directory)?;
+
+        // stop existing process if running
+        self.stop_auto_backup().await?;
+
+        self.auto_backup_status.store(1, Ordering::Relaxed);
+        let account_manager = self.clone();
+        let keep = keep.unwrap_or(DEFAULT_AUTO_BACKUP_KEEP).max(1);
+        // kept until the process stops, zeroized when dropped
+        let stronghold_password = Zeroizing::new(stronghold_password);
+        spawn_background_process(false, move || async move {
+            let mut last_changes = account_manager.backup_changes.load(Ordering::Relaxed);
+            let mut last_backup = Instant::now();
+            'outer: loop {
+                // split the check interval to seconds so stopping the process doesn't have to wait long
+                for _ in 0..AUTO_BACKUP_CHECK_INTERVAL.as_secs() {
+                    if account_manager.auto_backup_status.load(Ordering::Relaxed) == 2 {
+                        break 'outer;
+                    }
+                    sleep(Duration::from_secs(1)).await;
+                }
+
+                let changes = account_manager.backup_changes.load(Ordering::Relaxed);
+                let interval_elapsed = interval.map_or(false, |interval| last_backup.elapsed() >= interval);
+                if changes == last_changes && !interval_elapsed {
+                    continue;
+                }
+
+                log::debug!("[auto_backup]: creating backup");
+                let result = account_manager
+                    .auto_backup(&directory, stronghold_password.to_string(), keep)
+                    .await;
+                last_changes = changes;
+                last_backup = Instant::now();
+
+                #[cfg(feature = "events")]
+                let event = match &result {
+                    Ok(path) => AutoBackupEvent::Created(path.to_string_lossy().into()),
+                    Err(err) => AutoBackupEvent::Failed(err.to_string()),
+                };
+                if let Err(err) = result {
+                    log::debug!("[auto_backup] error: {}", err);
+                }
+                #[cfg(feature = "events")]
+                account_manager
+                    .event_emitter
+                    .lock()
+                    .await
+                    .emit(0, WalletEvent::AutoBackup(event));
+            }
+            drop(stronghold_password);
+            account_manager.auto_backup_status.store(0, Ordering::Relaxed);
+            log::debug!("[auto_backup]: stopped");
+        })
+        .map_err(|err| {
+            self.auto_backup_status.store(0, Ordering::Relaxed);
+            err
+        })
+    }
+
+    /// Stop creating backups automatically
+    pub async fn stop_auto_backup(&self) -> crate::Result<()> {
+        log::debug!("[stop_auto_backup]");
+        // immediately return if not running
+        if self.auto_backup_status.load(Ordering::Relaxed) == 0 {
+            return Ok(());
+        }
+        // send stop request
+        self.auto_backup_status.store(2, Ordering::Relaxed);
+        // wait until it stopped
+        while self.auto_backup_status.load(Ordering::Relaxed) != 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        Ok(())
+    }
+
+    // Creates a backup with a timestamped name in the directory and removes the oldest ones, so only `keep` are left.
+    async fn auto_backup(&self, directory: &Path, stronghold_password: String, keep: usize) -> crate::Result<PathBuf> {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_millis();
+        let backup_path = directory.join(format!(
+            "{AUTO_BACKUP_FILE_PREFIX}{timestamp}{AUTO_BACKUP_FILE_EXTENSION}"
+        ));
+        self.backup(backup_path.clone(), stronghold_password).await?;
+
+        let mut backups = Vec::new();
+        for entry in std::fs::read_dir(directory)? {
+            let path = entry?.path();
+            let backup_timestamp = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(AUTO_BACKUP_FILE_PREFIX))
+                .and_then(|name| name.strip_suffix(AUTO_BACKUP_FILE_EXTENSION))
+                .and_then(|timestamp| timestamp.parse::<u128>().ok());
+            if let Some(backup_timestamp) = backup_timestamp {
+                backups.push((backup_timestamp, path));
+            }
+        }
+        // newest first
+        backups.sort_by(|a, b| b.0.cmp(&a.0));
+        for (_, path) in backups.into_iter().skip(keep) {
+            log::debug!("[auto_backup] removing old backup {}", path.display());
+            std::fs::remove_file(path)?;
+        }
+
+        Ok(backup_path)
+    }
+}
@@ -3,15 +3,55 @@
 
 use std::{sync::atomic::Ordering, time::Duration};
 
+use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 
-use crate::{account::operations::syncing::SyncOptions, account_manager::AccountManager};
+use crate::{
+    account::{
+        operations::{helpers::time::unix_timestamp_millis, syncing::SyncOptions},
+        types::AccountIdentifier,
+    },
+    account_manager::AccountManager,
+    task::spawn_background_process,
+};
 
 /// The default interval for background syncing
 pub(crate) const DEFAULT_BACKGROUNDSYNCING_INTERVAL: Duration = Duration::from_secs(7);
+/// The maximum interval for background syncing while an account has pending transactions
+pub(crate) const PENDING_TRANSACTIONS_BACKGROUNDSYNCING_INTERVAL: Duration = Duration::from_secs(2);
+/// The maximum delay after failed syncs, unless the interval of the account is longer
+pub(crate) const MAX_BACKGROUNDSYNCING_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Background syncing options for a single account, which replace the ones the background syncing was started with.
+#[derive(Debug, Clone, Default)]
+pub struct AccountBackgroundSyncOptions {
+    /// The sync options for the account.
+    pub sync_options: Option<SyncOptions>,
+    /// The interval in which the account is synced.
+    pub interval: Option<Duration>,
+}
+
+/// The background syncing status of an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBackgroundSyncStatus {
+    /// The account index.
+    pub account_index: u32,
+    /// The timestamp in milliseconds of the latest successful sync.
+    pub last_synced: Option<u128>,
+    /// The error of the latest sync, if it failed.
+    pub last_error: Option<String>,
+    /// The number of failed syncs since the latest successful one.
+    pub consecutive_errors: u32,
+    /// The timestamp in milliseconds after which the account is synced next.
+    pub next_sync: u128,
+}
 
 impl AccountManager {
-    /// Start the background syncing process for all accounts, default interval is 7 seconds
+    /// Start the background syncing process for all accounts, default interval is 7 seconds. Every account is synced
+    /// with its own schedule, the `options` and `interval` can be overwritten per account with
+    /// [`AccountManager::set_background_syncing_options()`]. Accounts with pending transactions are synced at least
+    /// every 2 seconds and after a failed sync the interval is doubled until it succeeds again, up to 5 minutes.
     pub async fn start_background_syncing(
         &self,
         options: Option<SyncOptions>,
@@ -29,47 +69,100 @@ impl AccountManager {
         }
 
         background_syncing_status.store(1, Ordering::Relaxed);
-        let accounts = self.accounts.clone();
-        let _background_syncing = std::thread::spawn(move || {
-            #[cfg(not(target_family = "wasm"))]
-            let runtime = tokio::runtime::Builder::new_multi_thread()
-                .enable_all()
-                .build()
-                .unwrap();
-            #[cfg(target_family = "wasm")]
-            let runtime = tokio::runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()
-                .unwrap();
-            runtime.block_on(async {
-                'outer: loop {
-                    log::debug!("[background_syncing]: syncing accounts");
-                    for account in accounts.read().await.iter() {
-                        // Check if the process should stop before syncing each account so it stops faster
-                        if background_syncing_status.load(Ordering::Relaxed) == 2 {
-                            log::debug!("[background_syncing]: stopping");
-                            break 'outer;
-                        }
-                        match account.sync(options.clone()).await {
-                            Ok(_) => {}
-                            Err(err) => log::debug!("[background_syncing] error: {}", err),
-                        };
+        self.background_syncing_statuses.write().await.clear();
+        let account_manager = self.clone();
+        spawn_background_process(true, move || async move {
+            'outer: loop {
+                let accounts = account_manager.accounts.read().await.clone();
+                for account in accounts.iter() {
+                    // Check if the process should stop before syncing each account so it stops faster
+                    if background_syncing_status.load(Ordering::Relaxed) == 2 {
+                        log::debug!("[background_syncing]: stopping");
+                        break 'outer;
+                    }
+                    let account_index = *account.read().await.index();
+                    let sync_due = account_manager
+                        .background_syncing_statuses
+                        .read()
+                        .await
+                        .get(&account_index)
+                        .map_or(true, |status| status.next_sync <= unix_timestamp_millis());
+                    if !sync_due {
+                        continue;
                     }
-                    // split interval syncing to seconds so stopping the process doesn't have to wait long
-                    let seconds = interval.unwrap_or(DEFAULT_BACKGROUNDSYNCING_INTERVAL).as_secs();
-                    for _ in 0..seconds {
-                        if background_syncing_status.load(Ordering::Relaxed) == 2 {
-                            log::debug!("[background_syncing]: stopping");
-                            break 'outer;
+
+                    let account_options = account_manager
+                        .background_syncing_options
+                        .read()
+                        .await
+                        .get(&account_index)
+                        .cloned()
+                        .unwrap_or_default();
+                    log::debug!("[background_syncing]: syncing account {account_index}");
+                    let result = account
+                        .sync(account_options.sync_options.or_else(|| options.clone()))
+                        .await;
+                    let pending_transactions = !account.read().await.pending_transactions().is_empty();
+
+                    let mut statuses = account_manager.background_syncing_statuses.write().await;
+                    let status = statuses
+                        .entry(account_index)
+                        .or_insert_with(|| AccountBackgroundSyncStatus {
+                            account_index,
+                            last_synced: None,
+                            last_error: None,
+                            consecutive_errors: 0,
+                            next_sync: 0,
+                        });
+                    match result {
+                        Ok(_) => {
+                            status.last_synced = Some(unix_timestamp_millis());
+                            status.last_error = None;
+                            status.consecutive_errors = 0;
+                        }
+                        Err(err) => {
+                            log::debug!("[background_syncing] error: {}", err);
+                            status.last_error = Some(err.to_string());
+                            status.consecutive_errors += 1;
                         }
-                        sleep(Duration::from_secs(1)).await;
                     }
+                    let delay = next_sync_delay(
+                        account_options
+                            .interval
+                            .or(interval)
+                            .unwrap_or(DEFAULT_BACKGROUNDSYNCING_INTERVAL),
+                        pending_transactions,
+                        status.consecutive_errors,
+                    );
+                    status.next_sync = unix_timestamp_millis() + delay.as_millis();
                 }
-                background_syncing_status.store(0, Ordering::Relaxed);
-                log::debug!("[background_syncing]: stopped");
-            });
-        });
-        Ok(())
+
+                // Remove the status of removed accounts
+                let mut account_indexes = Vec::new();
+                for account in accounts.iter() {
+                    account_indexes.push(*account.read().await.index());
+                }
+                account_manager
+                    .background_syncing_statuses
+                    .write()
+                    .await
+                    .retain(|account_index, _| account_indexes.contains(account_index));
+
+                // check every second which accounts need to be synced, so stopping the process doesn't have to
+                // wait long
+                if background_syncing_status.load(Ordering::Relaxed) == 2 {
+                    log::debug!("[background_syncing]: stopping");
+                    break 'outer;
+                }
+                sleep(Duration::from_secs(1)).await;
+            }
+            background_syncing_status.store(0, Ordering::Relaxed);
+            log::debug!("[background_syncing]: stopped");
+        })
+        .map_err(|err| {
+            self.background_syncing_status.store(0, Ordering::Relaxed);
+            err
+        })
     }
 
     /// Stop the background syncing of the accounts
@@ -90,4 +183,76 @@ impl AccountManager {
         }
         Ok(())
     }
+
+    /// Set the background syncing options for an account, which are used from its next sync on. Setting the default
+    /// options lets the account be synced with the options the background syncing was started with again.
+    pub async fn set_background_syncing_options<I: Into<AccountIdentifier> + Send>(
+        &self,
+        account_id: I,
+        options: AccountBackgroundSyncOptions,
+    ) -> crate::Result<()> {
+        let account_index = *self.get_account(account_id).await?.read().await.index();
+        log::debug!("[set_background_syncing_options] account {account_index}: {options:?}");
+        let mut background_syncing_options = self.background_syncing_options.write().await;
+        if options.sync_options.is_none() && options.interval.is_none() {
+            background_syncing_options.remove(&account_index);
+        } else {
+            background_syncing_options.insert(account_index, options);
+        }
+        Ok(())
+    }
+
+    /// Get the background syncing status of the accounts that were synced since the background syncing was started,
+    /// ordered by account index.
+    pub async fn get_background_syncing_status(&self) -> Vec<AccountBackgroundSyncStatus> {
+        let mut statuses = self
+            .background_syncing_statuses
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        statuses.sort_by_key(|status| status.account_index);
+        statuses
+    }
+}
+
+// The delay until the next sync of an account, shorter with pending transactions and increasing with failed syncs
+fn next_sync_delay(interval: Duration, pending_transactions: bool, consecutive_errors: u32) -> Duration {
+    let interval = if pending_transactions {
+        interval.min(PENDING_TRANSACTIONS_BACKGROUNDSYNCING_INTERVAL)
+    } else {
+        interval
+    };
+    if consecutive_errors == 0 {
+        return interval;
+    }
+    interval
+        .saturating_mul(2u32.saturating_pow(consecutive_errors))
+        .min(MAX_BACKGROUNDSYNCING_BACKOFF.max(interval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_delay() {
+        let interval = Duration::from_secs(10);
+        assert_eq!(next_sync_delay(interval, false, 0), interval);
+        assert_eq!(
+            next_sync_delay(interval, true, 0),
+            PENDING_TRANSACTIONS_BACKGROUNDSYNCING_INTERVAL
+        );
+        // Shorter intervals are kept with pending transactions
+        assert_eq!(next_sync_delay(Duration::from_secs(1), true, 0), Duration::from_secs(1));
+
+        // Exponential backoff on errors
+        assert_eq!(next_sync_delay(interval, false, 1), Duration::from_secs(20));
+        assert_eq!(next_sync_delay(interval, false, 3), Duration::from_secs(80));
+        assert_eq!(next_sync_delay(interval, false, 100), MAX_BACKGROUNDSYNCING_BACKOFF);
+        // Longer intervals aren't shortened by the maximum backoff
+        let interval = Duration::from_secs(3600);
+        assert_eq!(next_sync_delay(interval, false, 5), interval);
+    }
 }
@@ -0,0 +1,333 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Portable backup format, an alternative to Stronghold snapshots that can be inspected without Stronghold.
+//!
+//! A backup file is a JSON object:
+//!
+//! ```json
+//! {
+//!   "format": "iota-wallet-backup",
+//!   "version": 1,
+//!   "kdf": { "algorithm": "pbkdf2-hmac-sha512", "iterations": 100000, "salt": "0x..." },
+//!   "cipher": "xchacha20-poly1305",
+//!   "ciphertext": "0x...",
+//!   "checksum": "0x..."
+//! }
+//! ```
+//!
+//! The first 32 bytes of the PBKDF2 output are the encryption key. The ciphertext is the 24 byte nonce, followed by
+//! the encrypted data and the 16 byte tag. The checksum is the Blake2b-256 hash of the ciphertext bytes, so a file
+//! can be checked for corruption without the password.
+//!
+//! The decrypted data is a JSON object with the fields `clientOptions`, `coinType`, `accounts` and the optional
+//! `secretManager`.
+
+use std::{path::PathBuf, sync::atomic::Ordering};
+
+use crypto::{
+    ciphers::chacha,
+    hashes::{blake2b::Blake2b256, Digest},
+    keys::pbkdf::PBKDF2_HMAC_SHA512,
+    utils::rand,
+};
+use iota_client::secret::{SecretManager, SecretManagerDto};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::{account::Account, account_manager::AccountManager, ClientOptions};
+
+pub(crate) const BACKUP_FILE_FORMAT: &str = "iota-wallet-backup";
+pub(crate) const BACKUP_FILE_VERSION: u8 = 1;
+const KDF_ALGORITHM: &str = "pbkdf2-hmac-sha512";
+const KDF_ITERATIONS: usize = 100_000;
+// Fewer iterations make brute forcing the password cheap, more would make decrypting a crafted file take forever
+const KDF_MIN_ITERATIONS: usize = 10_000;
+const KDF_MAX_ITERATIONS: usize = 10_000_000;
+const KDF_SALT_LENGTH: usize = 32;
+const CIPHER: &str = "xchacha20-poly1305";
+
+/// The encrypted container that is written to the backup file.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackupFile {
+    pub(crate) format: String,
+    pub(crate) version: u8,
+    pub(crate) kdf: BackupFileKdf,
+    pub(crate) cipher: String,
+    pub(crate) ciphertext: String,
+    pub(crate) checksum: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackupFileKdf {
+    pub(crate) algorithm: String,
+    pub(crate) iterations: usize,
+    pub(crate) salt: String,
+}
+
+/// The data that is encrypted in a [`BackupFile`].
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackupData {
+    pub(crate) client_options: ClientOptions,
+    pub(crate) coin_type: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) secret_manager: Option<SecretManagerDto>,
+    pub(crate) accounts: Vec<Account>,
+}
+
+impl BackupFile {
+    /// Encrypts the backup data with a key derived from the password.
+    pub(crate) fn encrypt(data: &BackupData, password: &str) -> crate::Result<Self> {
+        let mut salt = [0u8; KDF_SALT_LENGTH];
+        rand::fill(&mut salt)?;
+
+        let mut key = derive_key(password, &salt, KDF_ITERATIONS)?;
+        let mut plaintext = serde_json::to_vec(data)?;
+        let ciphertext = chacha::aead_encrypt(&key, &plaintext);
+        key.zeroize();
+        plaintext.zeroize();
+        let ciphertext = ciphertext?;
+
+        Ok(Self {
+            format: BACKUP_FILE_FORMAT.to_string(),
+            version: BACKUP_FILE_VERSION,
+            kdf: BackupFileKdf {
+                algorithm: KDF_ALGORITHM.to_string(),
+                iterations: KDF_ITERATIONS,
+                salt: prefix_hex::encode(salt),
+            },
+            cipher: CIPHER.to_string(),
+            checksum: prefix_hex::encode(Blake2b256::digest(&ciphertext).to_vec()),
+            ciphertext: prefix_hex::encode(ciphertext),
+        })
+    }
+
+    /// Checks the format, version and checksum, without decrypting the data.
+    pub(crate) fn verify_checksum(&self) -> crate::Result<Vec<u8>> {
+        if self.format != BACKUP_FILE_FORMAT {
+            return Err(crate::Error::Backup("invalid backup file format"));
+        }
+        if self.version != BACKUP_FILE_VERSION {
+            return Err(crate::Error::Backup("invalid backup file version"));
+        }
+        if self.kdf.algorithm != KDF_ALGORITHM || self.cipher != CIPHER {
+            return Err(crate::Error::Backup("unsupported backup file algorithm"));
+        }
+        if !(KDF_MIN_ITERATIONS..=KDF_MAX_ITERATIONS).contains(&self.kdf.iterations) {
+            return Err(crate::Error::Backup("unsupported backup file kdf iterations"));
+        }
+
+        let ciphertext: Vec<u8> =
+            prefix_hex::decode(&self.ciphertext).map_err(|_| crate::Error::Backup("invalid ciphertext"))?;
+        let checksum: Vec<u8> =
+            prefix_hex::decode(&self.checksum).map_err(|_| crate::Error::Backup("invalid checksum"))?;
+        if Blake2b256::digest(&ciphertext).as_slice() != checksum.as_slice() {
+            return Err(crate::Error::Backup("checksum mismatch, the backup file is corrupted"));
+        }
+
+        Ok(ciphertext)
+    }
+
+    /// Verifies the checksum and decrypts the backup data.
+    pub(crate) fn decrypt(&self, password: &str) -> crate::Result<BackupData> {
+        let ciphertext = self.verify_checksum()?;
+        let salt: Vec<u8> = prefix_hex::decode(&self.kdf.salt).map_err(|_| crate::Error::Backup("invalid salt"))?;
+
+        let mut key = derive_key(password, &salt, self.kdf.iterations)?;
+        let plaintext = chacha::aead_decrypt(&key, &ciphertext);
+        key.zeroize();
+        let mut plaintext = plaintext.map_err(|_| crate::Error::Backup("invalid password"))?;
+
+        let data = serde_json::from_slice(&plaintext);
+        plaintext.zeroize();
+
+        Ok(data?)
+    }
+}
+
+fn derive_key(password: &str, salt: &[u8], iterations: usize) -> crate::Result<[u8; 32]> {
+    let mut buffer = [0u8; 64];
+    PBKDF2_HMAC_SHA512(password.as_bytes(), salt, iterations, &mut buffer)?;
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&buffer[..32]);
+    buffer.zeroize();
+
+    Ok(key)
+}
+
+impl AccountManager {
+    /// Backup the client options, coin type, accounts and optionally the secret manager configuration in a
+    /// password encrypted JSON file, which can be inspected without Stronghold. The data is encrypted with
+    /// XChaCha20-Poly1305, using a key derived with PBKDF2-HMAC-SHA512, and the file contains a Blake2b-256 checksum
+    /// of the ciphertext. In contrast to the Stronghold backup, no seed or mnemonic is stored, so a mnemonic secret
+    /// manager can't be included.
+    pub async fn backup_to_file(
+        &self,
+        backup_path: PathBuf,
+        mut password: String,
+        include_secret_manager: bool,
+    ) -> crate::Result<()> {
+        log::debug!("[backup_to_file] creating a backup file");
+
+        let secret_manager = if include_secret_manager {
+            match SecretManagerDto::from(&*self.secret_manager.read().await) {
+                SecretManagerDto::Mnemonic(_) => {
+                    password.zeroize();
+                    return Err(crate::Error::Backup(
+                        "a mnemonic secret manager can't be included in a backup file",
+                    ));
+                }
+                secret_manager_dto => Some(secret_manager_dto),
+            }
+        } else {
+            None
+        };
+
+        let mut accounts = Vec::new();
+        for account in self.accounts.read().await.iter() {
+            accounts.push(account.read().await.clone());
+        }
+
+        let data = BackupData {
+            client_options: self.client_options.read().await.clone(),
+            coin_type: self.coin_type.load(Ordering::Relaxed),
+            secret_manager,
+            accounts,
+        };
+
+        let backup_file = BackupFile::encrypt(&data, &password);
+        password.zeroize();
+
+        std::fs::write(backup_path, serde_json::to_string_pretty(&backup_file?)?)?;
+
+        Ok(())
+    }
+
+    /// Restore a backup from a file created with [`AccountManager::backup_to_file()`].
+    /// Replaces client_options, coin_type, accounts and the secret_manager, if it was included and isn't a
+    /// Stronghold, since the seed isn't part of the backup. Returns an error if accounts were already created.
+    /// if ignore_if_coin_type_mismatch.is_some(), client options will not be restored
+    /// if ignore_if_coin_type_mismatch == Some(true), client options coin type and accounts will not be restored if the
+    /// cointype doesn't match
+    pub async fn restore_from_file(
+        &self,
+        backup_path: PathBuf,
+        mut password: String,
+        ignore_if_coin_type_mismatch: Option<bool>,
+    ) -> crate::Result<()> {
+        log::debug!("[restore_from_file] loading backup file");
+
+        if !backup_path.is_file() {
+            return Err(crate::Error::Backup("backup path doesn't exist"));
+        }
+
+        let backup_file: BackupFile = serde_json::from_str(&std::fs::read_to_string(backup_path)?)?;
+        let data = backup_file.decrypt(&password);
+        password.zeroize();
+        let data = data?;
+
+        let mut accounts = self.accounts.write().await;
+        // We don't want to overwrite possible existing accounts
+        if !accounts.is_empty() {
+            return Err(crate::Error::Backup(
+                "can't restore backup when there are already accounts",
+            ));
+        }
+
+        // If the coin type is not matching the current one, then the addresses in the accounts will also not be
+        // correct, so we will not restore them
+        let ignore_backup_values = ignore_if_coin_type_mismatch == Some(true)
+            && self.coin_type.load(Ordering::Relaxed) != data.coin_type;
+
+        if ignore_if_coin_type_mismatch.is_none() {
+            *self.client_options.write().await = data.client_options;
+        }
+
+        let mut secret_manager = self.secret_manager.write().await;
+        match data.secret_manager {
+            #[cfg(feature = "stronghold")]
+            Some(SecretManagerDto::Stronghold(_)) => {}
+            Some(secret_manager_dto) => {
+                *secret_manager = SecretManager::try_from(&secret_manager_dto)
+                    .map_err(|_| crate::Error::Backup("invalid secret_manager"))?;
+            }
+            None => {}
+        }
+
+        if !ignore_backup_values {
+            self.coin_type.store(data.coin_type, Ordering::Relaxed);
+
+            let client = self.client_options.read().await.clone().finish()?;
+
+            *accounts = data
+                .accounts
+                .into_iter()
+                .map(|account| self.restored_account_handle(account, &client))
+                .collect();
+        }
+
+        // drop secret manager, otherwise we get a deadlock in save_restored_data
+        drop(secret_manager);
+
+        // store new data
+        #[cfg(feature = "storage")]
+        self.save_restored_data(&accounts).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backup_data() -> BackupData {
+        BackupData {
+            client_options: ClientOptions::new(),
+            coin_type: 4219,
+            secret_manager: None,
+            accounts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn encrypt_decrypt() {
+        let backup_file = BackupFile::encrypt(&backup_data(), "password").unwrap();
+        assert_eq!(backup_file.format, BACKUP_FILE_FORMAT);
+
+        let data = backup_file.decrypt("password").unwrap();
+        assert_eq!(data.coin_type, 4219);
+
+        assert!(matches!(
+            backup_file.decrypt("wrong password"),
+            Err(crate::Error::Backup("invalid password"))
+        ));
+    }
+
+    #[test]
+    fn corrupted_ciphertext() {
+        let mut backup_file = BackupFile::encrypt(&backup_data(), "password").unwrap();
+        let mut ciphertext: Vec<u8> = prefix_hex::decode(&backup_file.ciphertext).unwrap();
+        ciphertext[0] ^= 1;
+        backup_file.ciphertext = prefix_hex::encode(ciphertext);
+
+        assert!(backup_file.verify_checksum().is_err());
+    }
+
+    #[test]
+    fn kdf_iterations_bounds() {
+        let mut backup_file = BackupFile::encrypt(&backup_data(), "password").unwrap();
+
+        for iterations in [0, KDF_MIN_ITERATIONS - 1, KDF_MAX_ITERATIONS + 1, usize::MAX] {
+            backup_file.kdf.iterations = iterations;
+            assert!(matches!(
+                backup_file.decrypt("password"),
+                Err(crate::Error::Backup("unsupported backup file kdf iterations"))
+            ));
+        }
+    }
+}
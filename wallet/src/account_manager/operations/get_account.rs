@@ -33,4 +33,19 @@ impl AccountManager {
 
         Err(crate::Error::AccountNotFound(serde_json::to_string(&account_id)?))
     }
+
+    /// Get the accounts with an alias or metadata that contains the query, ignoring the case.
+    pub async fn search_accounts(&self, query: &str) -> Vec<AccountHandle> {
+        let query = query.to_lowercase();
+        let accounts = self.accounts.read().await;
+
+        let mut matching_accounts = Vec::new();
+        for account_handle in accounts.iter() {
+            let account = account_handle.read().await;
+            if account.alias().to_lowercase().contains(&query) || account.metadata().matches(&query) {
+                matching_accounts.push(account_handle.clone());
+            }
+        }
+        matching_accounts
+    }
 }
@@ -3,11 +3,17 @@
 
 pub(crate) mod account_recovery;
 pub(crate) mod address_generation;
+#[cfg(feature = "stronghold")]
+pub(crate) mod auto_backup;
 pub(crate) mod background_syncing;
+pub(crate) mod backup_file;
 pub(crate) mod client;
 pub(crate) mod get_account;
 #[cfg(feature = "ledger_nano")]
 pub(crate) mod ledger_nano;
+#[cfg(feature = "mqtt")]
+pub(crate) mod output_notifications;
+pub(crate) mod restore;
 #[cfg(feature = "stronghold")]
 pub(crate) mod stronghold;
 #[cfg(feature = "stronghold")]
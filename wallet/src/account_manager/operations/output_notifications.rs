@@ -0,0 +1,376 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::Ordering,
+    time::Duration,
+};
+
+use instant::Instant;
+use iota_client::{
+    block::{
+        input::Input,
+        payload::transaction::{TransactionEssence, TransactionId},
+    },
+    node_api::mqtt::{MqttEvent, Topic, TopicEvent},
+    Client,
+};
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver},
+    time::timeout,
+};
+
+use crate::{
+    account::{operations::syncing::SyncOptions, Account},
+    account_manager::{operations::background_syncing::DEFAULT_BACKGROUNDSYNCING_INTERVAL, AccountManager},
+    task::spawn_background_process,
+};
+
+// Interval in which the process checks if it should stop, subscribe to new topics or poll the accounts
+const OUTPUT_NOTIFICATIONS_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+// The account and its addresses that need to be synced when a notification for a topic is received
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NotificationTarget {
+    account_index: u32,
+    addresses: Vec<String>,
+}
+
+impl AccountManager {
+    /// Start syncing the accounts based on notifications from the node's MQTT event API. Topics for the outputs of all
+    /// account addresses and for the inclusion of pending transactions are subscribed and when a notification arrives,
+    /// only the affected addresses are synced with the provided `options`. While the broker connection is dropped, all
+    /// accounts are polled in the `fallback_interval`, by default 7 seconds, and after reconnecting they are synced
+    /// once, so missed notifications don't matter. The broker is configured with
+    /// [`ClientOptions::with_mqtt_broker_options()`](crate::ClientOptions), so a local broker can be used for testing.
+    pub async fn start_output_notifications(
+        &self,
+        options: Option<SyncOptions>,
+        fallback_interval: Option<Duration>,
+    ) -> crate::Result<()> {
+        log::debug!("[start_output_notifications]");
+        // stop existing process if running
+        self.stop_output_notifications().await?;
+
+        let client_options = self.client_options.read().await.clone();
+        self.output_notifications_status.store(1, Ordering::Relaxed);
+        let account_manager = self.clone();
+        let options = options.unwrap_or_default();
+        let fallback_interval = fallback_interval.unwrap_or(DEFAULT_BACKGROUNDSYNCING_INTERVAL);
+        spawn_background_process(true, move || async move {
+            // The client is created in this runtime, because the MQTT connection is handled by its tasks
+            match client_options.finish() {
+                Ok(client) => {
+                    account_manager
+                        .output_notifications(&client, &options, fallback_interval)
+                        .await
+                }
+                Err(err) => log::debug!("[output_notifications] error: {}", err),
+            }
+            account_manager.output_notifications_status.store(0, Ordering::Relaxed);
+            log::debug!("[output_notifications]: stopped");
+        })
+        .map_err(|err| {
+            self.output_notifications_status.store(0, Ordering::Relaxed);
+            err
+        })
+    }
+
+    /// Stop syncing the accounts based on output notifications
+    pub async fn stop_output_notifications(&self) -> crate::Result<()> {
+        log::debug!("[stop_output_notifications]");
+        // immediately return if not running
+        if self.output_notifications_status.load(Ordering::Relaxed) == 0 {
+            return Ok(());
+        }
+        // send stop request
+        self.output_notifications_status.store(2, Ordering::Relaxed);
+        // wait until it stopped
+        while self.output_notifications_status.load(Ordering::Relaxed) != 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        Ok(())
+    }
+
+    async fn output_notifications(&self, client: &Client, options: &SyncOptions, fallback_interval: Duration) {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let mut mqtt_event_receiver = client.mqtt_event_receiver();
+        let mut subscribed_targets = HashMap::new();
+        let mut was_connected = false;
+        let mut last_poll: Option<Instant> = None;
+
+        while self.output_notifications_status.load(Ordering::Relaxed) != 2 {
+            let targets = self.notification_targets().await;
+            let mut subscribed = self
+                .update_subscriptions(client, &subscribed_targets, &targets, &sender)
+                .await;
+            if subscribed {
+                subscribed_targets = targets;
+            }
+            let connected = subscribed && matches!(*mqtt_event_receiver.borrow_and_update(), MqttEvent::Connected);
+
+            // Poll all accounts while no notifications are received and once after (re)connecting, for the outputs
+            // that were created while not subscribed
+            let poll_due = last_poll.map_or(true, |last_poll| last_poll.elapsed() >= fallback_interval);
+            if (connected && !was_connected) || (!connected && poll_due) {
+                log::debug!("[output_notifications]: polling accounts, connected: {connected}");
+                for account in self.accounts.read().await.iter() {
+                    if self.output_notifications_status.load(Ordering::Relaxed) == 2 {
+                        break;
+                    }
+                    if let Err(err) = account.sync(Some(options.clone())).await {
+                        log::debug!("[output_notifications] error: {}", err);
+                    }
+                }
+                last_poll = Some(Instant::now());
+            }
+            if !connected {
+                // Resubscribe to all topics after the connection is back
+                subscribed_targets.clear();
+                subscribed = false;
+            }
+            was_connected = connected;
+
+            let notified_topics = next_notified_topics(&mut receiver).await;
+            if subscribed && !notified_topics.is_empty() {
+                self.sync_notified_addresses(&subscribed_targets, &notified_topics, options)
+                    .await;
+            }
+        }
+
+        let topics = topics(subscribed_targets.keys());
+        if !topics.is_empty() {
+            if let Err(err) = client.unsubscribe(topics).await {
+                log::debug!("[output_notifications] error: {}", err);
+            }
+        }
+    }
+
+    // The topics for the outputs of all addresses and the inclusion of pending transactions of all accounts
+    async fn notification_targets(&self) -> HashMap<String, NotificationTarget> {
+        let mut targets = HashMap::new();
+        for account_handle in self.accounts.read().await.iter() {
+            targets.extend(account_notification_targets(&*account_handle.read().await));
+        }
+        targets
+    }
+
+    // Subscribe to the new topics and unsubscribe from the old ones, returns false if the subscription failed
+    async fn update_subscriptions(
+        &self,
+        client: &Client,
+        subscribed_targets: &HashMap<String, NotificationTarget>,
+        targets: &HashMap<String, NotificationTarget>,
+        sender: &mpsc::UnboundedSender<String>,
+    ) -> bool {
+        let old_topics = topics(subscribed_targets.keys().filter(|topic| !targets.contains_key(*topic)));
+        if !old_topics.is_empty() {
+            if let Err(err) = client.unsubscribe(old_topics).await {
+                log::debug!("[output_notifications] error: {}", err);
+            }
+        }
+
+        let new_topics = topics(targets.keys().filter(|topic| !subscribed_targets.contains_key(*topic)));
+        if new_topics.is_empty() {
+            return true;
+        }
+        log::debug!("[output_notifications]: subscribing to {} topics", new_topics.len());
+        let sender = sender.clone();
+        match client
+            .subscribe(new_topics, move |event: &TopicEvent| {
+                // The receiver is only dropped when the process stopped
+                sender.send(event.topic.clone()).ok();
+            })
+            .await
+        {
+            Ok(()) => true,
+            Err(err) => {
+                log::debug!("[output_notifications] error: {}", err);
+                false
+            }
+        }
+    }
+
+    // Sync only the addresses that are affected by the notifications
+    async fn sync_notified_addresses(
+        &self,
+        targets: &HashMap<String, NotificationTarget>,
+        notified_topics: &HashSet<String>,
+        options: &SyncOptions,
+    ) {
+        for (account_index, addresses) in addresses_to_sync(targets, notified_topics) {
+            let account_handle = match self.get_account(account_index).await {
+                Ok(account_handle) => account_handle,
+                Err(err) => {
+                    log::debug!("[output_notifications] error: {}", err);
+                    continue;
+                }
+            };
+            log::debug!(
+                "[output_notifications]: syncing {} addresses of account {account_index}",
+                addresses.len()
+            );
+            let options = SyncOptions {
+                addresses: addresses.into_iter().collect(),
+                force_syncing: true,
+                address_gap_limit: None,
+                ..options.clone()
+            };
+            if let Err(err) = account_handle.sync(Some(options)).await {
+                log::debug!("[output_notifications] error: {}", err);
+            }
+        }
+    }
+}
+
+// Wait for the next notification or until the check interval elapsed and return all topics that got notified
+async fn next_notified_topics(receiver: &mut UnboundedReceiver<String>) -> HashSet<String> {
+    let mut notified_topics = HashSet::new();
+    if let Ok(Some(topic)) = timeout(OUTPUT_NOTIFICATIONS_CHECK_INTERVAL, receiver.recv()).await {
+        notified_topics.insert(topic);
+        while let Ok(topic) = receiver.try_recv() {
+            notified_topics.insert(topic);
+        }
+    }
+    notified_topics
+}
+
+fn topics<'a>(topics: impl Iterator<Item = &'a String>) -> Vec<Topic> {
+    topics
+        .filter_map(|topic| match Topic::try_from(topic.clone()) {
+            Ok(topic) => Some(topic),
+            Err(err) => {
+                log::debug!("[output_notifications] invalid topic {topic}: {}", err);
+                None
+            }
+        })
+        .collect()
+}
+
+fn address_topic(bech32_address: &str) -> String {
+    format!("outputs/unlock/address/{bech32_address}")
+}
+
+fn transaction_included_topic(transaction_id: &TransactionId) -> String {
+    format!("transactions/{transaction_id}/included-block")
+}
+
+fn account_notification_targets(account: &Account) -> HashMap<String, NotificationTarget> {
+    let mut targets = HashMap::new();
+    let addresses = account.public_addresses().iter().chain(account.internal_addresses());
+    for address in addresses.clone() {
+        let bech32_address = address.address().to_bech32();
+        targets.insert(
+            address_topic(&bech32_address),
+            NotificationTarget {
+                account_index: *account.index(),
+                addresses: vec![bech32_address],
+            },
+        );
+    }
+
+    // When a transaction gets included, the addresses of its inputs are synced, so they are marked as spent
+    for transaction_id in account.pending_transactions() {
+        if let Some(transaction) = account.transactions().get(transaction_id) {
+            let TransactionEssence::Regular(essence) = transaction.payload.essence();
+            let input_addresses = essence
+                .inputs()
+                .iter()
+                .filter_map(|input| match input {
+                    Input::Utxo(input) => account.outputs().get(input.output_id()),
+                    _ => None,
+                })
+                .filter_map(|output_data| {
+                    addresses
+                        .clone()
+                        .find(|address| address.address().inner == output_data.address)
+                })
+                .map(|address| address.address().to_bech32())
+                .collect::<HashSet<_>>();
+            targets.insert(
+                transaction_included_topic(transaction_id),
+                NotificationTarget {
+                    account_index: *account.index(),
+                    addresses: input_addresses.into_iter().collect(),
+                },
+            );
+        }
+    }
+    targets
+}
+
+// The addresses that need to be synced per account index for the notified topics
+fn addresses_to_sync(
+    targets: &HashMap<String, NotificationTarget>,
+    notified_topics: &HashSet<String>,
+) -> HashMap<u32, HashSet<String>> {
+    let mut addresses_to_sync: HashMap<u32, HashSet<String>> = HashMap::new();
+    for target in notified_topics.iter().filter_map(|topic| targets.get(topic)) {
+        if target.addresses.is_empty() {
+            continue;
+        }
+        addresses_to_sync
+            .entry(target.account_index)
+            .or_default()
+            .extend(target.addresses.iter().cloned());
+    }
+    addresses_to_sync
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "rms1qpllaj0pyveqfkwxmnngz2c488hfdtmfrj3wfkgxtk4gtyrax0jaxzt70zy";
+    const ADDRESS_2: &str = "rms1qzev36lk0gzld0k28fd2fauz26qqzh4hd4cwymlqlv96x7phjxcw6v3ea5a";
+
+    #[test]
+    fn topics_are_valid() {
+        let topics = [
+            address_topic(ADDRESS),
+            transaction_included_topic(&TransactionId::from([1; TransactionId::LENGTH])),
+        ];
+        for topic in topics {
+            assert!(Topic::try_from(topic).is_ok());
+        }
+    }
+
+    #[test]
+    fn notified_addresses_to_sync() {
+        let targets = HashMap::from([
+            (
+                address_topic(ADDRESS),
+                NotificationTarget {
+                    account_index: 0,
+                    addresses: vec![ADDRESS.to_string()],
+                },
+            ),
+            (
+                address_topic(ADDRESS_2),
+                NotificationTarget {
+                    account_index: 1,
+                    addresses: vec![ADDRESS_2.to_string()],
+                },
+            ),
+            (
+                transaction_included_topic(&TransactionId::from([1; TransactionId::LENGTH])),
+                NotificationTarget {
+                    account_index: 0,
+                    addresses: vec![ADDRESS.to_string()],
+                },
+            ),
+        ]);
+
+        let notified_topics = HashSet::from([
+            address_topic(ADDRESS),
+            transaction_included_topic(&TransactionId::from([1; TransactionId::LENGTH])),
+            // Unknown topics are ignored
+            address_topic("rms1qp5kej7w3e0zuve8qfpwk0t4vcsqc3wv0lp3uqpmqe5ywmse6uzs67d3qsg"),
+        ]);
+        let addresses_to_sync = addresses_to_sync(&targets, &notified_topics);
+
+        assert_eq!(addresses_to_sync.len(), 1);
+        assert_eq!(addresses_to_sync[&0], HashSet::from([ADDRESS.to_string()]));
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shared steps of the Stronghold and backup file restores.
+
+use iota_client::Client;
+
+#[cfg(feature = "storage")]
+use crate::account_manager::AccountManagerBuilder;
+use crate::{
+    account::Account,
+    account_manager::{AccountHandle, AccountManager},
+};
+
+impl AccountManager {
+    /// Create the handle of an account from a backup, sharing the secret manager and limits of the account manager.
+    pub(crate) fn restored_account_handle(&self, account: Account, client: &Client) -> AccountHandle {
+        AccountHandle::new(
+            account,
+            client.clone(),
+            self.secret_manager.clone(),
+            self.request_limiter.clone(),
+            #[cfg(feature = "stronghold")]
+            self.backup_changes.clone(),
+            #[cfg(feature = "events")]
+            self.event_emitter.clone(),
+            #[cfg(feature = "storage")]
+            self.storage_manager.clone(),
+        )
+    }
+
+    /// Store the account manager data and the accounts after a restore. The secret manager must not be locked,
+    /// otherwise storing the account manager data deadlocks.
+    #[cfg(feature = "storage")]
+    pub(crate) async fn save_restored_data(&self, accounts: &[AccountHandle]) -> crate::Result<()> {
+        let account_manager_builder = AccountManagerBuilder::from_account_manager(self).await;
+        self.storage_manager
+            .lock()
+            .await
+            .save_account_manager_data(&account_manager_builder)
+            .await?;
+        // also save account to db
+        for account in accounts {
+            account.save(None).await?;
+        }
+
+        Ok(())
+    }
+}
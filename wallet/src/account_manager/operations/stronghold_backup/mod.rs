@@ -1,7 +1,9 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod restore_options;
 mod stronghold_snapshot;
+pub(crate) mod verify;
 
 use std::{fs, path::PathBuf, sync::atomic::Ordering};
 
@@ -9,9 +11,7 @@ use iota_client::secret::{stronghold::StrongholdSecretManager, SecretManager, Se
 use zeroize::Zeroize;
 
 use self::stronghold_snapshot::{read_data_from_stronghold_snapshot, store_data_to_stronghold};
-#[cfg(feature = "storage")]
-use crate::account_manager::AccountManagerBuilder;
-use crate::account_manager::{AccountHandle, AccountManager};
+use crate::account_manager::AccountManager;
 
 impl AccountManager {
     /// Backup the account manager data in a Stronghold file
@@ -145,50 +145,19 @@ impl AccountManager {
             if let Some(read_accounts) = read_accounts {
                 let client = self.client_options.read().await.clone().finish()?;
 
-                let mut restored_account_handles = Vec::new();
-                for account in read_accounts {
-                    restored_account_handles.push(AccountHandle::new(
-                        account,
-                        client.clone(),
-                        self.secret_manager.clone(),
-                        #[cfg(feature = "events")]
-                        self.event_emitter.clone(),
-                        #[cfg(feature = "storage")]
-                        self.storage_manager.clone(),
-                    ))
-                }
-                *accounts = restored_account_handles;
+                *accounts = read_accounts
+                    .into_iter()
+                    .map(|account| self.restored_account_handle(account, &client))
+                    .collect();
             }
         }
 
+        // drop secret manager, otherwise we get a deadlock in save_restored_data
+        drop(secret_manager);
+
         // store new data
         #[cfg(feature = "storage")]
-        {
-            let account_manager_builder = AccountManagerBuilder::new()
-                .with_secret_manager_arc(self.secret_manager.clone())
-                .with_storage_path(
-                    &self
-                        .storage_options
-                        .storage_path
-                        .clone()
-                        .into_os_string()
-                        .into_string()
-                        .expect("can't convert os string"),
-                )
-                .with_client_options(self.client_options.read().await.clone())
-                .with_coin_type(self.coin_type.load(Ordering::Relaxed));
-            // drop secret manager, otherwise we get a deadlock in save_account_manager_data
-            drop(secret_manager);
-            self.storage_manager
-                .lock()
-                .await
-                .save_account_manager_data(&account_manager_builder)
-                .await?;
-            // also save account to db
-            for account in accounts.iter() {
-                account.save(None).await?;
-            }
-        }
+        self.save_restored_data(&accounts).await?;
 
         Ok(())
     }
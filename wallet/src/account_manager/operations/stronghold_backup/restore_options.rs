@@ -0,0 +1,323 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fs, path::PathBuf, sync::atomic::Ordering};
+
+use iota_client::secret::{stronghold::StrongholdSecretManager, SecretManage, SecretManager, SecretManagerDto};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use super::stronghold_snapshot::read_data_from_stronghold_snapshot;
+use crate::{
+    account::{types::address::AccountAddress, Account},
+    account_manager::AccountManager,
+};
+
+/// Options to select the parts of a backup that are restored with
+/// [`AccountManager::restore_backup_with_options()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RestoreOptions {
+    /// Restore the client options.
+    pub client_options: bool,
+    /// Restore the coin type.
+    pub coin_type: bool,
+    /// Restore the secret manager, ignored when merging accounts, because the accounts need to derive from the current
+    /// one.
+    pub secret_manager: bool,
+    /// Restore the accounts.
+    pub accounts: bool,
+    /// Merge the accounts from the backup with the existing ones by index, instead of failing if there are already
+    /// accounts.
+    pub merge_accounts: bool,
+}
+
+impl Default for RestoreOptions {
+    fn default() -> Self {
+        Self {
+            client_options: true,
+            coin_type: true,
+            secret_manager: true,
+            accounts: true,
+            merge_accounts: false,
+        }
+    }
+}
+
+/// The changes made by [`AccountManager::restore_backup_with_options()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreReport {
+    /// Whether the client options were replaced.
+    pub client_options: bool,
+    /// The restored coin type, if it was restored.
+    pub coin_type: Option<u32>,
+    /// Whether the secret manager was replaced.
+    pub secret_manager: bool,
+    /// The indexes of the accounts that were added.
+    pub added_accounts: Vec<u32>,
+    /// The accounts that already existed and were merged with the ones from the backup.
+    pub merged_accounts: Vec<MergedAccount>,
+}
+
+/// An existing account that was merged with the account with the same index from a backup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergedAccount {
+    /// The account index.
+    pub index: u32,
+    /// The alias before the restore.
+    pub previous_alias: String,
+    /// The alias from the backup.
+    pub alias: String,
+    /// The number of addresses from the backup that didn't exist in the account.
+    pub added_addresses: usize,
+}
+
+impl AccountManager {
+    /// Restore selected parts of a backup from a Stronghold file, see [`RestoreOptions`].
+    /// In contrast to [`AccountManager::restore_backup()`], every address of the accounts from the backup is derived
+    /// again with the secret manager that is used after the restore, and the restore fails if one doesn't match. The
+    /// same applies to existing accounts if the secret manager or coin type is replaced without restoring accounts. The
+    /// accounts can be merged with the existing ones by index, merged accounts get the alias and missing addresses
+    /// from the backup.
+    /// Returns a report of what was changed.
+    pub async fn restore_backup_with_options(
+        &self,
+        backup_path: PathBuf,
+        mut stronghold_password: String,
+        options: RestoreOptions,
+    ) -> crate::Result<RestoreReport> {
+        log::debug!("[restore_backup_with_options] loading stronghold backup with {options:?}");
+
+        if !backup_path.is_file() {
+            return Err(crate::Error::Backup("backup path doesn't exist"));
+        }
+
+        let mut accounts = self.accounts.write().await;
+        // Without merging, we don't want to overwrite possible existing accounts
+        if options.accounts && !options.merge_accounts && !accounts.is_empty() {
+            return Err(crate::Error::Backup(
+                "can't restore backup when there are already accounts",
+            ));
+        }
+
+        // We'll create a new stronghold to load the backup
+        let mut new_stronghold = StrongholdSecretManager::builder()
+            .password(&stronghold_password)
+            .build(backup_path.clone())?;
+
+        let (read_client_options, read_coin_type, read_secret_manager, read_accounts) =
+            read_data_from_stronghold_snapshot(&mut new_stronghold).await?;
+
+        let read_accounts = if options.accounts {
+            read_accounts.unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        // Validate everything before changing anything
+        let current_coin_type = self.coin_type.load(Ordering::Relaxed);
+        let coin_type = match read_coin_type {
+            Some(read_coin_type) if options.coin_type => read_coin_type,
+            _ => current_coin_type,
+        };
+        let restores_secret_manager =
+            options.secret_manager && !options.merge_accounts && read_secret_manager.is_some();
+
+        // Accounts that already exist are kept, so they have to use the coin type and derive from the secret manager
+        // that are used after the restore
+        let mut existing_accounts = Vec::new();
+        if coin_type != current_coin_type || restores_secret_manager {
+            for account_handle in accounts.iter() {
+                existing_accounts.push(account_handle.read().await.clone());
+            }
+        }
+        if read_accounts
+            .iter()
+            .chain(&existing_accounts)
+            .any(|account| *account.coin_type() != coin_type)
+        {
+            return Err(crate::Error::Backup("coin type of the accounts doesn't match"));
+        }
+
+        if !read_accounts.is_empty() || !existing_accounts.is_empty() {
+            // The backup Stronghold contains the seed, the snapshot path of the stored secret manager isn't valid here
+            let backup_secret_manager = match &read_secret_manager {
+                Some(SecretManagerDto::Stronghold(_)) if restores_secret_manager => {
+                    Some(SecretManager::Stronghold(new_stronghold))
+                }
+                Some(read_secret_manager) if restores_secret_manager => Some(
+                    SecretManager::try_from(read_secret_manager)
+                        .map_err(|_| crate::Error::Backup("invalid secret_manager"))?,
+                ),
+                _ => None,
+            };
+            let accounts_to_verify = read_accounts.iter().chain(&existing_accounts);
+            match &backup_secret_manager {
+                Some(secret_manager) => verify_account_addresses(secret_manager, accounts_to_verify).await?,
+                None => verify_account_addresses(&*self.secret_manager.read().await, accounts_to_verify).await?,
+            }
+        }
+
+        if options.merge_accounts && !read_accounts.is_empty() {
+            if coin_type != current_coin_type {
+                return Err(crate::Error::Backup("can't merge accounts with a different coin type"));
+            }
+
+            for read_account in &read_accounts {
+                for account_handle in accounts.iter() {
+                    let account = account_handle.read().await;
+                    if account.index() != read_account.index() && account.alias() == read_account.alias() {
+                        return Err(crate::Error::AccountAliasAlreadyExists(read_account.alias().clone()));
+                    }
+                }
+            }
+        }
+
+        let mut report = RestoreReport::default();
+
+        if options.client_options {
+            if let Some(read_client_options) = read_client_options {
+                *self.client_options.write().await = read_client_options;
+                report.client_options = true;
+            }
+        }
+
+        if options.coin_type {
+            if let Some(read_coin_type) = read_coin_type {
+                self.coin_type.store(read_coin_type, Ordering::Relaxed);
+                report.coin_type = Some(read_coin_type);
+            }
+        }
+
+        let mut secret_manager = self.secret_manager.as_ref().write().await;
+        if restores_secret_manager {
+            if let Some(mut read_secret_manager) = read_secret_manager {
+                // We have to replace the snapshot path with the current one, when building stronghold
+                let new_snapshot_path = if let SecretManager::Stronghold(stronghold) = &mut *secret_manager {
+                    stronghold.snapshot_path.clone()
+                } else {
+                    PathBuf::from("wallet.stronghold")
+                };
+                if let SecretManagerDto::Stronghold(stronghold_dto) = &mut read_secret_manager {
+                    stronghold_dto.snapshot_path = new_snapshot_path.clone().into_os_string().to_string_lossy().into();
+                }
+
+                let mut restored_secret_manager = SecretManager::try_from(&read_secret_manager)
+                    .map_err(|_| crate::Error::Backup("invalid secret_manager"))?;
+
+                if let SecretManager::Stronghold(stronghold) = &mut restored_secret_manager {
+                    // Copy Stronghold file so the seed is available in the new location
+                    fs::copy(&backup_path, new_snapshot_path)?;
+
+                    // Set password to restored secret manager
+                    stronghold.set_password(&stronghold_password).await?;
+                }
+                *secret_manager = restored_secret_manager;
+                report.secret_manager = true;
+            }
+        }
+        // Release the lock, merging accounts generates addresses with the secret manager
+        drop(secret_manager);
+
+        stronghold_password.zeroize();
+
+        if !read_accounts.is_empty() {
+            let client = self.client_options.read().await.clone().finish()?;
+
+            for read_account in read_accounts {
+                let mut existing_account_handle = None;
+                for account_handle in accounts.iter() {
+                    if account_handle.read().await.index() == read_account.index() {
+                        existing_account_handle.replace(account_handle.clone());
+                        break;
+                    }
+                }
+
+                match existing_account_handle {
+                    Some(account_handle) => {
+                        let (previous_alias, public_addresses, internal_addresses) = {
+                            let account = account_handle.read().await;
+                            (
+                                account.alias().clone(),
+                                missing_addresses(&account.public_addresses, &read_account.public_addresses),
+                                missing_addresses(&account.internal_addresses, &read_account.internal_addresses),
+                            )
+                        };
+                        let added_addresses = public_addresses.len() + internal_addresses.len();
+
+                        if &previous_alias != read_account.alias() {
+                            account_handle.set_alias(read_account.alias()).await?;
+                        }
+                        if !public_addresses.is_empty() {
+                            account_handle.update_account_addresses(false, public_addresses).await?;
+                        }
+                        if !internal_addresses.is_empty() {
+                            account_handle
+                                .update_account_addresses(true, internal_addresses)
+                                .await?;
+                        }
+
+                        report.merged_accounts.push(MergedAccount {
+                            index: *read_account.index(),
+                            previous_alias,
+                            alias: read_account.alias().clone(),
+                            added_addresses,
+                        });
+                    }
+                    None => {
+                        report.added_accounts.push(*read_account.index());
+                        accounts.push(self.restored_account_handle(read_account, &client));
+                    }
+                }
+            }
+        }
+
+        // store new data
+        #[cfg(feature = "storage")]
+        self.save_restored_data(&accounts).await?;
+
+        Ok(report)
+    }
+}
+
+// Derives every address of the accounts again and fails if one doesn't match, so addresses from another seed are never
+// imported and existing accounts are never left with a seed that can't sign for them.
+async fn verify_account_addresses<'a>(
+    secret_manager: &SecretManager,
+    accounts: impl IntoIterator<Item = &'a Account>,
+) -> crate::Result<()> {
+    for account in accounts {
+        for (addresses, internal) in [(&account.public_addresses, false), (&account.internal_addresses, true)] {
+            for address in addresses.iter() {
+                let derived_address = secret_manager
+                    .generate_addresses(
+                        *account.coin_type(),
+                        *account.index(),
+                        address.key_index..address.key_index + 1,
+                        internal,
+                        None,
+                    )
+                    .await?;
+
+                if derived_address.first() != Some(&address.address.inner) {
+                    return Err(crate::Error::Backup(
+                        "accounts don't derive from the secret manager of the restore",
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Addresses from the backup with a key index that doesn't exist yet.
+fn missing_addresses(existing: &[AccountAddress], restored: &[AccountAddress]) -> Vec<AccountAddress> {
+    restored
+        .iter()
+        .filter(|address| !existing.iter().any(|existing| existing.key_index == address.key_index))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,153 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use iota_client::{
+    secret::{stronghold::StrongholdSecretManager, SecretManage, SecretManagerDto},
+    storage::StorageProvider,
+};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use super::stronghold_snapshot::{read_data_from_stronghold_snapshot, BACKUP_SCHEMA_VERSION, BACKUP_SCHEMA_VERSION_KEY};
+use crate::{
+    account::{types::address::AccountAddress, Account},
+    account_manager::AccountManager,
+};
+
+// The number of public and internal addresses per account that are derived again to verify the seed.
+const VERIFY_ADDRESS_AMOUNT: usize = 5;
+
+/// A summary of a verified backup, returned by [`AccountManager::verify_backup()`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSummary {
+    /// The backup schema version.
+    pub schema_version: u8,
+    /// Whether client options are stored.
+    pub client_options: bool,
+    /// The stored coin type.
+    pub coin_type: Option<u32>,
+    /// Whether a secret manager is stored.
+    pub secret_manager: bool,
+    /// Whether the backup contains the seed, which was used to verify the addresses of the accounts.
+    pub seed: bool,
+    /// The stored accounts.
+    pub accounts: Vec<BackupAccountSummary>,
+}
+
+/// A summary of an account in a verified backup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupAccountSummary {
+    /// The account index.
+    pub index: u32,
+    /// The account alias.
+    pub alias: String,
+    /// The number of public addresses.
+    pub public_addresses: usize,
+    /// The number of internal addresses.
+    pub internal_addresses: usize,
+    /// The number of outputs.
+    pub outputs: usize,
+    /// The number of transactions.
+    pub transactions: usize,
+    /// The number of addresses that were derived from the seed and matched the stored ones.
+    pub verified_addresses: usize,
+}
+
+impl AccountManager {
+    /// Verify that a backup from a Stronghold file can be restored, without changing the account manager.
+    /// Opens the backup with the password, checks the schema version and deserializes all data. If the backup contains
+    /// the seed, the first addresses of every account are derived again and compared with the stored ones.
+    /// Returns an error if any check fails, otherwise a summary of the backup.
+    pub async fn verify_backup(backup_path: PathBuf, mut stronghold_password: String) -> crate::Result<BackupSummary> {
+        log::debug!("[verify_backup] verifying stronghold backup");
+
+        if !backup_path.is_file() {
+            return Err(crate::Error::Backup("backup path doesn't exist"));
+        }
+
+        let backup_stronghold = StrongholdSecretManager::builder()
+            .password(&stronghold_password)
+            .build(backup_path);
+        stronghold_password.zeroize();
+        let mut backup_stronghold = backup_stronghold?;
+
+        let schema_version = match backup_stronghold.get(BACKUP_SCHEMA_VERSION_KEY.as_bytes()).await? {
+            Some(version) if version.first() == Some(&BACKUP_SCHEMA_VERSION) => BACKUP_SCHEMA_VERSION,
+            Some(_) => return Err(crate::Error::Backup("invalid backup_schema_version")),
+            None => return Err(crate::Error::Backup("missing backup_schema_version")),
+        };
+
+        let (client_options, coin_type, secret_manager, accounts) =
+            read_data_from_stronghold_snapshot(&mut backup_stronghold).await?;
+        let accounts = accounts.unwrap_or_default();
+
+        // The seed is only stored if the Stronghold secret manager was backed up
+        let seed = matches!(secret_manager, Some(SecretManagerDto::Stronghold(_)));
+
+        let mut account_summaries = Vec::new();
+        for account in &accounts {
+            if coin_type.map_or(false, |coin_type| coin_type != *account.coin_type()) {
+                return Err(crate::Error::Backup("coin type of the accounts doesn't match"));
+            }
+
+            let verified_addresses = if seed {
+                verify_addresses(&backup_stronghold, account, &account.public_addresses, false).await?
+                    + verify_addresses(&backup_stronghold, account, &account.internal_addresses, true).await?
+            } else {
+                0
+            };
+
+            account_summaries.push(BackupAccountSummary {
+                index: *account.index(),
+                alias: account.alias().clone(),
+                public_addresses: account.public_addresses.len(),
+                internal_addresses: account.internal_addresses.len(),
+                outputs: account.outputs().len(),
+                transactions: account.transactions().len(),
+                verified_addresses,
+            });
+        }
+
+        Ok(BackupSummary {
+            schema_version,
+            client_options: client_options.is_some(),
+            coin_type,
+            secret_manager: secret_manager.is_some(),
+            seed,
+            accounts: account_summaries,
+        })
+    }
+}
+
+// Derives the first addresses again and compares them with the stored ones, returns the number of verified addresses.
+async fn verify_addresses(
+    stronghold: &StrongholdSecretManager,
+    account: &Account,
+    addresses: &[AccountAddress],
+    internal: bool,
+) -> crate::Result<usize> {
+    let mut verified_addresses = 0;
+
+    for address in addresses.iter().take(VERIFY_ADDRESS_AMOUNT) {
+        let derived_address = stronghold
+            .generate_addresses(
+                *account.coin_type(),
+                *account.index(),
+                address.key_index..address.key_index + 1,
+                internal,
+                None,
+            )
+            .await?;
+
+        if derived_address.first() != Some(&address.address.inner) {
+            return Err(crate::Error::Backup("stored address doesn't match the one derived from the seed"));
+        }
+        verified_addresses += 1;
+    }
+
+    Ok(verified_addresses)
+}
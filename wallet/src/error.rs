@@ -3,7 +3,10 @@
 
 use std::fmt::Debug;
 
-use iota_client::block::payload::transaction::TransactionId;
+use iota_client::block::{
+    output::{OutputId, TokenId},
+    payload::transaction::TransactionId,
+};
 use serde::{
     ser::{SerializeMap, Serializer},
     Serialize,
@@ -21,9 +24,17 @@ pub enum Error {
     /// Address not found in account
     #[error("address {0} not found in account")]
     AddressNotFoundInAccount(String),
+    /// Alias not found in unspent outputs
+    #[error("alias not found in unspent outputs")]
+    AliasNotFoundInUnspentOutputs,
     /// Errors during backup creation or restoring
     #[error("backup failed {0}")]
     Backup(&'static str),
+    /// Errors during the execution of a message batch
+    #[cfg(feature = "message_interface")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "message_interface")))]
+    #[error("batch failed: {0}")]
+    Batch(String),
     /// Error from block crate.
     #[error("{0}")]
     Block(Box<iota_client::block::Error>),
@@ -48,18 +59,44 @@ pub enum Error {
     /// Failed to get remainder
     #[error("failed to get remainder address")]
     FailedToGetRemainder,
+    /// The foundry of a native token isn't controlled by the account.
+    #[error("foundry of native token {0} isn't controlled by the account")]
+    FoundryNotControlled(TokenId),
+    /// The foundry of a native token couldn't be found.
+    #[error("foundry of native token {0} not found")]
+    FoundryNotFound(TokenId),
     /// Insufficient funds to send transaction.
     #[error("insufficient funds {available}/{required} available")]
     InsufficientFunds { available: u64, required: u64 },
+    /// The message interface protocol version of a message isn't supported
+    #[cfg(feature = "message_interface")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "message_interface")))]
+    #[error(
+        "incompatible message interface protocol version {requested}, supported versions: {min_supported}-{current}"
+    )]
+    IncompatibleProtocolVersion {
+        requested: u32,
+        min_supported: u32,
+        current: u32,
+    },
     /// Invalid coin type, all accounts need to have the same coin type
     #[error("invalid coin type for new account: {new_coin_type}, existing coin type is: {existing_coin_type}")]
     InvalidCoinType {
         new_coin_type: u32,
         existing_coin_type: u32,
     },
+    /// Invalid IRC27 NFT metadata
+    #[error("invalid irc27 metadata: {0}")]
+    InvalidIrc27Metadata(String),
+    /// Invalid native token decimals
+    #[error("invalid native token decimals: {0}, the maximum is 77")]
+    InvalidNativeTokenDecimals(u32),
     /// Invalid mnemonic error
     #[error("invalid mnemonic: {0}")]
     InvalidMnemonic(String),
+    /// Invalid payment URI
+    #[error("invalid payment uri: {0}")]
+    InvalidPaymentUri(String),
     /// Invalid output kind.
     #[error("invalid output kind: {0}")]
     InvalidOutputKind(String),
@@ -72,6 +109,15 @@ pub enum Error {
     /// Minting failed
     #[error("minting failed {0}")]
     MintingFailed(String),
+    /// Errors during encrypting or decrypting memos
+    #[error("messaging error: {0}")]
+    Messaging(&'static str),
+    /// No messaging key is known for an address
+    #[error("no messaging key known for address {0}")]
+    MessagingKeyNotFound(String),
+    /// The messaging key of the account can't be derived with the secret manager
+    #[error("the messaging key of the account isn't available with this secret manager")]
+    MessagingKeyUnavailable,
     /// Missing parameter.
     #[error("missing parameter: {0}")]
     MissingParameter(&'static str),
@@ -99,9 +145,21 @@ pub enum Error {
         /// The consolidation threshold.
         consolidation_threshold: usize,
     },
+    /// Output not found in account
+    #[error("output {0} not found in account")]
+    OutputNotFound(OutputId),
+    /// Payment request not found in account
+    #[error("payment request {0} not found")]
+    PaymentRequestNotFound(u32),
+    /// Another open payment request has the same tag
+    #[error("an open payment request with tag {0} already exists")]
+    PaymentRequestTagInUse(String),
     /// Storage access error.
     #[error("error accessing storage: {0}")]
     Storage(String),
+    /// Syncing was cancelled with the cancellation token of the sync options
+    #[error("syncing was cancelled")]
+    SyncCancelled,
     /// Can't use AccountManager API because the storage is encrypted
     #[error(
         "can't perform operation while storage is encrypted; use AccountManager::set_storage_password to decrypt storage"
@@ -113,6 +171,16 @@ pub enum Error {
     /// Transaction not found
     #[error("transaction {0} not found")]
     TransactionNotFound(TransactionId),
+    /// Unknown account method in the message interface
+    #[cfg(feature = "message_interface")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "message_interface")))]
+    #[error("unknown account method: {0}")]
+    UnknownAccountMethod(String),
+    /// Unknown message in the message interface
+    #[cfg(feature = "message_interface")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "message_interface")))]
+    #[error("unknown message: {0}")]
+    UnknownMessage(String),
 }
 
 // Serialize type with Display error
@@ -39,8 +39,12 @@ impl EventEmitter {
                 WalletEventType::TransactionInclusion,
                 WalletEventType::TransactionProgress,
                 WalletEventType::ConsolidationRequired,
+                WalletEventType::PaymentRequest,
+                WalletEventType::SyncProgress,
                 #[cfg(feature = "ledger_nano")]
                 WalletEventType::LedgerAddressGeneration,
+                #[cfg(feature = "stronghold")]
+                WalletEventType::AutoBackup,
             ] {
                 let event_handlers = self.handlers.entry(*event_type).or_insert_with(Vec::new);
                 event_handlers.push(Box::new(handler.clone()));
@@ -73,8 +77,12 @@ impl EventEmitter {
             WalletEvent::TransactionInclusion(_) => WalletEventType::TransactionInclusion,
             WalletEvent::TransactionProgress(_) => WalletEventType::TransactionProgress,
             WalletEvent::ConsolidationRequired => WalletEventType::ConsolidationRequired,
+            WalletEvent::PaymentRequest(_) => WalletEventType::PaymentRequest,
+            WalletEvent::SyncProgress(_) => WalletEventType::SyncProgress,
             #[cfg(feature = "ledger_nano")]
             WalletEvent::LedgerAddressGeneration(_) => WalletEventType::LedgerAddressGeneration,
+            #[cfg(feature = "stronghold")]
+            WalletEvent::AutoBackup(_) => WalletEventType::AutoBackup,
         };
         let event = Event { account_index, event };
         if let Some(handlers) = self.handlers.get(&event_type) {
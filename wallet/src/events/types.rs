@@ -9,7 +9,7 @@ use iota_client::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::account::types::{address::AddressWrapper, InclusionState, OutputDataDto};
+use crate::account::types::{address::AddressWrapper, InclusionState, OutputDataDto, PaymentRequestDto};
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Event {
     /// Associated account index.
@@ -21,24 +21,35 @@ pub struct Event {
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum WalletEvent {
+    #[cfg(feature = "stronghold")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
+    AutoBackup(AutoBackupEvent),
     ConsolidationRequired,
     #[cfg(feature = "ledger_nano")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
     LedgerAddressGeneration(AddressData),
     NewOutput(Box<NewOutputEvent>),
+    /// The status of a payment request changed.
+    PaymentRequest(Box<PaymentRequestDto>),
     SpentOutput(Box<SpentOutputEvent>),
+    SyncProgress(SyncProgressEvent),
     TransactionInclusion(TransactionInclusionEvent),
     TransactionProgress(TransactionProgressEvent),
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum WalletEventType {
+    #[cfg(feature = "stronghold")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
+    AutoBackup,
     ConsolidationRequired,
     #[cfg(feature = "ledger_nano")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ledger_nano")))]
     LedgerAddressGeneration,
     NewOutput,
+    PaymentRequest,
     SpentOutput,
+    SyncProgress,
     TransactionInclusion,
     TransactionProgress,
 }
@@ -48,11 +59,15 @@ impl TryFrom<&str> for WalletEventType {
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let event_type = match value {
+            #[cfg(feature = "stronghold")]
+            "AutoBackup" => Self::AutoBackup,
             "ConsolidationRequired" => Self::ConsolidationRequired,
             #[cfg(feature = "ledger_nano")]
             "LedgerAddressGeneration" => Self::LedgerAddressGeneration,
             "NewOutput" => Self::NewOutput,
+            "PaymentRequest" => Self::PaymentRequest,
             "SpentOutput" => Self::SpentOutput,
+            "SyncProgress" => Self::SyncProgress,
             "TransactionInclusion" => Self::TransactionInclusion,
             "TransactionProgress" => Self::TransactionProgress,
             _ => return Err(format!("invalid event type {value}")),
@@ -61,6 +76,17 @@ impl TryFrom<&str> for WalletEventType {
     }
 }
 
+/// The result of an automatic backup.
+#[cfg(feature = "stronghold")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AutoBackupEvent {
+    /// The backup was created at the path.
+    Created(String),
+    /// Creating the backup failed with the error.
+    Failed(String),
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct NewOutputEvent {
     /// The new output.
@@ -79,6 +105,60 @@ pub struct SpentOutputEvent {
     pub output: OutputDataDto,
 }
 
+/// The progress of syncing or recovering an account.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SyncProgressEvent {
+    /// Searching for used addresses with the address gap limit.
+    DiscoveringAddresses,
+    /// Searching for outputs on new addresses during account recovery.
+    SearchingAddresses {
+        /// The number of public addresses that are searched.
+        #[serde(rename = "addressGapLimit")]
+        address_gap_limit: u32,
+        /// The number of internal addresses that are searched.
+        #[serde(rename = "addressGapLimitInternal")]
+        address_gap_limit_internal: u32,
+    },
+    /// Requesting the output ids of the addresses.
+    RequestingOutputIds {
+        /// The number of addresses with requested output ids.
+        processed: usize,
+        /// The number of addresses to sync.
+        total: usize,
+    },
+    /// Requesting the outputs of the addresses.
+    RequestingOutputs {
+        /// The number of addresses with requested outputs.
+        processed: usize,
+        /// The number of addresses with outputs.
+        total: usize,
+        /// The number of fetched outputs.
+        outputs: usize,
+    },
+    /// Requesting the outputs of alias and nft addresses.
+    RequestingAliasAndNftOutputs {
+        /// The number of alias and nft addresses.
+        addresses: usize,
+    },
+    /// Requesting the metadata of outputs that might be spent.
+    CheckingSpentOutputs {
+        /// The number of outputs to check.
+        outputs: usize,
+    },
+    /// Requesting the transactions of received outputs.
+    SyncingIncomingTransactions,
+    /// Requesting the foundries of native tokens.
+    SyncingNativeTokenFoundries,
+    /// Updating the account with the synced data.
+    UpdatingAccount,
+    /// Checking pending transactions.
+    SyncingPendingTransactions,
+    /// Syncing finished.
+    Finished,
+    /// Syncing was cancelled, the account wasn't updated with the partially synced data.
+    Cancelled,
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct TransactionInclusionEvent {
     #[serde(rename = "transactionId")]
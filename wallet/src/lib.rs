@@ -46,7 +46,12 @@ pub use primitive_types::U256;
 pub use self::{
     account::operations::transaction::high_level::{
         minting::{
-            increase_native_token_supply::IncreaseNativeTokenSupplyOptions, mint_native_token::NativeTokenOptions,
+            increase_native_token_supply::IncreaseNativeTokenSupplyOptions,
+            mint_native_token::NativeTokenOptions,
+            mint_nft_collection::{
+                NftCollectionMinting, NftCollectionMintingDto, NftCollectionMintingOptions, NftCollectionTransaction,
+                NftCollectionTransactionDto,
+            },
             mint_nfts::NftOptions,
         },
         send_amount::AddressWithAmount,
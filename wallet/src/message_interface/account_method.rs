@@ -12,6 +12,7 @@ use iota_client::{
             FoundryId, OutputId,
         },
         payload::transaction::TransactionId,
+        BlockId,
     },
 };
 #[cfg(feature = "participation")]
@@ -35,13 +36,16 @@ use crate::{
                     create_alias::AliasOutputOptionsDto,
                     minting::{
                         increase_native_token_supply::IncreaseNativeTokenSupplyOptionsDto,
-                        mint_native_token::NativeTokenOptionsDto, mint_nfts::NftOptionsDto,
+                        mint_native_token::NativeTokenOptionsDto, mint_nft_collection::NftCollectionMintingDto,
+                        mint_nfts::NftOptionsDto,
                     },
+                    update_alias::AliasGovernanceUpdateDto,
                 },
                 prepare_output::OutputOptionsDto,
                 TransactionOptionsDto,
             },
         },
+        types::{AccountMetadata, NativeTokenMetadataOverrides, PaymentRequestOptionsDto},
     },
     message_interface::dtos::{AddressWithAmountDto, AddressWithMicroAmountDto},
     AddressAndNftId, AddressNativeTokens,
@@ -164,6 +168,32 @@ pub enum AccountMethod {
         alias_id: AliasIdDto,
         options: Option<TransactionOptionsDto>,
     },
+    /// Update the state metadata of an alias with a state transition.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    UpdateAliasState {
+        #[serde(rename = "aliasId")]
+        alias_id: AliasIdDto,
+        /// Hex encoded bytes
+        #[serde(rename = "stateMetadata")]
+        state_metadata: String,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Change the state controller, governor or metadata of an alias with a governance transition.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    UpdateAliasGovernance {
+        #[serde(rename = "aliasId")]
+        alias_id: AliasIdDto,
+        update: AliasGovernanceUpdateDto,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Transfer an alias to another address, which becomes its state controller and governor.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    TransferAlias {
+        #[serde(rename = "aliasId")]
+        alias_id: AliasIdDto,
+        address: String,
+        options: Option<TransactionOptionsDto>,
+    },
     /// Function to destroy a foundry output with a circulating supply of 0.
     /// Native tokens in the foundry (minted by other foundries) will be transacted to the controlling alias
     /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
@@ -249,6 +279,16 @@ pub enum AccountMethod {
         melt_amount: U256Dto,
         options: Option<TransactionOptionsDto>,
     },
+    /// Replace the mutable metadata feature of a foundry controlled by the account, empty metadata removes it.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    UpdateFoundryMetadata {
+        /// Native token id
+        #[serde(rename = "tokenId")]
+        token_id: TokenIdDto,
+        /// Hex encoded metadata
+        metadata: String,
+        options: Option<TransactionOptionsDto>,
+    },
     /// Calculate the minimum required storage deposit for an output.
     /// Expected response:
     /// [`MinimumRequiredStorageDeposit`](crate::message_interface::Response::MinimumRequiredStorageDeposit)
@@ -280,6 +320,29 @@ pub enum AccountMethod {
         nfts_options: Vec<NftOptionsDto>,
         options: Option<TransactionOptionsDto>,
     },
+    /// Mint any number of NFTs with the issuer NFT of a collection, split into multiple transactions.
+    /// Expected response: [`NftCollectionMinting`](crate::message_interface::Response::NftCollectionMinting)
+    MintNftCollection {
+        #[serde(rename = "issuerNftId")]
+        issuer_nft_id: NftIdDto,
+        #[serde(rename = "nftsOptions")]
+        nfts_options: Vec<NftOptionsDto>,
+        #[serde(rename = "maxNftsPerTransaction")]
+        max_nfts_per_transaction: Option<usize>,
+        #[serde(rename = "maxPendingTransactions")]
+        max_pending_transactions: Option<usize>,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Continue minting an NFT collection that failed partway.
+    /// Expected response: [`NftCollectionMinting`](crate::message_interface::Response::NftCollectionMinting)
+    ResumeNftCollectionMinting {
+        minting: NftCollectionMintingDto,
+        #[serde(rename = "maxNftsPerTransaction")]
+        max_nfts_per_transaction: Option<usize>,
+        #[serde(rename = "maxPendingTransactions")]
+        max_pending_transactions: Option<usize>,
+        options: Option<TransactionOptionsDto>,
+    },
     /// Get account balance information.
     /// Expected response: [`Balance`](crate::message_interface::Response::Balance)
     GetBalance,
@@ -323,6 +386,9 @@ pub enum AccountMethod {
         /// Sync options
         options: Option<SyncOptions>,
     },
+    /// Cancel the running sync of the account, it's only stopped before the account gets updated.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    CancelSync,
     /// Send amount.
     /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
     SendAmount {
@@ -354,6 +420,22 @@ pub enum AccountMethod {
     /// Set the alias of the account.
     /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
     SetAlias { alias: String },
+    /// Set the label of an address, `None` removes it.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetAddressLabel { address: String, label: Option<String> },
+    /// Set the label of an output, `None` removes it.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetOutputLabel {
+        #[serde(rename = "outputId")]
+        output_id: OutputId,
+        label: Option<String>,
+    },
+    /// Set the user defined metadata of the account.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetAccountMetadata { metadata: AccountMetadata },
+    /// Search the labels of addresses and outputs, ignoring the case.
+    /// Expected response: [`LabelSearchResult`](crate::message_interface::Response::LabelSearchResult)
+    SearchLabels { query: String },
     /// Send outputs in a transaction.
     /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
     SendOutputs {
@@ -469,4 +551,98 @@ pub enum AccountMethod {
     GetParticipationEvents,
     /// Expected response: [`Faucet`](crate::message_interface::Response::Faucet)
     RequestFundsFromFaucet { url: String, address: String },
+    /// Create a payment request.
+    /// Expected response: [`PaymentRequest`](crate::message_interface::Response::PaymentRequest)
+    CreatePaymentRequest { options: PaymentRequestOptionsDto },
+    /// Get a payment request.
+    /// Expected response: [`PaymentRequest`](crate::message_interface::Response::PaymentRequest)
+    GetPaymentRequest { id: u32 },
+    /// Get all payment requests.
+    /// Expected response: [`PaymentRequests`](crate::message_interface::Response::PaymentRequests)
+    GetPaymentRequests,
+    /// Remove a payment request.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    CancelPaymentRequest { id: u32 },
+    /// Get the balance at a unix timestamp in seconds.
+    /// Expected response: [`BalanceSnapshot`](crate::message_interface::Response::BalanceSnapshot)
+    GetBalanceAt { timestamp: u32 },
+    /// Get the balance after every change between two unix timestamps in seconds.
+    /// Expected response: [`BalanceHistory`](crate::message_interface::Response::BalanceHistory)
+    GetBalanceHistory { start: Option<u32>, end: Option<u32> },
+    /// Get the metadata of all native tokens known to the account.
+    /// Expected response: [`NativeTokensMetadata`](crate::message_interface::Response::NativeTokensMetadata)
+    GetNativeTokensMetadata,
+    /// Set values that replace the ones of the IRC30 metadata of a native token.
+    /// Expected response: [`NativeTokenMetadata`](crate::message_interface::Response::NativeTokenMetadata)
+    SetNativeTokenMetadataOverrides {
+        #[serde(rename = "tokenId")]
+        token_id: TokenIdDto,
+        overrides: NativeTokenMetadataOverrides,
+    },
+    /// Mark a native token as trusted or untrusted.
+    /// Expected response: [`NativeTokenMetadata`](crate::message_interface::Response::NativeTokenMetadata)
+    SetNativeTokenTrusted {
+        #[serde(rename = "tokenId")]
+        token_id: TokenIdDto,
+        trusted: bool,
+    },
+    /// Get the unspent NFTs of the account with their decoded metadata.
+    /// Expected response: [`Nfts`](crate::message_interface::Response::Nfts)
+    GetNfts,
+    /// Get the unspent NFTs of the account grouped by their issuer.
+    /// Expected response: [`NftCollections`](crate::message_interface::Response::NftCollections)
+    GetNftCollections,
+    /// Get the current supply of a native token.
+    /// Expected response: [`NativeTokenSupply`](crate::message_interface::Response::NativeTokenSupply)
+    GetNativeTokenSupply {
+        #[serde(rename = "tokenId")]
+        token_id: TokenIdDto,
+    },
+    /// Get the supply of all native tokens known to the account.
+    /// Expected response: [`NativeTokenSupplies`](crate::message_interface::Response::NativeTokenSupplies)
+    GetNativeTokenSupplies,
+    /// Get a page of the addresses that hold a native token with a foundry controlled by the account, without cursor
+    /// the first one. An address with outputs in several pages is a holder in each of them with its amount there.
+    /// Expected response: [`NativeTokenHolders`](crate::message_interface::Response::NativeTokenHolders)
+    GetNativeTokenHolders {
+        #[serde(rename = "tokenId")]
+        token_id: TokenIdDto,
+        cursor: Option<String>,
+    },
+    /// Get the messaging key of the account, derived in the secret manager from the key of a dedicated chain.
+    /// Expected response: [`MessagingKey`](crate::message_interface::Response::MessagingKey)
+    GetMessagingKey,
+    /// Get the messaging key of an address from the memos it sent in the transactions of the account.
+    /// Expected response: [`AddressMessagingKey`](crate::message_interface::Response::AddressMessagingKey)
+    GetAddressMessagingKey { address: String },
+    /// Encrypt a memo to a messaging key, the payload can be set as `taggedDataPayload` of the transaction options.
+    /// Expected response: [`MemoPayload`](crate::message_interface::Response::MemoPayload)
+    PrepareMemo {
+        #[serde(rename = "messagingKey")]
+        messaging_key: String,
+        memo: String,
+    },
+    /// Send an encrypted memo to an address, with an output of the minimum storage deposit.
+    /// Expected response: [`SentTransaction`](crate::message_interface::Response::SentTransaction)
+    SendMemo {
+        address: String,
+        memo: String,
+        /// The messaging key of the recipient, taken from the memos in its transactions if not provided
+        #[serde(rename = "messagingKey", default)]
+        messaging_key: Option<String>,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Send an encrypted memo in a block without a transaction.
+    /// Expected response: [`BlockId`](crate::message_interface::Response::BlockId)
+    SendMemoBlock {
+        #[serde(rename = "messagingKey")]
+        messaging_key: String,
+        memo: String,
+    },
+    /// Read an encrypted memo from a block.
+    /// Expected response: [`Memo`](crate::message_interface::Response::Memo)
+    ReadMemoBlock {
+        #[serde(rename = "blockId")]
+        block_id: BlockId,
+    },
 }
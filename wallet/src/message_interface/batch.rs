@@ -0,0 +1,162 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use iota_client::{
+    api::PreparedTransactionData,
+    block::{input::Input, output::OutputId, payload::transaction::TransactionEssence},
+};
+use serde_json::Value;
+
+use crate::{
+    account::types::AccountIdentifier, account_manager::AccountManager, message_interface::Response, Error, Result,
+};
+
+/// The key of an object that references the response of an earlier step in a batch.
+const REFERENCE_KEY: &str = "$ref";
+
+/// Replaces all `{"$ref": "<index>/<json pointer>"}` objects in `message` with the referenced value from the
+/// serialized responses of the earlier steps.
+pub(crate) fn resolve_references(message: &mut Value, responses: &[Value]) -> Result<()> {
+    match message {
+        Value::Object(object) => {
+            let resolved = match object.get(REFERENCE_KEY) {
+                Some(Value::String(reference)) if object.len() == 1 => Some(resolve_reference(reference, responses)?),
+                _ => None,
+            };
+            if let Some(resolved) = resolved {
+                *message = resolved;
+                return Ok(());
+            }
+            for value in object.values_mut() {
+                resolve_references(value, responses)?;
+            }
+        }
+        Value::Array(array) => {
+            for value in array.iter_mut() {
+                resolve_references(value, responses)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn resolve_reference(reference: &str, responses: &[Value]) -> Result<Value> {
+    let (index, pointer) = reference.split_at(reference.find('/').unwrap_or(reference.len()));
+    let response = index
+        .parse::<usize>()
+        .ok()
+        .and_then(|index| responses.get(index))
+        .ok_or_else(|| Error::Batch(format!("invalid reference {reference}: no earlier step with this index")))?;
+
+    if matches!(response.get("type").and_then(Value::as_str), Some("error" | "panic")) {
+        return Err(Error::Batch(format!("invalid reference {reference}: the step failed")));
+    }
+
+    response
+        .pointer(pointer)
+        .cloned()
+        .ok_or_else(|| Error::Batch(format!("invalid reference {reference}: value not found")))
+}
+
+/// The inputs of transactions that were prepared in a batch. An atomic batch only unlocks these, so outputs that
+/// other operations locked while the batch was running stay locked.
+#[derive(Default)]
+pub(crate) struct PreparedInputs {
+    accounts: Vec<(AccountIdentifier, Vec<OutputId>)>,
+}
+
+impl PreparedInputs {
+    /// Records the inputs of a transaction that an account method of the batch prepared.
+    pub(crate) fn record(&mut self, account_id: &AccountIdentifier, response: &Response) -> Result<()> {
+        if let Response::PreparedTransaction(prepared_transaction) = response {
+            let prepared_transaction = PreparedTransactionData::try_from_dto_unverified(prepared_transaction)?;
+            self.accounts.push((
+                account_id.clone(),
+                prepared_transaction
+                    .inputs_data
+                    .iter()
+                    .map(|input| *input.output_metadata.output_id())
+                    .collect(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Unlocks the recorded inputs that are still locked, except the inputs of submitted transactions.
+    pub(crate) async fn unlock(&self, account_manager: &AccountManager) -> Result<()> {
+        for (account_id, inputs) in &self.accounts {
+            let account_handle = account_manager.get_account(account_id.clone()).await?;
+            let account = account_handle.read().await;
+
+            let submitted_inputs = account
+                .transactions()
+                .values()
+                .flat_map(|transaction| {
+                    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+                    essence.inputs().iter().filter_map(|input| match input {
+                        Input::Utxo(input) => Some(*input.output_id()),
+                        _ => None,
+                    })
+                })
+                .collect::<HashSet<_>>();
+            let outputs_to_unlock: Vec<OutputId> = inputs
+                .iter()
+                .filter(|output_id| {
+                    account.locked_outputs().contains(output_id) && !submitted_inputs.contains(output_id)
+                })
+                .copied()
+                .collect();
+            drop(account);
+
+            if !outputs_to_unlock.is_empty() {
+                account_handle.unlock_outputs(&outputs_to_unlock).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::resolve_references;
+
+    #[test]
+    fn resolve_batch_references() {
+        let responses = vec![
+            json!({ "type": "generatedAddress", "payload": [{ "address": "rms1qp" }] }),
+            json!({ "type": "error", "payload": { "type": "client", "error": "failed" } }),
+        ];
+
+        let mut message = json!({
+            "cmd": "callAccountMethod",
+            "payload": {
+                "accountId": 0,
+                "method": {
+                    "name": "sendAmount",
+                    "data": { "addressesWithAmount": [{ "address": { "$ref": "0/payload/0/address" }, "amount": "1" }] }
+                }
+            }
+        });
+        resolve_references(&mut message, &responses).unwrap();
+        assert_eq!(
+            message["payload"]["method"]["data"]["addressesWithAmount"][0]["address"],
+            "rms1qp"
+        );
+
+        // Reference to a failed step
+        let mut message = json!({ "$ref": "1/payload" });
+        assert!(resolve_references(&mut message, &responses).is_err());
+        // Reference to a step that wasn't executed yet
+        let mut message = json!({ "$ref": "2" });
+        assert!(resolve_references(&mut message, &responses).is_err());
+        // Whole response
+        let mut message = json!({ "$ref": "0" });
+        resolve_references(&mut message, &responses).unwrap();
+        assert_eq!(message, responses[0]);
+    }
+}
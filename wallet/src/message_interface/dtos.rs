@@ -16,7 +16,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     account::{
-        types::{address::AddressWrapper, AccountAddress, AddressWithUnspentOutputs, TransactionDto},
+        types::{address::AddressWrapper, AccountAddress, AccountMetadata, AddressWithUnspentOutputs, TransactionDto},
         Account, OutputDataDto,
     },
     AddressWithAmount, AddressWithMicroAmount,
@@ -138,6 +138,15 @@ pub struct AccountDto {
     /// Foundries for native tokens in outputs
     #[serde(rename = "nativeTokenFoundries", default)]
     pub native_token_foundries: HashMap<FoundryId, FoundryOutputDto>,
+    /// User defined metadata
+    #[serde(default)]
+    pub metadata: AccountMetadata,
+    /// The highest public address index with outputs found by the address discovery during syncing
+    #[serde(rename = "highestUsedPublicAddressIndex", default)]
+    pub highest_used_public_address_index: Option<u32>,
+    /// The highest internal address index with outputs found by the address discovery during syncing
+    #[serde(rename = "highestUsedInternalAddressIndex", default)]
+    pub highest_used_internal_address_index: Option<u32>,
 }
 
 impl From<&Account> for AccountDto {
@@ -180,6 +189,9 @@ impl From<&Account> for AccountDto {
                 .iter()
                 .map(|(id, foundry)| (*id, FoundryOutputDto::from(foundry)))
                 .collect(),
+            metadata: value.metadata().clone(),
+            highest_used_public_address_index: *value.highest_used_public_address_index(),
+            highest_used_internal_address_index: *value.highest_used_internal_address_index(),
         }
     }
 }
@@ -1,13 +1,16 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::fmt::{Debug, Formatter, Result};
-#[cfg(feature = "stronghold")]
-use std::path::PathBuf;
+use std::{
+    fmt::{Debug, Formatter, Result},
+    path::PathBuf,
+};
 
 use serde::{Deserialize, Serialize};
 
 use super::account_method::AccountMethod;
+#[cfg(feature = "stronghold")]
+use crate::account_manager::RestoreOptions;
 #[cfg(feature = "events")]
 use crate::events::types::{WalletEvent, WalletEventType};
 use crate::{
@@ -42,6 +45,9 @@ pub enum Message {
     /// Read accounts.
     /// Expected response: [`Accounts`](crate::message_interface::Response::Accounts)
     GetAccounts,
+    /// Read the accounts with an alias or metadata that contains the query, ignoring the case.
+    /// Expected response: [`Accounts`](crate::message_interface::Response::Accounts)
+    SearchAccounts { query: String },
     /// Consume an account method.
     /// Returns [`Response`](crate::message_interface::Response)
     CallAccountMethod {
@@ -61,6 +67,18 @@ pub enum Message {
         /// Stronghold file password.
         password: String,
     },
+    /// Backup client options, coin type, accounts and optionally the secret manager configuration in a password
+    /// encrypted JSON file.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    BackupToFile {
+        /// The backup destination.
+        destination: PathBuf,
+        /// The password to encrypt the backup with.
+        password: String,
+        /// Whether the secret manager configuration should be included, default false.
+        #[serde(rename = "includeSecretManager", default)]
+        include_secret_manager: bool,
+    },
     /// Change the Stronghold password to another one and also re-encrypt the values in the loaded snapshot with it.
     /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
     #[cfg(feature = "stronghold")]
@@ -117,6 +135,42 @@ pub enum Message {
         #[serde(rename = "ignoreIfCoinTypeMismatch")]
         ignore_if_coin_type_mismatch: Option<bool>,
     },
+    /// Restore selected parts of a backup from a Stronghold file, optionally merging the accounts with the existing
+    /// ones.
+    /// Expected response: [`RestoreReport`](crate::message_interface::Response::RestoreReport)
+    #[cfg(feature = "stronghold")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
+    RestoreBackupWithOptions {
+        /// The path to the backed up Stronghold.
+        source: PathBuf,
+        /// Stronghold file password.
+        password: String,
+        /// The parts to restore, by default everything without merging.
+        #[serde(default)]
+        options: RestoreOptions,
+    },
+    /// Restore a backup from a file created with [`BackupToFile`](crate::message_interface::Message::BackupToFile).
+    /// Replaces client_options, coin_type, accounts and the secret_manager, if it was included and isn't a
+    /// Stronghold. Returns an error if accounts were already created.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    RestoreFromFile {
+        /// The path to the backup file.
+        source: PathBuf,
+        /// The password the backup was encrypted with.
+        password: String,
+        #[serde(rename = "ignoreIfCoinTypeMismatch")]
+        ignore_if_coin_type_mismatch: Option<bool>,
+    },
+    /// Verify that a backup from a Stronghold file can be restored, without changing the account manager.
+    /// Expected response: [`BackupSummary`](crate::message_interface::Response::BackupSummary)
+    #[cfg(feature = "stronghold")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
+    VerifyBackup {
+        /// The path to the backed up Stronghold.
+        source: PathBuf,
+        /// Stronghold file password.
+        password: String,
+    },
     /// Removes the latest account (account with the largest account index).
     /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
     RemoveLatestAccount,
@@ -192,6 +246,42 @@ pub enum Message {
     /// Stop background syncing.
     /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
     StopBackgroundSync,
+    /// Set the background syncing options for an account, without options and interval the ones background syncing
+    /// was started with are used.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    SetBackgroundSyncOptions {
+        #[serde(rename = "accountId")]
+        account_id: AccountIdentifier,
+        /// Sync options
+        options: Option<SyncOptions>,
+        /// Interval in milliseconds
+        #[serde(rename = "intervalInMilliseconds")]
+        interval_in_milliseconds: Option<u64>,
+    },
+    /// Get the background syncing status of the accounts.
+    /// Expected response: [`BackgroundSyncStatus`](crate::message_interface::Response::BackgroundSyncStatus)
+    GetBackgroundSyncStatus,
+    /// Start creating backups automatically after new accounts, addresses or transactions and optionally in an
+    /// interval.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    #[cfg(feature = "stronghold")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
+    StartAutoBackup {
+        /// The directory in which the backups are stored.
+        directory: PathBuf,
+        /// Stronghold file password.
+        password: String,
+        /// The number of backups to keep, default 5.
+        keep: Option<usize>,
+        /// Interval in milliseconds
+        #[serde(rename = "intervalInMilliseconds")]
+        interval_in_milliseconds: Option<u64>,
+    },
+    /// Stop creating backups automatically.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    #[cfg(feature = "stronghold")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
+    StopAutoBackup,
     /// Emits an event for testing if the event system is working
     /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
     #[cfg(feature = "events")]
@@ -228,6 +318,23 @@ pub enum Message {
         /// Authentication options
         auth: Option<NodeAuth>,
     },
+    /// Execute multiple messages in order. Later messages can reference values of earlier responses with
+    /// `{"$ref": "<index>/<json pointer>"}`, for example `{"$ref": "0/payload/0/address"}`.
+    /// If `atomic` is true, the execution stops at the first failed message and outputs locked by transactions that
+    /// were prepared in the batch, but not submitted, get unlocked again.
+    /// Expected response: [`Batch`](crate::message_interface::Response::Batch)
+    Batch {
+        /// The messages to execute, as JSON so they can contain references.
+        messages: Vec<serde_json::Value>,
+        /// Stop at the first failed message and unlock outputs locked during the batch.
+        atomic: Option<bool>,
+    },
+    /// Get a JSON Schema of all messages, account methods and responses of this build.
+    /// Expected response: [`Schema`](crate::message_interface::Response::Schema)
+    GetSchema,
+    /// Get the protocol version, enabled features and available variants of this build.
+    /// Expected response: [`Capabilities`](crate::message_interface::Response::Capabilities)
+    GetCapabilities,
 }
 
 // Custom Debug implementation to not log secrets
@@ -240,6 +347,7 @@ impl Debug for Message {
             Self::GetAccountIndexes => write!(f, "GetAccountIndexes"),
             Self::GetAccount { account_id } => write!(f, "GetAccount{{ account_id: {account_id:?} }}"),
             Self::GetAccounts => write!(f, "GetAccounts"),
+            Self::SearchAccounts { query } => write!(f, "SearchAccounts{{ query: {query:?} }}"),
             Self::CallAccountMethod { account_id, method } => write!(
                 f,
                 "CallAccountMethod{{ account_id: {account_id:?}, method: {method:?} }}"
@@ -261,6 +369,14 @@ impl Debug for Message {
                 destination,
                 password: _,
             } => write!(f, "Backup{{ destination: {destination:?} }}"),
+            Self::BackupToFile {
+                destination,
+                password: _,
+                include_secret_manager,
+            } => write!(
+                f,
+                "BackupToFile{{ destination: {destination:?}, password: <omitted>, include_secret_manager: {include_secret_manager:?} }}"
+            ),
             Self::RecoverAccounts {
                 account_start_index,
                 account_gap_limit,
@@ -280,6 +396,27 @@ impl Debug for Message {
                 f,
                 "RestoreBackup{{ source: {source:?}, password: <ommited>, ignore_if_coin_type_mismatch: {ignore_if_coin_type_mismatch:?} }}"
             ),
+            #[cfg(feature = "stronghold")]
+            Self::RestoreBackupWithOptions {
+                source,
+                password: _,
+                options,
+            } => write!(
+                f,
+                "RestoreBackupWithOptions{{ source: {source:?}, password: <omitted>, options: {options:?} }}"
+            ),
+            #[cfg(feature = "stronghold")]
+            Self::VerifyBackup { source, password: _ } => {
+                write!(f, "VerifyBackup{{ source: {source:?}, password: <omitted> }}")
+            }
+            Self::RestoreFromFile {
+                source,
+                password: _,
+                ignore_if_coin_type_mismatch,
+            } => write!(
+                f,
+                "RestoreFromFile{{ source: {source:?}, password: <omitted>, ignore_if_coin_type_mismatch: {ignore_if_coin_type_mismatch:?} }}"
+            ),
             Self::GenerateMnemonic => write!(f, "GenerateMnemonic"),
             Self::VerifyMnemonic { mnemonic: _ } => write!(f, "VerifyMnemonic{{ mnemonic: <omitted> }}"),
             Self::SetClientOptions { client_options } => {
@@ -321,6 +458,28 @@ impl Debug for Message {
                 "StartBackgroundSync{{ options: {options:?}, interval: {interval_in_milliseconds:?} }}"
             ),
             Self::StopBackgroundSync => write!(f, "StopBackgroundSync"),
+            Self::SetBackgroundSyncOptions {
+                account_id,
+                options,
+                interval_in_milliseconds,
+            } => write!(
+                f,
+                "SetBackgroundSyncOptions{{ account_id: {account_id:?}, options: {options:?}, interval: {:?} }}",
+                interval_in_milliseconds
+            ),
+            Self::GetBackgroundSyncStatus => write!(f, "GetBackgroundSyncStatus"),
+            #[cfg(feature = "stronghold")]
+            Self::StartAutoBackup {
+                directory,
+                password: _,
+                keep,
+                interval_in_milliseconds,
+            } => write!(
+                f,
+                "StartAutoBackup{{ directory: {directory:?}, password: <omitted>, keep: {keep:?}, interval: {interval_in_milliseconds:?} }}"
+            ),
+            #[cfg(feature = "stronghold")]
+            Self::StopAutoBackup => write!(f, "StopAutoBackup"),
             #[cfg(feature = "events")]
             Self::EmitTestEvent { event } => write!(f, "EmitTestEvent{{ event: {event:?} }}"),
             Self::Bech32ToHex { bech32_address } => write!(f, "Bech32ToHex{{ bech32_address: {bech32_address:?} }}"),
@@ -330,6 +489,13 @@ impl Debug for Message {
             #[cfg(feature = "events")]
             Self::ClearListeners { event_types } => write!(f, "ClearListeners{{ event_types: {event_types:?} }}"),
             Self::UpdateNodeAuth { url, auth: _ } => write!(f, "UpdateNodeAuth{{ url: {url}, auth: <omitted> }}"),
+            Self::Batch { messages, atomic } => write!(
+                f,
+                "Batch{{ messages: <{} messages omitted>, atomic: {atomic:?} }}",
+                messages.len()
+            ),
+            Self::GetSchema => write!(f, "GetSchema"),
+            Self::GetCapabilities => write!(f, "GetCapabilities"),
         }
     }
 }
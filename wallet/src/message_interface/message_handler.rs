@@ -20,6 +20,7 @@ use iota_client::{
             dto::{OutputBuilderAmountDto, OutputDto},
             AliasId, AliasOutput, BasicOutput, FoundryOutput, NftId, NftOutput, Output, Rent, TokenId,
         },
+        payload::dto::TaggedDataPayloadDto,
         DtoError,
     },
     constants::SHIMMER_TESTNET_BECH32_HRP,
@@ -33,20 +34,32 @@ use crate::events::types::{Event, WalletEventType};
 use crate::{
     account::{
         operations::transaction::{
-            high_level::{create_alias::AliasOutputOptions, minting::mint_native_token::MintTokenTransactionDto},
+            high_level::{
+                create_alias::AliasOutputOptions, minting::mint_native_token::MintTokenTransactionDto,
+                update_alias::AliasGovernanceUpdate,
+            },
             prepare_output::OutputOptions,
             TransactionOptions,
         },
-        types::{AccountBalanceDto, AccountIdentifier, TransactionDto},
+        types::{
+            AccountBalanceDto, AccountIdentifier, BalanceSnapshotDto, NativeTokenHoldersDto, NativeTokenSupplyDto,
+            NftCollectionDto, OwnedNftDto, PaymentRequestDto, PaymentRequestOptions, TransactionDto,
+        },
         OutputDataDto,
     },
-    account_manager::AccountManager,
+    account_manager::{AccountBackgroundSyncOptions, AccountManager},
     message_interface::{
-        account_method::AccountMethod, dtos::AccountDto, message::Message, response::Response,
+        account_method::AccountMethod,
+        batch::{resolve_references, PreparedInputs},
+        dtos::AccountDto,
+        message::Message,
+        parse_message_value,
+        response::Response,
+        schema::{self, Capabilities},
         AddressWithUnspentOutputsDto,
     },
-    AddressWithAmount, AddressWithMicroAmount, IncreaseNativeTokenSupplyOptions, NativeTokenOptions, NftOptions,
-    Result,
+    AddressWithAmount, AddressWithMicroAmount, Error, IncreaseNativeTokenSupplyOptions, NativeTokenOptions,
+    NftCollectionMinting, NftCollectionMintingDto, NftCollectionMintingOptions, NftOptions, Result,
 };
 
 fn panic_to_response_message(panic: Box<dyn Any>) -> Response {
@@ -127,7 +140,25 @@ impl WalletMessageHandler {
     pub async fn send_message(&self, message: Message) -> Response {
         log::debug!("Message: {:?}", message);
 
+        let response = match message {
+            Message::Batch { messages, atomic } => {
+                match convert_async_panics(|| async { self.batch(messages, atomic.unwrap_or(false)).await }).await {
+                    Ok(r) => r,
+                    Err(e) => Response::Error(e),
+                }
+            }
+            message => self.handle_message(message).await,
+        };
+
+        log::debug!("Response: {:?}", response);
+
+        response
+    }
+
+    /// Handle a single message, batches are handled in [`WalletMessageHandler::batch`].
+    async fn handle_message(&self, message: Message) -> Response {
         let response: Result<Response> = match message {
+            Message::Batch { .. } => Err(Error::Batch("batches can't be nested".to_string())),
             Message::CreateAccount { alias, bech32_hrp } => {
                 convert_async_panics(|| async { self.create_account(alias, bech32_hrp).await }).await
             }
@@ -146,6 +177,17 @@ impl WalletMessageHandler {
                 .await
             }
             Message::GetAccounts => convert_async_panics(|| async { self.get_accounts().await }).await,
+            Message::SearchAccounts { query } => {
+                convert_async_panics(|| async {
+                    let mut accounts = Vec::new();
+                    for account_handle in self.account_manager.search_accounts(&query).await {
+                        let account = account_handle.read().await;
+                        accounts.push(AccountDto::from(&*account));
+                    }
+                    Ok(Response::Accounts(accounts))
+                })
+                .await
+            }
             Message::CallAccountMethod { account_id, method } => {
                 convert_async_panics(|| async { self.call_account_method(&account_id, method).await }).await
             }
@@ -153,6 +195,19 @@ impl WalletMessageHandler {
             Message::Backup { destination, password } => {
                 convert_async_panics(|| async { self.backup(destination.to_path_buf(), password).await }).await
             }
+            Message::BackupToFile {
+                destination,
+                password,
+                include_secret_manager,
+            } => {
+                convert_async_panics(|| async {
+                    self.account_manager
+                        .backup_to_file(destination.to_path_buf(), password, include_secret_manager)
+                        .await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
             #[cfg(feature = "stronghold")]
             Message::ChangeStrongholdPassword {
                 mut current_password,
@@ -223,6 +278,42 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            #[cfg(feature = "stronghold")]
+            Message::RestoreBackupWithOptions {
+                source,
+                password,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let report = self
+                        .account_manager
+                        .restore_backup_with_options(source.to_path_buf(), password, options)
+                        .await?;
+                    Ok(Response::RestoreReport(report))
+                })
+                .await
+            }
+            #[cfg(feature = "stronghold")]
+            Message::VerifyBackup { source, password } => {
+                convert_async_panics(|| async {
+                    let summary = AccountManager::verify_backup(source.to_path_buf(), password).await?;
+                    Ok(Response::BackupSummary(summary))
+                })
+                .await
+            }
+            Message::RestoreFromFile {
+                source,
+                password,
+                ignore_if_coin_type_mismatch,
+            } => {
+                convert_async_panics(|| async {
+                    self.account_manager
+                        .restore_from_file(source.to_path_buf(), password, ignore_if_coin_type_mismatch)
+                        .await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
             Message::GenerateMnemonic => convert_panics(|| {
                 self.account_manager
                     .generate_mnemonic()
@@ -330,6 +421,54 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            Message::SetBackgroundSyncOptions {
+                account_id,
+                options,
+                interval_in_milliseconds,
+            } => {
+                convert_async_panics(|| async {
+                    let options = AccountBackgroundSyncOptions {
+                        sync_options: options,
+                        interval: interval_in_milliseconds.map(Duration::from_millis),
+                    };
+                    self.account_manager
+                        .set_background_syncing_options(account_id, options)
+                        .await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            Message::GetBackgroundSyncStatus => {
+                convert_async_panics(|| async {
+                    let status = self.account_manager.get_background_syncing_status().await;
+                    Ok(Response::BackgroundSyncStatus(status))
+                })
+                .await
+            }
+            #[cfg(feature = "stronghold")]
+            Message::StartAutoBackup {
+                directory,
+                password,
+                keep,
+                interval_in_milliseconds,
+            } => {
+                convert_async_panics(|| async {
+                    let duration = interval_in_milliseconds.map(Duration::from_millis);
+                    self.account_manager
+                        .start_auto_backup(directory.to_path_buf(), password, keep, duration)
+                        .await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            #[cfg(feature = "stronghold")]
+            Message::StopAutoBackup => {
+                convert_async_panics(|| async {
+                    self.account_manager.stop_auto_backup().await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
             #[cfg(feature = "events")]
             Message::EmitTestEvent { event } => {
                 convert_async_panics(|| async {
@@ -370,16 +509,67 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            Message::GetSchema => convert_panics(|| Ok(Response::Schema(schema::schema()))),
+            Message::GetCapabilities => convert_panics(|| Ok(Response::Capabilities(Capabilities::current()))),
         };
 
-        let response = match response {
+        match response {
             Ok(r) => r,
             Err(e) => Response::Error(e),
-        };
+        }
+    }
 
-        log::debug!("Response: {:?}", response);
+    /// Execute the messages of a batch in order. Objects in the form of `{"$ref": "<index>/<json pointer>"}` are
+    /// replaced with the value at the pointer in the serialized response of the step with the index. If `atomic` is
+    /// true, the execution stops at the first failing step and outputs that were locked by transactions prepared
+    /// during the batch, but never submitted, are unlocked again. Already submitted transactions can't be reverted.
+    async fn batch(&self, messages: Vec<serde_json::Value>, atomic: bool) -> Result<Response> {
+        let mut prepared_inputs = PreparedInputs::default();
+        let mut serialized_responses = Vec::with_capacity(messages.len());
+        let mut responses = Vec::with_capacity(messages.len());
 
-        response
+        for (index, mut message) in messages.into_iter().enumerate() {
+            // Nested messages get the same protocol version and unknown variant checks as single messages
+            let message =
+                resolve_references(&mut message, &serialized_responses).and_then(|_| parse_message_value(message));
+            let mut response = match message {
+                Ok(message) => {
+                    log::debug!("Batch message {index}: {message:?}");
+                    let account_id = match &message {
+                        Message::CallAccountMethod { account_id, .. } => Some(account_id.clone()),
+                        _ => None,
+                    };
+                    let response = self.handle_message(message).await;
+                    match account_id.map(|account_id| prepared_inputs.record(&account_id, &response)) {
+                        Some(Err(e)) => Response::Error(e),
+                        _ => response,
+                    }
+                }
+                Err(e) => Response::Error(e),
+            };
+            let serialized_response = match serde_json::to_value(&response) {
+                Ok(serialized_response) => serialized_response,
+                Err(e) => {
+                    response = Response::Error(e.into());
+                    // References to a failed step are rejected by its type
+                    serde_json::json!({ "type": "error" })
+                }
+            };
+            let failed = matches!(response, Response::Error(_) | Response::Panic(_));
+
+            serialized_responses.push(serialized_response);
+            responses.push(response);
+
+            if failed && atomic {
+                // The responses of the executed steps are returned even if unlocking fails
+                if let Err(e) = prepared_inputs.unlock(&self.account_manager).await {
+                    log::error!("[BATCH] couldn't unlock the inputs of prepared transactions: {e}");
+                }
+                break;
+            }
+        }
+
+        Ok(Response::Batch(responses))
     }
 
     #[cfg(feature = "stronghold")]
@@ -577,6 +767,57 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::UpdateAliasState {
+                alias_id,
+                state_metadata,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle
+                        .update_alias_state(
+                            AliasId::try_from(&alias_id)?,
+                            prefix_hex::decode(&state_metadata).map_err(|_| DtoError::InvalidField("stateMetadata"))?,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
+            AccountMethod::UpdateAliasGovernance {
+                alias_id,
+                update,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle
+                        .update_alias_governance(
+                            AliasId::try_from(&alias_id)?,
+                            AliasGovernanceUpdate::try_from(&update)?,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
+            AccountMethod::TransferAlias {
+                alias_id,
+                address,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle
+                        .transfer_alias(
+                            AliasId::try_from(&alias_id)?,
+                            address,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
             AccountMethod::DestroyFoundry { foundry_id, options } => {
                 convert_async_panics(|| async {
                     let transaction = account_handle
@@ -685,6 +926,23 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::UpdateFoundryMetadata {
+                token_id,
+                metadata,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle
+                        .update_foundry_metadata(
+                            TokenId::try_from(&token_id)?,
+                            prefix_hex::decode(&metadata).map_err(|_| DtoError::InvalidField("metadata"))?,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
             AccountMethod::IncreaseNativeTokenSupply {
                 token_id,
                 mint_amount,
@@ -757,6 +1015,59 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::MintNftCollection {
+                issuer_nft_id,
+                nfts_options,
+                max_nfts_per_transaction,
+                max_pending_transactions,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let minting = account_handle
+                        .mint_nft_collection(
+                            NftId::try_from(&issuer_nft_id)?,
+                            nfts_options
+                                .iter()
+                                .map(NftOptions::try_from)
+                                .collect::<Result<Vec<NftOptions>>>()?,
+                            Some(NftCollectionMintingOptions {
+                                max_nfts_per_transaction,
+                                max_pending_transactions,
+                                transaction_options: options
+                                    .as_ref()
+                                    .map(TransactionOptions::try_from_dto)
+                                    .transpose()?,
+                            }),
+                        )
+                        .await?;
+                    Ok(Response::NftCollectionMinting(NftCollectionMintingDto::from(&minting)))
+                })
+                .await
+            }
+            AccountMethod::ResumeNftCollectionMinting {
+                minting,
+                max_nfts_per_transaction,
+                max_pending_transactions,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let minting = account_handle
+                        .resume_nft_collection_minting(
+                            NftCollectionMinting::try_from(&minting)?,
+                            Some(NftCollectionMintingOptions {
+                                max_nfts_per_transaction,
+                                max_pending_transactions,
+                                transaction_options: options
+                                    .as_ref()
+                                    .map(TransactionOptions::try_from_dto)
+                                    .transpose()?,
+                            }),
+                        )
+                        .await?;
+                    Ok(Response::NftCollectionMinting(NftCollectionMintingDto::from(&minting)))
+                })
+                .await
+            }
             AccountMethod::GetBalance => Ok(Response::Balance(AccountBalanceDto::from(
                 &account_handle.balance().await?,
             ))),
@@ -828,6 +1139,10 @@ impl WalletMessageHandler {
             AccountMethod::SyncAccount { options } => Ok(Response::Balance(AccountBalanceDto::from(
                 &account_handle.sync(options).await?,
             ))),
+            AccountMethod::CancelSync => {
+                account_handle.cancel_sync().await;
+                Ok(Response::Ok(()))
+            }
             AccountMethod::SendAmount {
                 addresses_with_amount,
                 options,
@@ -901,6 +1216,33 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::SetAddressLabel { address, label } => {
+                convert_async_panics(|| async {
+                    account_handle.set_address_label(&address, label).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            AccountMethod::SetOutputLabel { output_id, label } => {
+                convert_async_panics(|| async {
+                    account_handle.set_output_label(output_id, label).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            AccountMethod::SetAccountMetadata { metadata } => {
+                convert_async_panics(|| async {
+                    account_handle.set_metadata(metadata).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            AccountMethod::SearchLabels { query } => {
+                convert_async_panics(|| async {
+                    Ok(Response::LabelSearchResult(account_handle.search_labels(&query).await))
+                })
+                .await
+            }
             AccountMethod::SendOutputs { outputs, options } => {
                 convert_async_panics(|| async {
                     let token_supply = account_handle.client.get_token_supply().await?;
@@ -1065,6 +1407,172 @@ impl WalletMessageHandler {
                 })
                 .await
             }
+            AccountMethod::CreatePaymentRequest { options } => {
+                convert_async_panics(|| async {
+                    let payment_request = account_handle
+                        .create_payment_request(PaymentRequestOptions::try_from(&options)?)
+                        .await?;
+                    Ok(Response::PaymentRequest(PaymentRequestDto::from(&payment_request)))
+                })
+                .await
+            }
+            AccountMethod::GetPaymentRequest { id } => {
+                convert_async_panics(|| async {
+                    let payment_request = account_handle.get_payment_request(id).await?;
+                    Ok(Response::PaymentRequest(PaymentRequestDto::from(&payment_request)))
+                })
+                .await
+            }
+            AccountMethod::GetPaymentRequests => {
+                convert_async_panics(|| async {
+                    let payment_requests = account_handle.payment_requests().await;
+                    Ok(Response::PaymentRequests(
+                        payment_requests.iter().map(PaymentRequestDto::from).collect(),
+                    ))
+                })
+                .await
+            }
+            AccountMethod::CancelPaymentRequest { id } => {
+                convert_async_panics(|| async {
+                    account_handle.cancel_payment_request(id).await?;
+                    Ok(Response::Ok(()))
+                })
+                .await
+            }
+            AccountMethod::GetBalanceAt { timestamp } => {
+                convert_async_panics(|| async {
+                    let balance = account_handle.balance_at(timestamp).await?;
+                    Ok(Response::BalanceSnapshot(BalanceSnapshotDto::from(&balance)))
+                })
+                .await
+            }
+            AccountMethod::GetBalanceHistory { start, end } => {
+                convert_async_panics(|| async {
+                    let history = account_handle.balance_history(start, end).await?;
+                    Ok(Response::BalanceHistory(
+                        history.iter().map(BalanceSnapshotDto::from).collect(),
+                    ))
+                })
+                .await
+            }
+            AccountMethod::GetNativeTokensMetadata => {
+                convert_async_panics(|| async {
+                    Ok(Response::NativeTokensMetadata(
+                        account_handle.native_tokens_metadata().await,
+                    ))
+                })
+                .await
+            }
+            AccountMethod::SetNativeTokenMetadataOverrides { token_id, overrides } => {
+                convert_async_panics(|| async {
+                    let metadata = account_handle
+                        .set_native_token_metadata_overrides(TokenId::try_from(&token_id)?, overrides)
+                        .await?;
+                    Ok(Response::NativeTokenMetadata(metadata))
+                })
+                .await
+            }
+            AccountMethod::SetNativeTokenTrusted { token_id, trusted } => {
+                convert_async_panics(|| async {
+                    let metadata = account_handle
+                        .set_native_token_trusted(TokenId::try_from(&token_id)?, trusted)
+                        .await?;
+                    Ok(Response::NativeTokenMetadata(metadata))
+                })
+                .await
+            }
+            AccountMethod::GetNfts => {
+                convert_async_panics(|| async {
+                    let nfts = account_handle.nfts().await?;
+                    Ok(Response::Nfts(nfts.iter().map(OwnedNftDto::from).collect()))
+                })
+                .await
+            }
+            AccountMethod::GetNftCollections => {
+                convert_async_panics(|| async {
+                    let collections = account_handle.nft_collections().await?;
+                    Ok(Response::NftCollections(
+                        collections.iter().map(NftCollectionDto::from).collect(),
+                    ))
+                })
+                .await
+            }
+            AccountMethod::GetNativeTokenSupply { token_id } => {
+                convert_async_panics(|| async {
+                    let supply = account_handle
+                        .native_token_supply(&TokenId::try_from(&token_id)?)
+                        .await?;
+                    Ok(Response::NativeTokenSupply(NativeTokenSupplyDto::from(&supply)))
+                })
+                .await
+            }
+            AccountMethod::GetNativeTokenSupplies => {
+                convert_async_panics(|| async {
+                    let supplies = account_handle.native_token_supplies().await;
+                    Ok(Response::NativeTokenSupplies(
+                        supplies.iter().map(NativeTokenSupplyDto::from).collect(),
+                    ))
+                })
+                .await
+            }
+            AccountMethod::GetNativeTokenHolders { token_id, cursor } => {
+                convert_async_panics(|| async {
+                    let holders = account_handle
+                        .native_token_holders(&TokenId::try_from(&token_id)?, cursor)
+                        .await?;
+                    Ok(Response::NativeTokenHolders(NativeTokenHoldersDto::from(&holders)))
+                })
+                .await
+            }
+            AccountMethod::GetMessagingKey => {
+                convert_async_panics(|| async { Ok(Response::MessagingKey(account_handle.messaging_key().await?)) })
+                    .await
+            }
+            AccountMethod::GetAddressMessagingKey { address } => {
+                convert_async_panics(|| async {
+                    Ok(Response::AddressMessagingKey(
+                        account_handle.messaging_key_of(&address).await?,
+                    ))
+                })
+                .await
+            }
+            AccountMethod::PrepareMemo { messaging_key, memo } => {
+                convert_async_panics(|| async {
+                    let payload = account_handle.prepare_memo(&messaging_key, &memo).await?;
+                    Ok(Response::MemoPayload(TaggedDataPayloadDto::from(&payload)))
+                })
+                .await
+            }
+            AccountMethod::SendMemo {
+                address,
+                memo,
+                messaging_key,
+                options,
+            } => {
+                convert_async_panics(|| async {
+                    let transaction = account_handle
+                        .send_memo(
+                            address,
+                            memo,
+                            messaging_key,
+                            options.as_ref().map(TransactionOptions::try_from_dto).transpose()?,
+                        )
+                        .await?;
+                    Ok(Response::SentTransaction(TransactionDto::from(&transaction)))
+                })
+                .await
+            }
+            AccountMethod::SendMemoBlock { messaging_key, memo } => {
+                convert_async_panics(|| async {
+                    let block_id = account_handle.send_memo_block(&messaging_key, &memo).await?;
+                    Ok(Response::BlockId(block_id))
+                })
+                .await
+            }
+            AccountMethod::ReadMemoBlock { block_id } => {
+                convert_async_panics(|| async { Ok(Response::Memo(account_handle.read_memo_block(&block_id).await?)) })
+                    .await
+            }
         }
     }
 
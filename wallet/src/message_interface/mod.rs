@@ -2,13 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod account_method;
+mod batch;
 pub mod dtos;
 mod message;
 mod message_handler;
 mod response;
+pub mod schema;
 
 use fern_logger::{logger_init, LoggerConfig, LoggerOutputConfigBuilder};
-use iota_client::secret::{SecretManager, SecretManagerDto};
+use iota_client::{
+    block::DtoError,
+    secret::{SecretManager, SecretManagerDto},
+};
 use serde::{Deserialize, Serialize, Serializer};
 
 pub use self::{
@@ -17,6 +22,7 @@ pub use self::{
     message::Message,
     message_handler::WalletMessageHandler,
     response::Response,
+    schema::{Capabilities, MIN_SUPPORTED_PROTOCOL_VERSION, PROTOCOL_VERSION},
 };
 use crate::{account_manager::AccountManager, ClientOptions};
 
@@ -63,6 +69,40 @@ pub fn init_logger(config: String) -> Result<(), fern_logger::Error> {
     logger_init(config)
 }
 
+/// Parses a JSON encoded [`Message`]. Unlike plain deserialization this checks the optional `protocolVersion` field
+/// and returns structured errors for unknown messages and account methods, so bindings can report incompatibilities.
+pub fn parse_message(message: &str) -> crate::Result<Message> {
+    parse_message_value(serde_json::from_str(message)?)
+}
+
+/// Parses a [`Message`] from a JSON value with the same checks as [`parse_message`].
+pub(crate) fn parse_message_value(mut value: serde_json::Value) -> crate::Result<Message> {
+    if let Some(object) = value.as_object_mut() {
+        if let Some(protocol_version) = object.remove(schema::PROTOCOL_VERSION_KEY) {
+            let protocol_version = protocol_version
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or(DtoError::InvalidField("protocolVersion"))?;
+            schema::check_protocol_version(protocol_version)?;
+        }
+    }
+
+    let cmd = value
+        .get("cmd")
+        .and_then(serde_json::Value::as_str)
+        .ok_or(crate::Error::MissingParameter("cmd"))?;
+    if !schema::is_known_message(cmd) {
+        return Err(crate::Error::UnknownMessage(cmd.to_string()));
+    }
+    if let Some(method) = value.pointer("/payload/method/name").and_then(serde_json::Value::as_str) {
+        if !schema::is_known_account_method(method) {
+            return Err(crate::Error::UnknownAccountMethod(method.to_string()));
+        }
+    }
+
+    Ok(serde_json::from_value(value)?)
+}
+
 pub async fn create_message_handler(options: Option<ManagerOptions>) -> crate::Result<WalletMessageHandler> {
     log::debug!(
         "create_message_handler with options: {}",
@@ -1,15 +1,18 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::fmt::{Debug, Formatter, Result};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter, Result},
+};
 
 #[cfg(feature = "ledger_nano")]
 use iota_client::secret::LedgerNanoStatus;
 use iota_client::{
     api::{PreparedTransactionDataDto, SignedTransactionDataDto},
     block::{
-        output::{dto::OutputDto, OutputId},
-        payload::transaction::TransactionId,
+        output::{dto::OutputDto, OutputId, TokenId},
+        payload::{dto::TaggedDataPayloadDto, transaction::TransactionId},
         BlockId,
     },
     NodeInfoWrapper,
@@ -19,17 +22,26 @@ use serde::Serialize;
 use {
     crate::account::operations::participation::{AccountParticipationOverview, ParticipationEventWithNodes},
     iota_client::api_types::plugins::participation::types::{ParticipationEventId, ParticipationEventStatus},
-    std::collections::HashMap,
 };
 
+#[cfg(feature = "stronghold")]
+use crate::account_manager::{BackupSummary, RestoreReport};
 use crate::{
     account::{
         operations::transaction::high_level::minting::mint_native_token::MintTokenTransactionDto,
-        types::{address::AccountAddress, AccountBalanceDto, TransactionDto},
+        types::{
+            address::AccountAddress, AccountBalanceDto, BalanceSnapshotDto, LabelSearchResult, Memo,
+            NativeTokenHoldersDto, NativeTokenMetadata, NativeTokenSupplyDto, NftCollectionDto, OwnedNftDto,
+            PaymentRequestDto, TransactionDto,
+        },
         OutputDataDto,
     },
-    message_interface::dtos::{AccountDto, AddressWithUnspentOutputsDto},
-    Error,
+    account_manager::AccountBackgroundSyncStatus,
+    message_interface::{
+        dtos::{AccountDto, AddressWithUnspentOutputsDto},
+        schema::Capabilities,
+    },
+    Error, NftCollectionMintingDto,
 };
 
 /// The response message.
@@ -42,7 +54,9 @@ pub enum Response {
     Account(AccountDto),
     /// Response for [`GetAccountIndexes`](crate::message_interface::Message::GetAccountIndexes)
     AccountIndexes(Vec<u32>),
-    /// Response for [`GetAccounts`](crate::message_interface::Message::GetAccounts)
+    /// Response for
+    /// [`GetAccounts`](crate::message_interface::Message::GetAccounts),
+    /// [`SearchAccounts`](crate::message_interface::Message::SearchAccounts)
     Accounts(Vec<AccountDto>),
     /// Response for [`Addresses`](crate::message_interface::AccountMethod::Addresses)
     Addresses(Vec<AccountAddress>),
@@ -94,6 +108,8 @@ pub enum Response {
     /// [`GetBalance`](crate::message_interface::AccountMethod::GetBalance),
     /// [`SyncAccount`](crate::message_interface::AccountMethod::SyncAccount)
     Balance(AccountBalanceDto),
+    /// Response for [`GetBackgroundSyncStatus`](crate::message_interface::Message::GetBackgroundSyncStatus)
+    BackgroundSyncStatus(Vec<AccountBackgroundSyncStatus>),
     /// Response for
     /// [`GetLedgerNanoStatus`](crate::message_interface::Message::GetLedgerNanoStatus),
     #[cfg(feature = "ledger_nano")]
@@ -121,11 +137,19 @@ pub enum Response {
     /// [`StopParticipating`](crate::message_interface::AccountMethod::StopParticipating)
     /// [`IncreaseVotingPower`](crate::message_interface::AccountMethod::IncreaseVotingPower)
     /// [`DecreaseVotingPower`](crate::message_interface::AccountMethod::DecreaseVotingPower)
+    /// [`UpdateAliasState`](crate::message_interface::AccountMethod::UpdateAliasState)
+    /// [`UpdateAliasGovernance`](crate::message_interface::AccountMethod::UpdateAliasGovernance)
+    /// [`TransferAlias`](crate::message_interface::AccountMethod::TransferAlias)
+    /// [`UpdateFoundryMetadata`](crate::message_interface::AccountMethod::UpdateFoundryMetadata)
     SentTransaction(TransactionDto),
     /// Response for
     /// [`MintNativeToken`](crate::message_interface::AccountMethod::MintNativeToken),
     MintTokenTransaction(MintTokenTransactionDto),
     /// Response for
+    /// [`MintNftCollection`](crate::message_interface::AccountMethod::MintNftCollection),
+    /// [`ResumeNftCollectionMinting`](crate::message_interface::AccountMethod::ResumeNftCollectionMinting)
+    NftCollectionMinting(NftCollectionMintingDto),
+    /// Response for
     /// [`IsStrongholdPasswordAvailable`](crate::message_interface::Message::IsStrongholdPasswordAvailable)
     StrongholdPasswordIsAvailable(bool),
     /// An error occurred.
@@ -174,11 +198,68 @@ pub enum Response {
     Bech32Address(String),
     /// Response for [`RequestFundsFromFaucet`](crate::message_interface::AccountMethod::RequestFundsFromFaucet)
     Faucet(String),
+    /// Response for [`RestoreBackupWithOptions`](crate::message_interface::Message::RestoreBackupWithOptions)
+    #[cfg(feature = "stronghold")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
+    RestoreReport(RestoreReport),
+    /// Response for [`VerifyBackup`](crate::message_interface::Message::VerifyBackup)
+    #[cfg(feature = "stronghold")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
+    BackupSummary(BackupSummary),
+    /// Response for [`SearchLabels`](crate::message_interface::AccountMethod::SearchLabels)
+    LabelSearchResult(LabelSearchResult),
+    /// Response for
+    /// [`CreatePaymentRequest`](crate::message_interface::AccountMethod::CreatePaymentRequest),
+    /// [`GetPaymentRequest`](crate::message_interface::AccountMethod::GetPaymentRequest)
+    PaymentRequest(PaymentRequestDto),
+    /// Response for [`GetPaymentRequests`](crate::message_interface::AccountMethod::GetPaymentRequests)
+    PaymentRequests(Vec<PaymentRequestDto>),
+    /// Response for [`GetBalanceAt`](crate::message_interface::AccountMethod::GetBalanceAt)
+    BalanceSnapshot(BalanceSnapshotDto),
+    /// Response for [`GetBalanceHistory`](crate::message_interface::AccountMethod::GetBalanceHistory)
+    BalanceHistory(Vec<BalanceSnapshotDto>),
+    /// Response for [`GetNativeTokensMetadata`](crate::message_interface::AccountMethod::GetNativeTokensMetadata)
+    NativeTokensMetadata(HashMap<TokenId, NativeTokenMetadata>),
+    /// Response for
+    /// [`SetNativeTokenMetadataOverrides`](crate::message_interface::AccountMethod::SetNativeTokenMetadataOverrides),
+    /// [`SetNativeTokenTrusted`](crate::message_interface::AccountMethod::SetNativeTokenTrusted)
+    NativeTokenMetadata(NativeTokenMetadata),
+    /// Response for [`GetNfts`](crate::message_interface::AccountMethod::GetNfts)
+    Nfts(Vec<OwnedNftDto>),
+    /// Response for [`GetNftCollections`](crate::message_interface::AccountMethod::GetNftCollections)
+    NftCollections(Vec<NftCollectionDto>),
+    /// Response for [`GetNativeTokenSupply`](crate::message_interface::AccountMethod::GetNativeTokenSupply)
+    NativeTokenSupply(NativeTokenSupplyDto),
+    /// Response for [`GetNativeTokenSupplies`](crate::message_interface::AccountMethod::GetNativeTokenSupplies)
+    NativeTokenSupplies(Vec<NativeTokenSupplyDto>),
+    /// Response for [`GetNativeTokenHolders`](crate::message_interface::AccountMethod::GetNativeTokenHolders)
+    NativeTokenHolders(NativeTokenHoldersDto),
+    /// Response for [`GetMessagingKey`](crate::message_interface::AccountMethod::GetMessagingKey)
+    MessagingKey(String),
+    /// Response for [`GetAddressMessagingKey`](crate::message_interface::AccountMethod::GetAddressMessagingKey)
+    AddressMessagingKey(Option<String>),
+    /// Response for [`PrepareMemo`](crate::message_interface::AccountMethod::PrepareMemo)
+    MemoPayload(TaggedDataPayloadDto),
+    /// Response for [`ReadMemoBlock`](crate::message_interface::AccountMethod::ReadMemoBlock)
+    Memo(Memo),
+    /// Response for [`Batch`](crate::message_interface::Message::Batch), one response per executed message
+    Batch(Vec<Response>),
+    /// Response for [`GetSchema`](crate::message_interface::Message::GetSchema)
+    Schema(serde_json::Value),
+    /// Response for [`GetCapabilities`](crate::message_interface::Message::GetCapabilities)
+    Capabilities(Capabilities),
     /// Response for
     /// [`Backup`](crate::message_interface::Message::Backup),
+    /// [`BackupToFile`](crate::message_interface::Message::BackupToFile),
+    /// [`CancelPaymentRequest`](crate::message_interface::AccountMethod::CancelPaymentRequest),
+    /// [`CancelSync`](crate::message_interface::AccountMethod::CancelSync),
     /// [`ClearStrongholdPassword`](crate::message_interface::Message::ClearStrongholdPassword),
     /// [`DeregisterParticipationEvent`](crate::message_interface::AccountMethod::DeregisterParticipationEvent),
+    /// [`SetAccountMetadata`](crate::message_interface::AccountMethod::SetAccountMetadata),
+    /// [`SetAddressLabel`](crate::message_interface::AccountMethod::SetAddressLabel),
+    /// [`SetOutputLabel`](crate::message_interface::AccountMethod::SetOutputLabel),
     /// [`RestoreBackup`](crate::message_interface::Message::RestoreBackup),
+    /// [`RestoreFromFile`](crate::message_interface::Message::RestoreFromFile),
     /// [`VerifyMnemonic`](crate::message_interface::Message::VerifyMnemonic),
     /// [`SetClientOptions`](crate::message_interface::Message::SetClientOptions),
     /// [`SetStrongholdPassword`](crate::message_interface::Message::SetStrongholdPassword),
@@ -186,6 +267,8 @@ pub enum Response {
     /// [`StoreMnemonic`](crate::message_interface::Message::StoreMnemonic),
     /// [`StartBackgroundSync`](crate::message_interface::Message::StartBackgroundSync),
     /// [`StopBackgroundSync`](crate::message_interface::Message::StopBackgroundSync),
+    /// [`StartAutoBackup`](crate::message_interface::Message::StartAutoBackup),
+    /// [`StopAutoBackup`](crate::message_interface::Message::StopAutoBackup),
     /// [`EmitTestEvent`](crate::message_interface::Message::EmitTestEvent),
     Ok(()),
 }
@@ -217,6 +300,7 @@ impl Debug for Response {
             }
             Self::GeneratedAddress(addresses) => write!(f, "GeneratedAddress({addresses:?})"),
             Self::Balance(balance) => write!(f, "Balance({balance:?})"),
+            Self::BackgroundSyncStatus(status) => write!(f, "BackgroundSyncStatus({status:?})"),
             Self::IncomingTransactionData(transaction_data) => {
                 write!(f, "IncomingTransactionData({transaction_data:?})")
             }
@@ -224,6 +308,7 @@ impl Debug for Response {
                 write!(f, "IncomingTransactionsData({transactions_data:?})")
             }
             Self::SentTransaction(transaction) => write!(f, "SentTransaction({transaction:?})"),
+            Self::NftCollectionMinting(minting) => write!(f, "NftCollectionMinting({minting:?})"),
             Self::MintTokenTransaction(mint_transaction) => {
                 write!(f, "MintTokenTransaction({mint_transaction:?})")
             }
@@ -254,6 +339,29 @@ impl Debug for Response {
                 write!(f, "AccountParticipationOverview({overview:?})")
             }
             Self::Faucet(response) => write!(f, "Faucet({response:?})"),
+            #[cfg(feature = "stronghold")]
+            Self::RestoreReport(report) => write!(f, "RestoreReport({report:?})"),
+            #[cfg(feature = "stronghold")]
+            Self::BackupSummary(summary) => write!(f, "BackupSummary({summary:?})"),
+            Self::LabelSearchResult(result) => write!(f, "LabelSearchResult({result:?})"),
+            Self::PaymentRequest(payment_request) => write!(f, "PaymentRequest({payment_request:?})"),
+            Self::PaymentRequests(payment_requests) => write!(f, "PaymentRequests({payment_requests:?})"),
+            Self::BalanceSnapshot(balance) => write!(f, "BalanceSnapshot({balance:?})"),
+            Self::BalanceHistory(history) => write!(f, "BalanceHistory({history:?})"),
+            Self::NativeTokensMetadata(metadata) => write!(f, "NativeTokensMetadata({metadata:?})"),
+            Self::NativeTokenMetadata(metadata) => write!(f, "NativeTokenMetadata({metadata:?})"),
+            Self::Nfts(nfts) => write!(f, "Nfts({nfts:?})"),
+            Self::NftCollections(collections) => write!(f, "NftCollections({collections:?})"),
+            Self::NativeTokenSupply(supply) => write!(f, "NativeTokenSupply({supply:?})"),
+            Self::NativeTokenSupplies(supplies) => write!(f, "NativeTokenSupplies({supplies:?})"),
+            Self::NativeTokenHolders(holders) => write!(f, "NativeTokenHolders({holders:?})"),
+            Self::MessagingKey(key) => write!(f, "MessagingKey({key})"),
+            Self::AddressMessagingKey(key) => write!(f, "AddressMessagingKey({key:?})"),
+            Self::MemoPayload(payload) => write!(f, "MemoPayload({payload:?})"),
+            Self::Memo(memo) => write!(f, "Memo({memo:?})"),
+            Self::Batch(responses) => write!(f, "Batch({responses:?})"),
+            Self::Schema(schema) => write!(f, "Schema({schema})"),
+            Self::Capabilities(capabilities) => write!(f, "Capabilities({capabilities:?})"),
         }
     }
 }
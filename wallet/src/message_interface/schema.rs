@@ -0,0 +1,1297 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Machine-readable description of the message interface, so bindings can detect incompatible changes of
+//! [`Message`](crate::message_interface::Message), [`AccountMethod`](crate::message_interface::AccountMethod) and
+//! [`Response`](crate::message_interface::Response) variants at runtime instead of failing silently.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use self::FieldType::{Any, Array, Boolean, Integer, Object, String as Str};
+
+/// The current version of the message interface protocol. Must be increased whenever a `Message`, `AccountMethod`
+/// or `Response` variant or one of their fields is renamed or removed.
+pub const PROTOCOL_VERSION: u32 = 1;
+/// The oldest protocol version that can still be handled.
+pub const MIN_SUPPORTED_PROTOCOL_VERSION: u32 = 1;
+
+/// The JSON key of the optional protocol version in a serialized message.
+pub(crate) const PROTOCOL_VERSION_KEY: &str = "protocolVersion";
+
+/// JSON type of a variant field.
+#[derive(Clone, Copy, Debug)]
+enum FieldType {
+    Any,
+    Array,
+    Boolean,
+    Integer,
+    Object,
+    String,
+}
+
+impl FieldType {
+    fn to_schema(self) -> Value {
+        match self {
+            Self::Any => json!({}),
+            Self::Array => json!({ "type": "array" }),
+            Self::Boolean => json!({ "type": "boolean" }),
+            Self::Integer => json!({ "type": "integer" }),
+            Self::Object => json!({ "type": "object" }),
+            Self::String => json!({ "type": "string" }),
+        }
+    }
+}
+
+/// A field of a variant: name, type and whether it's required.
+type Field = (&'static str, FieldType, bool);
+
+/// A variant of one of the message interface enums.
+struct Variant {
+    name: &'static str,
+    fields: &'static [Field],
+}
+
+const fn variant(name: &'static str, fields: &'static [Field]) -> Variant {
+    Variant { name, fields }
+}
+
+const TRANSACTION_OPTIONS: Field = ("options", Object, false);
+
+const MESSAGES: &[Variant] = &[
+    variant("createAccount", &[("alias", Str, false), ("bech32Hrp", Str, false)]),
+    variant("getAccount", &[("accountId", Any, true)]),
+    variant("getAccountIndexes", &[]),
+    variant("getAccounts", &[]),
+    variant("searchAccounts", &[("query", Str, true)]),
+    variant("callAccountMethod", &[("accountId", Any, true), ("method", Object, true)]),
+    #[cfg(feature = "stronghold")]
+    variant("backup", &[("destination", Str, true), ("password", Str, true)]),
+    variant(
+        "backupToFile",
+        &[
+            ("destination", Str, true),
+            ("password", Str, true),
+            ("includeSecretManager", Boolean, false),
+        ],
+    ),
+    #[cfg(feature = "stronghold")]
+    variant(
+        "changeStrongholdPassword",
+        &[("currentPassword", Str, true), ("newPassword", Str, true)],
+    ),
+    #[cfg(feature = "stronghold")]
+    variant("clearStrongholdPassword", &[]),
+    #[cfg(feature = "stronghold")]
+    variant("isStrongholdPasswordAvailable", &[]),
+    variant(
+        "recoverAccounts",
+        &[
+            ("accountStartIndex", Integer, true),
+            ("accountGapLimit", Integer, true),
+            ("addressGapLimit", Integer, true),
+            ("syncOptions", Object, false),
+        ],
+    ),
+    #[cfg(feature = "stronghold")]
+    variant(
+        "restoreBackup",
+        &[
+            ("source", Str, true),
+            ("password", Str, true),
+            ("ignoreIfCoinTypeMismatch", Boolean, false),
+        ],
+    ),
+    #[cfg(feature = "stronghold")]
+    variant(
+        "restoreBackupWithOptions",
+        &[("source", Str, true), ("password", Str, true), ("options", Object, false)],
+    ),
+    #[cfg(feature = "stronghold")]
+    variant("verifyBackup", &[("source", Str, true), ("password", Str, true)]),
+    variant(
+        "restoreFromFile",
+        &[
+            ("source", Str, true),
+            ("password", Str, true),
+            ("ignoreIfCoinTypeMismatch", Boolean, false),
+        ],
+    ),
+    variant("removeLatestAccount", &[]),
+    variant("generateMnemonic", &[]),
+    variant("verifyMnemonic", &[("mnemonic", Str, true)]),
+    variant("setClientOptions", &[("clientOptions", Object, true)]),
+    variant(
+        "generateAddress",
+        &[
+            ("accountIndex", Integer, true),
+            ("internal", Boolean, true),
+            ("addressIndex", Integer, true),
+            ("options", Object, false),
+            ("bech32Hrp", Str, false),
+        ],
+    ),
+    #[cfg(feature = "ledger_nano")]
+    variant("getLedgerNanoStatus", &[]),
+    variant("getNodeInfo", &[("url", Str, false), ("auth", Object, false)]),
+    #[cfg(feature = "stronghold")]
+    variant("setStrongholdPassword", &[("password", Str, true)]),
+    #[cfg(feature = "stronghold")]
+    variant(
+        "setStrongholdPasswordClearInterval",
+        &[("intervalInMilliseconds", Integer, false)],
+    ),
+    #[cfg(feature = "stronghold")]
+    variant("storeMnemonic", &[("mnemonic", Str, true)]),
+    variant(
+        "startBackgroundSync",
+        &[("options", Object, false), ("intervalInMilliseconds", Integer, false)],
+    ),
+    variant("stopBackgroundSync", &[]),
+    variant(
+        "setBackgroundSyncOptions",
+        &[
+            ("accountId", Any, true),
+            ("options", Object, false),
+            ("intervalInMilliseconds", Integer, false),
+        ],
+    ),
+    variant("getBackgroundSyncStatus", &[]),
+    #[cfg(feature = "stronghold")]
+    variant(
+        "startAutoBackup",
+        &[
+            ("directory", Str, true),
+            ("password", Str, true),
+            ("keep", Integer, false),
+            ("intervalInMilliseconds", Integer, false),
+        ],
+    ),
+    #[cfg(feature = "stronghold")]
+    variant("stopAutoBackup", &[]),
+    #[cfg(feature = "events")]
+    variant("emitTestEvent", &[("event", Any, true)]),
+    variant("bech32ToHex", &[("bech32Address", Str, true)]),
+    variant("hexToBech32", &[("hex", Str, true), ("bech32Hrp", Str, false)]),
+    #[cfg(feature = "events")]
+    variant("clearListeners", &[("eventTypes", Array, true)]),
+    variant("updateNodeAuth", &[("url", Str, true), ("auth", Object, false)]),
+    variant("batch", &[("messages", Array, true), ("atomic", Boolean, false)]),
+    variant("getSchema", &[]),
+    variant("getCapabilities", &[]),
+];
+
+const ACCOUNT_METHODS: &[Variant] = &[
+    variant(
+        "buildAliasOutput",
+        &[
+            ("amount", Str, false),
+            ("nativeTokens", Array, false),
+            ("aliasId", Str, true),
+            ("stateIndex", Integer, false),
+            ("stateMetadata", Array, false),
+            ("foundryCounter", Integer, false),
+            ("unlockConditions", Array, true),
+            ("features", Array, false),
+            ("immutableFeatures", Array, false),
+        ],
+    ),
+    variant(
+        "buildBasicOutput",
+        &[
+            ("amount", Str, false),
+            ("nativeTokens", Array, false),
+            ("unlockConditions", Array, true),
+            ("features", Array, false),
+        ],
+    ),
+    variant(
+        "buildFoundryOutput",
+        &[
+            ("amount", Str, false),
+            ("nativeTokens", Array, false),
+            ("serialNumber", Integer, true),
+            ("tokenScheme", Object, true),
+            ("unlockConditions", Array, true),
+            ("features", Array, false),
+            ("immutableFeatures", Array, false),
+        ],
+    ),
+    variant(
+        "buildNftOutput",
+        &[
+            ("amount", Str, false),
+            ("nativeTokens", Array, false),
+            ("nftId", Str, true),
+            ("unlockConditions", Array, true),
+            ("features", Array, false),
+            ("immutableFeatures", Array, false),
+        ],
+    ),
+    variant(
+        "burnNativeToken",
+        &[("tokenId", Str, true), ("burnAmount", Str, true), TRANSACTION_OPTIONS],
+    ),
+    variant("burnNft", &[("nftId", Str, true), TRANSACTION_OPTIONS]),
+    variant(
+        "consolidateOutputs",
+        &[("force", Boolean, true), ("outputConsolidationThreshold", Integer, false)],
+    ),
+    variant(
+        "createAliasOutput",
+        &[("aliasOutputOptions", Object, false), TRANSACTION_OPTIONS],
+    ),
+    variant("destroyAlias", &[("aliasId", Str, true), TRANSACTION_OPTIONS]),
+    variant(
+        "updateAliasState",
+        &[
+            ("aliasId", Str, true),
+            ("stateMetadata", Str, true),
+            TRANSACTION_OPTIONS,
+        ],
+    ),
+    variant(
+        "updateAliasGovernance",
+        &[("aliasId", Str, true), ("update", Object, true), TRANSACTION_OPTIONS],
+    ),
+    variant(
+        "transferAlias",
+        &[("aliasId", Str, true), ("address", Str, true), TRANSACTION_OPTIONS],
+    ),
+    variant("destroyFoundry", &[("foundryId", Str, true), TRANSACTION_OPTIONS]),
+    variant("generateAddresses", &[("amount", Integer, true), ("options", Object, false)]),
+    variant("getOutput", &[("outputId", Str, true)]),
+    variant("getFoundryOutput", &[("tokenId", Str, true)]),
+    variant(
+        "getOutputsWithAdditionalUnlockConditions",
+        &[("outputsToClaim", Str, true)],
+    ),
+    variant("getTransaction", &[("transactionId", Str, true)]),
+    variant("getIncomingTransactionData", &[("transactionId", Str, true)]),
+    variant("addresses", &[]),
+    variant("addressesWithUnspentOutputs", &[]),
+    variant("outputs", &[("filterOptions", Object, false)]),
+    variant("unspentOutputs", &[("filterOptions", Object, false)]),
+    variant("incomingTransactions", &[]),
+    variant("transactions", &[]),
+    variant("pendingTransactions", &[]),
+    variant(
+        "decreaseNativeTokenSupply",
+        &[("tokenId", Str, true), ("meltAmount", Str, true), TRANSACTION_OPTIONS],
+    ),
+    variant(
+        "updateFoundryMetadata",
+        &[("tokenId", Str, true), ("metadata", Str, true), TRANSACTION_OPTIONS],
+    ),
+    variant("minimumRequiredStorageDeposit", &[("output", Object, true)]),
+    variant(
+        "increaseNativeTokenSupply",
+        &[
+            ("tokenId", Str, true),
+            ("mintAmount", Str, true),
+            ("increaseNativeTokenSupplyOptions", Object, false),
+            TRANSACTION_OPTIONS,
+        ],
+    ),
+    variant(
+        "mintNativeToken",
+        &[("nativeTokenOptions", Object, true), TRANSACTION_OPTIONS],
+    ),
+    variant("mintNfts", &[("nftsOptions", Array, true), TRANSACTION_OPTIONS]),
+    variant(
+        "mintNftCollection",
+        &[
+            ("issuerNftId", Str, true),
+            ("nftsOptions", Array, true),
+            ("maxNftsPerTransaction", Integer, false),
+            ("maxPendingTransactions", Integer, false),
+            TRANSACTION_OPTIONS,
+        ],
+    ),
+    variant(
+        "resumeNftCollectionMinting",
+        &[
+            ("minting", Object, true),
+            ("maxNftsPerTransaction", Integer, false),
+            ("maxPendingTransactions", Integer, false),
+            TRANSACTION_OPTIONS,
+        ],
+    ),
+    variant("getBalance", &[]),
+    variant(
+        "prepareOutput",
+        &[("options", Object, true), ("transactionOptions", Object, false)],
+    ),
+    variant("prepareTransaction", &[("outputs", Array, true), TRANSACTION_OPTIONS]),
+    variant(
+        "prepareSendAmount",
+        &[("addressesWithAmount", Array, true), TRANSACTION_OPTIONS],
+    ),
+    variant(
+        "retryTransactionUntilIncluded",
+        &[
+            ("transactionId", Str, true),
+            ("interval", Integer, false),
+            ("maxAttempts", Integer, false),
+        ],
+    ),
+    variant("syncAccount", &[("options", Object, false)]),
+    variant("cancelSync", &[]),
+    variant("sendAmount", &[("addressesWithAmount", Array, true), TRANSACTION_OPTIONS]),
+    variant(
+        "sendMicroTransaction",
+        &[("addressesWithMicroAmount", Array, true), TRANSACTION_OPTIONS],
+    ),
+    variant(
+        "sendNativeTokens",
+        &[("addressesNativeTokens", Array, true), TRANSACTION_OPTIONS],
+    ),
+    variant("sendNft", &[("addressesAndNftIds", Array, true), TRANSACTION_OPTIONS]),
+    variant("setAlias", &[("alias", Str, true)]),
+    variant("setAddressLabel", &[("address", Str, true), ("label", Str, false)]),
+    variant("setOutputLabel", &[("outputId", Str, true), ("label", Str, false)]),
+    variant("setAccountMetadata", &[("metadata", Object, true)]),
+    variant("searchLabels", &[("query", Str, true)]),
+    variant("sendOutputs", &[("outputs", Array, true), TRANSACTION_OPTIONS]),
+    variant(
+        "signTransactionEssence",
+        &[("preparedTransactionData", Object, true)],
+    ),
+    variant(
+        "submitAndStoreTransaction",
+        &[("signedTransactionData", Object, true)],
+    ),
+    variant("claimOutputs", &[("outputIdsToClaim", Array, true)]),
+    #[cfg(feature = "participation")]
+    variant("vote", &[("eventId", Str, false), ("answers", Array, false)]),
+    #[cfg(feature = "participation")]
+    variant("stopParticipating", &[("eventId", Str, true)]),
+    #[cfg(feature = "participation")]
+    variant("getVotingPower", &[]),
+    #[cfg(feature = "participation")]
+    variant("getParticipationOverview", &[("eventIds", Array, false)]),
+    #[cfg(feature = "participation")]
+    variant("increaseVotingPower", &[("amount", Str, true)]),
+    #[cfg(feature = "participation")]
+    variant("decreaseVotingPower", &[("amount", Str, true)]),
+    #[cfg(feature = "participation")]
+    variant("registerParticipationEvents", &[("options", Object, true)]),
+    #[cfg(feature = "participation")]
+    variant("deregisterParticipationEvent", &[("eventId", Str, true)]),
+    #[cfg(feature = "participation")]
+    variant("getParticipationEvent", &[("eventId", Str, true)]),
+    #[cfg(feature = "participation")]
+    variant(
+        "getParticipationEventIds",
+        &[("node", Object, true), ("eventType", Integer, false)],
+    ),
+    #[cfg(feature = "participation")]
+    variant("getParticipationEventStatus", &[("eventId", Str, true)]),
+    #[cfg(feature = "participation")]
+    variant("getParticipationEvents", &[]),
+    variant("requestFundsFromFaucet", &[("url", Str, true), ("address", Str, true)]),
+    variant("createPaymentRequest", &[("options", Object, true)]),
+    variant("getPaymentRequest", &[("id", Integer, true)]),
+    variant("getPaymentRequests", &[]),
+    variant("cancelPaymentRequest", &[("id", Integer, true)]),
+    variant("getBalanceAt", &[("timestamp", Integer, true)]),
+    variant(
+        "getBalanceHistory",
+        &[("start", Integer, false), ("end", Integer, false)],
+    ),
+    variant("getNativeTokensMetadata", &[]),
+    variant(
+        "setNativeTokenMetadataOverrides",
+        &[("tokenId", Str, true), ("overrides", Object, true)],
+    ),
+    variant(
+        "setNativeTokenTrusted",
+        &[("tokenId", Str, true), ("trusted", Boolean, true)],
+    ),
+    variant("getNfts", &[]),
+    variant("getNftCollections", &[]),
+    variant("getNativeTokenSupply", &[("tokenId", Str, true)]),
+    variant("getNativeTokenSupplies", &[]),
+    variant("getNativeTokenHolders", &[("tokenId", Str, true), ("cursor", Str, false)]),
+    variant("getMessagingKey", &[]),
+    variant("getAddressMessagingKey", &[("address", Str, true)]),
+    variant("prepareMemo", &[("messagingKey", Str, true), ("memo", Str, true)]),
+    variant(
+        "sendMemo",
+        &[
+            ("address", Str, true),
+            ("memo", Str, true),
+            ("messagingKey", Str, false),
+            TRANSACTION_OPTIONS,
+        ],
+    ),
+    variant("sendMemoBlock", &[("messagingKey", Str, true), ("memo", Str, true)]),
+    variant("readMemoBlock", &[("blockId", Str, true)]),
+];
+
+/// Response variants with the JSON type of their payload.
+const RESPONSES: &[(&str, FieldType)] = &[
+    ("account", Object),
+    ("accountIndexes", Array),
+    ("accounts", Array),
+    ("addresses", Array),
+    ("addressesWithUnspentOutputs", Array),
+    ("blockId", Str),
+    ("output", Object),
+    ("minimumRequiredStorageDeposit", Str),
+    ("outputIds", Array),
+    ("outputData", Any),
+    ("outputsData", Array),
+    ("preparedTransaction", Object),
+    ("transaction", Any),
+    ("transactions", Array),
+    ("signedTransactionData", Object),
+    ("generatedAddress", Array),
+    ("balance", Object),
+    #[cfg(feature = "ledger_nano")]
+    ("ledgerNanoStatus", Object),
+    ("incomingTransactionData", Any),
+    ("incomingTransactionsData", Array),
+    ("sentTransaction", Object),
+    ("mintTokenTransaction", Object),
+    ("nftCollectionMinting", Object),
+    ("strongholdPasswordIsAvailable", Boolean),
+    ("error", Object),
+    ("panic", Str),
+    ("generatedMnemonic", Str),
+    ("nodeInfo", Object),
+    #[cfg(feature = "participation")]
+    ("participationEvent", Any),
+    #[cfg(feature = "participation")]
+    ("participationEventIds", Array),
+    #[cfg(feature = "participation")]
+    ("participationEventStatus", Object),
+    #[cfg(feature = "participation")]
+    ("participationEvents", Object),
+    #[cfg(feature = "participation")]
+    ("votingPower", Str),
+    #[cfg(feature = "participation")]
+    ("accountParticipationOverview", Object),
+    ("hexAddress", Str),
+    ("bech32Address", Str),
+    ("faucet", Str),
+    #[cfg(feature = "stronghold")]
+    ("restoreReport", Object),
+    #[cfg(feature = "stronghold")]
+    ("backupSummary", Object),
+    ("labelSearchResult", Object),
+    ("paymentRequest", Object),
+    ("paymentRequests", Array),
+    ("balanceSnapshot", Object),
+    ("balanceHistory", Array),
+    ("nativeTokensMetadata", Object),
+    ("nativeTokenMetadata", Object),
+    ("nfts", Array),
+    ("nftCollections", Array),
+    ("nativeTokenSupply", Object),
+    ("nativeTokenSupplies", Array),
+    ("nativeTokenHolders", Array),
+    ("messagingKey", Str),
+    ("addressMessagingKey", Any),
+    ("memoPayload", Object),
+    ("memo", Object),
+    ("batch", Array),
+    ("schema", Object),
+    ("capabilities", Object),
+    ("backgroundSyncStatus", Array),
+    ("ok", Any),
+];
+
+/// The cargo features the library was compiled with, which determine the available variants.
+pub fn enabled_features() -> Vec<String> {
+    [
+        ("events", cfg!(feature = "events")),
+        ("ledger_nano", cfg!(feature = "ledger_nano")),
+        ("message_interface", cfg!(feature = "message_interface")),
+        ("participation", cfg!(feature = "participation")),
+        ("rocksdb", cfg!(feature = "rocksdb")),
+        ("storage", cfg!(feature = "storage")),
+        ("stronghold", cfg!(feature = "stronghold")),
+    ]
+    .into_iter()
+    .filter_map(|(feature, enabled)| enabled.then(|| feature.to_string()))
+    .collect()
+}
+
+/// Short description of what the message interface of this build supports.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    /// The current protocol version.
+    pub protocol_version: u32,
+    /// The oldest supported protocol version.
+    pub min_supported_protocol_version: u32,
+    /// The version of the wallet library.
+    pub library_version: String,
+    /// The enabled cargo features.
+    pub features: Vec<String>,
+    /// The names of all available messages.
+    pub messages: Vec<String>,
+    /// The names of all available account methods.
+    pub account_methods: Vec<String>,
+    /// The names of all possible responses.
+    pub responses: Vec<String>,
+}
+
+impl Capabilities {
+    /// Get the capabilities of the current build.
+    pub fn current() -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            min_supported_protocol_version: MIN_SUPPORTED_PROTOCOL_VERSION,
+            library_version: env!("CARGO_PKG_VERSION").to_string(),
+            features: enabled_features(),
+            messages: MESSAGES.iter().map(|v| v.name.to_string()).collect(),
+            account_methods: ACCOUNT_METHODS.iter().map(|v| v.name.to_string()).collect(),
+            responses: RESPONSES.iter().map(|(name, _)| name.to_string()).collect(),
+        }
+    }
+}
+
+fn variant_schema(tag: &str, content: &str, variant: &Variant) -> Value {
+    let mut properties = Map::new();
+    properties.insert(tag.to_string(), json!({ "const": variant.name }));
+
+    if !variant.fields.is_empty() {
+        let mut fields = Map::new();
+        let mut required = Vec::new();
+        for (name, field_type, is_required) in variant.fields {
+            fields.insert(name.to_string(), field_type.to_schema());
+            if *is_required {
+                required.push(*name);
+            }
+        }
+        properties.insert(
+            content.to_string(),
+            json!({ "type": "object", "properties": fields, "required": required }),
+        );
+    }
+
+    let required = if variant.fields.iter().any(|(_, _, is_required)| *is_required) {
+        vec![tag, content]
+    } else {
+        vec![tag]
+    };
+
+    json!({ "type": "object", "properties": properties, "required": required })
+}
+
+/// Returns a JSON Schema describing all [`Message`](crate::message_interface::Message),
+/// [`AccountMethod`](crate::message_interface::AccountMethod) and
+/// [`Response`](crate::message_interface::Response) variants available in this build.
+pub fn schema() -> Value {
+    let messages: Vec<Value> = MESSAGES
+        .iter()
+        .map(|variant| {
+            let mut schema = variant_schema("cmd", "payload", variant);
+            schema["properties"][PROTOCOL_VERSION_KEY] = json!({
+                "type": "integer",
+                "minimum": MIN_SUPPORTED_PROTOCOL_VERSION,
+                "maximum": PROTOCOL_VERSION,
+            });
+            schema
+        })
+        .collect();
+    let account_methods: Vec<Value> = ACCOUNT_METHODS
+        .iter()
+        .map(|variant| variant_schema("name", "data", variant))
+        .collect();
+    let responses: Vec<Value> = RESPONSES
+        .iter()
+        .map(|(name, payload)| {
+            json!({
+                "type": "object",
+                "properties": { "type": { "const": name }, "payload": payload.to_schema() },
+                "required": ["type"],
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "IOTA wallet message interface",
+        "protocolVersion": PROTOCOL_VERSION,
+        "minSupportedProtocolVersion": MIN_SUPPORTED_PROTOCOL_VERSION,
+        "features": enabled_features(),
+        "$defs": {
+            "message": { "oneOf": messages },
+            "accountMethod": { "oneOf": account_methods },
+            "response": { "oneOf": responses },
+        },
+        "$ref": "#/$defs/message",
+    })
+}
+
+/// Checks if a message with the provided protocol version can be handled.
+pub fn check_protocol_version(protocol_version: u32) -> crate::Result<()> {
+    if !(MIN_SUPPORTED_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(&protocol_version) {
+        return Err(crate::Error::IncompatibleProtocolVersion {
+            requested: protocol_version,
+            min_supported: MIN_SUPPORTED_PROTOCOL_VERSION,
+            current: PROTOCOL_VERSION,
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn is_known_message(name: &str) -> bool {
+    MESSAGES.iter().any(|v| v.name == name)
+}
+
+pub(crate) fn is_known_account_method(name: &str) -> bool {
+    ACCOUNT_METHODS.iter().any(|v| v.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use serde::{
+        de::{value, DeserializeOwned, DeserializeSeed, Error, IntoDeserializer, MapAccess, Visitor},
+        forward_to_deserialize_any, Deserializer,
+    };
+
+    use super::*;
+    use crate::message_interface::{AccountMethod, Message, Response};
+
+    // Generates an exhaustive match over the variants of an enum and a list of their names, so adding a variant
+    // breaks the build of the tests until it's listed here and `schema_lists_all_variants` checks the tables
+    macro_rules! variants {
+        ($enum:ident, $name_fn:ident, $names:ident, [$($(#[$attr:meta])* $variant:ident,)*]) => {
+            fn $name_fn(value: &$enum) -> &'static str {
+                match value {
+                    $($(#[$attr])* $enum::$variant { .. } => stringify!($variant),)*
+                }
+            }
+            const $names: &[&str] = &[$($(#[$attr])* stringify!($variant),)*];
+        };
+    }
+
+    // Like `variants!`, but the match destructures every field, so adding or removing a field also breaks the build
+    // until it's listed here and `schema_lists_all_fields` checks the table of the variant
+    macro_rules! variants_with_fields {
+        (
+            $enum:ident,
+            $name_fn:ident,
+            $names:ident,
+            [$($(#[$attr:meta])* $variant:ident { $($field:ident),* $(,)? },)*]
+        ) => {
+            fn $name_fn(value: &$enum) -> &'static str {
+                match value {
+                    $($(#[$attr])* $enum::$variant { $($field: _),* } => stringify!($variant),)*
+                }
+            }
+            const $names: &[(&str, &[&str])] = &[$($(#[$attr])* (stringify!($variant), &[$(stringify!($field)),*]),)*];
+        };
+    }
+
+    variants_with_fields!(
+        Message,
+        message_name,
+        MESSAGE_VARIANTS,
+        [
+            CreateAccount { alias, bech32_hrp },
+            GetAccount { account_id },
+            GetAccountIndexes {},
+            GetAccounts {},
+            SearchAccounts { query },
+            CallAccountMethod { account_id, method },
+            #[cfg(feature = "stronghold")]
+            Backup { destination, password },
+            BackupToFile {
+                destination,
+                password,
+                include_secret_manager
+            },
+            #[cfg(feature = "stronghold")]
+            ChangeStrongholdPassword {
+                current_password,
+                new_password
+            },
+            #[cfg(feature = "stronghold")]
+            ClearStrongholdPassword {},
+            #[cfg(feature = "stronghold")]
+            IsStrongholdPasswordAvailable {},
+            RecoverAccounts {
+                account_start_index,
+                account_gap_limit,
+                address_gap_limit,
+                sync_options
+            },
+            #[cfg(feature = "stronghold")]
+            RestoreBackup {
+                source,
+                password,
+                ignore_if_coin_type_mismatch
+            },
+            #[cfg(feature = "stronghold")]
+            RestoreBackupWithOptions {
+                source,
+                password,
+                options
+            },
+            RestoreFromFile {
+                source,
+                password,
+                ignore_if_coin_type_mismatch
+            },
+            #[cfg(feature = "stronghold")]
+            VerifyBackup { source, password },
+            RemoveLatestAccount {},
+            GenerateMnemonic {},
+            VerifyMnemonic { mnemonic },
+            SetClientOptions { client_options },
+            GenerateAddress {
+                account_index,
+                internal,
+                address_index,
+                options,
+                bech32_hrp
+            },
+            #[cfg(feature = "ledger_nano")]
+            GetLedgerNanoStatus {},
+            GetNodeInfo { url, auth },
+            #[cfg(feature = "stronghold")]
+            SetStrongholdPassword { password },
+            #[cfg(feature = "stronghold")]
+            SetStrongholdPasswordClearInterval {
+                interval_in_milliseconds
+            },
+            #[cfg(feature = "stronghold")]
+            StoreMnemonic { mnemonic },
+            StartBackgroundSync {
+                options,
+                interval_in_milliseconds
+            },
+            StopBackgroundSync {},
+            SetBackgroundSyncOptions {
+                account_id,
+                options,
+                interval_in_milliseconds
+            },
+            GetBackgroundSyncStatus {},
+            #[cfg(feature = "stronghold")]
+            StartAutoBackup {
+                directory,
+                password,
+                keep,
+                interval_in_milliseconds
+            },
+            #[cfg(feature = "stronghold")]
+            StopAutoBackup {},
+            #[cfg(feature = "events")]
+            EmitTestEvent { event },
+            Bech32ToHex { bech32_address },
+            HexToBech32 { hex, bech32_hrp },
+            #[cfg(feature = "events")]
+            ClearListeners { event_types },
+            UpdateNodeAuth { url, auth },
+            Batch { messages, atomic },
+            GetSchema {},
+            GetCapabilities {},
+        ]
+    );
+
+    variants_with_fields!(
+        AccountMethod,
+        account_method_name,
+        ACCOUNT_METHOD_VARIANTS,
+        [
+            BuildAliasOutput {
+                amount,
+                native_tokens,
+                alias_id,
+                state_index,
+                state_metadata,
+                foundry_counter,
+                unlock_conditions,
+                features,
+                immutable_features,
+            },
+            BuildBasicOutput {
+                amount,
+                native_tokens,
+                unlock_conditions,
+                features
+            },
+            BuildFoundryOutput {
+                amount,
+                native_tokens,
+                serial_number,
+                token_scheme,
+                unlock_conditions,
+                features,
+                immutable_features,
+            },
+            BuildNftOutput {
+                amount,
+                native_tokens,
+                nft_id,
+                unlock_conditions,
+                features,
+                immutable_features
+            },
+            BurnNativeToken {
+                token_id,
+                burn_amount,
+                options
+            },
+            BurnNft { nft_id, options },
+            ConsolidateOutputs {
+                force,
+                output_consolidation_threshold
+            },
+            CreateAliasOutput {
+                alias_output_options,
+                options
+            },
+            DestroyAlias { alias_id, options },
+            UpdateAliasState {
+                alias_id,
+                state_metadata,
+                options
+            },
+            UpdateAliasGovernance {
+                alias_id,
+                update,
+                options
+            },
+            TransferAlias {
+                alias_id,
+                address,
+                options
+            },
+            DestroyFoundry { foundry_id, options },
+            GenerateAddresses { amount, options },
+            GetOutput { output_id },
+            GetFoundryOutput { token_id },
+            GetOutputsWithAdditionalUnlockConditions { outputs_to_claim },
+            GetTransaction { transaction_id },
+            GetIncomingTransactionData { transaction_id },
+            Addresses {},
+            AddressesWithUnspentOutputs {},
+            Outputs { filter_options },
+            UnspentOutputs { filter_options },
+            IncomingTransactions {},
+            Transactions {},
+            PendingTransactions {},
+            DecreaseNativeTokenSupply {
+                token_id,
+                melt_amount,
+                options
+            },
+            UpdateFoundryMetadata {
+                token_id,
+                metadata,
+                options
+            },
+            MinimumRequiredStorageDeposit { output },
+            IncreaseNativeTokenSupply {
+                token_id,
+                mint_amount,
+                increase_native_token_supply_options,
+                options
+            },
+            MintNativeToken {
+                native_token_options,
+                options
+            },
+            MintNfts { nfts_options, options },
+            MintNftCollection {
+                issuer_nft_id,
+                nfts_options,
+                max_nfts_per_transaction,
+                max_pending_transactions,
+                options,
+            },
+            ResumeNftCollectionMinting {
+                minting,
+                max_nfts_per_transaction,
+                max_pending_transactions,
+                options
+            },
+            GetBalance {},
+            PrepareOutput {
+                options,
+                transaction_options
+            },
+            PrepareTransaction { outputs, options },
+            PrepareSendAmount {
+                addresses_with_amount,
+                options
+            },
+            RetryTransactionUntilIncluded {
+                transaction_id,
+                interval,
+                max_attempts
+            },
+            SyncAccount { options },
+            CancelSync {},
+            SendAmount {
+                addresses_with_amount,
+                options
+            },
+            SendMicroTransaction {
+                addresses_with_micro_amount,
+                options
+            },
+            SendNativeTokens {
+                addresses_native_tokens,
+                options
+            },
+            SendNft {
+                addresses_nft_ids,
+                options
+            },
+            SetAlias { alias },
+            SetAddressLabel { address, label },
+            SetOutputLabel { output_id, label },
+            SetAccountMetadata { metadata },
+            SearchLabels { query },
+            SendOutputs { outputs, options },
+            SignTransactionEssence {
+                prepared_transaction_data
+            },
+            SubmitAndStoreTransaction {
+                signed_transaction_data
+            },
+            ClaimOutputs { output_ids_to_claim },
+            #[cfg(feature = "participation")]
+            Vote { event_id, answers },
+            #[cfg(feature = "participation")]
+            StopParticipating { event_id },
+            #[cfg(feature = "participation")]
+            GetVotingPower {},
+            #[cfg(feature = "participation")]
+            GetParticipationOverview { event_ids },
+            #[cfg(feature = "participation")]
+            IncreaseVotingPower { amount },
+            #[cfg(feature = "participation")]
+            DecreaseVotingPower { amount },
+            #[cfg(feature = "participation")]
+            RegisterParticipationEvents { options },
+            #[cfg(feature = "participation")]
+            DeregisterParticipationEvent { event_id },
+            #[cfg(feature = "participation")]
+            GetParticipationEvent { event_id },
+            #[cfg(feature = "participation")]
+            GetParticipationEventIds { node, event_type },
+            #[cfg(feature = "participation")]
+            GetParticipationEventStatus { event_id },
+            #[cfg(feature = "participation")]
+            GetParticipationEvents {},
+            RequestFundsFromFaucet { url, address },
+            CreatePaymentRequest { options },
+            GetPaymentRequest { id },
+            GetPaymentRequests {},
+            CancelPaymentRequest { id },
+            GetBalanceAt { timestamp },
+            GetBalanceHistory { start, end },
+            GetNativeTokensMetadata {},
+            SetNativeTokenMetadataOverrides { token_id, overrides },
+            SetNativeTokenTrusted { token_id, trusted },
+            GetNfts {},
+            GetNftCollections {},
+            GetNativeTokenSupply { token_id },
+            GetNativeTokenSupplies {},
+            GetNativeTokenHolders { token_id, cursor },
+            GetMessagingKey {},
+            GetAddressMessagingKey { address },
+            PrepareMemo { messaging_key, memo },
+            SendMemo {
+                address,
+                memo,
+                messaging_key,
+                options
+            },
+            SendMemoBlock { messaging_key, memo },
+            ReadMemoBlock { block_id },
+        ]
+    );
+
+    variants!(
+        Response,
+        response_name,
+        RESPONSE_VARIANTS,
+        [
+            Account,
+            AccountIndexes,
+            Accounts,
+            Addresses,
+            AddressesWithUnspentOutputs,
+            BlockId,
+            Output,
+            MinimumRequiredStorageDeposit,
+            OutputIds,
+            OutputData,
+            OutputsData,
+            PreparedTransaction,
+            Transaction,
+            Transactions,
+            SignedTransactionData,
+            GeneratedAddress,
+            Balance,
+            BackgroundSyncStatus,
+            #[cfg(feature = "ledger_nano")]
+            LedgerNanoStatus,
+            IncomingTransactionData,
+            IncomingTransactionsData,
+            SentTransaction,
+            MintTokenTransaction,
+            NftCollectionMinting,
+            StrongholdPasswordIsAvailable,
+            Error,
+            Panic,
+            GeneratedMnemonic,
+            NodeInfo,
+            #[cfg(feature = "participation")]
+            ParticipationEvent,
+            #[cfg(feature = "participation")]
+            ParticipationEventIds,
+            #[cfg(feature = "participation")]
+            ParticipationEventStatus,
+            #[cfg(feature = "participation")]
+            ParticipationEvents,
+            #[cfg(feature = "participation")]
+            VotingPower,
+            #[cfg(feature = "participation")]
+            AccountParticipationOverview,
+            HexAddress,
+            Bech32Address,
+            Faucet,
+            #[cfg(feature = "stronghold")]
+            RestoreReport,
+            #[cfg(feature = "stronghold")]
+            BackupSummary,
+            LabelSearchResult,
+            PaymentRequest,
+            PaymentRequests,
+            BalanceSnapshot,
+            BalanceHistory,
+            NativeTokensMetadata,
+            NativeTokenMetadata,
+            Nfts,
+            NftCollections,
+            NativeTokenSupply,
+            NativeTokenSupplies,
+            NativeTokenHolders,
+            MessagingKey,
+            AddressMessagingKey,
+            MemoPayload,
+            Memo,
+            Batch,
+            Schema,
+            Capabilities,
+            Ok,
+        ]
+    );
+
+    // The serde name of a variant with `rename_all = "camelCase"`
+    fn camel_case(variant: &str) -> String {
+        let mut chars = variant.chars();
+        chars
+            .next()
+            .map(|first| first.to_lowercase().chain(chars).collect())
+            .unwrap_or_default()
+    }
+
+    fn sorted_names(names: impl IntoIterator<Item = String>) -> Vec<String> {
+        let mut names = names.into_iter().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    // Deserializes `{ tag: variant, content: { field: .. } }` and returns if `field` is a field of the variant. Serde
+    // deserializes the values of unknown fields as `IgnoredAny`, known fields ask for their own type.
+    fn is_serde_field<T: DeserializeOwned>(tag: &str, content: &str, variant: &str, field: &str) -> bool {
+        let known = Cell::new(None);
+        let probe = Probe {
+            value: ProbeValue::Map(vec![
+                (tag, ProbeValue::Str(variant)),
+                (content, ProbeValue::Content(field)),
+            ]),
+            known: &known,
+        };
+        // The probe always fails after it recorded the field
+        assert!(T::deserialize(probe).is_err());
+        known.get().expect("the field wasn't deserialized")
+    }
+
+    enum ProbeValue<'a> {
+        Map(Vec<(&'a str, ProbeValue<'a>)>),
+        Str(&'a str),
+        // The content of the variant with a single field
+        Content(&'a str),
+        Field,
+    }
+
+    struct Probe<'a> {
+        value: ProbeValue<'a>,
+        known: &'a Cell<Option<bool>>,
+    }
+
+    impl<'de> Deserializer<'de> for Probe<'_> {
+        type Error = value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            match self.value {
+                ProbeValue::Map(entries) => visitor.visit_map(ProbeMap {
+                    entries: entries.into_iter(),
+                    value: None,
+                    known: self.known,
+                }),
+                ProbeValue::Str(value) => visitor.visit_str(value),
+                ProbeValue::Content(field) => visitor.visit_map(ProbeMap {
+                    entries: vec![(field, ProbeValue::Field)].into_iter(),
+                    value: None,
+                    known: self.known,
+                }),
+                ProbeValue::Field => {
+                    self.known.set(Some(true));
+                    Err(Error::custom("known field"))
+                }
+            }
+        }
+
+        fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            match self.value {
+                ProbeValue::Field => {
+                    self.known.set(Some(false));
+                    Err(Error::custom("unknown field"))
+                }
+                _ => self.deserialize_any(visitor),
+            }
+        }
+
+        forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit
+            unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier
+        }
+    }
+
+    struct ProbeMap<'a> {
+        entries: std::vec::IntoIter<(&'a str, ProbeValue<'a>)>,
+        value: Option<ProbeValue<'a>>,
+        known: &'a Cell<Option<bool>>,
+    }
+
+    impl<'de> MapAccess<'de> for ProbeMap<'_> {
+        type Error = value::Error;
+
+        fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+            self.entries
+                .next()
+                .map(|(key, value)| {
+                    self.value = Some(value);
+                    seed.deserialize(key.into_deserializer())
+                })
+                .transpose()
+        }
+
+        fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+            seed.deserialize(Probe {
+                value: self.value.take().expect("value without key"),
+                known: self.known,
+            })
+        }
+    }
+
+    #[test]
+    fn known_variants() {
+        assert!(is_known_message("getAccounts"));
+        assert!(is_known_message("callAccountMethod"));
+        assert!(!is_known_message("getAccountz"));
+        assert!(is_known_account_method("sendAmount"));
+        assert!(!is_known_account_method("sendAmounts"));
+    }
+
+    #[test]
+    fn unit_messages_match_serialization() {
+        for message in [
+            Message::GetAccountIndexes,
+            Message::GetAccounts,
+            Message::GenerateMnemonic,
+            Message::StopBackgroundSync,
+            Message::GetBackgroundSyncStatus,
+            Message::GetSchema,
+            Message::GetCapabilities,
+        ] {
+            let serialized = serde_json::to_value(&message).unwrap();
+            assert_eq!(serialized["cmd"], camel_case(message_name(&message)));
+            assert!(is_known_message(serialized["cmd"].as_str().unwrap()));
+        }
+
+        let method = AccountMethod::GetBalance;
+        assert_eq!(
+            serde_json::to_value(&method).unwrap()["name"],
+            camel_case(account_method_name(&method))
+        );
+        let response = Response::Ok(());
+        assert_eq!(
+            serde_json::to_value(&response).unwrap()["type"],
+            camel_case(response_name(&response))
+        );
+    }
+
+    #[test]
+    fn protocol_version() {
+        assert!(check_protocol_version(PROTOCOL_VERSION).is_ok());
+        assert!(check_protocol_version(PROTOCOL_VERSION + 1).is_err());
+    }
+
+    #[test]
+    fn schema_lists_all_variants() {
+        assert_eq!(
+            sorted_names(MESSAGES.iter().map(|v| v.name.to_string())),
+            sorted_names(MESSAGE_VARIANTS.iter().map(|(v, _)| camel_case(v)))
+        );
+        assert_eq!(
+            sorted_names(ACCOUNT_METHODS.iter().map(|v| v.name.to_string())),
+            sorted_names(ACCOUNT_METHOD_VARIANTS.iter().map(|(v, _)| camel_case(v)))
+        );
+        assert_eq!(
+            sorted_names(RESPONSES.iter().map(|(name, _)| name.to_string())),
+            sorted_names(RESPONSE_VARIANTS.iter().map(|v| camel_case(v)))
+        );
+
+        let schema = schema();
+        assert_eq!(
+            schema["$defs"]["message"]["oneOf"].as_array().unwrap().len(),
+            MESSAGES.len()
+        );
+        assert_eq!(
+            schema["$defs"]["accountMethod"]["oneOf"].as_array().unwrap().len(),
+            ACCOUNT_METHODS.len()
+        );
+    }
+    #[test]
+    fn schema_lists_all_fields() {
+        fn check_fields<T: DeserializeOwned>(
+            tag: &str,
+            content: &str,
+            table: &[Variant],
+            variants: &[(&str, &[&str])],
+        ) {
+            for (variant, fields) in variants {
+                let name = camel_case(variant);
+                let table_variant = table.iter().find(|v| v.name == name).unwrap();
+                // The table has as many fields as the variant and all of them are serde fields of the variant
+                assert_eq!(table_variant.fields.len(), fields.len(), "fields of {name}");
+                for (field, _, _) in table_variant.fields {
+                    assert!(
+                        is_serde_field::<T>(tag, content, &name, field),
+                        "{name} has no field {field}"
+                    );
+                }
+            }
+        }
+
+        check_fields::<Message>("cmd", "payload", MESSAGES, MESSAGE_VARIANTS);
+        check_fields::<AccountMethod>("name", "data", ACCOUNT_METHODS, ACCOUNT_METHOD_VARIANTS);
+    }
+}
@@ -18,3 +18,27 @@ where
 {
     Ok(future.await)
 }
+
+/// Run the future returned by `process` in a new thread with its own runtime, used for the processes of the account
+/// manager that run in the background. Returns an error if the runtime can't be created.
+pub(crate) fn spawn_background_process<P, F>(
+    #[cfg_attr(target_family = "wasm", allow(unused_variables))] multi_thread: bool,
+    process: P,
+) -> crate::Result<()>
+where
+    P: FnOnce() -> F + Send + 'static,
+    F: futures::Future<Output = ()>,
+{
+    #[cfg(not(target_family = "wasm"))]
+    let mut runtime_builder = if multi_thread {
+        tokio::runtime::Builder::new_multi_thread()
+    } else {
+        tokio::runtime::Builder::new_current_thread()
+    };
+    #[cfg(target_family = "wasm")]
+    let mut runtime_builder = tokio::runtime::Builder::new_current_thread();
+    let runtime = runtime_builder.enable_all().build()?;
+
+    std::thread::spawn(move || runtime.block_on(process()));
+    Ok(())
+}
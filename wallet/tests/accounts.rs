@@ -3,7 +3,10 @@
 
 mod common;
 
-use iota_wallet::Result;
+use iota_wallet::{
+    account::types::{AccountMetadata, PaymentRequestOptions},
+    Result,
+};
 #[cfg(feature = "stronghold")]
 use {
     iota_client::{constants::SHIMMER_COIN_TYPE, secret::stronghold::StrongholdSecretManager},
@@ -168,6 +171,72 @@ async fn account_rename_alias() -> Result<()> {
     common::tear_down(storage_path)
 }
 
+#[tokio::test]
+async fn account_labels_and_metadata() -> Result<()> {
+    let storage_path = "test-storage/account_labels_and_metadata";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let account = manager
+        .create_account()
+        .with_alias("Alice".to_string())
+        .finish()
+        .await?;
+
+    let address = account.addresses().await?[0].address().to_bech32();
+    account
+        .set_address_label(&address, Some("Salary".to_string()))
+        .await?;
+    assert!(account.set_address_label("rms1invalid", None).await.is_err());
+
+    let search_result = account.search_labels("salary").await;
+    assert_eq!(search_result.addresses.len(), 1);
+    assert_eq!(search_result.addresses[0].label().as_deref(), Some("Salary"));
+    assert!(account.search_labels("rent").await.addresses.is_empty());
+
+    let metadata = AccountMetadata {
+        description: Some("Savings".to_string()),
+        archived: true,
+        ..Default::default()
+    };
+    account.set_metadata(metadata.clone()).await?;
+    assert_eq!(account.read().await.metadata(), &metadata);
+
+    assert_eq!(manager.search_accounts("savings").await.len(), 1);
+    assert_eq!(manager.search_accounts("ALICE").await.len(), 1);
+    assert!(manager.search_accounts("bob").await.is_empty());
+
+    common::tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn account_payment_request_ids_and_tags() -> Result<()> {
+    let storage_path = "test-storage/account_payment_request_ids_and_tags";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let account = manager.create_account().finish().await?;
+
+    let tagged_options = PaymentRequestOptions {
+        amount: 1_000_000,
+        tag: Some(b"order-1".to_vec()),
+        ..Default::default()
+    };
+    let tagged = account.create_payment_request(tagged_options.clone()).await?;
+    let untagged = account.create_payment_request(PaymentRequestOptions::default()).await?;
+    assert_eq!((tagged.id, untagged.id), (0, 1));
+
+    // Only one open request can have a tag, because it would be paid by the same outputs
+    assert!(account.create_payment_request(tagged_options.clone()).await.is_err());
+
+    // The id of a cancelled request isn't used again
+    account.cancel_payment_request(untagged.id).await?;
+    account.cancel_payment_request(tagged.id).await?;
+    assert_eq!(account.create_payment_request(tagged_options).await?.id, 2);
+
+    common::tear_down(storage_path)
+}
+
 #[tokio::test]
 async fn account_first_address_exists() -> Result<()> {
     let storage_path = "test-storage/account_first_address_exists";
@@ -0,0 +1,76 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+use iota_client::block::output::Output;
+use iota_wallet::{account::AliasGovernanceUpdate, Result};
+
+#[ignore]
+#[tokio::test]
+async fn update_and_transfer_alias() -> Result<()> {
+    let storage_path = "test-storage/update_and_transfer_alias";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let accounts = &common::create_accounts_with_funds(&manager, 2).await?;
+
+    let transaction = accounts[0].create_alias_output(None, None).await?;
+    accounts[0]
+        .retry_transaction_until_included(&transaction.transaction_id, None, None)
+        .await?;
+    let balance = accounts[0].sync(None).await?;
+    let alias_id = balance.aliases[0];
+
+    let transaction = accounts[0]
+        .update_alias_state(alias_id, b"new state".to_vec(), None)
+        .await?;
+    accounts[0]
+        .retry_transaction_until_included(&transaction.transaction_id, None, None)
+        .await?;
+    accounts[0].sync(None).await?;
+
+    let transaction = accounts[0]
+        .update_alias_governance(
+            alias_id,
+            AliasGovernanceUpdate {
+                metadata: Some(b"some alias metadata".to_vec()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await?;
+    accounts[0]
+        .retry_transaction_until_included(&transaction.transaction_id, None, None)
+        .await?;
+    accounts[0].sync(None).await?;
+
+    let alias_output = accounts[0]
+        .unspent_outputs(None)
+        .await?
+        .into_iter()
+        .find_map(|output_data| match output_data.output {
+            Output::Alias(alias) if alias.alias_id_non_null(&output_data.output_id) == alias_id => Some(alias),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(alias_output.state_index(), 1);
+    assert_eq!(alias_output.state_metadata(), b"new state");
+    assert_eq!(
+        alias_output.features().metadata().unwrap().data(),
+        b"some alias metadata"
+    );
+
+    let transaction = accounts[0]
+        .transfer_alias(alias_id, accounts[1].addresses().await?[0].address().to_bech32(), None)
+        .await?;
+    accounts[0]
+        .retry_transaction_until_included(&transaction.transaction_id, None, None)
+        .await?;
+    let balance = accounts[0].sync(None).await?;
+    assert!(!balance.aliases.contains(&alias_id));
+    let balance = accounts[1].sync(None).await?;
+    assert!(balance.aliases.contains(&alias_id));
+
+    common::tear_down(storage_path)
+}
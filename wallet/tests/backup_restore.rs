@@ -14,7 +14,10 @@ use iota_client::{
     secret::{mnemonic::MnemonicSecretManager, stronghold::StrongholdSecretManager, SecretManager},
 };
 #[cfg(all(feature = "stronghold", feature = "storage"))]
-use iota_wallet::{account_manager::AccountManager, ClientOptions, Result};
+use iota_wallet::{
+    account_manager::{AccountManager, RestoreOptions},
+    ClientOptions, Result,
+};
 
 #[tokio::test]
 #[cfg(all(feature = "stronghold", feature = "storage"))]
@@ -464,3 +467,412 @@ async fn backup_and_restore_different_coin_type_dont_ignore() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[tokio::test]
+#[cfg(all(feature = "stronghold", feature = "storage"))]
+// Backup and restore with the encrypted backup file
+async fn backup_and_restore_file() -> Result<()> {
+    let storage_path = "test-storage/backup_and_restore_file";
+    common::setup(storage_path)?;
+
+    let client_options = ClientOptions::new().with_node(common::NODE_LOCAL)?;
+
+    let secret_manager = MnemonicSecretManager::try_from_mnemonic(
+        "inhale gorilla deny three celery song category owner lottery rent author wealth penalty crawl hobby obtain glad warm early rain clutch slab august bleak",
+    )?;
+
+    let manager = AccountManager::builder()
+        .with_secret_manager(SecretManager::Mnemonic(secret_manager))
+        .with_client_options(client_options.clone())
+        .with_coin_type(SHIMMER_COIN_TYPE)
+        .with_storage_path("test-storage/backup_and_restore_file/1")
+        .finish()
+        .await?;
+
+    let account = manager
+        .create_account()
+        .with_alias("Alice".to_string())
+        .finish()
+        .await?;
+
+    let password = "some_hopefully_secure_password";
+
+    std::fs::create_dir_all(storage_path).unwrap_or(());
+    manager
+        .backup_to_file(
+            PathBuf::from("test-storage/backup_and_restore_file/backup.json"),
+            password.to_string(),
+            true,
+        )
+        .await?;
+
+    // The backup file can be read without the password
+    let backup_file: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(
+        "test-storage/backup_and_restore_file/backup.json",
+    )?)?;
+    assert_eq!(backup_file["format"], "iota-wallet-backup");
+    assert_eq!(backup_file["version"], 1);
+
+    // restore from backup
+
+    let secret_manager = MnemonicSecretManager::try_from_mnemonic(
+        "inhale gorilla deny three celery song category owner lottery rent author wealth penalty crawl hobby obtain glad warm early rain clutch slab august bleak",
+    )?;
+
+    let restore_manager = AccountManager::builder()
+        .with_storage_path("test-storage/backup_and_restore_file/2")
+        .with_secret_manager(SecretManager::Mnemonic(secret_manager))
+        // Build with a different coin type, to check if it gets replaced by the one from the backup
+        .with_coin_type(IOTA_COIN_TYPE)
+        .with_client_options(ClientOptions::new().with_node(common::NODE_OTHER)?)
+        .finish()
+        .await?;
+
+    // Wrong password fails
+    restore_manager
+        .restore_from_file(
+            PathBuf::from("test-storage/backup_and_restore_file/backup.json"),
+            "wrong password".to_string(),
+            None,
+        )
+        .await
+        .unwrap_err();
+
+    restore_manager
+        .restore_from_file(
+            PathBuf::from("test-storage/backup_and_restore_file/backup.json"),
+            password.to_string(),
+            None,
+        )
+        .await?;
+
+    // Validate restored data
+
+    // Restored coin type is used
+    let new_account = restore_manager.create_account().finish().await?;
+    assert_eq!(new_account.read().await.coin_type(), &SHIMMER_COIN_TYPE);
+
+    // compare restored client options
+    let client_options = restore_manager.get_client_options().await;
+    let node_dto = NodeDto::Node(Node::from(Url::parse(common::NODE_LOCAL).unwrap()));
+    assert!(client_options.node_manager_builder.nodes.contains(&node_dto));
+
+    // Get account
+    let recovered_account = restore_manager.get_account("Alice").await?;
+    assert_eq!(account.addresses().await?, recovered_account.addresses().await?);
+
+    common::tear_down(storage_path)
+}
+
+#[tokio::test]
+#[cfg(all(feature = "stronghold", feature = "storage"))]
+// Restore only the secret manager or coin type, while there are accounts
+async fn backup_and_restore_keep_existing_accounts() -> Result<()> {
+    let storage_path = "test-storage/backup_and_restore_keep_existing_accounts";
+    common::setup(storage_path)?;
+
+    let stronghold_password = "some_hopefully_secure_password";
+
+    // Create directory if not existing, because stronghold panics otherwise
+    std::fs::create_dir_all(storage_path).unwrap_or(());
+    let mut stronghold = StrongholdSecretManager::builder()
+        .password(stronghold_password)
+        .build(PathBuf::from(
+            "test-storage/backup_and_restore_keep_existing_accounts/1.stronghold",
+        ))?;
+    stronghold
+        .store_mnemonic(common::DEFAULT_MNEMONIC.to_string())
+        .await
+        .unwrap();
+
+    let manager = AccountManager::builder()
+        .with_secret_manager(SecretManager::Stronghold(stronghold))
+        .with_client_options(ClientOptions::new().with_node(common::NODE_LOCAL)?)
+        .with_coin_type(SHIMMER_COIN_TYPE)
+        .with_storage_path("test-storage/backup_and_restore_keep_existing_accounts/1")
+        .finish()
+        .await?;
+    manager
+        .create_account()
+        .with_alias("Alice".to_string())
+        .finish()
+        .await?;
+    manager
+        .backup(
+            PathBuf::from("test-storage/backup_and_restore_keep_existing_accounts/backup.stronghold"),
+            stronghold_password.to_string(),
+        )
+        .await?;
+
+    let options = RestoreOptions {
+        client_options: false,
+        accounts: false,
+        ..Default::default()
+    };
+
+    // The existing account of another seed can't be kept with the secret manager from the backup
+    let other_manager =
+        common::make_manager("test-storage/backup_and_restore_keep_existing_accounts/2", None, None).await?;
+    other_manager.create_account().finish().await?;
+    other_manager
+        .restore_backup_with_options(
+            PathBuf::from("test-storage/backup_and_restore_keep_existing_accounts/backup.stronghold"),
+            stronghold_password.to_string(),
+            options,
+        )
+        .await
+        .unwrap_err();
+    // The secret manager wasn't replaced
+    assert!(matches!(
+        &*other_manager.get_secret_manager().read().await,
+        SecretManager::Mnemonic(_)
+    ));
+
+    // The existing account of another coin type can't be kept with the coin type from the backup
+    let iota_manager = AccountManager::builder()
+        .with_secret_manager(SecretManager::Mnemonic(MnemonicSecretManager::try_from_mnemonic(
+            common::DEFAULT_MNEMONIC,
+        )?))
+        .with_client_options(ClientOptions::new().with_node(common::NODE_LOCAL)?)
+        .with_coin_type(IOTA_COIN_TYPE)
+        .with_storage_path("test-storage/backup_and_restore_keep_existing_accounts/3")
+        .finish()
+        .await?;
+    iota_manager.create_account().finish().await?;
+    iota_manager
+        .restore_backup_with_options(
+            PathBuf::from("test-storage/backup_and_restore_keep_existing_accounts/backup.stronghold"),
+            stronghold_password.to_string(),
+            RestoreOptions {
+                secret_manager: false,
+                ..options
+            },
+        )
+        .await
+        .unwrap_err();
+    let new_account = iota_manager.create_account().finish().await?;
+    assert_eq!(new_account.read().await.coin_type(), &IOTA_COIN_TYPE);
+
+    common::tear_down(storage_path)
+}
+
+#[tokio::test]
+#[cfg(all(feature = "stronghold", feature = "storage"))]
+// Restore only the accounts and merge them with the existing ones
+async fn backup_and_restore_merge_accounts() -> Result<()> {
+    let storage_path = "test-storage/backup_and_restore_merge_accounts";
+    common::setup(storage_path)?;
+
+    let mnemonic = "inhale gorilla deny three celery song category owner lottery rent author wealth penalty crawl hobby obtain glad warm early rain clutch slab august bleak";
+
+    let manager =
+        common::make_manager("test-storage/backup_and_restore_merge_accounts/1", Some(mnemonic), None).await?;
+    manager
+        .create_account()
+        .with_alias("Alice".to_string())
+        .finish()
+        .await?;
+    manager.create_account().with_alias("Bob".to_string()).finish().await?;
+
+    std::fs::create_dir_all(storage_path).unwrap_or(());
+    let stronghold_password = "some_hopefully_secure_password";
+    manager
+        .backup(
+            PathBuf::from("test-storage/backup_and_restore_merge_accounts/backup.stronghold"),
+            stronghold_password.to_string(),
+        )
+        .await?;
+
+    let options = RestoreOptions {
+        client_options: false,
+        secret_manager: false,
+        merge_accounts: true,
+        ..Default::default()
+    };
+
+    // Accounts from another seed can't be merged
+    let other_manager = common::make_manager(
+        "test-storage/backup_and_restore_merge_accounts/2",
+        None,
+        Some(common::NODE_OTHER),
+    )
+    .await?;
+    other_manager
+        .restore_backup_with_options(
+            PathBuf::from("test-storage/backup_and_restore_merge_accounts/backup.stronghold"),
+            stronghold_password.to_string(),
+            options,
+        )
+        .await
+        .unwrap_err();
+    // Also not without merging, if the secret manager isn't restored
+    other_manager
+        .restore_backup_with_options(
+            PathBuf::from("test-storage/backup_and_restore_merge_accounts/backup.stronghold"),
+            stronghold_password.to_string(),
+            RestoreOptions {
+                merge_accounts: false,
+                ..options
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(other_manager.get_accounts().await?.is_empty());
+
+    let restore_manager = common::make_manager(
+        "test-storage/backup_and_restore_merge_accounts/3",
+        Some(mnemonic),
+        Some(common::NODE_OTHER),
+    )
+    .await?;
+    let existing_account = restore_manager
+        .create_account()
+        .with_alias("Existing".to_string())
+        .finish()
+        .await?;
+
+    let report = restore_manager
+        .restore_backup_with_options(
+            PathBuf::from("test-storage/backup_and_restore_merge_accounts/backup.stronghold"),
+            stronghold_password.to_string(),
+            options,
+        )
+        .await?;
+
+    assert!(!report.client_options);
+    assert!(!report.secret_manager);
+    assert_eq!(report.coin_type, Some(SHIMMER_COIN_TYPE));
+    assert_eq!(report.added_accounts, vec![1]);
+    assert_eq!(report.merged_accounts.len(), 1);
+    assert_eq!(report.merged_accounts[0].previous_alias, "Existing");
+    assert_eq!(report.merged_accounts[0].alias, "Alice");
+
+    // The existing account got the alias from the backup
+    assert_eq!(existing_account.read().await.alias(), "Alice");
+    restore_manager.get_account("Bob").await?;
+
+    // Client options weren't restored
+    let client_options = restore_manager.get_client_options().await;
+    let node_dto = NodeDto::Node(Node::from(Url::parse(common::NODE_OTHER).unwrap()));
+    assert!(client_options.node_manager_builder.nodes.contains(&node_dto));
+
+    common::tear_down(storage_path)
+}
+
+#[tokio::test]
+#[cfg(all(feature = "stronghold", feature = "storage"))]
+// Verify a backup without restoring it
+async fn backup_and_verify() -> Result<()> {
+    let storage_path = "test-storage/backup_and_verify";
+    common::setup(storage_path)?;
+
+    let stronghold_password = "some_hopefully_secure_password";
+
+    // Create directory if not existing, because stronghold panics otherwise
+    std::fs::create_dir_all(storage_path).unwrap_or(());
+    let mut stronghold = StrongholdSecretManager::builder()
+        .password(stronghold_password)
+        .build(PathBuf::from("test-storage/backup_and_verify/1.stronghold"))?;
+
+    stronghold.store_mnemonic("inhale gorilla deny three celery song category owner lottery rent author wealth penalty crawl hobby obtain glad warm early rain clutch slab august bleak".to_string()).await.unwrap();
+
+    let manager = AccountManager::builder()
+        .with_secret_manager(SecretManager::Stronghold(stronghold))
+        .with_client_options(ClientOptions::new().with_node(common::NODE_LOCAL)?)
+        .with_coin_type(SHIMMER_COIN_TYPE)
+        .with_storage_path("test-storage/backup_and_verify/1")
+        .finish()
+        .await?;
+
+    let account = manager
+        .create_account()
+        .with_alias("Alice".to_string())
+        .finish()
+        .await?;
+    account.generate_addresses(2, None).await?;
+
+    manager
+        .backup(
+            PathBuf::from("test-storage/backup_and_verify/backup.stronghold"),
+            stronghold_password.to_string(),
+        )
+        .await?;
+
+    // Wrong password fails
+    AccountManager::verify_backup(
+        PathBuf::from("test-storage/backup_and_verify/backup.stronghold"),
+        "wrong password".to_string(),
+    )
+    .await
+    .unwrap_err();
+
+    let summary = AccountManager::verify_backup(
+        PathBuf::from("test-storage/backup_and_verify/backup.stronghold"),
+        stronghold_password.to_string(),
+    )
+    .await?;
+
+    assert!(summary.client_options);
+    assert!(summary.seed);
+    assert_eq!(summary.coin_type, Some(SHIMMER_COIN_TYPE));
+    assert_eq!(summary.accounts.len(), 1);
+    assert_eq!(summary.accounts[0].alias, "Alice");
+    assert_eq!(summary.accounts[0].public_addresses, 3);
+    assert_eq!(summary.accounts[0].verified_addresses, 3);
+
+    common::tear_down(storage_path)
+}
+
+#[tokio::test]
+#[cfg(all(feature = "stronghold", feature = "storage"))]
+// Automatic backups after changes, with rotation
+async fn auto_backup() -> Result<()> {
+    let storage_path = "test-storage/auto_backup";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager("test-storage/auto_backup/wallet", None, None).await?;
+
+    let backup_directory = PathBuf::from("test-storage/auto_backup/backups");
+    manager
+        .start_auto_backup(
+            backup_directory.clone(),
+            "some_hopefully_secure_password".to_string(),
+            Some(1),
+            None,
+        )
+        .await?;
+
+    let backup_count = || std::fs::read_dir(&backup_directory).unwrap().count();
+    assert_eq!(backup_count(), 0);
+
+    // A new account triggers a backup
+    manager.create_account().finish().await?;
+    tokio::time::sleep(std::time::Duration::from_secs(7)).await;
+    assert_eq!(backup_count(), 1);
+
+    let latest_backup = || {
+        std::fs::read_dir(&backup_directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .max()
+            .unwrap()
+    };
+
+    // A new address triggers a backup, which replaces the older one
+    let account = manager.get_account(0u32).await?;
+    let first_backup = latest_backup();
+    account.generate_addresses(1, None).await?;
+    tokio::time::sleep(std::time::Duration::from_secs(7)).await;
+    assert_eq!(backup_count(), 1);
+    let second_backup = latest_backup();
+    assert_ne!(first_backup, second_backup);
+
+    // Other changes of the account don't trigger a backup
+    account.set_alias("Alice").await?;
+    tokio::time::sleep(std::time::Duration::from_secs(7)).await;
+    assert_eq!(latest_backup(), second_backup);
+
+    manager.stop_auto_backup().await?;
+
+    common::tear_down(storage_path)
+}
@@ -122,6 +122,17 @@ async fn message_interface_create_account() -> Result<()> {
         _ => panic!("unexpected response {response:?}"),
     }
 
+    // Cancelling without a running sync does nothing
+    let response = wallet_handle
+        .send_message(Message::CallAccountMethod {
+            account_id: 0.into(),
+            method: AccountMethod::CancelSync,
+        })
+        .await;
+    let Response::Ok(_) = response else {
+        panic!("unexpected response {response:?}");
+    };
+
     common::tear_down(storage_path)
 }
 
@@ -451,3 +462,109 @@ async fn message_interface_address_generation() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[cfg(feature = "message_interface")]
+#[test]
+fn message_interface_parse_message() {
+    use iota_wallet::{
+        message_interface::{parse_message, PROTOCOL_VERSION},
+        Error,
+    };
+
+    let message = parse_message(&format!(
+        r#"{{"cmd":"getAccounts","protocolVersion":{PROTOCOL_VERSION}}}"#
+    ))
+    .unwrap();
+    assert!(matches!(message, Message::GetAccounts));
+
+    // Messages without a protocol version are still accepted
+    let message = parse_message(r#"{"cmd":"getCapabilities"}"#).unwrap();
+    assert!(matches!(message, Message::GetCapabilities));
+
+    let error = parse_message(&format!(
+        r#"{{"cmd":"getAccounts","protocolVersion":{}}}"#,
+        PROTOCOL_VERSION + 1
+    ))
+    .unwrap_err();
+    assert!(matches!(error, Error::IncompatibleProtocolVersion { .. }));
+
+    let error = parse_message(r#"{"cmd":"getAccountz"}"#).unwrap_err();
+    assert!(matches!(error, Error::UnknownMessage(cmd) if cmd == "getAccountz"));
+
+    let error = parse_message(
+        r#"{"cmd":"callAccountMethod","payload":{"accountId":0,"method":{"name":"sendAmounts","data":{}}}}"#,
+    )
+    .unwrap_err();
+    assert!(matches!(error, Error::UnknownAccountMethod(method) if method == "sendAmounts"));
+}
+
+#[cfg(feature = "message_interface")]
+#[tokio::test]
+async fn message_interface_batch() -> Result<()> {
+    let storage_path = "test-storage/message_interface_batch";
+    common::setup(storage_path)?;
+
+    let secret_manager = r#"{"Mnemonic":"acoustic trophy damage hint search taste love bicycle foster cradle brown govern endless depend situate athlete pudding blame question genius transfer van random vast"}"#;
+    let client_options = r#"{"nodes":["http://localhost:14265"]}"#;
+
+    let options = ManagerOptions {
+        #[cfg(feature = "storage")]
+        storage_path: Some(storage_path.to_string()),
+        client_options: Some(ClientBuilder::new().from_json(client_options).unwrap()),
+        coin_type: Some(SHIMMER_COIN_TYPE),
+        secret_manager: Some(serde_json::from_str(secret_manager).unwrap()),
+    };
+
+    let wallet_handle = create_message_handler(Some(options)).await.unwrap();
+
+    let messages = serde_json::json!([
+        {
+            "cmd": "generateAddress",
+            "payload": { "accountIndex": 0, "internal": false, "addressIndex": 0, "bech32Hrp": "rms" }
+        },
+        { "cmd": "bech32ToHex", "payload": { "bech32Address": { "$ref": "0/payload" } } },
+        { "cmd": "bech32ToHex", "payload": { "bech32Address": { "$ref": "5/payload" } } },
+        { "cmd": "generateMnemonic" }
+    ]);
+
+    let response = wallet_handle
+        .send_message(Message::Batch {
+            messages: serde_json::from_value(messages.clone()).unwrap(),
+            atomic: None,
+        })
+        .await;
+
+    match response {
+        Response::Batch(responses) => {
+            assert_eq!(responses.len(), 4);
+            let Response::HexAddress(hex) = &responses[1] else {
+                panic!("Unexpected response type: {:?}", responses[1]);
+            };
+            assert_eq!(
+                hex,
+                "0xb2c8ebf67a05f6beca3a5aa4f7825680015eb76d70e26fe0fb0ba3783791b0ed"
+            );
+            assert!(matches!(responses[2], Response::Error(_)));
+            assert!(matches!(responses[3], Response::GeneratedMnemonic(_)));
+        }
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+
+    // Atomic batches stop at the first error
+    let response = wallet_handle
+        .send_message(Message::Batch {
+            messages: serde_json::from_value(messages).unwrap(),
+            atomic: Some(true),
+        })
+        .await;
+
+    match response {
+        Response::Batch(responses) => {
+            assert_eq!(responses.len(), 3);
+            assert!(matches!(responses[2], Response::Error(_)));
+        }
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+
+    common::tear_down(storage_path)
+}
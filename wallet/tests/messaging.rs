@@ -0,0 +1,162 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(all(feature = "stronghold", feature = "storage"))]
+mod common;
+
+#[cfg(all(feature = "stronghold", feature = "storage"))]
+use std::path::PathBuf;
+
+#[cfg(all(feature = "stronghold", feature = "storage"))]
+use iota_client::{
+    constants::SHIMMER_COIN_TYPE,
+    secret::{stronghold::StrongholdSecretManager, SecretManager},
+};
+#[cfg(all(feature = "stronghold", feature = "storage"))]
+use iota_wallet::{account_manager::AccountManager, ClientOptions, Result};
+
+// A manager with a Stronghold, the messaging keys of its accounts are derived in the vault
+#[cfg(all(feature = "stronghold", feature = "storage"))]
+async fn make_stronghold_manager(storage_path: &str) -> Result<AccountManager> {
+    // Create directory if not existing, because stronghold panics otherwise
+    std::fs::create_dir_all(storage_path).unwrap_or(());
+    let stronghold = StrongholdSecretManager::builder()
+        .password("some_hopefully_secure_password")
+        .build(PathBuf::from(format!("{storage_path}/test.stronghold")))?;
+
+    let manager = AccountManager::builder()
+        .with_secret_manager(SecretManager::Stronghold(stronghold))
+        .with_client_options(ClientOptions::new().with_node(common::NODE_LOCAL)?)
+        .with_coin_type(SHIMMER_COIN_TYPE)
+        .with_storage_path(storage_path)
+        .finish()
+        .await?;
+    manager.store_mnemonic(common::DEFAULT_MNEMONIC.to_string()).await?;
+
+    Ok(manager)
+}
+
+#[tokio::test]
+#[cfg(all(feature = "stronghold", feature = "storage"))]
+async fn read_memo_with_messaging_key() -> Result<()> {
+    let storage_path = "test-storage/read_memo_with_messaging_key";
+    common::setup(storage_path)?;
+
+    let manager = make_stronghold_manager(storage_path).await?;
+    let sender = manager
+        .create_account()
+        .with_bech32_hrp("rms".to_string())
+        .finish()
+        .await?;
+    let recipient = manager
+        .create_account()
+        .with_bech32_hrp("rms".to_string())
+        .finish()
+        .await?;
+    let recipient_key = recipient.messaging_key().await?;
+    assert_ne!(sender.messaging_key().await?, recipient_key);
+
+    // The recipient and, with the copy for the sender, the sender can read the memo
+    let payload = sender.prepare_memo(&recipient_key, "thanks for the coffee").await?;
+    assert_eq!(recipient.read_memo(&payload).await?.text, "thanks for the coffee");
+    assert_eq!(recipient.read_memo(&payload).await?.recipient_key, recipient_key);
+    assert_eq!(sender.read_memo(&payload).await?.text, "thanks for the coffee");
+
+    // The messaging key isn't kept after the Stronghold password was cleared
+    manager.clear_stronghold_password().await?;
+    assert!(recipient.messaging_key().await.is_err());
+    assert!(recipient.read_memo(&payload).await.is_err());
+    manager
+        .set_stronghold_password("some_hopefully_secure_password")
+        .await?;
+    assert_eq!(recipient.messaging_key().await?, recipient_key);
+
+    // A wallet with the same mnemonic derives the same messaging keys
+    let mnemonic_manager = common::make_manager(
+        &format!("{storage_path}/mnemonic"),
+        Some(common::DEFAULT_MNEMONIC),
+        None,
+    )
+    .await?;
+    mnemonic_manager
+        .create_account()
+        .with_bech32_hrp("rms".to_string())
+        .finish()
+        .await?;
+    let restored_recipient = mnemonic_manager
+        .create_account()
+        .with_bech32_hrp("rms".to_string())
+        .finish()
+        .await?;
+    assert_eq!(restored_recipient.messaging_key().await?, recipient_key);
+    assert_eq!(
+        restored_recipient.read_memo(&payload).await?.text,
+        "thanks for the coffee"
+    );
+
+    // Memos for other messaging keys can't be read
+    let other_manager = common::make_manager(&format!("{storage_path}/other"), None, None).await?;
+    let other = other_manager
+        .create_account()
+        .with_bech32_hrp("rms".to_string())
+        .finish()
+        .await?;
+    assert!(other.read_memo(&payload).await.is_err());
+    let payload = other.prepare_memo(&recipient_key, "you're welcome").await?;
+    assert_eq!(recipient.read_memo(&payload).await?.text, "you're welcome");
+    assert!(sender.read_memo(&payload).await.is_err());
+
+    assert!(sender.prepare_memo("0x1234", "thanks for the coffee").await.is_err());
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+#[cfg(all(feature = "stronghold", feature = "storage"))]
+async fn send_and_decrypt_memo() -> Result<()> {
+    let storage_path = "test-storage/send_and_decrypt_memo";
+    common::setup(storage_path)?;
+
+    let manager = make_stronghold_manager(storage_path).await?;
+    let accounts = &common::create_accounts_with_funds(&manager, 2).await?;
+    let sender_address = accounts[0].addresses().await?[0].address().to_bech32();
+    let recipient_address = accounts[1].addresses().await?[0].address().to_bech32();
+
+    // Without a previous memo from the recipient its messaging key isn't known
+    assert!(accounts[0].messaging_key_of(&recipient_address).await?.is_none());
+
+    let transaction = accounts[0]
+        .send_memo(
+            recipient_address,
+            "thanks for the coffee".to_string(),
+            Some(accounts[1].messaging_key().await?),
+            None,
+        )
+        .await?;
+    accounts[0]
+        .retry_transaction_until_included(&transaction.transaction_id, None, None)
+        .await?;
+    accounts[0].sync(None).await?;
+    let sent_transaction = accounts[0].get_transaction(&transaction.transaction_id).await.unwrap();
+    assert_eq!(sent_transaction.memo.unwrap().text, "thanks for the coffee");
+
+    // The transaction of the tagged output is requested without syncing incoming transactions
+    accounts[1].sync(None).await?;
+    let incoming_transaction = accounts[1]
+        .get_incoming_transaction_data(&transaction.transaction_id)
+        .await
+        .unwrap();
+    assert_eq!(incoming_transaction.memo.unwrap().text, "thanks for the coffee");
+    assert_eq!(
+        accounts[1].messaging_key_of(&sender_address).await?,
+        Some(accounts[0].messaging_key().await?)
+    );
+
+    let block_id = accounts[1]
+        .send_memo_block(&accounts[0].messaging_key().await?, "you're welcome")
+        .await?;
+    assert_eq!(accounts[0].read_memo_block(&block_id).await?.text, "you're welcome");
+
+    common::tear_down(storage_path)
+}
@@ -3,7 +3,14 @@
 
 mod common;
 
-use iota_wallet::{account::SyncOptions, NativeTokenOptions, Result, U256};
+use iota_client::block::output::TokenId;
+use iota_wallet::{
+    account::{
+        types::{NativeTokenHolder, NativeTokenMetadataOverrides, MAX_NATIVE_TOKEN_DECIMALS},
+        SyncOptions,
+    },
+    Error, NativeTokenOptions, Result, U256,
+};
 
 #[ignore]
 #[tokio::test]
@@ -124,3 +131,123 @@ async fn native_token_foundry_metadata() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[ignore]
+#[tokio::test]
+async fn native_token_supply_and_foundry_metadata() -> Result<()> {
+    let storage_path = "test-storage/native_token_supply_and_foundry_metadata";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account = &common::create_accounts_with_funds(&manager, 1).await?[0];
+
+    let tx = account.create_alias_output(None, None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let mint_tx = account
+        .mint_native_token(
+            NativeTokenOptions {
+                alias_id: None,
+                circulating_supply: U256::from(50),
+                maximum_supply: U256::from(100),
+                foundry_metadata: None,
+            },
+            None,
+        )
+        .await?;
+    account
+        .retry_transaction_until_included(&mint_tx.transaction.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let tx = account
+        .decrease_native_token_supply(mint_tx.token_id, U256::from(10), None)
+        .await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let supply = account.native_token_supply(&mint_tx.token_id).await?;
+    assert!(supply.controlled);
+    assert_eq!(supply.minted_tokens, U256::from(50));
+    assert_eq!(supply.melted_tokens, U256::from(10));
+    assert_eq!(supply.circulating_supply, U256::from(40));
+    assert_eq!(supply.maximum_supply, U256::from(100));
+    assert!(supply.metadata.is_none());
+
+    let mut holders = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = account.native_token_holders(&mint_tx.token_id, cursor).await?;
+        holders.extend(page.holders);
+        cursor = page.cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    let holders = NativeTokenHolder::aggregate(holders);
+    assert_eq!(holders.len(), 1);
+    assert_eq!(holders[0].amount, U256::from(40));
+
+    let tx = account
+        .update_foundry_metadata(mint_tx.token_id, b"some foundry metadata".to_vec(), None)
+        .await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let supply = account.native_token_supply(&mint_tx.token_id).await?;
+    assert_eq!(supply.metadata.as_deref(), Some(&b"some foundry metadata"[..]));
+    assert_eq!(supply.circulating_supply, U256::from(40));
+
+    common::tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn native_token_metadata_decimals_limit() -> Result<()> {
+    let storage_path = "test-storage/native_token_metadata_decimals_limit";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+    let account = manager
+        .create_account()
+        .with_bech32_hrp("rms".to_string())
+        .finish()
+        .await?;
+    let token_id = TokenId::null();
+
+    let metadata = account
+        .set_native_token_metadata_overrides(
+            token_id,
+            NativeTokenMetadataOverrides {
+                decimals: Some(MAX_NATIVE_TOKEN_DECIMALS),
+                ..Default::default()
+            },
+        )
+        .await?;
+    assert_eq!(metadata.decimals(), Some(MAX_NATIVE_TOKEN_DECIMALS));
+
+    // More decimals than the digits of the largest amount are rejected and the previous overrides are kept
+    let result = account
+        .set_native_token_metadata_overrides(
+            token_id,
+            NativeTokenMetadataOverrides {
+                decimals: Some(MAX_NATIVE_TOKEN_DECIMALS + 1),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert!(matches!(result, Err(Error::InvalidNativeTokenDecimals(78))));
+    assert_eq!(
+        account.native_token_metadata(&token_id).await.unwrap().decimals(),
+        Some(MAX_NATIVE_TOKEN_DECIMALS)
+    );
+
+    common::tear_down(storage_path)
+}
@@ -0,0 +1,390 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod common;
+
+#[cfg(feature = "mqtt")]
+use std::{collections::HashSet, time::Duration};
+
+#[cfg(feature = "mqtt")]
+use iota_client::{constants::SHIMMER_COIN_TYPE, node_api::mqtt::BrokerOptions, Client};
+#[cfg(feature = "mqtt")]
+use iota_wallet::{
+    account_manager::AccountManager,
+    secret::{mnemonic::MnemonicSecretManager, SecretManager},
+    ClientOptions, Result,
+};
+
+#[cfg(feature = "mqtt")]
+#[tokio::test]
+// Subscriptions for the account addresses on a local mock broker
+async fn output_notifications_subscriptions() -> Result<()> {
+    let storage_path = "test-storage/output_notifications_subscriptions";
+    common::setup(storage_path)?;
+
+    let (broker, broker_port) = mock_broker::MockBroker::start();
+    let (_node, node_port) = mock_node::MockNode::start();
+    let manager = make_manager(storage_path, node_port, broker_port).await?;
+    let account = manager
+        .create_account()
+        .with_bech32_hrp("rms".to_string())
+        .finish()
+        .await?;
+    let first_topic = address_topic(account.addresses().await?[0].address().to_bech32());
+
+    manager.start_output_notifications(None, None).await?;
+    assert!(broker.wait_for(|topics| topics.subscribed.contains(&first_topic)).await);
+
+    // A new address is subscribed while the process is running
+    let new_topic = address_topic(account.generate_addresses(1, None).await?[0].address().to_bech32());
+    assert!(broker.wait_for(|topics| topics.subscribed.contains(&new_topic)).await);
+
+    // All topics are unsubscribed when the process stops
+    manager.stop_output_notifications().await?;
+    let address_topics = HashSet::from([first_topic, new_topic]);
+    assert!(
+        broker
+            .wait_for(|topics| topics.unsubscribed.is_superset(&address_topics))
+            .await
+    );
+
+    common::tear_down(storage_path)
+}
+
+#[cfg(feature = "mqtt")]
+#[tokio::test]
+// Notifications from a local mock broker sync only the notified address, a dropped connection falls back to polling
+async fn output_notifications_sync_and_fallback() -> Result<()> {
+    let storage_path = "test-storage/output_notifications_sync_and_fallback";
+    common::setup(storage_path)?;
+
+    let (broker, broker_port) = mock_broker::MockBroker::start();
+    let (node, node_port) = mock_node::MockNode::start();
+    let manager = make_manager(storage_path, node_port, broker_port).await?;
+    let account = manager
+        .create_account()
+        .with_bech32_hrp("rms".to_string())
+        .finish()
+        .await?;
+    let first_address = account.addresses().await?[0].address().to_bech32();
+    let new_address = account.generate_addresses(1, None).await?[0].address().to_bech32();
+
+    manager
+        .start_output_notifications(None, Some(Duration::from_secs(2)))
+        .await?;
+    let topics = HashSet::from([address_topic(first_address.clone()), address_topic(new_address.clone())]);
+    assert!(
+        broker
+            .wait_for(|received| received.subscribed.is_superset(&topics))
+            .await
+    );
+
+    // All addresses are synced after connecting, wait until these syncs are done
+    assert!(
+        node.wait_for(|paths| paths.iter().any(|path| path.contains(&first_address)))
+            .await
+    );
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    node.clear();
+
+    // An output notification syncs only the notified address
+    broker.publish(&address_topic(new_address.clone()), b"{}");
+    assert!(
+        node.wait_for(|paths| paths.iter().any(|path| path.contains(&new_address)))
+            .await
+    );
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(!node.paths().iter().any(|path| path.contains(&first_address)));
+
+    // Without broker connection, all addresses are polled in the fallback interval
+    broker.disconnect();
+    node.clear();
+    for _ in 0..2 {
+        assert!(
+            node.wait_for(|paths| paths.iter().any(|path| path.contains(&first_address)))
+                .await
+        );
+        node.clear();
+    }
+
+    manager.stop_output_notifications().await?;
+
+    common::tear_down(storage_path)
+}
+
+// An account manager that uses the local mock node and broker
+#[cfg(feature = "mqtt")]
+async fn make_manager(storage_path: &str, node_port: u16, broker_port: u16) -> Result<AccountManager> {
+    // The connection counts as lost on the first error, instead of reconnecting forever
+    let broker_options = BrokerOptions::new()
+        .use_ws(false)
+        .port(broker_port)
+        .max_reconnection_attempts(1);
+    let client_options = ClientOptions::new()
+        .with_node(&format!("http://127.0.0.1:{node_port}"))?
+        .with_ignore_node_health()
+        .with_mqtt_broker_options(broker_options);
+    #[allow(unused_mut)]
+    let mut account_manager_builder = AccountManager::builder()
+        .with_secret_manager(SecretManager::Mnemonic(MnemonicSecretManager::try_from_mnemonic(
+            &Client::generate_mnemonic()?,
+        )?))
+        .with_client_options(client_options)
+        .with_coin_type(SHIMMER_COIN_TYPE);
+    #[cfg(feature = "storage")]
+    {
+        account_manager_builder = account_manager_builder.with_storage_path(storage_path);
+    }
+    #[cfg(not(feature = "storage"))]
+    let _ = storage_path;
+    account_manager_builder.finish().await
+}
+
+#[cfg(feature = "mqtt")]
+fn address_topic(bech32_address: String) -> String {
+    format!("outputs/unlock/address/{bech32_address}")
+}
+
+#[cfg(feature = "mqtt")]
+mod mock_broker {
+    use std::{
+        collections::HashSet,
+        io::{Read, Write},
+        net::{Shutdown, TcpListener, TcpStream},
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    /// The topics a mock broker received (un)subscriptions for.
+    #[derive(Debug, Default)]
+    pub(crate) struct Topics {
+        pub(crate) subscribed: HashSet<String>,
+        pub(crate) unsubscribed: HashSet<String>,
+    }
+
+    #[derive(Debug, Default)]
+    struct State {
+        topics: Topics,
+        // the connected clients, to publish to them
+        streams: Vec<TcpStream>,
+        // set when the broker dropped the connections, new ones are closed right away
+        refuse_connections: bool,
+    }
+
+    /// A minimal MQTT 3.1.1 broker, which acknowledges connections, pings and (un)subscriptions and records the
+    /// topics. Messages are published with QoS 0 to all connected clients.
+    #[derive(Debug, Clone, Default)]
+    pub(crate) struct MockBroker(Arc<Mutex<State>>);
+
+    impl MockBroker {
+        /// Start the broker on a free local port, returns the broker and the port.
+        pub(crate) fn start() -> (Self, u16) {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let broker = Self::default();
+            let connection_broker = broker.clone();
+            std::thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    let broker = connection_broker.clone();
+                    std::thread::spawn(move || broker.handle_connection(stream));
+                }
+            });
+            (broker, port)
+        }
+
+        /// Wait up to 20 seconds until the condition on the received topics is true.
+        pub(crate) async fn wait_for(&self, condition: impl Fn(&Topics) -> bool) -> bool {
+            for _ in 0..200 {
+                if condition(&self.0.lock().unwrap().topics) {
+                    return true;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            false
+        }
+
+        /// Publish the payload for the topic to all connected clients.
+        pub(crate) fn publish(&self, topic: &str, payload: &[u8]) {
+            let mut body = (topic.len() as u16).to_be_bytes().to_vec();
+            body.extend_from_slice(topic.as_bytes());
+            body.extend_from_slice(payload);
+            let publish = packet(0x30, &body);
+            self.0
+                .lock()
+                .unwrap()
+                .streams
+                .retain_mut(|stream| stream.write_all(&publish).is_ok());
+        }
+
+        /// Drop all connections and refuse new ones.
+        pub(crate) fn disconnect(&self) {
+            let mut state = self.0.lock().unwrap();
+            state.refuse_connections = true;
+            for stream in state.streams.drain(..) {
+                stream.shutdown(Shutdown::Both).ok();
+            }
+        }
+
+        fn handle_connection(&self, mut stream: TcpStream) {
+            while let Some((packet_type, body)) = read_packet(&mut stream) {
+                let response = match packet_type {
+                    // CONNECT, accepted with CONNACK
+                    1 => {
+                        let mut state = self.0.lock().unwrap();
+                        if state.refuse_connections {
+                            return;
+                        }
+                        if let Ok(stream) = stream.try_clone() {
+                            state.streams.push(stream);
+                        }
+                        packet(0x20, &[0, 0])
+                    }
+                    // SUBSCRIBE, every topic is granted QoS 0 in the SUBACK
+                    8 => {
+                        let topics = read_topics(&body[2..], true);
+                        let mut suback = body[..2].to_vec();
+                        suback.resize(2 + topics.len(), 0);
+                        self.0.lock().unwrap().topics.subscribed.extend(topics);
+                        packet(0x90, &suback)
+                    }
+                    // UNSUBSCRIBE, acknowledged with UNSUBACK
+                    10 => {
+                        let topics = read_topics(&body[2..], false);
+                        self.0.lock().unwrap().topics.unsubscribed.extend(topics);
+                        packet(0xb0, &body[..2])
+                    }
+                    // PINGREQ, answered with PINGRESP
+                    12 => packet(0xd0, &[]),
+                    // DISCONNECT
+                    14 => return,
+                    _ => continue,
+                };
+                if stream.write_all(&response).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    // Read the type and the body of the next packet, `None` if the connection is closed
+    fn read_packet(stream: &mut TcpStream) -> Option<(u8, Vec<u8>)> {
+        let mut byte = [0; 1];
+        stream.read_exact(&mut byte).ok()?;
+        let packet_type = byte[0] >> 4;
+
+        let mut remaining_length = 0;
+        for shift in (0..28).step_by(7) {
+            stream.read_exact(&mut byte).ok()?;
+            remaining_length |= ((byte[0] & 0x7f) as usize) << shift;
+            if byte[0] & 0x80 == 0 {
+                break;
+            }
+        }
+
+        let mut body = vec![0; remaining_length];
+        stream.read_exact(&mut body).ok()?;
+        Some((packet_type, body))
+    }
+
+    // The topic filters of a SUBSCRIBE or UNSUBSCRIBE payload, subscriptions have a QoS byte after every topic
+    fn read_topics(mut payload: &[u8], with_qos: bool) -> Vec<String> {
+        let mut topics = Vec::new();
+        while payload.len() >= 2 {
+            let length = u16::from_be_bytes([payload[0], payload[1]]) as usize;
+            let topic_end = (2 + length).min(payload.len());
+            topics.push(String::from_utf8_lossy(&payload[2..topic_end]).into_owned());
+            payload = &payload[(topic_end + usize::from(with_qos)).min(payload.len())..];
+        }
+        topics
+    }
+
+    // A packet with the fixed header byte and the encoded remaining length
+    fn packet(header: u8, body: &[u8]) -> Vec<u8> {
+        let mut packet = vec![header];
+        let mut remaining_length = body.len();
+        loop {
+            let mut byte = (remaining_length % 128) as u8;
+            remaining_length /= 128;
+            if remaining_length > 0 {
+                byte |= 0x80;
+            }
+            packet.push(byte);
+            if remaining_length == 0 {
+                break;
+            }
+        }
+        packet.extend_from_slice(body);
+        packet
+    }
+}
+
+#[cfg(feature = "mqtt")]
+mod mock_node {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::{TcpListener, TcpStream},
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    /// A node that records the paths of the requests and answers all of them with 404, so syncs fail fast.
+    #[derive(Debug, Clone, Default)]
+    pub(crate) struct MockNode(Arc<Mutex<Vec<String>>>);
+
+    impl MockNode {
+        /// Start the node on a free local port, returns the node and the port.
+        pub(crate) fn start() -> (Self, u16) {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let node = Self::default();
+            let connection_node = node.clone();
+            std::thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    let node = connection_node.clone();
+                    std::thread::spawn(move || node.handle_connection(stream));
+                }
+            });
+            (node, port)
+        }
+
+        /// The paths with query of the requests since the last clear.
+        pub(crate) fn paths(&self) -> Vec<String> {
+            self.0.lock().unwrap().clone()
+        }
+
+        pub(crate) fn clear(&self) {
+            self.0.lock().unwrap().clear();
+        }
+
+        /// Wait up to 20 seconds until the condition on the requested paths is true.
+        pub(crate) async fn wait_for(&self, condition: impl Fn(&[String]) -> bool) -> bool {
+            for _ in 0..200 {
+                if condition(&self.0.lock().unwrap()) {
+                    return true;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            false
+        }
+
+        fn handle_connection(&self, stream: TcpStream) {
+            let mut reader = BufReader::new(&stream);
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).is_err() {
+                return;
+            }
+            // The path of `GET <path> HTTP/1.1`
+            if let Some(path) = request_line.split_whitespace().nth(1) {
+                self.0.lock().unwrap().push(path.to_string());
+            }
+            // Skip the headers, requests of the client have no body
+            let mut line = String::new();
+            while reader.read_line(&mut line).map_or(false, |read| read > 2) {
+                line.clear();
+            }
+            (&stream)
+                .write_all(b"HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .ok();
+        }
+    }
+}
@@ -3,14 +3,24 @@
 
 mod common;
 
-use iota_client::block::output::{
-    unlock_condition::{
-        AddressUnlockCondition, ExpirationUnlockCondition, GovernorAddressUnlockCondition,
-        StateControllerAddressUnlockCondition, StorageDepositReturnUnlockCondition,
+use std::time::Duration;
+
+use iota_client::{
+    block::output::{
+        unlock_condition::{
+            AddressUnlockCondition, ExpirationUnlockCondition, GovernorAddressUnlockCondition,
+            StateControllerAddressUnlockCondition, StorageDepositReturnUnlockCondition,
+        },
+        AliasId, AliasOutputBuilder, BasicOutputBuilder, NftId, NftOutputBuilder, UnlockCondition,
     },
-    AliasId, AliasOutputBuilder, BasicOutputBuilder, NftId, NftOutputBuilder, UnlockCondition,
+    constants::SHIMMER_COIN_TYPE,
+    Client,
+};
+use iota_wallet::{
+    account::SyncOptions,
+    secret::{mnemonic::MnemonicSecretManager, SecretManager},
+    AddressWithAmount, Result,
 };
-use iota_wallet::{account::SyncOptions, Result};
 
 #[ignore]
 #[tokio::test]
@@ -119,3 +129,136 @@ async fn sync_only_most_basic_outputs() -> Result<()> {
 
     common::tear_down(storage_path)
 }
+
+#[ignore]
+#[tokio::test]
+async fn sync_with_address_gap_limit() -> Result<()> {
+    let storage_path = "test-storage/sync_with_address_gap_limit";
+    common::setup(storage_path)?;
+
+    let mnemonic = Client::generate_mnemonic()?;
+    let client = Client::builder().with_node(common::NODE_LOCAL)?.finish()?;
+
+    let secret_manager = SecretManager::Mnemonic(MnemonicSecretManager::try_from_mnemonic(&mnemonic)?);
+
+    // Address with key_index 3, like another wallet instance with the same seed would generate it
+    let address = client
+        .get_addresses(&secret_manager)
+        .with_coin_type(SHIMMER_COIN_TYPE)
+        .with_bech32_hrp(client.get_bech32_hrp().await?)
+        .with_account_index(0)
+        .with_range(3..4)
+        .finish()
+        .await?;
+
+    iota_client::request_funds_from_faucet(common::FAUCET_URL, &address[0]).await?;
+
+    // Wait for faucet transaction
+    tokio::time::sleep(Duration::new(10, 0)).await;
+
+    let manager = common::make_manager(storage_path, Some(&mnemonic), None).await?;
+    let account = manager.create_account().finish().await?;
+
+    // Without a gap limit only the first address is synced
+    let balance = account.sync(None).await?;
+    assert_eq!(balance.base_coin.total, 0);
+
+    // A gap limit smaller than the gap doesn't find the address
+    let balance = account
+        .sync(Some(SyncOptions {
+            address_gap_limit: Some(2),
+            force_syncing: true,
+            ..Default::default()
+        }))
+        .await?;
+    assert_eq!(balance.base_coin.total, 0);
+    assert_eq!(account.read().await.public_addresses().len(), 1);
+
+    let balance = account
+        .sync(Some(SyncOptions {
+            address_gap_limit: Some(5),
+            force_syncing: true,
+            ..Default::default()
+        }))
+        .await?;
+    assert!(balance.base_coin.total > 0);
+    let account = account.read().await;
+    // The addresses up to the used one got generated, but not the probed empty ones after it
+    assert_eq!(account.public_addresses().len(), 4);
+    assert_eq!(*account.highest_used_public_address_index(), Some(3));
+    assert!(account.internal_addresses().is_empty());
+
+    common::tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn sync_incremental() -> Result<()> {
+    let storage_path = "test-storage/sync_incremental";
+    common::setup(storage_path)?;
+
+    let manager = common::make_manager(storage_path, None, None).await?;
+
+    let account_0 = &common::create_accounts_with_funds(&manager, 1).await?[0];
+    let account_1 = manager.create_account().finish().await?;
+
+    let incremental_sync_options = SyncOptions {
+        force_syncing: true,
+        incremental: true,
+        ..Default::default()
+    };
+
+    // Without incremental syncing no checkpoint is created
+    account_1.sync(None).await?;
+    assert!(account_1.read().await.sync_checkpoint().is_none());
+
+    // The first incremental sync of all addresses creates the checkpoint
+    account_1.sync(Some(incremental_sync_options.clone())).await?;
+    assert!(account_1.read().await.sync_checkpoint().is_some());
+
+    let amount = 1_000_000;
+    let tx = account_0
+        .send_amount(
+            vec![AddressWithAmount {
+                address: account_1.addresses().await?[0].address().to_bech32(),
+                amount,
+            }],
+            None,
+        )
+        .await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    // The new output is found with the checkpoint
+    let balance = account_1.sync(Some(incremental_sync_options.clone())).await?;
+    assert_eq!(balance.base_coin.available, amount);
+    account_0.sync(Some(incremental_sync_options.clone())).await?;
+    assert!(account_0.read().await.sync_checkpoint().is_some());
+
+    // The known output is detected as spent with the checkpoint
+    let tx = account_1
+        .send_amount(
+            vec![AddressWithAmount {
+                address: account_0.addresses().await?[0].address().to_bech32(),
+                amount,
+            }],
+            None,
+        )
+        .await?;
+    account_1
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    let incremental_balance = account_0.sync(Some(incremental_sync_options.clone())).await?;
+    let full_balance = account_0
+        .sync(Some(SyncOptions {
+            force_syncing: true,
+            ..Default::default()
+        }))
+        .await?;
+    assert_eq!(incremental_balance, full_balance);
+    let balance = account_1.sync(Some(incremental_sync_options)).await?;
+    assert_eq!(balance.base_coin.total, 0);
+
+    common::tear_down(storage_path)
+}